@@ -46,3 +46,14 @@ export const BETTING_POOL_SEED = "betting_pool"
 export const ROUND_SEED        = "round"
 export const BET_SEED          = "bet"
 export const SEASON_PRED_SEED  = "season_prediction"
+export const USER_BET_COUNTER_SEED = "user_bet_counter"
+export const USER_VOLUME_SEED = "user_volume"
+export const OPEN_LIABILITY_SEED = "open_liability"
+export const DAILY_VOLUME_SEED = "daily_volume"
+export const ACTIVE_BETTORS_SEED = "active_bettors"
+export const PENDING_CLAIMS_SEED = "pending_claims"
+export const ROUND_METADATA_SEED = "round_metadata"
+export const ROUND_VAULT_SEED = "round_vault"
+export const ODDS_HISTORY_SEED = "odds_history"
+export const LEG_FEE_SCHEDULE_SEED = "leg_fees"
+export const STAKE_POLICY_SEED = "stake_policy"
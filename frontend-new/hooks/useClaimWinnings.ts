@@ -1,11 +1,12 @@
 import { useMutation, useQueryClient } from "@tanstack/react-query"
 import { useWallet } from "@solana/wallet-adapter-react"
-import { SystemProgram } from "@solana/web3.js"
-import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token"
+import { PublicKey, SystemProgram } from "@solana/web3.js"
+import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token"
 import BN from "bn.js"
 import { useProgram } from "@/hooks/useProgram"
-import { getBettingPoolPda, getRoundPda, getBetPda } from "@/utils/pda"
-import type { BettingPool, Bet } from "@/types/sportsbook"
+import { getBettingPoolPda, getRoundPda, getRoundVaultPda, getBetPda, getPendingClaimsPda } from "@/utils/pda"
+import { getClaimHookAccounts, getMintTokenProgram } from "@/utils/transfer-hook"
+import type { BettingPool, Bet, RoundAccounting } from "@/types/sportsbook"
 
 export interface ClaimWinningsParams {
   bet: Bet
@@ -35,24 +36,49 @@ export function useClaimWinnings() {
       const pool = poolData as unknown as BettingPool
 
       const tokenMint = pool.tokenMint
+      const connection = program.provider.connection
+      const tokenProgram = await getMintTokenProgram(connection, tokenMint)
+      const isToken2022 = tokenProgram.equals(TOKEN_2022_PROGRAM_ID)
 
       // ── Derive PDAs ────────────────────────────────────────────────────────
       const [roundPda] = getRoundPda(bettingPoolPda, bet.roundId)
       const [betPda] = getBetPda(bettingPoolPda, bet.betId)
+      const [pendingClaimsPda] = getPendingClaimsPda(bettingPoolPda)
 
       // ── Token accounts ─────────────────────────────────────────────────────
       // The original bettor's token account (where winnings go if claimer == bettor)
-      const bettorTokenAccount = await getAssociatedTokenAddress(tokenMint, bet.bettor)
+      const bettorTokenAccount = await getAssociatedTokenAddress(tokenMint, bet.bettor, false, tokenProgram)
 
       // The pool's token account (source of funds)
       const bettingPoolTokenAccount = await getAssociatedTokenAddress(
         tokenMint,
         bettingPoolPda,
-        true // allowOwnerOffCurve for PDA
+        true, // allowOwnerOffCurve for PDA
+        tokenProgram
       )
 
+      // Isolated rounds pay out of their own vault
+      const round = (await program.account.roundAccounting.fetch(roundPda)) as unknown as RoundAccounting
+      const roundVault = round.isolated ? getRoundVaultPda(bettingPoolPda, bet.roundId)[0] : null
+
       // The claimer's token account (= bettorTokenAccount when claimer is the bettor)
-      const claimerTokenAccount = await getAssociatedTokenAddress(tokenMint, publicKey)
+      const claimerTokenAccount = await getAssociatedTokenAddress(tokenMint, publicKey, false, tokenProgram)
+
+      // Copied bets pay the leader a success fee on a win
+      const leaderTokenAccount = bet.copyLeader && !bet.copyLeader.equals(PublicKey.default)
+        ? await getAssociatedTokenAddress(tokenMint, bet.copyLeader, false, tokenProgram)
+        : null
+
+      // Token-2022 mints with a transfer hook need the hook's accounts too
+      const hookAccounts = isToken2022
+        ? await getClaimHookAccounts(
+            connection,
+            tokenMint,
+            roundVault ?? bettingPoolTokenAccount,
+            bettorTokenAccount,
+            roundVault ? roundPda : bettingPoolPda
+          )
+        : []
 
       // ── Send transaction ───────────────────────────────────────────────────
       const tx = await program.methods
@@ -61,13 +87,18 @@ export function useClaimWinnings() {
           bettingPool: bettingPoolPda,
           roundAccounting: roundPda,
           bet: betPda,
+          pendingClaims: pendingClaimsPda,
           bettingPoolTokenAccount,
+          roundVault,
           bettorTokenAccount,
           claimer: publicKey,
           claimerTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          leaderTokenAccount,
+          tokenMint: isToken2022 ? tokenMint : null,
+          tokenProgram,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts(hookAccounts)
         .rpc()
 
       return { signature: tx }
@@ -0,0 +1,65 @@
+import type { NextApiRequest, NextApiResponse } from "next"
+import { AnchorProvider, Program } from "@coral-xyz/anchor"
+import { Connection, Keypair, PublicKey, Transaction, clusterApiUrl } from "@solana/web3.js"
+import { IDL } from "@/idl/sportsbook"
+import { PROGRAM_ID, TOKEN_DECIMALS } from "@/config/program"
+import { decodeBetRequestParams } from "@/utils/solana-pay"
+import { preparePlaceBet } from "@/utils/place-bet-tx"
+
+const RPC_URL = process.env.SOLANA_RPC_URL ?? clusterApiUrl("devnet")
+const OUTCOME_NAMES = ["", "Home", "Away", "Draw"]
+
+/**
+ * Solana Pay transaction request for placing a bet.
+ *
+ * GET  → wallet display metadata.
+ * POST → `{ account }` from the scanning wallet; returns an unsigned placeBet
+ *        transaction with that wallet as bettor and fee payer.
+ */
+export default async function handler(req: NextApiRequest, res: NextApiResponse) {
+  if (req.method === "GET") {
+    const host = req.headers.host ?? "localhost:3000"
+    return res.status(200).json({
+      label: "PhantomZero",
+      icon: `https://${host}/android-icon-192x192.png`,
+    })
+  }
+  if (req.method !== "POST") {
+    res.setHeader("Allow", "GET, POST")
+    return res.status(405).json({ error: "Method not allowed" })
+  }
+
+  try {
+    const params = decodeBetRequestParams(req.query)
+
+    let bettor: PublicKey
+    try {
+      bettor = new PublicKey(req.body?.account)
+    } catch {
+      return res.status(400).json({ error: "Invalid account" })
+    }
+
+    // Read-only provider: the server never signs, it only builds
+    const connection = new Connection(RPC_URL, "confirmed")
+    const readOnlyWallet = { publicKey: Keypair.generate().publicKey } as any
+    const provider = new AnchorProvider(connection, readOnlyWallet, { commitment: "confirmed" })
+    const program = new Program(IDL as any, PROGRAM_ID, provider)
+
+    const { method, betId } = await preparePlaceBet(program, bettor, params)
+    const instruction = await method.instruction()
+
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash("confirmed")
+    const transaction = new Transaction({ feePayer: bettor, blockhash, lastValidBlockHeight }).add(instruction)
+    const serialized = transaction.serialize({ requireAllSignatures: false, verifySignatures: false })
+
+    const legs = params.matchIndices.map((m, i) => `M${m + 1} ${OUTCOME_NAMES[params.outcomes[i]]}`).join(", ")
+    const amount = params.amountRaw.toNumber() / 10 ** TOKEN_DECIMALS
+
+    return res.status(200).json({
+      transaction: serialized.toString("base64"),
+      message: `Bet #${betId.toString()} on round ${params.roundId.toString()}: ${legs} for ${amount} tokens`,
+    })
+  } catch (err) {
+    return res.status(400).json({ error: err instanceof Error ? err.message : "Could not build transaction" })
+  }
+}
@@ -42,6 +42,9 @@ export interface BettingPool {
   seasonEnded:        boolean
   seasonWinningTeam:  number
   bump:               number
+  legFees:            boolean
+  stakePolicy:        boolean
+  oddsUpdateLimits:   boolean
 }
 
 export interface RoundAccounting {
@@ -68,6 +71,8 @@ export interface RoundAccounting {
   roundEndTime:            BN
   settled:                 boolean
   bump:                    number
+  /** Funds live in the round's own vault (getRoundVaultPda), not the pool ATA */
+  isolated:                boolean
 }
 
 export interface Bet {
@@ -86,6 +91,9 @@ export interface Bet {
   claimDeadline:    BN
   bountyClaimer:    PublicKey | null
   bump:             number
+  /** Leader this bet was copied from (default pubkey = not a copy) */
+  copyLeader:       PublicKey
+  copyFeeBps:       number
 }
 
 export interface SeasonPrediction {
@@ -0,0 +1,40 @@
+import type { Program } from "@coral-xyz/anchor"
+import BN from "bn.js"
+import { getBettingPoolPda, getOddsHistoryPda } from "@/utils/pda"
+
+/** Must match ODDS_HISTORY_LEN in the program */
+const ODDS_HISTORY_LEN = 16
+
+/** One odds update, odds scaled by 1e9 */
+export interface OddsPoint {
+  homeOdds: BN
+  awayOdds: BN
+  drawOdds: BN
+  timestamp: BN
+}
+
+/**
+ * Recent odds updates of a match, oldest first (at most ODDS_HISTORY_LEN).
+ * Returns an empty list if nobody started a history for the match.
+ */
+export async function fetchOddsHistory(
+  program: Program,
+  roundId: BN | number,
+  matchIndex: number
+): Promise<OddsPoint[]> {
+  const [bettingPoolPda] = getBettingPoolPda()
+  const [historyPda] = getOddsHistoryPda(bettingPoolPda, roundId, matchIndex)
+
+  const account = (await program.account.oddsHistory.fetchNullable(historyPda)) as {
+    points: OddsPoint[]
+    totalUpdates: number
+  } | null
+  if (!account) return []
+
+  const stored = Math.min(account.totalUpdates, ODDS_HISTORY_LEN)
+  const points: OddsPoint[] = []
+  for (let update = account.totalUpdates - stored; update < account.totalUpdates; update++) {
+    points.push(account.points[update % ODDS_HISTORY_LEN])
+  }
+  return points
+}
@@ -6,6 +6,17 @@ import {
   ROUND_SEED,
   BET_SEED,
   SEASON_PRED_SEED,
+  USER_BET_COUNTER_SEED,
+  USER_VOLUME_SEED,
+  OPEN_LIABILITY_SEED,
+  DAILY_VOLUME_SEED,
+  ACTIVE_BETTORS_SEED,
+  PENDING_CLAIMS_SEED,
+  ROUND_METADATA_SEED,
+  ROUND_VAULT_SEED,
+  ODDS_HISTORY_SEED,
+  LEG_FEE_SCHEDULE_SEED,
+  STAKE_POLICY_SEED,
 } from "@/config/program"
 
 export function getBettingPoolPda(): [PublicKey, number] {
@@ -30,6 +41,106 @@ export function getRoundPda(
   )
 }
 
+export function getOpenLiabilityPda(
+  bettingPool: PublicKey,
+  roundId: BN | number
+): [PublicKey, number] {
+  const id = typeof roundId === "number" ? new BN(roundId) : roundId
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(OPEN_LIABILITY_SEED),
+      bettingPool.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  )
+}
+
+export function getRoundMetadataPda(
+  bettingPool: PublicKey,
+  roundId: BN | number
+): [PublicKey, number] {
+  const id = typeof roundId === "number" ? new BN(roundId) : roundId
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(ROUND_METADATA_SEED),
+      bettingPool.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  )
+}
+
+/** Token vault of a round created with round vaults on (RoundAccounting.isolated) */
+export function getRoundVaultPda(
+  bettingPool: PublicKey,
+  roundId: BN | number
+): [PublicKey, number] {
+  const id = typeof roundId === "number" ? new BN(roundId) : roundId
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(ROUND_VAULT_SEED),
+      bettingPool.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  )
+}
+
+export function getOddsHistoryPda(
+  bettingPool: PublicKey,
+  roundId: BN | number,
+  matchIndex: number
+): [PublicKey, number] {
+  const id = typeof roundId === "number" ? new BN(roundId) : roundId
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(ODDS_HISTORY_SEED),
+      bettingPool.toBuffer(),
+      id.toArrayLike(Buffer, "le", 8),
+      Buffer.from([matchIndex]),
+    ],
+    PROGRAM_ID
+  )
+}
+
+/** Per-parlay-length fee schedule, passed with bets when BettingPool.legFees is set */
+export function getLegFeeSchedulePda(bettingPool: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(LEG_FEE_SCHEDULE_SEED), bettingPool.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
+/** Minimum stake and rounding unit, passed with bets when BettingPool.stakePolicy is set */
+export function getStakePolicyPda(bettingPool: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(STAKE_POLICY_SEED), bettingPool.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
+export function getDailyVolumePda(bettingPool: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DAILY_VOLUME_SEED), bettingPool.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
+export function getActiveBettorsPda(bettingPool: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(ACTIVE_BETTORS_SEED), bettingPool.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
+export function getPendingClaimsPda(bettingPool: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PENDING_CLAIMS_SEED), bettingPool.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
 export function getBetPda(
   bettingPool: PublicKey,
   betId: BN | number
@@ -45,6 +156,43 @@ export function getBetPda(
   )
 }
 
+export function getUserBetPda(
+  bettingPool: PublicKey,
+  bettor: PublicKey,
+  userNonce: BN | number
+): [PublicKey, number] {
+  const nonce = typeof userNonce === "number" ? new BN(userNonce) : userNonce
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(BET_SEED),
+      bettingPool.toBuffer(),
+      bettor.toBuffer(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ],
+    PROGRAM_ID
+  )
+}
+
+export function getUserBetCounterPda(
+  bettingPool: PublicKey,
+  bettor: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(USER_BET_COUNTER_SEED), bettingPool.toBuffer(), bettor.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
+export function getUserVolumePda(
+  bettingPool: PublicKey,
+  bettor: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(USER_VOLUME_SEED), bettingPool.toBuffer(), bettor.toBuffer()],
+    PROGRAM_ID
+  )
+}
+
 export function getSeasonPredictionPda(
   user: PublicKey,
   bettingPool: PublicKey,
@@ -0,0 +1,114 @@
+import type { Program } from "@coral-xyz/anchor"
+import { PublicKey, SystemProgram } from "@solana/web3.js"
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token"
+import BN from "bn.js"
+import {
+  getBettingPoolPda,
+  getRoundPda,
+  getRoundVaultPda,
+  getBetPda,
+  getOpenLiabilityPda,
+  getDailyVolumePda,
+  getActiveBettorsPda,
+  getPendingClaimsPda,
+  getUserVolumePda,
+  getLegFeeSchedulePda,
+  getStakePolicyPda,
+} from "@/utils/pda"
+import type { BettingPool, RoundAccounting } from "@/types/sportsbook"
+
+export interface PlaceBetRequest {
+  /** Round to bet on (defaults to the pool's current round) */
+  roundId?: BN
+  matchIndices: number[]
+  outcomes: number[]
+  /** Total wager in raw token units */
+  amountRaw: BN
+  /** Index into bettingPool.teamTokenMints[] if the bettor holds a team token */
+  teamTokenIndex?: number | null
+}
+
+/**
+ * Resolves every account placeBet needs for `bettor` and returns the
+ * prepared method call, shared by the wallet flow (`.rpc()`) and the
+ * Solana Pay transaction builder (`.instruction()`).
+ */
+export async function preparePlaceBet(
+  program: Program,
+  bettor: PublicKey,
+  { roundId, matchIndices, outcomes, amountRaw, teamTokenIndex }: PlaceBetRequest
+) {
+  if (matchIndices.length === 0) throw new Error("No selections made")
+  if (matchIndices.length > 10) throw new Error("Maximum 10 selections per bet")
+  if (matchIndices.length !== outcomes.length) throw new Error("Each selection needs an outcome")
+
+  // ── Fetch live pool data ───────────────────────────────────────────────
+  const [bettingPoolPda] = getBettingPoolPda()
+  const poolData = await program.account.bettingPool.fetch(bettingPoolPda)
+  const pool = poolData as unknown as BettingPool
+
+  const betId = pool.nextBetId
+  const currentRoundId = roundId ?? new BN(pool.nextRoundId.toNumber() - 1)
+
+  if (currentRoundId.ltn(0)) throw new Error("No active round")
+
+  // ── Derive PDAs ───────────────────────────────────────────────────────
+  const [roundPda] = getRoundPda(bettingPoolPda, currentRoundId)
+  const [openLiabilityPda] = getOpenLiabilityPda(bettingPoolPda, currentRoundId)
+  const [betPda] = getBetPda(bettingPoolPda, betId)
+  const [dailyVolumePda] = getDailyVolumePda(bettingPoolPda)
+  const [activeBettorsPda] = getActiveBettorsPda(bettingPoolPda)
+  const [pendingClaimsPda] = getPendingClaimsPda(bettingPoolPda)
+  const [userVolumePda] = getUserVolumePda(bettingPoolPda, bettor)
+
+  // Isolated rounds take the stake into their own vault
+  const round = (await program.account.roundAccounting.fetch(roundPda)) as unknown as RoundAccounting
+  const roundVault = round.isolated ? getRoundVaultPda(bettingPoolPda, currentRoundId)[0] : null
+  // Pools pricing by parlay length need their fee schedule
+  const legFeeSchedule = pool.legFees ? getLegFeeSchedulePda(bettingPoolPda)[0] : null
+  const stakePolicy = pool.stakePolicy ? getStakePolicyPda(bettingPoolPda)[0] : null
+
+  // ── Token accounts ────────────────────────────────────────────────────
+  const tokenMint = pool.tokenMint
+  const bettorTokenAccount = await getAssociatedTokenAddress(tokenMint, bettor)
+  const bettingPoolTokenAccount = await getAssociatedTokenAddress(
+    tokenMint,
+    bettingPoolPda,
+    true // allowOwnerOffCurve = true for PDA
+  )
+  const protocolTreasuryTokenAccount = await getAssociatedTokenAddress(
+    tokenMint,
+    pool.protocolTreasury
+  )
+
+  // ── Optional team token account ───────────────────────────────────────
+  let teamTokenAccount: PublicKey | null = null
+  if (teamTokenIndex != null && pool.teamTokenMints[teamTokenIndex]) {
+    teamTokenAccount = await getAssociatedTokenAddress(pool.teamTokenMints[teamTokenIndex], bettor)
+  }
+
+  const method = program.methods
+    .placeBet(currentRoundId.toNumber(), matchIndices, outcomes, amountRaw)
+    .accounts({
+      bettingPool: bettingPoolPda,
+      roundAccounting: roundPda,
+      openLiability: openLiabilityPda,
+      dailyVolume: dailyVolumePda,
+      activeBettors: activeBettorsPda,
+      pendingClaims: pendingClaimsPda,
+      userVolume: userVolumePda,
+      bet: betPda,
+      bettorTokenAccount,
+      bettingPoolTokenAccount,
+      roundVault,
+      legFeeSchedule,
+      stakePolicy,
+      protocolTreasuryTokenAccount,
+      teamTokenAccount,
+      bettor,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+
+  return { method, betId, roundId: currentRoundId }
+}
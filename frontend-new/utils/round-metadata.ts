@@ -0,0 +1,58 @@
+import type { Program } from "@coral-xyz/anchor"
+import BN from "bn.js"
+import { getBettingPoolPda, getRoundMetadataPda } from "@/utils/pda"
+
+const IPFS_GATEWAY = process.env.NEXT_PUBLIC_IPFS_GATEWAY ?? "https://ipfs.io/ipfs/"
+
+/** Display metadata document a round's metadata URI points at */
+export interface RoundMetadataDocument {
+  league?: string
+  matches?: {
+    home: { name: string; logo?: string }
+    away: { name: string; logo?: string }
+  }[]
+  [key: string]: unknown
+}
+
+function resolveUri(uri: string): string {
+  return uri.startsWith("ipfs://") ? IPFS_GATEWAY + uri.slice("ipfs://".length) : uri
+}
+
+async function sha256(bytes: ArrayBuffer): Promise<Uint8Array> {
+  return new Uint8Array(await crypto.subtle.digest("SHA-256", bytes))
+}
+
+/**
+ * Fetch a round's metadata document and check it against the content hash
+ * stored on-chain. Returns null if the round has no metadata; throws if the
+ * document served at the URI doesn't match the hash.
+ */
+export async function fetchRoundMetadata(
+  program: Program,
+  roundId: BN | number
+): Promise<RoundMetadataDocument | null> {
+  const [bettingPoolPda] = getBettingPoolPda()
+  const [metadataPda] = getRoundMetadataPda(bettingPoolPda, roundId)
+
+  const account = (await program.account.roundMetadata.fetchNullable(metadataPda)) as {
+    uri: number[]
+    contentHash: number[]
+  } | null
+  if (!account) return null
+
+  const uriBytes = Uint8Array.from(account.uri)
+  const end = uriBytes.indexOf(0)
+  const uri = new TextDecoder().decode(end === -1 ? uriBytes : uriBytes.subarray(0, end))
+
+  const response = await fetch(resolveUri(uri))
+  if (!response.ok) throw new Error(`Round metadata unavailable (${response.status})`)
+  const body = await response.arrayBuffer()
+
+  const digest = await sha256(body)
+  const expected = Uint8Array.from(account.contentHash)
+  if (digest.length !== expected.length || digest.some((b, i) => b !== expected[i])) {
+    throw new Error("Round metadata does not match its on-chain hash")
+  }
+
+  return JSON.parse(new TextDecoder().decode(body)) as RoundMetadataDocument
+}
@@ -0,0 +1,76 @@
+import BN from "bn.js"
+import type { BetSlipEntry } from "@/types/sportsbook"
+
+/** API route that builds bet transactions for Solana Pay wallets */
+export const SOLANA_PAY_BET_PATH = "/api/solana-pay/bet"
+
+export interface BetRequestParams {
+  roundId: BN
+  matchIndices: number[]
+  outcomes: number[]
+  /** Total wager in raw token units */
+  amountRaw: BN
+}
+
+/**
+ * Encode a bet as a Solana Pay transaction-request URL
+ * (`solana:<https link>`). Render it with any QR encoder; a scanning
+ * wallet POSTs its address to `baseUrl` + SOLANA_PAY_BET_PATH and signs the
+ * placeBet transaction it gets back.
+ */
+export function encodeBetRequestUrl(
+  baseUrl: string,
+  { roundId, matchIndices, outcomes, amountRaw }: BetRequestParams
+): string {
+  const link = new URL(SOLANA_PAY_BET_PATH, baseUrl)
+  link.searchParams.set("round", roundId.toString())
+  link.searchParams.set("legs", matchIndices.map((m, i) => `${m}-${outcomes[i]}`).join(","))
+  link.searchParams.set("amount", amountRaw.toString())
+  return `solana:${encodeURIComponent(link.toString())}`
+}
+
+/** Solana Pay URL for the selections on a bet slip */
+export function betSlipRequestUrl(baseUrl: string, roundId: BN, selections: BetSlipEntry[], amountRaw: BN): string {
+  return encodeBetRequestUrl(baseUrl, {
+    roundId,
+    matchIndices: selections.map((s) => s.matchIndex),
+    outcomes: selections.map((s) => s.outcome),
+    amountRaw,
+  })
+}
+
+/**
+ * Parse and validate the query of a bet transaction request.
+ * Throws on anything placeBet would reject outright.
+ */
+export function decodeBetRequestParams(query: Record<string, string | string[] | undefined>): BetRequestParams {
+  const single = (key: string): string => {
+    const value = query[key]
+    if (typeof value !== "string" || value === "") throw new Error(`Missing ${key}`)
+    return value
+  }
+  const integer = (value: string, what: string): BN => {
+    if (!/^\d+$/.test(value)) throw new Error(`Invalid ${what}`)
+    return new BN(value)
+  }
+
+  const roundId = integer(single("round"), "round")
+  const amountRaw = integer(single("amount"), "amount")
+  if (amountRaw.isZero()) throw new Error("Amount must be positive")
+
+  const legs = single("legs").split(",")
+  if (legs.length > 10) throw new Error("Maximum 10 selections per bet")
+
+  const matchIndices: number[] = []
+  const outcomes: number[] = []
+  for (const leg of legs) {
+    const match = /^(\d+)-([123])$/.exec(leg)
+    if (!match) throw new Error(`Invalid selection ${leg}`)
+    const matchIndex = Number(match[1])
+    if (matchIndex > 9 || matchIndices.includes(matchIndex)) throw new Error(`Invalid selection ${leg}`)
+    matchIndices.push(matchIndex)
+    outcomes.push(Number(match[2]))
+  }
+
+  return { roundId, matchIndices, outcomes, amountRaw }
+}
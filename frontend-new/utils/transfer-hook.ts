@@ -0,0 +1,49 @@
+import type { AccountMeta, Connection, PublicKey } from "@solana/web3.js"
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createTransferCheckedWithTransferHookInstruction,
+  getMint,
+  getTransferHook,
+} from "@solana/spl-token"
+
+/** Token program that owns `mint` (SPL Token or Token-2022) */
+export async function getMintTokenProgram(connection: Connection, mint: PublicKey): Promise<PublicKey> {
+  const info = await connection.getAccountInfo(mint)
+  if (!info) throw new Error("Pool mint not found")
+  return info.owner.equals(TOKEN_2022_PROGRAM_ID) ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID
+}
+
+/**
+ * Accounts a Token-2022 transfer hook needs for a payout from the pool,
+ * to pass as remaining accounts on claims. Empty for mints without a hook.
+ *
+ * Resolved for the transfer to `destination`; hooks whose extra accounts
+ * don't depend on the destination (the common case) work for bounty and
+ * copy-leader transfers too.
+ */
+export async function getClaimHookAccounts(
+  connection: Connection,
+  mintAddress: PublicKey,
+  source: PublicKey,
+  destination: PublicKey,
+  poolAuthority: PublicKey
+): Promise<AccountMeta[]> {
+  const mint = await getMint(connection, mintAddress, "confirmed", TOKEN_2022_PROGRAM_ID)
+  if (!getTransferHook(mint)) return []
+
+  const transfer = await createTransferCheckedWithTransferHookInstruction(
+    connection,
+    source,
+    mintAddress,
+    destination,
+    poolAuthority,
+    BigInt(0),
+    mint.decimals,
+    [],
+    "confirmed",
+    TOKEN_2022_PROGRAM_ID
+  )
+  // Drop source, mint, destination and authority; the pool PDA signs by CPI
+  return transfer.keys.slice(4).map((key) => ({ ...key, isSigner: false }))
+}
@@ -0,0 +1,4 @@
+//! In-memory model of the program's settlement flow, shared by the
+//! `sequence-fuzz` binary and the lifecycle tests under `tests/`
+
+pub mod model;
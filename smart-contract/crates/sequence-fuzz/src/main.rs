@@ -0,0 +1,249 @@
+//! sequence-fuzz: random instruction sequences against the settlement code
+//!
+//! Generates valid-ish sequences of fund, create round, place, settle,
+//! claim, settle-lost and finalize instructions (plus time passing), runs
+//! them through the program's own handler logic (see `model`), and after
+//! every accepted instruction checks the pool's global invariants:
+//!
+//! - no bet is paid twice
+//! - the rounds' accounting matches the pool token balance
+//! - the pool balance covers every unclaimed payout and refund of settled
+//!   rounds
+//! - resolution counters and PendingClaims match the bets
+//!
+//! ```text
+//! sequence-fuzz [--seeds <n>] [--start <seed>] [--ops <n>]
+//! sequence-fuzz --seed <seed> [--ops <n>]      replay one seed, printing every step
+//! ```
+//!
+//! Sequences are fully determined by their seed, so a failure prints the
+//! seed to replay. Exits 0 when every sequence holds, 1 on a violation,
+//! 2 on bad arguments.
+
+use std::process::ExitCode;
+
+use anchor_lang::error::Error;
+
+use sequence_fuzz::model::{Op, Violation, World};
+use sportsbook::constants::{MATCHES_PER_ROUND, SEED_PER_ROUND};
+
+const DEFAULT_SEEDS: u64 = 1_000;
+const DEFAULT_OPS: usize = 200;
+
+/// Start of the simulated clock
+const START_TIME: i64 = 1_700_000_000;
+
+/// Bettors taking part in a sequence
+const BETTORS: u8 = 4;
+
+/// splitmix64; small, seedable and the same everywhere
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in 0..n
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    /// An index into a list of `len`, occasionally one past the end
+    fn index(&mut self, len: usize) -> usize {
+        if len == 0 || self.chance(5) {
+            len
+        } else {
+            self.below(len as u64) as usize
+        }
+    }
+}
+
+/// A bet that's waiting on a claim or loss settlement, or now and then
+/// any bet at all (claimed ones included, to try double claims)
+fn pick_bet(rng: &mut Rng, world: &World) -> usize {
+    let claimable: Vec<usize> = world
+        .bets
+        .iter()
+        .enumerate()
+        .filter(|(_, bet)| !bet.claimed && world.rounds[bet.round_id as usize].settled)
+        .map(|(bet_index, _)| bet_index)
+        .collect();
+    if claimable.is_empty() || rng.chance(15) {
+        rng.index(world.bets.len())
+    } else {
+        claimable[rng.below(claimable.len() as u64) as usize]
+    }
+}
+
+/// Next instruction, biased toward ones that make sense in `world`
+fn next_op(rng: &mut Rng, world: &World) -> Op {
+    let open_round = world.rounds.iter().rposition(|round| !round.settled);
+    match rng.below(100) {
+        0..=4 => Op::FundLiquidity { amount: SEED_PER_ROUND * (1 + rng.below(3)) },
+        5..=9 => Op::CreateRound,
+        10..=49 => {
+            let round = match open_round {
+                Some(round) if !rng.chance(5) => round,
+                None if !rng.chance(10) => {
+                    return if world.lp_balance < SEED_PER_ROUND {
+                        Op::FundLiquidity { amount: SEED_PER_ROUND * (1 + rng.below(3)) }
+                    } else {
+                        Op::CreateRound
+                    };
+                }
+                _ => rng.index(world.rounds.len()),
+            };
+            let leg_count = if rng.chance(60) { 1 } else { 2 + rng.below(4) as usize };
+            let legs = (0..leg_count)
+                .map(|_| (rng.below(MATCHES_PER_ROUND as u64) as u8, 1 + rng.below(3) as u8))
+                .collect();
+            // Mostly 1..100 tokens, sometimes whale-sized or zero
+            let amount = match rng.below(20) {
+                0 => 0,
+                1 => 1_000_000_000_000 + rng.below(9_000_000_000_000),
+                _ => 1_000_000_000 + rng.below(99_000_000_000),
+            };
+            Op::PlaceBet { round, bettor: rng.below(BETTORS as u64) as u8, legs, amount }
+        }
+        50..=57 => {
+            let round = open_round.unwrap_or_else(|| rng.index(world.rounds.len()));
+            let mut results = [0u8; MATCHES_PER_ROUND];
+            for result in results.iter_mut() {
+                // Void now and then
+                *result = if rng.chance(5) { 4 } else { 1 + rng.below(3) as u8 };
+            }
+            Op::Settle { round, results }
+        }
+        58..=79 => Op::Claim { bet: pick_bet(rng, world), by_bettor: !rng.chance(20) },
+        80..=87 => Op::SettleLost { bet: pick_bet(rng, world) },
+        88..=92 => Op::Finalize { round: rng.index(world.rounds.len()) },
+        _ => Op::Wait { seconds: [60, 3_600, 86_400, 90_000][rng.below(4) as usize] },
+    }
+}
+
+/// Run the sequence for `seed`, stopping at the first broken invariant
+fn run_sequence(seed: u64, ops: usize, verbose: bool) -> Result<World, Violation> {
+    let mut rng = Rng(seed);
+    let mut world = World::new(START_TIME);
+
+    for op_index in 0..ops {
+        let op = next_op(&mut rng, &world);
+        let outcome = world.apply(&op);
+        if verbose {
+            match &outcome {
+                Ok(()) => println!("{:>4} ok       {:?}", op_index, op),
+                Err(Error::AnchorError(e)) => {
+                    println!("{:>4} rejected {:?}: {}", op_index, op, e.error_name)
+                }
+                Err(e) => println!("{:>4} rejected {:?}: {}", op_index, op, e),
+            }
+        }
+        if outcome.is_ok() {
+            world
+                .check_invariants()
+                .map_err(|message| Violation { op_index, message })?;
+        }
+    }
+
+    Ok(world)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut seeds = DEFAULT_SEEDS;
+    let mut start = 0u64;
+    let mut ops = DEFAULT_OPS;
+    let mut replay = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1).and_then(|value| value.parse::<u64>().ok());
+        match (args[i].as_str(), value) {
+            ("--seeds", Some(value)) => seeds = value,
+            ("--start", Some(value)) => start = value,
+            ("--ops", Some(value)) => ops = value as usize,
+            ("--seed", Some(value)) => replay = Some(value),
+            _ => {
+                eprintln!("usage: sequence-fuzz [--seeds <n>] [--start <seed>] [--ops <n>] | --seed <seed>");
+                return ExitCode::from(2);
+            }
+        }
+        i += 2;
+    }
+
+    if let Some(seed) = replay {
+        return match run_sequence(seed, ops, true) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(violation) => {
+                println!("violation at op {}: {}", violation.op_index, violation.message);
+                ExitCode::from(1)
+            }
+        };
+    }
+
+    for seed in start..start + seeds {
+        if let Err(violation) = run_sequence(seed, ops, false) {
+            println!(
+                "seed {}: violation at op {}: {}",
+                seed, violation.op_index, violation.message
+            );
+            println!("replay with: sequence-fuzz --seed {} --ops {}", seed, ops);
+            return ExitCode::from(1);
+        }
+    }
+
+    println!("{} sequences of {} instructions, no violations", seeds, ops);
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_seeds_keep_invariants() {
+        for seed in 0..64 {
+            if let Err(violation) = run_sequence(seed, DEFAULT_OPS, false) {
+                panic!("seed {}: op {}: {}", seed, violation.op_index, violation.message);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sequences_reach_claims_and_finalization() {
+        let worlds: Vec<World> = (0..16)
+            .map(|seed| run_sequence(seed, DEFAULT_OPS, false).expect("invariants hold"))
+            .collect();
+        assert!(worlds.iter().any(|world| world.payments.contains(&1)));
+        assert!(worlds
+            .iter()
+            .any(|world| world.rounds.iter().any(|round| round.revenue_distributed)));
+    }
+
+    #[test]
+    fn test_double_claim_rejected() {
+        let mut world = World::new(START_TIME);
+        world.apply(&Op::FundLiquidity { amount: SEED_PER_ROUND * 3 }).unwrap();
+        world.apply(&Op::CreateRound).unwrap();
+        world
+            .apply(&Op::PlaceBet { round: 0, bettor: 0, legs: vec![(0, 1)], amount: 10_000_000_000 })
+            .unwrap();
+        world.apply(&Op::Settle { round: 0, results: [1; MATCHES_PER_ROUND] }).unwrap();
+
+        world.apply(&Op::Claim { bet: 0, by_bettor: true }).unwrap();
+        let balance = world.pool_balance;
+        assert!(world.apply(&Op::Claim { bet: 0, by_bettor: true }).is_err());
+        assert_eq!(world.pool_balance, balance);
+        assert_eq!(world.payments, vec![1]);
+        world.check_invariants().unwrap();
+    }
+}
@@ -0,0 +1,444 @@
+//! In-memory pool driven through the program's own settlement code
+//!
+//! Each operation checks the same account constraints and `require!`s as
+//! its instruction, then runs the program's functions on real
+//! `RoundAccounting`, `Bet` and `PendingClaims` values. Token accounts are
+//! plain balances. A rejected operation leaves the world untouched, like a
+//! failed transaction.
+
+use anchor_lang::prelude::*;
+use sportsbook::clock::FixedClock;
+use sportsbook::constants::*;
+use sportsbook::errors::SportsbookError;
+use sportsbook::instructions::{
+    apply_match_results, bounty_split, calculate_bet_payout, voided_bet_refund, ClaimWindow,
+};
+use sportsbook::state::{Bet, PendingClaims, Prediction, RoundAccounting};
+use sportsbook::utils::parlay::{
+    calculate_max_payout, calculate_odds_weighted_allocations, calculate_parlay_multiplier_dynamic,
+};
+use sportsbook::utils::seeding::calculate_pseudo_random_seeds;
+use sportsbook_core::{
+    calculate_locked_odds_from_seeds, calculate_potential_payout, calculate_protocol_fee,
+    calculate_revenue_split,
+};
+
+/// finalize_round_revenue's buffer after the claim deadline
+const FINALIZE_BUFFER_SECONDS: i64 = 3600;
+
+/// Season pool share used for finalization
+const SEASON_POOL_SHARE_BPS: u16 = 200;
+
+/// One instruction, with its accounts picked by index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// fund_liquidity_pool
+    FundLiquidity { amount: u64 },
+    /// initialize_round followed by seed_round_pools
+    CreateRound,
+    /// place_bet; `legs` are (match_index, outcome)
+    PlaceBet { round: usize, bettor: u8, legs: Vec<(u8, u8)>, amount: u64 },
+    /// settle_round
+    Settle { round: usize, results: [u8; MATCHES_PER_ROUND] },
+    /// claim_winnings, by the bettor or by a third party
+    Claim { bet: usize, by_bettor: bool },
+    /// settle_losing_bet
+    SettleLost { bet: usize },
+    /// finalize_round_revenue
+    Finalize { round: usize },
+    /// Let cluster time pass
+    Wait { seconds: i64 },
+}
+
+/// Why an operation was rejected
+pub type Rejection = anchor_lang::error::Error;
+
+/// A broken invariant, with the operation that broke it
+#[derive(Debug)]
+pub struct Violation {
+    pub op_index: usize,
+    pub message: String,
+}
+
+#[derive(Clone)]
+pub struct World {
+    pub now: i64,
+
+    /// Liquidity pool token account
+    pub lp_balance: u64,
+
+    /// Betting pool token account
+    pub pool_balance: u64,
+
+    pub pending_claims: PendingClaims,
+    pub rounds: Vec<RoundAccounting>,
+    pub bets: Vec<Bet>,
+
+    /// Times each bet has been paid (payout or refund); never above 1
+    pub payments: Vec<u32>,
+}
+
+impl World {
+    pub fn new(start_time: i64) -> Self {
+        World {
+            now: start_time,
+            lp_balance: 0,
+            pool_balance: 0,
+            pending_claims: zeroed(),
+            rounds: Vec::new(),
+            bets: Vec::new(),
+            payments: Vec::new(),
+        }
+    }
+
+    /// Run `op` as one transaction: all of it or none of it
+    pub fn apply(&mut self, op: &Op) -> std::result::Result<(), Rejection> {
+        let mut next = self.clone();
+        match op {
+            Op::FundLiquidity { amount } => next.fund_liquidity(*amount),
+            Op::CreateRound => next.create_round(),
+            Op::PlaceBet { round, bettor, legs, amount } => next.place_bet(*round, *bettor, legs, *amount),
+            Op::Settle { round, results } => next.settle(*round, results),
+            Op::Claim { bet, by_bettor } => next.claim(*bet, *by_bettor),
+            Op::SettleLost { bet } => next.settle_lost(*bet),
+            Op::Finalize { round } => next.finalize(*round),
+            Op::Wait { seconds } => {
+                next.now += seconds;
+                Ok(())
+            }
+        }?;
+        *self = next;
+        Ok(())
+    }
+
+    fn fund_liquidity(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, SportsbookError::InvalidAmount);
+        self.lp_balance = self.lp_balance.checked_add(amount).ok_or(SportsbookError::CalculationOverflow)?;
+        Ok(())
+    }
+
+    fn create_round(&mut self) -> Result<()> {
+        let round_id = self.rounds.len() as u64;
+        let mut round: RoundAccounting = zeroed();
+        round.round_id = round_id;
+        round.round_start_time = self.now;
+
+        let mut total_seed_amount = 0u64;
+        for match_index in 0..MATCHES_PER_ROUND {
+            let (home_seed, away_seed, draw_seed) = calculate_pseudo_random_seeds(
+                (match_index as u64) * 2,
+                (match_index as u64) * 2 + 1,
+                round_id,
+            );
+            let pool = &mut round.match_pools[match_index];
+            pool.home_win_pool = home_seed;
+            pool.away_win_pool = away_seed;
+            pool.draw_pool = draw_seed;
+            pool.total_pool = home_seed + away_seed + draw_seed;
+            total_seed_amount += pool.total_pool;
+            round.total_bet_volume += pool.total_pool;
+
+            let (home_odds, away_odds, draw_odds) =
+                calculate_locked_odds_from_seeds(home_seed, away_seed, draw_seed);
+            let locked_odds = &mut round.locked_odds[match_index];
+            locked_odds.home_odds = home_odds;
+            locked_odds.away_odds = away_odds;
+            locked_odds.draw_odds = draw_odds;
+            locked_odds.locked = true;
+        }
+
+        require!(self.lp_balance >= total_seed_amount, SportsbookError::InsufficientLPLiquidity);
+        self.lp_balance -= total_seed_amount;
+        self.pool_balance += total_seed_amount;
+        round.protocol_seed_amount = total_seed_amount;
+        round.seeded = true;
+        self.rounds.push(round);
+        Ok(())
+    }
+
+    fn place_bet(&mut self, round_index: usize, bettor: u8, legs: &[(u8, u8)], amount: u64) -> Result<()> {
+        let round = self.rounds.get_mut(round_index).ok_or(SportsbookError::InvalidRoundId)?;
+        require!(round.seeded, SportsbookError::RoundNotSeeded);
+        require!(!round.settled, SportsbookError::RoundAlreadySettled);
+        require!(amount > 0, SportsbookError::InvalidAmount);
+        require!(amount <= MAX_BET_AMOUNT, SportsbookError::BetExceedsMaximum);
+        require!(
+            !legs.is_empty() && legs.len() <= MATCHES_PER_ROUND,
+            SportsbookError::InvalidBetCount
+        );
+        for (match_index, outcome) in legs {
+            require!((*match_index as usize) < MATCHES_PER_ROUND, SportsbookError::InvalidMatchIndex);
+            require!((1..=3).contains(outcome), SportsbookError::InvalidOutcome);
+        }
+
+        let match_indices: Vec<u8> = legs.iter().map(|(match_index, _)| *match_index).collect();
+        let outcomes: Vec<u8> = legs.iter().map(|(_, outcome)| *outcome).collect();
+        let leg_odds: Vec<u64> = legs
+            .iter()
+            .map(|(match_index, outcome)| round.locked_odds[*match_index as usize].get_odds(*outcome))
+            .collect();
+
+        let (protocol_fee, _) =
+            calculate_protocol_fee(amount, DEFAULT_PROTOCOL_FEE_BPS).map_err(SportsbookError::from)?;
+        let amount_after_fee = amount - protocol_fee;
+        round.protocol_fee_collected += protocol_fee;
+        round.total_bet_volume += amount_after_fee;
+        round.total_user_deposits += amount_after_fee;
+
+        let parlay_multiplier =
+            calculate_parlay_multiplier_dynamic(round, &match_indices, legs.len() as u8);
+        let max_possible_payout = calculate_max_payout(amount_after_fee, legs.len() as u8, parlay_multiplier);
+        // The handler reads the token account as loaded, before the stake
+        // transfer
+        require!(
+            self.pool_balance >= max_possible_payout,
+            SportsbookError::InsufficientProtocolLiquidity
+        );
+        round.bets_placed = round.bets_placed.saturating_add(1);
+        if legs.len() > 1 {
+            round.parlay_count += 1;
+        }
+
+        let (allocations, total_allocated, _) = calculate_odds_weighted_allocations(
+            round,
+            &match_indices,
+            &outcomes,
+            amount_after_fee,
+            parlay_multiplier,
+        )
+        .map_err(|_| SportsbookError::CalculationOverflow)?;
+        let priced: Vec<(u64, u64)> = allocations.iter().copied().zip(leg_odds.iter().copied()).collect();
+        let potential_payout = calculate_potential_payout(&priced, parlay_multiplier)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        require!(potential_payout <= MAX_PAYOUT_PER_BET, SportsbookError::PayoutExceedsCap);
+
+        let mut bet: Bet = zeroed();
+        bet.bettor = bettor_key(bettor);
+        bet.round_id = round.round_id;
+        bet.bet_id = self.bets.len() as u64;
+        bet.amount = amount;
+        bet.amount_after_fee = amount_after_fee;
+        bet.allocated_amount = total_allocated;
+        bet.locked_multiplier = parlay_multiplier;
+        bet.num_predictions = legs.len() as u8;
+        bet.potential_payout = potential_payout;
+        bet.fee_bps = DEFAULT_PROTOCOL_FEE_BPS;
+        for (i, (match_index, outcome)) in legs.iter().enumerate() {
+            bet.predictions[i] = Prediction {
+                match_index: *match_index,
+                predicted_outcome: *outcome,
+                amount_in_pool: allocations[i],
+            };
+            round.match_pools[*match_index as usize].add_to_pool(*outcome, allocations[i])?;
+        }
+
+        self.pool_balance += amount_after_fee;
+        self.pending_claims.record_bet(potential_payout);
+        self.bets.push(bet);
+        self.payments.push(0);
+        Ok(())
+    }
+
+    fn settle(&mut self, round_index: usize, results: &[u8; MATCHES_PER_ROUND]) -> Result<()> {
+        let round = self.rounds.get_mut(round_index).ok_or(SportsbookError::InvalidRoundId)?;
+        require!(round.seeded, SportsbookError::RoundNotSeeded);
+        require!(!round.settled, SportsbookError::RoundAlreadySettled);
+        apply_match_results(round, results, self.now, None, None)
+    }
+
+    fn claim(&mut self, bet_index: usize, by_bettor: bool) -> Result<()> {
+        let bet = self.bets.get_mut(bet_index).ok_or(SportsbookError::InvalidBetAccount)?;
+        let round = &mut self.rounds[bet.round_id as usize];
+        require!(round.settled, SportsbookError::RoundNotSettled);
+        require!(!bet.claimed, SportsbookError::BetAlreadyClaimed);
+
+        let window = ClaimWindow::evaluate(&FixedClock(self.now), round, by_bettor)?;
+        require!(!window.paused, SportsbookError::ClaimsPaused);
+        require!(window.claimer_allowed, SportsbookError::NotBettor);
+
+        let refund = voided_bet_refund(bet, round);
+        let (won, _, final_payout) = match refund {
+            Some(_) => (false, 0, 0),
+            None => calculate_bet_payout(bet, round)?,
+        };
+
+        bet.claimed = true;
+        bet.settled = true;
+        round.record_bet_resolved();
+
+        let mut paid = 0;
+        let mut bounty = 0;
+        if won && final_payout > 0 {
+            require!(
+                round.total_paid_out + final_payout <= MAX_ROUND_PAYOUTS,
+                SportsbookError::RoundPayoutLimitReached
+            );
+            round.total_claimed += final_payout;
+            round.total_paid_out += final_payout;
+
+            let (bettor_amount, bounty_amount) =
+                bounty_split(final_payout, window.is_bounty_claim, round.protocol_funded_bounty)?;
+            let total_out = bettor_amount + bounty_amount;
+            require!(self.pool_balance >= total_out, SportsbookError::InsufficientProtocolLiquidity);
+            self.pool_balance -= total_out;
+            paid = final_payout;
+            bounty = bounty_amount;
+        } else if let Some(refund) = refund {
+            round.total_refunded += refund;
+            require!(self.pool_balance >= refund, SportsbookError::InsufficientProtocolLiquidity);
+            self.pool_balance -= refund;
+            paid = refund;
+        }
+
+        if paid > 0 {
+            self.payments[bet_index] += 1;
+        }
+        self.pending_claims.record_resolution(bet.potential_payout, paid, bounty);
+        Ok(())
+    }
+
+    fn settle_lost(&mut self, bet_index: usize) -> Result<()> {
+        let bet = self.bets.get_mut(bet_index).ok_or(SportsbookError::InvalidBetAccount)?;
+        let round = &mut self.rounds[bet.round_id as usize];
+        require!(round.settled, SportsbookError::RoundNotSettled);
+        require!(!bet.claimed, SportsbookError::BetAlreadyClaimed);
+        require!(!round.claims_blocked(self.now), SportsbookError::ClaimsPaused);
+
+        let (won, _, _) = calculate_bet_payout(bet, round)?;
+        require!(!won, SportsbookError::BetNotLost);
+
+        bet.claimed = true;
+        bet.settled = true;
+        round.record_bet_resolved();
+        self.pending_claims.record_resolution(bet.potential_payout, 0, 0);
+        Ok(())
+    }
+
+    fn finalize(&mut self, round_index: usize) -> Result<()> {
+        let round = self.rounds.get_mut(round_index).ok_or(SportsbookError::InvalidRoundId)?;
+        require!(round.settled, SportsbookError::RoundNotSettled);
+        require!(!round.claims_blocked(self.now), SportsbookError::ClaimsPaused);
+        require!(
+            self.now >= round.claim_deadline() + FINALIZE_BUFFER_SECONDS,
+            SportsbookError::RevenueDistributedBeforeClaims
+        );
+
+        if !round.revenue_allocated {
+            let total_paid = round
+                .total_paid_out
+                .saturating_add(round.total_refunded)
+                .saturating_add(round.protocol_bounties_paid);
+            let split = calculate_revenue_split(
+                round.total_user_deposits,
+                round.protocol_fee_collected,
+                total_paid,
+                SEASON_POOL_SHARE_BPS,
+                self.pool_balance,
+            )
+            .map_err(SportsbookError::from)?;
+            round.protocol_revenue_share = split.protocol_revenue;
+            round.season_revenue_share = split.season_share;
+            round.revenue_allocated = true;
+        }
+        // Shared pool: the shares are held in the pool account, nothing moves
+        round.revenue_distributed = true;
+        Ok(())
+    }
+
+    /// Every invariant the program has to keep, whatever the order of
+    /// instructions that got it here
+    pub fn check_invariants(&self) -> std::result::Result<(), String> {
+        // No bet is ever paid twice
+        if let Some(bet_index) = self.payments.iter().position(|payments| *payments > 1) {
+            return Err(format!("bet {} paid {} times", bet_index, self.payments[bet_index]));
+        }
+
+        // Round accounting accounts for every token in the pool; one round
+        // may pay out more than it took in, so only the sum has to balance
+        let taken_in: u64 = self
+            .rounds
+            .iter()
+            .map(|round| round.protocol_seed_amount + round.total_user_deposits)
+            .sum();
+        let paid_out: u64 = self
+            .rounds
+            .iter()
+            .map(|round| round.total_paid_out + round.total_refunded + round.protocol_bounties_paid)
+            .sum();
+        let accounted = taken_in.checked_sub(paid_out);
+        if accounted != Some(self.pool_balance) {
+            return Err(format!(
+                "round accounting holds {:?}, pool account holds {}",
+                accounted, self.pool_balance
+            ));
+        }
+
+        // Pool balance covers what settled rounds still owe
+        let mut owed = 0u64;
+        for bet in self.bets.iter().filter(|bet| !bet.claimed) {
+            let round = &self.rounds[bet.round_id as usize];
+            if !round.settled {
+                continue;
+            }
+            owed += match voided_bet_refund(bet, round) {
+                Some(refund) => refund,
+                None => match calculate_bet_payout(bet, round) {
+                    Ok((true, _, payout)) => payout,
+                    Ok(_) => 0,
+                    Err(e) => return Err(format!("bet {} can't be priced: {}", bet.bet_id, e)),
+                },
+            };
+        }
+        if self.pool_balance < owed {
+            return Err(format!(
+                "pool holds {} but settled rounds owe {}",
+                self.pool_balance, owed
+            ));
+        }
+
+        // Resolution counters match the bets
+        for round in &self.rounds {
+            let resolved = self
+                .bets
+                .iter()
+                .filter(|bet| bet.round_id == round.round_id && bet.claimed)
+                .count() as u32;
+            if round.bets_resolved != resolved || round.bets_resolved > round.bets_placed {
+                return Err(format!(
+                    "round {} counts {} of {} bets resolved, {} actually are",
+                    round.round_id, round.bets_resolved, round.bets_placed, resolved
+                ));
+            }
+            if round.total_paid_out > MAX_ROUND_PAYOUTS {
+                return Err(format!("round {} paid out {}", round.round_id, round.total_paid_out));
+            }
+        }
+
+        let unresolved: Vec<&Bet> = self.bets.iter().filter(|bet| !bet.claimed).collect();
+        let unresolved_potential: u64 = unresolved.iter().map(|bet| bet.potential_payout).sum();
+        if self.pending_claims.unresolved_bets != unresolved.len() as u64
+            || self.pending_claims.unresolved_potential_payout != unresolved_potential
+        {
+            return Err(format!(
+                "pending claims show {} bets / {} owed, {} / {} are unresolved",
+                self.pending_claims.unresolved_bets,
+                self.pending_claims.unresolved_potential_payout,
+                unresolved.len(),
+                unresolved_potential
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Deterministic key for bettor number `index`
+pub fn bettor_key(index: u8) -> Pubkey {
+    Pubkey::new_from_array([index.wrapping_add(1); 32])
+}
+
+/// An account as `init` leaves it: all zeroes, before the handler fills it in
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = [0u8; 2048];
+    T::deserialize(&mut &data[..]).expect("zeroed account data deserializes")
+}
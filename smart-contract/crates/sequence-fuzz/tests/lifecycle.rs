@@ -0,0 +1,123 @@
+//! A round's full lifecycle, end to end through the program's handler logic:
+//! fund the pool, create a round, place parlays, settle, claim inside the
+//! claim window, bounty-claim after it, settle the losers and finalize the
+//! revenue. Pool invariants are checked after every step.
+
+use sequence_fuzz::model::{Op, World};
+use sportsbook::constants::{CLAIM_WINDOW_SECONDS, MATCHES_PER_ROUND, SEED_PER_ROUND};
+use sportsbook::instructions::calculate_bet_payout;
+use sportsbook_core::split_bounty;
+
+const START_TIME: i64 = 1_700_000_000;
+
+const STAKE: u64 = 10_000_000_000;
+
+/// Home, away, draw, home, then home for the rest
+fn results() -> [u8; MATCHES_PER_ROUND] {
+    let mut results = [1; MATCHES_PER_ROUND];
+    results[1] = 2;
+    results[2] = 3;
+    results
+}
+
+fn apply(world: &mut World, op: Op) {
+    if let Err(e) = world.apply(&op) {
+        panic!("{:?} rejected: {}", op, e);
+    }
+    world.check_invariants().unwrap();
+}
+
+/// A settled round with three parlays: bets 0 and 1 won, bet 2 lost
+fn settled_round() -> World {
+    let mut world = World::new(START_TIME);
+    apply(&mut world, Op::FundLiquidity { amount: SEED_PER_ROUND * 2 });
+    apply(&mut world, Op::CreateRound);
+    apply(&mut world, Op::PlaceBet { round: 0, bettor: 0, legs: vec![(0, 1), (1, 2)], amount: STAKE });
+    apply(&mut world, Op::PlaceBet { round: 0, bettor: 1, legs: vec![(2, 3), (3, 1)], amount: STAKE });
+    apply(&mut world, Op::PlaceBet { round: 0, bettor: 2, legs: vec![(0, 2), (1, 2)], amount: STAKE });
+    assert_eq!(world.rounds[0].parlay_count, 3);
+    assert_eq!(world.pending_claims.unresolved_bets, 3);
+
+    apply(&mut world, Op::Settle { round: 0, results: results() });
+    assert!(world.rounds[0].settled);
+    world
+}
+
+fn payout(world: &World, bet: usize) -> u64 {
+    let (won, _, payout) = calculate_bet_payout(&world.bets[bet], &world.rounds[0]).unwrap();
+    assert!(won, "bet {} should have won", bet);
+    payout
+}
+
+#[test]
+fn test_round_lifecycle() {
+    let mut world = settled_round();
+
+    // Inside the window the bettor claims the full payout
+    let payout_0 = payout(&world, 0);
+    let balance = world.pool_balance;
+    apply(&mut world, Op::Claim { bet: 0, by_bettor: true });
+    assert_eq!(world.pool_balance, balance - payout_0);
+    assert_eq!(world.payments[0], 1);
+    assert_eq!(world.pending_claims.total_bounty_paid, 0);
+
+    // After the window anyone can claim bet 1 and keep the bounty
+    let deadline = world.rounds[0].claim_deadline();
+    assert_eq!(deadline, world.rounds[0].round_end_time + CLAIM_WINDOW_SECONDS);
+    let past_deadline = deadline - world.now + 1;
+    apply(&mut world, Op::Wait { seconds: past_deadline });
+    let payout_1 = payout(&world, 1);
+    let (_, bounty) = split_bounty(payout_1).unwrap();
+    assert!(bounty > 0);
+    let balance = world.pool_balance;
+    apply(&mut world, Op::Claim { bet: 1, by_bettor: false });
+    assert_eq!(world.pool_balance, balance - payout_1);
+    assert_eq!(world.pending_claims.total_bounty_paid, bounty);
+    assert_eq!(world.pending_claims.total_paid_out, payout_0 + payout_1);
+
+    // The losing parlay is closed out without paying anything
+    let balance = world.pool_balance;
+    apply(&mut world, Op::SettleLost { bet: 2 });
+    assert_eq!(world.pool_balance, balance);
+    assert_eq!(world.payments, vec![1, 1, 0]);
+    assert_eq!(world.rounds[0].bets_resolved, 3);
+    assert_eq!(world.pending_claims.unresolved_bets, 0);
+
+    // Revenue is finalized once the buffer after the deadline has passed
+    apply(&mut world, Op::Wait { seconds: 3600 });
+    apply(&mut world, Op::Finalize { round: 0 });
+    let round = &world.rounds[0];
+    assert!(round.revenue_allocated && round.revenue_distributed);
+    assert_eq!(round.total_paid_out, payout_0 + payout_1);
+}
+
+#[test]
+fn test_lifecycle_rejects_out_of_order_steps() {
+    let mut world = World::new(START_TIME);
+    apply(&mut world, Op::FundLiquidity { amount: SEED_PER_ROUND * 2 });
+    apply(&mut world, Op::CreateRound);
+    apply(&mut world, Op::PlaceBet { round: 0, bettor: 0, legs: vec![(0, 1), (1, 2)], amount: STAKE });
+    // Nothing is claimable or final before settlement
+    assert!(world.apply(&Op::Claim { bet: 0, by_bettor: true }).is_err());
+    assert!(world.apply(&Op::Finalize { round: 0 }).is_err());
+
+    let mut world = settled_round();
+    // No betting into a settled round
+    assert!(world
+        .apply(&Op::PlaceBet { round: 0, bettor: 3, legs: vec![(4, 1), (5, 1)], amount: STAKE })
+        .is_err());
+    // Inside the window only the bettor may claim
+    assert!(world.apply(&Op::Claim { bet: 0, by_bettor: false }).is_err());
+    // A loser has nothing to claim, a winner can't be settled as lost
+    apply(&mut world, Op::Claim { bet: 2, by_bettor: true });
+    assert_eq!(world.payments[2], 0);
+    assert!(world.apply(&Op::SettleLost { bet: 0 }).is_err());
+    // Revenue waits for the claim window and its buffer
+    assert!(world.apply(&Op::Finalize { round: 0 }).is_err());
+    let past_deadline = world.rounds[0].claim_deadline() - world.now + 1;
+    apply(&mut world, Op::Wait { seconds: past_deadline });
+    assert!(world.apply(&Op::Finalize { round: 0 }).is_err());
+
+    assert_eq!(world.pending_claims.unresolved_bets, 2);
+    world.check_invariants().unwrap();
+}
@@ -0,0 +1,267 @@
+//! sim-replay: recompute a simulated round and diff it against the chain
+//!
+//! Reads a round snapshot and replays the simulation with
+//! `sportsbook_core::simulate_round`, the same code the program runs at
+//! fulfill_vrf_request, then compares every outcome with the round's
+//! on-chain `match_results`.
+//!
+//! ```text
+//! sim-replay <snapshot-file | ->
+//! ```
+//!
+//! The snapshot is plain text, one entry per line (`#` starts a comment):
+//!
+//! ```text
+//! randomness <640 hex chars>                  VrfRequest.randomness
+//! model <base_goals_milli> <home_advantage> <rating_scale> <max_goals>
+//!                                             RoundFixtures.goal_model
+//! fixture <home> <away> <home_rating> <away_rating>
+//!                                             RoundFixtures.fixtures[i], in order
+//! results <r0> .. <r9>                        RoundAccounting.match_results
+//!                                             (1 home, 2 away, 3 draw, 0 pending)
+//! ```
+//!
+//! Rounds that didn't come from a league have no `model` or `fixture` lines.
+//! Exits 0 when every outcome matches, 1 on any mismatch, 2 on bad input.
+
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use sportsbook_core::{
+    simulate_round, GoalModel, SimulatedMatch, ROUND_RANDOMNESS_LEN, SIMULATED_MATCHES,
+};
+
+/// Everything needed to replay one round
+#[derive(Debug)]
+struct Snapshot {
+    randomness: [u8; ROUND_RANDOMNESS_LEN],
+    model: Option<GoalModel>,
+    /// (home_team, away_team, home_rating, away_rating)
+    fixtures: Vec<(u8, u8, u16, u16)>,
+    results: [u8; SIMULATED_MATCHES],
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 2 {
+        eprintln!("usage: sim-replay <snapshot-file | ->");
+        return ExitCode::from(2);
+    }
+
+    let input = if args[1] == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer).map(|_| buffer)
+    } else {
+        fs::read_to_string(&args[1])
+    };
+    let snapshot = match input.map_err(|e| e.to_string()).and_then(|text| parse_snapshot(&text)) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            eprintln!("sim-replay: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let simulated = replay(&snapshot);
+    let mismatches = report(&snapshot, &simulated);
+    if mismatches == 0 {
+        println!("OK: all {} outcomes match", SIMULATED_MATCHES);
+        ExitCode::SUCCESS
+    } else {
+        println!("MISMATCH: {} of {} outcomes differ", mismatches, SIMULATED_MATCHES);
+        ExitCode::from(1)
+    }
+}
+
+fn replay(snapshot: &Snapshot) -> [SimulatedMatch; SIMULATED_MATCHES] {
+    let ratings: Vec<(u16, u16)> = snapshot
+        .fixtures
+        .iter()
+        .map(|&(_, _, home_rating, away_rating)| (home_rating, away_rating))
+        .collect();
+    let model = snapshot.model.unwrap_or(GoalModel::DEFAULT);
+    simulate_round(&model, &ratings, &snapshot.randomness)
+}
+
+/// Print one line per match and return how many outcomes differ
+fn report(snapshot: &Snapshot, simulated: &[SimulatedMatch; SIMULATED_MATCHES]) -> usize {
+    let mut mismatches = 0;
+    for (i, (simulated, on_chain)) in simulated.iter().zip(snapshot.results.iter()).enumerate() {
+        let matchup = match snapshot.fixtures.get(i) {
+            Some((home, away, home_rating, away_rating)) => {
+                format!("team {:>2} ({}) v team {:>2} ({})", home, home_rating, away, away_rating)
+            }
+            None => "unweighted".to_string(),
+        };
+        let score = match simulated.score {
+            Some((home_goals, away_goals)) => format!("{}-{}", home_goals, away_goals),
+            None => "-".to_string(),
+        };
+        let status = if simulated.outcome == *on_chain {
+            "ok"
+        } else {
+            mismatches += 1;
+            "MISMATCH"
+        };
+        println!(
+            "match {:>2}  {:<34} score {:>5}  simulated {}  on-chain {}  {}",
+            i,
+            matchup,
+            score,
+            outcome_name(simulated.outcome),
+            outcome_name(*on_chain),
+            status
+        );
+    }
+    mismatches
+}
+
+fn outcome_name(outcome: u8) -> &'static str {
+    match outcome {
+        1 => "home",
+        2 => "away",
+        3 => "draw",
+        _ => "none",
+    }
+}
+
+fn parse_snapshot(text: &str) -> Result<Snapshot, String> {
+    let mut randomness = None;
+    let mut model = None;
+    let mut fixtures = Vec::new();
+    let mut results = None;
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let key = fields.next().unwrap_or("");
+        let values: Vec<&str> = fields.collect();
+        let context = |e: String| format!("line {}: {}", number + 1, e);
+
+        match key {
+            "randomness" => {
+                let [hex] = values[..] else {
+                    return Err(context("expected one hex string".to_string()));
+                };
+                randomness = Some(parse_hex(hex).map_err(context)?);
+            }
+            "model" => {
+                let [base_goals_milli, home_advantage, rating_scale, max_goals] =
+                    parse_numbers::<4>(&values).map_err(context)?;
+                let parsed = GoalModel {
+                    base_goals_milli: base_goals_milli as u16,
+                    home_advantage: home_advantage as u16,
+                    rating_scale: rating_scale as u16,
+                    max_goals: u8::try_from(max_goals).unwrap_or(0),
+                };
+                if !parsed.is_valid() {
+                    return Err(context("goal model out of range".to_string()));
+                }
+                model = Some(parsed);
+            }
+            "fixture" => {
+                let [home, away, home_rating, away_rating] =
+                    parse_numbers::<4>(&values).map_err(context)?;
+                if fixtures.len() == SIMULATED_MATCHES {
+                    return Err(context(format!("more than {} fixtures", SIMULATED_MATCHES)));
+                }
+                fixtures.push((home as u8, away as u8, home_rating as u16, away_rating as u16));
+            }
+            "results" => {
+                let parsed = parse_numbers::<SIMULATED_MATCHES>(&values).map_err(context)?;
+                results = Some(parsed.map(|result| result as u8));
+            }
+            _ => return Err(context(format!("unknown entry '{}'", key))),
+        }
+    }
+
+    if !fixtures.is_empty() && model.is_none() {
+        return Err("fixtures given without the round's goal model".to_string());
+    }
+
+    Ok(Snapshot {
+        randomness: randomness.ok_or("missing randomness")?,
+        model,
+        fixtures,
+        results: results.ok_or("missing results")?,
+    })
+}
+
+fn parse_hex(hex: &str) -> Result<[u8; ROUND_RANDOMNESS_LEN], String> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() != ROUND_RANDOMNESS_LEN * 2 {
+        return Err(format!(
+            "randomness must be {} hex chars, got {}",
+            ROUND_RANDOMNESS_LEN * 2,
+            hex.len()
+        ));
+    }
+
+    let mut bytes = [0u8; ROUND_RANDOMNESS_LEN];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|e| e.to_string())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| format!("invalid hex '{}'", pair))?;
+    }
+    Ok(bytes)
+}
+
+/// Exactly N numbers that each fit in a u16
+fn parse_numbers<const N: usize>(values: &[&str]) -> Result<[u64; N], String> {
+    if values.len() != N {
+        return Err(format!("expected {} numbers, got {}", N, values.len()));
+    }
+
+    let mut numbers = [0u64; N];
+    for (number, value) in numbers.iter_mut().zip(values) {
+        *number = value
+            .parse::<u16>()
+            .map_err(|_| format!("invalid number '{}'", value))? as u64;
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot_text(results: &str) -> String {
+        format!(
+            "# round 7\nrandomness {}\nmodel 1350 60 400 10\nfixture 0 3 1600 1450\nfixture 2 1 1500 1500\nresults {}\n",
+            "ab".repeat(ROUND_RANDOMNESS_LEN),
+            results
+        )
+    }
+
+    #[test]
+    fn test_replay_matches_its_own_results() {
+        let snapshot = parse_snapshot(&snapshot_text("0 0 0 0 0 0 0 0 0 0")).unwrap();
+        let outcomes: Vec<String> = replay(&snapshot)
+            .iter()
+            .map(|simulated| simulated.outcome.to_string())
+            .collect();
+
+        let snapshot = parse_snapshot(&snapshot_text(&outcomes.join(" "))).unwrap();
+        assert_eq!(report(&snapshot, &replay(&snapshot)), 0);
+
+        // Flip one on-chain result and it's flagged
+        let mut tampered = snapshot;
+        tampered.results[1] = tampered.results[1] % 3 + 1;
+        assert_eq!(report(&tampered, &replay(&tampered)), 1);
+    }
+
+    #[test]
+    fn test_rejects_bad_snapshots() {
+        assert!(parse_snapshot("results 1 2 3 1 2 3 1 2 3 1\n").is_err());
+        assert!(parse_snapshot(&snapshot_text("1 2 3")).is_err());
+
+        let without_model = snapshot_text("1 1 1 1 1 1 1 1 1 1").replace("model 1350 60 400 10\n", "");
+        assert!(parse_snapshot(&without_model).is_err());
+
+        let short_hex = snapshot_text("1 1 1 1 1 1 1 1 1 1").replace("abab", "ab");
+        assert!(parse_snapshot(&short_hex).is_err());
+    }
+}
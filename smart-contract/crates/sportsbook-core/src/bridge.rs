@@ -0,0 +1,108 @@
+//! Cross-chain round results message
+//!
+//! A settled round's results in a fixed big-endian layout (Wormhole payload
+//! convention), so a mirror deployment on another chain can decode them
+//! without Borsh and settle the same markets:
+//!
+//! ```text
+//! offset size field
+//!      0    1 payload_id      (ROUND_RESULTS_PAYLOAD_ID)
+//!      1    1 version         (ROUND_RESULTS_PAYLOAD_VERSION)
+//!      2   32 program_id      source program
+//!     34   32 betting_pool
+//!     66    8 round_id
+//!     74   10 match_results   1 home, 2 away, 3 draw, 4 void
+//!     84    8 round_end_time  unix seconds, signed
+//!     92   32 results_hash    RoundAttestation::results_hash of the round
+//! ```
+
+use crate::{RESULT_VOID, SIMULATED_MATCHES};
+
+/// Payload type tag for round results
+pub const ROUND_RESULTS_PAYLOAD_ID: u8 = 1;
+
+/// Layout version; bumped on any change to the layout above
+pub const ROUND_RESULTS_PAYLOAD_VERSION: u8 = 1;
+
+/// Encoded size of a round results payload
+pub const ROUND_RESULTS_PAYLOAD_LEN: usize = 1 + 1 + 32 + 32 + 8 + SIMULATED_MATCHES + 8 + 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundResultsPayload {
+    pub program_id: [u8; 32],
+    pub betting_pool: [u8; 32],
+    pub round_id: u64,
+    pub match_results: [u8; SIMULATED_MATCHES],
+    pub round_end_time: i64,
+    pub results_hash: [u8; 32],
+}
+
+impl RoundResultsPayload {
+    pub fn encode(&self) -> [u8; ROUND_RESULTS_PAYLOAD_LEN] {
+        let mut out = [0u8; ROUND_RESULTS_PAYLOAD_LEN];
+        out[0] = ROUND_RESULTS_PAYLOAD_ID;
+        out[1] = ROUND_RESULTS_PAYLOAD_VERSION;
+        out[2..34].copy_from_slice(&self.program_id);
+        out[34..66].copy_from_slice(&self.betting_pool);
+        out[66..74].copy_from_slice(&self.round_id.to_be_bytes());
+        out[74..84].copy_from_slice(&self.match_results);
+        out[84..92].copy_from_slice(&self.round_end_time.to_be_bytes());
+        out[92..124].copy_from_slice(&self.results_hash);
+        out
+    }
+
+    /// None if the bytes aren't a round results payload of this version
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != ROUND_RESULTS_PAYLOAD_LEN
+            || bytes[0] != ROUND_RESULTS_PAYLOAD_ID
+            || bytes[1] != ROUND_RESULTS_PAYLOAD_VERSION
+        {
+            return None;
+        }
+
+        let match_results: [u8; SIMULATED_MATCHES] = bytes[74..84].try_into().ok()?;
+        if match_results.iter().any(|result| !(1..=RESULT_VOID).contains(result)) {
+            return None;
+        }
+
+        Some(RoundResultsPayload {
+            program_id: bytes[2..34].try_into().ok()?,
+            betting_pool: bytes[34..66].try_into().ok()?,
+            round_id: u64::from_be_bytes(bytes[66..74].try_into().ok()?),
+            match_results,
+            round_end_time: i64::from_be_bytes(bytes[84..92].try_into().ok()?),
+            results_hash: bytes[92..124].try_into().ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_results_payload_round_trip() {
+        let payload = RoundResultsPayload {
+            program_id: [1; 32],
+            betting_pool: [2; 32],
+            round_id: 0x0102_0304,
+            match_results: [1, 2, 3, 1, 2, 3, 1, 2, 3, 1],
+            round_end_time: 1_700_000_000,
+            results_hash: [9; 32],
+        };
+        let bytes = payload.encode();
+        assert_eq!(bytes.len(), 124);
+        // Big-endian round id
+        assert_eq!(&bytes[66..74], &[0, 0, 0, 0, 1, 2, 3, 4]);
+        assert_eq!(RoundResultsPayload::decode(&bytes), Some(payload));
+
+        let mut unsettled = bytes;
+        unsettled[74] = 0;
+        assert_eq!(RoundResultsPayload::decode(&unsettled), None);
+
+        let mut future_version = bytes;
+        future_version[1] = 2;
+        assert_eq!(RoundResultsPayload::decode(&future_version), None);
+        assert_eq!(RoundResultsPayload::decode(&bytes[..100]), None);
+    }
+}
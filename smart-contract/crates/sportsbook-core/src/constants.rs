@@ -0,0 +1,23 @@
+/// Basis points denominator (100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Fixed-point scale for odds and multipliers (1e9)
+pub const ODDS_SCALE: u64 = 1_000_000_000;
+
+/// Max payout per bet (100,000 tokens with 9 decimals)
+pub const MAX_PAYOUT_PER_BET: u64 = 100_000_000_000_000;
+
+/// Match result code for a voided match (1-3 are home, away, draw)
+pub const RESULT_VOID: u8 = 4;
+
+/// Share of a late claim paid to the third-party claimer (10%)
+pub const BOUNTY_BPS: u64 = 1000;
+
+/// Odds compression range (raw parimutuel odds → 1.2x - 2.2x)
+pub const MIN_COMPRESSED_ODDS: u64 = 1_200_000_000;  // 1.2x (strong favorites)
+pub const MAX_COMPRESSED_ODDS: u64 = 2_200_000_000;  // 2.2x (heavy underdogs)
+pub const RAW_ODDS_MIN: u64 = 1_800_000_000;         // 1.8x raw (maps to 1.2x)
+pub const RAW_ODDS_MAX: u64 = 5_500_000_000;         // 5.5x raw (maps to 2.2x)
+
+/// Odds used when a match has no seed liquidity at all (1.5x)
+pub const FALLBACK_ODDS: u64 = 1_500_000_000;
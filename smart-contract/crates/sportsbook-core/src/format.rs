@@ -0,0 +1,204 @@
+//! Human-readable odds and token amounts for clients
+//!
+//! Odds come in as stored on chain (scaled by `ODDS_SCALE`) and amounts in
+//! base units, so every client renders and parses them the same way.
+
+use crate::constants::*;
+
+/// Decimal places in `ODDS_SCALE`
+const ODDS_DECIMALS: u32 = ODDS_SCALE.ilog10();
+
+/// Greatest common divisor, for reducing fractional odds
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Decimal odds as shown to bettors ("1.75"), rounded half up to `places`
+/// decimal places
+pub fn format_decimal_odds(odds: u64, places: u32) -> String {
+    let places = places.min(ODDS_DECIMALS);
+    let unit = 10u128.pow(ODDS_DECIMALS - places);
+    let rounded = (odds as u128 + unit / 2) / unit;
+    let divisor = 10u128.pow(places);
+
+    if places == 0 {
+        return rounded.to_string();
+    }
+    format!(
+        "{}.{:0width$}",
+        rounded / divisor,
+        rounded % divisor,
+        width = places as usize
+    )
+}
+
+/// Fractional odds (profit : stake) in lowest terms, e.g. 1.75x → (3, 4);
+/// None below 1.0x
+pub fn to_fractional_odds(odds: u64) -> Option<(u64, u64)> {
+    let profit = odds.checked_sub(ODDS_SCALE)?;
+    if profit == 0 {
+        return Some((0, 1));
+    }
+
+    let divisor = gcd(profit, ODDS_SCALE);
+    Some((profit / divisor, ODDS_SCALE / divisor))
+}
+
+/// Fractional odds as shown to bettors ("3/4")
+pub fn format_fractional_odds(odds: u64) -> Option<String> {
+    to_fractional_odds(odds).map(|(numerator, denominator)| format!("{}/{}", numerator, denominator))
+}
+
+/// Decimal odds for fractional odds `numerator/denominator`
+pub fn decimal_odds_from_fractional(numerator: u64, denominator: u64) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let profit = (numerator as u128)
+        .checked_mul(ODDS_SCALE as u128)?
+        .checked_div(denominator as u128)?;
+    u64::try_from(profit).ok()?.checked_add(ODDS_SCALE)
+}
+
+/// American (moneyline) odds, rounded to the nearest whole number:
+/// +150 for 2.5x, -200 for 1.5x. None at or below 1.0x, which has no
+/// moneyline.
+pub fn to_american_odds(odds: u64) -> Option<i64> {
+    let profit = odds.checked_sub(ODDS_SCALE).filter(|profit| *profit > 0)? as u128;
+    let scale = ODDS_SCALE as u128;
+
+    if profit >= scale {
+        // Underdog: profit on a 100 stake
+        let american = (profit * 100 + scale / 2) / scale;
+        i64::try_from(american).ok()
+    } else {
+        // Favourite: stake needed to win 100
+        let american = (scale * 100 + profit / 2) / profit;
+        i64::try_from(american).ok().map(|american| -american)
+    }
+}
+
+/// American odds as shown to bettors ("+150", "-200")
+pub fn format_american_odds(odds: u64) -> Option<String> {
+    to_american_odds(odds).map(|american| {
+        if american > 0 {
+            format!("+{}", american)
+        } else {
+            american.to_string()
+        }
+    })
+}
+
+/// Decimal odds for American odds; None for moneylines between -100 and
+/// +100 exclusive, which don't exist
+pub fn decimal_odds_from_american(american: i64) -> Option<u64> {
+    let scale = ODDS_SCALE as u128;
+    let profit = if american >= 100 {
+        (american as u128).checked_mul(scale)? / 100
+    } else if american <= -100 {
+        scale.checked_mul(100)? / american.unsigned_abs() as u128
+    } else {
+        return None;
+    };
+
+    u64::try_from(profit).ok()?.checked_add(ODDS_SCALE)
+}
+
+/// A token amount in base units as a human-readable number for a mint
+/// with `decimals` decimals, cut to at most `max_places` decimal places
+/// and without trailing zeros ("12.5"). Rounds down, so a payout is never
+/// shown as more than will arrive.
+pub fn format_token_amount(amount: u64, decimals: u8, max_places: u8) -> String {
+    let decimals = decimals as u32;
+    let divisor = 10u128.pow(decimals);
+    let whole = amount as u128 / divisor;
+    let fraction = amount as u128 % divisor;
+
+    let places = (max_places as u32).min(decimals);
+    let fraction = fraction / 10u128.pow(decimals - places);
+    let digits = format!("{:0width$}", fraction, width = places as usize);
+    let digits = digits.trim_end_matches('0');
+
+    if digits.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, digits)
+    }
+}
+
+/// Parse a human-readable token amount ("12.5") into base units for a mint
+/// with `decimals` decimals; None if it isn't a plain non-negative number,
+/// has more decimal places than the mint or doesn't fit in a u64
+pub fn parse_token_amount(text: &str, decimals: u8) -> Option<u64> {
+    let (whole, fraction) = match text.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (text, ""),
+    };
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty()
+        || !is_digits(whole)
+        || !is_digits(fraction)
+        || fraction.len() > decimals as usize
+    {
+        return None;
+    }
+
+    let scale = 10u64.checked_pow(decimals as u32)?;
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().ok()? * 10u64.pow(decimals as u32 - fraction.len() as u32)
+    };
+
+    whole.checked_mul(scale)?.checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odds_formats() {
+        assert_eq!(format_decimal_odds(1_750_000_000, 2), "1.75");
+        assert_eq!(format_decimal_odds(1_333_333_333, 2), "1.33");
+        assert_eq!(format_decimal_odds(1_995_000_000, 2), "2.00");
+        assert_eq!(format_decimal_odds(2_500_000_000, 0), "3");
+
+        assert_eq!(format_fractional_odds(1_750_000_000).as_deref(), Some("3/4"));
+        assert_eq!(format_fractional_odds(3_000_000_000).as_deref(), Some("2/1"));
+        assert_eq!(format_fractional_odds(ODDS_SCALE).as_deref(), Some("0/1"));
+        assert_eq!(to_fractional_odds(900_000_000), None);
+        assert_eq!(decimal_odds_from_fractional(3, 4), Some(1_750_000_000));
+        assert_eq!(decimal_odds_from_fractional(1, 0), None);
+
+        assert_eq!(format_american_odds(2_500_000_000).as_deref(), Some("+150"));
+        assert_eq!(format_american_odds(1_500_000_000).as_deref(), Some("-200"));
+        assert_eq!(format_american_odds(2_000_000_000).as_deref(), Some("+100"));
+        assert_eq!(to_american_odds(ODDS_SCALE), None);
+        assert_eq!(decimal_odds_from_american(150), Some(2_500_000_000));
+        assert_eq!(decimal_odds_from_american(-200), Some(1_500_000_000));
+        assert_eq!(decimal_odds_from_american(50), None);
+    }
+
+    #[test]
+    fn test_token_amounts() {
+        assert_eq!(format_token_amount(12_500_000_000, 9, 2), "12.5");
+        assert_eq!(format_token_amount(12_999_999_999, 9, 2), "12.99");
+        assert_eq!(format_token_amount(7_000_000, 6, 6), "7");
+        assert_eq!(format_token_amount(1, 9, 9), "0.000000001");
+        assert_eq!(format_token_amount(42, 0, 2), "42");
+
+        assert_eq!(parse_token_amount("12.5", 9), Some(12_500_000_000));
+        assert_eq!(parse_token_amount(".5", 6), Some(500_000));
+        assert_eq!(parse_token_amount("7", 6), Some(7_000_000));
+        assert_eq!(parse_token_amount("0.0000001", 6), None);
+        assert_eq!(parse_token_amount("-1", 6), None);
+        assert_eq!(parse_token_amount("", 6), None);
+        assert_eq!(parse_token_amount("99999999999999999999", 0), None);
+    }
+}
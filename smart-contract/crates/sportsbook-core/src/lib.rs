@@ -0,0 +1,56 @@
+//! Sportsbook core math
+//!
+//! Pure integer arithmetic for payouts, odds and revenue split, plus the
+//! locked-odds Merkle proofs, phantom-league fixture generation, the
+//! rating-weighted match simulation, the cross-chain round results
+//! payload, the bet terms hash bettors prove their wagers with and the
+//! odds and amount formatting clients show bettors. No Solana or Anchor
+//! dependency, so it builds for `wasm32-unknown-unknown`.
+//! The on-chain program calls into this crate, which lets a frontend run
+//! the exact same payout logic in the browser for previews.
+//!
+//! All odds and multipliers are scaled by `ODDS_SCALE` (1e9); shares are in
+//! basis points of `BPS_DENOMINATOR`.
+
+pub mod bridge;
+pub mod constants;
+pub mod format;
+pub mod merkle;
+pub mod odds;
+pub mod payout;
+pub mod receipt;
+pub mod revenue;
+pub mod schedule;
+pub mod simulation;
+
+pub use bridge::*;
+pub use constants::*;
+pub use format::*;
+pub use merkle::*;
+pub use odds::*;
+pub use payout::*;
+pub use receipt::*;
+pub use revenue::*;
+pub use schedule::*;
+pub use simulation::*;
+
+/// Errors from core calculations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreError {
+    /// Intermediate or final value doesn't fit
+    Overflow,
+
+    /// A winning leg's odds were never locked
+    OddsNotLocked,
+}
+
+impl core::fmt::Display for CoreError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CoreError::Overflow => write!(f, "calculation overflow"),
+            CoreError::OddsNotLocked => write!(f, "odds not locked"),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
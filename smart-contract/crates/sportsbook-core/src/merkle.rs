@@ -0,0 +1,211 @@
+//! Merkle commitment over a round's locked odds
+//!
+//! Each match's (home, away, draw) odds is a leaf; the table is padded to
+//! `ODDS_TREE_WIDTH` leaves with zero hashes. A multi-leg bet is proven with
+//! one compact multi-proof: the sibling hashes needed to rebuild the root
+//! from all of the bet's legs at once, ordered level by level, left to right.
+
+use sha2::{Digest, Sha256};
+
+/// Leaves in the odds tree (matches per round rounded up to a power of two)
+pub const ODDS_TREE_WIDTH: usize = 16;
+
+/// Domain tags so a leaf can never be passed off as an inner node
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+/// Hash of one match's locked odds
+pub fn odds_leaf(match_index: u8, home_odds: u64, away_odds: u64, draw_odds: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_TAG, match_index]);
+    hasher.update(home_odds.to_le_bytes());
+    hasher.update(away_odds.to_le_bytes());
+    hasher.update(draw_odds.to_le_bytes());
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Every level of the tree, leaves first, root last
+fn odds_tree(locked_odds: &[(u64, u64, u64)]) -> Vec<Vec<[u8; 32]>> {
+    let mut level = vec![[0u8; 32]; ODDS_TREE_WIDTH];
+    for (i, (home, away, draw)) in locked_odds.iter().take(ODDS_TREE_WIDTH).enumerate() {
+        level[i] = odds_leaf(i as u8, *home, *away, *draw);
+    }
+
+    let mut levels = vec![level];
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks_exact(2)
+            .map(|pair| hash_node(&pair[0], &pair[1]))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Root committing to a round's (home, away, draw) odds, indexed by match
+pub fn odds_root(locked_odds: &[(u64, u64, u64)]) -> [u8; 32] {
+    odds_tree(locked_odds)[ODDS_TREE_WIDTH.trailing_zeros() as usize][0]
+}
+
+/// Multi-proof for the given match indices (any order, duplicates ignored)
+pub fn prove_legs(locked_odds: &[(u64, u64, u64)], match_indices: &[u8]) -> Vec<[u8; 32]> {
+    let levels = odds_tree(locked_odds);
+    let mut known = known_positions(match_indices);
+    let mut proof = Vec::new();
+
+    for level in levels.iter().take(levels.len() - 1) {
+        for &position in &known {
+            let sibling = position ^ 1;
+            if !known.contains(&sibling) {
+                proof.push(level[sibling]);
+            }
+        }
+        known = parent_positions(&known);
+    }
+    proof
+}
+
+/// Check that `legs` — (match_index, leaf) pairs — are all in the tree with
+/// the given root, using a proof from `prove_legs`. The proof must be
+/// consumed exactly.
+pub fn verify_legs(root: &[u8; 32], legs: &[(u8, [u8; 32])], proof: &[[u8; 32]]) -> bool {
+    let mut nodes: Vec<(usize, [u8; 32])> = Vec::with_capacity(legs.len());
+    for (match_index, leaf) in legs {
+        let position = *match_index as usize;
+        if position >= ODDS_TREE_WIDTH {
+            return false;
+        }
+        match nodes.iter().find(|(p, _)| *p == position) {
+            Some((_, existing)) if existing != leaf => return false,
+            Some(_) => {}
+            None => nodes.push((position, *leaf)),
+        }
+    }
+    if nodes.is_empty() {
+        return false;
+    }
+    nodes.sort_by_key(|(position, _)| *position);
+
+    let mut proof = proof.iter();
+    for _ in 0..ODDS_TREE_WIDTH.trailing_zeros() {
+        let mut parents: Vec<(usize, [u8; 32])> = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (position, hash) = nodes[i];
+            let sibling = match nodes.get(i + 1) {
+                Some((next, next_hash)) if *next == position ^ 1 => {
+                    i += 1;
+                    *next_hash
+                }
+                _ => match proof.next() {
+                    Some(hash) => *hash,
+                    None => return false,
+                },
+            };
+
+            let parent = if position & 1 == 0 {
+                hash_node(&hash, &sibling)
+            } else {
+                hash_node(&sibling, &hash)
+            };
+            parents.push((position / 2, parent));
+            i += 1;
+        }
+        nodes = parents;
+    }
+
+    proof.next().is_none() && nodes.len() == 1 && nodes[0].1 == *root
+}
+
+fn known_positions(match_indices: &[u8]) -> Vec<usize> {
+    let mut positions: Vec<usize> = match_indices
+        .iter()
+        .map(|index| *index as usize)
+        .filter(|position| *position < ODDS_TREE_WIDTH)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+fn parent_positions(positions: &[usize]) -> Vec<usize> {
+    let mut parents: Vec<usize> = positions.iter().map(|position| position / 2).collect();
+    parents.dedup();
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Vec<(u64, u64, u64)> {
+        (0..10u64)
+            .map(|i| (1_200_000_000 + i, 1_800_000_000 + i, 2_000_000_000 + i))
+            .collect()
+    }
+
+    fn legs_for(table: &[(u64, u64, u64)], match_indices: &[u8]) -> Vec<(u8, [u8; 32])> {
+        match_indices
+            .iter()
+            .map(|&i| {
+                let (home, away, draw) = table[i as usize];
+                (i, odds_leaf(i, home, away, draw))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_leg_proof_roundtrip() {
+        let table = table();
+        let root = odds_root(&table);
+
+        for indices in [vec![0u8], vec![9], vec![0, 1], vec![3, 7, 2], (0..10).collect()] {
+            let proof = prove_legs(&table, &indices);
+            assert!(verify_legs(&root, &legs_for(&table, &indices), &proof), "{:?}", indices);
+        }
+
+        // Adjacent legs share hashes, so the proof is shorter than two singles
+        assert_eq!(prove_legs(&table, &[0]).len(), 4);
+        assert_eq!(prove_legs(&table, &[0, 1]).len(), 3);
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_odds() {
+        let table = table();
+        let root = odds_root(&table);
+        let proof = prove_legs(&table, &[2, 5]);
+
+        let mut legs = legs_for(&table, &[2, 5]);
+        let (home, away, draw) = table[5];
+        legs[1].1 = odds_leaf(5, home, away, draw + 1);
+        assert!(!verify_legs(&root, &legs, &proof));
+
+        // Same odds claimed for a different match
+        let mut legs = legs_for(&table, &[2, 5]);
+        legs[1].0 = 6;
+        assert!(!verify_legs(&root, &legs, &proof));
+    }
+
+    #[test]
+    fn test_proof_must_be_exact() {
+        let table = table();
+        let root = odds_root(&table);
+        let legs = legs_for(&table, &[4]);
+
+        let mut proof = prove_legs(&table, &[4]);
+        proof.push([0u8; 32]);
+        assert!(!verify_legs(&root, &legs, &proof));
+
+        proof.truncate(2);
+        assert!(!verify_legs(&root, &legs, &proof));
+        assert!(!verify_legs(&root, &[], &[]));
+    }
+}
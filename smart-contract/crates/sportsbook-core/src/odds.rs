@@ -0,0 +1,154 @@
+use crate::constants::*;
+
+/// Compress raw parimutuel odds to target 1.2x - 2.2x range
+///
+/// Maps raw odds (1.8x - 5.5x raw) to compressed range (1.2x - 2.2x)
+/// Strong favorites compress to near 1.2x; heavy underdogs to near 2.2x
+pub fn compress_odds(raw_odds: u64) -> u64 {
+    // Floor: 1.2x — even heavy favorites must pay something
+    if raw_odds < RAW_ODDS_MIN {
+        return MIN_COMPRESSED_ODDS;
+    }
+
+    // Ceiling: 2.2x — cap huge underdogs
+    if raw_odds > RAW_ODDS_MAX {
+        return MAX_COMPRESSED_ODDS;
+    }
+
+    // Linear compression formula:
+    // compressed = minOdds + (raw - minRaw) × (maxOdds - minOdds) / (maxRaw - minRaw)
+    // compressed = 1.2 + (raw - 1.8) × (2.2 - 1.2) / (5.5 - 1.8)
+    // compressed = 1.2 + (raw - 1.8) × 1.0 / 3.7
+    // compressed = 1.2 + (raw - 1.8) × 0.2703
+
+    let excess = raw_odds.saturating_sub(RAW_ODDS_MIN);
+    let range = RAW_ODDS_MAX - RAW_ODDS_MIN; // 3.7e9
+    let target_range = MAX_COMPRESSED_ODDS - MIN_COMPRESSED_ODDS; // 0.7e9
+
+    let scaled_excess = (excess as u128)
+        .checked_mul(target_range as u128)
+        .unwrap_or(0)
+        .checked_div(range as u128)
+        .unwrap_or(0) as u64;
+
+    MIN_COMPRESSED_ODDS + scaled_excess
+}
+
+/// Calculate locked odds from initial seed pools
+///
+/// This is called once at seeding time to lock odds for the entire round
+/// Everyone gets paid at these fixed odds, making accounting exact
+pub fn calculate_locked_odds_from_seeds(
+    home_seed: u64,
+    away_seed: u64,
+    draw_seed: u64,
+) -> (u64, u64, u64) {
+    let total_pool = home_seed + away_seed + draw_seed;
+
+    if total_pool == 0 {
+        // Fallback: equal odds
+        return (FALLBACK_ODDS, FALLBACK_ODDS, FALLBACK_ODDS);
+    }
+
+    // Calculate raw parimutuel odds
+    let raw_home_odds = (total_pool as u128)
+        .checked_mul(ODDS_SCALE as u128)
+        .unwrap_or(0)
+        .checked_div(home_seed as u128)
+        .unwrap_or(ODDS_SCALE as u128) as u64;
+
+    let raw_away_odds = (total_pool as u128)
+        .checked_mul(ODDS_SCALE as u128)
+        .unwrap_or(0)
+        .checked_div(away_seed as u128)
+        .unwrap_or(ODDS_SCALE as u128) as u64;
+
+    let raw_draw_odds = (total_pool as u128)
+        .checked_mul(ODDS_SCALE as u128)
+        .unwrap_or(0)
+        .checked_div(draw_seed as u128)
+        .unwrap_or(ODDS_SCALE as u128) as u64;
+
+    // Compress to target range
+    (
+        compress_odds(raw_home_odds),
+        compress_odds(raw_away_odds),
+        compress_odds(raw_draw_odds),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odds_to_x(odds: u64) -> f64 {
+        odds as f64 / 1_000_000_000.0
+    }
+
+    #[test]
+    fn test_compress_odds_floors_at_1_2x() {
+        // Raw below RAW_ODDS_MIN → clamp to 1.2x
+        assert_eq!(compress_odds(1_000_000_000), MIN_COMPRESSED_ODDS); // 1.0x raw
+        assert_eq!(compress_odds(1_500_000_000), MIN_COMPRESSED_ODDS); // 1.5x raw
+        assert_eq!(compress_odds(RAW_ODDS_MIN - 1), MIN_COMPRESSED_ODDS);
+        let result = odds_to_x(compress_odds(RAW_ODDS_MIN - 1));
+        assert!((result - 1.2).abs() < 0.01, "Expected ~1.2x, got {:.3}x", result);
+    }
+
+    #[test]
+    fn test_compress_odds_caps_at_2_2x() {
+        // Raw above RAW_ODDS_MAX → clamp to 2.2x
+        assert_eq!(compress_odds(10_000_000_000), MAX_COMPRESSED_ODDS); // 10x raw
+        assert_eq!(compress_odds(RAW_ODDS_MAX + 1), MAX_COMPRESSED_ODDS);
+        let result = odds_to_x(compress_odds(RAW_ODDS_MAX + 1));
+        assert!((result - 2.2).abs() < 0.01, "Expected ~2.2x, got {:.3}x", result);
+    }
+
+    #[test]
+    fn test_compress_odds_range_is_1_2_to_2_2() {
+        // All raw odds in [RAW_ODDS_MIN, RAW_ODDS_MAX] must compress to [1.2x, 2.2x]
+        let test_raws = [
+            RAW_ODDS_MIN,
+            2_000_000_000,  // 2.0x
+            3_000_000_000,  // 3.0x
+            4_000_000_000,  // 4.0x
+            5_000_000_000,  // 5.0x
+            RAW_ODDS_MAX,
+        ];
+        for raw in test_raws {
+            let c = compress_odds(raw);
+            let x = odds_to_x(c);
+            assert!(
+                (1.2..=2.2).contains(&x),
+                "raw={:.2}x compressed to {:.3}x — out of 1.2-2.2 range",
+                raw as f64 / 1e9,
+                x
+            );
+        }
+    }
+
+    #[test]
+    fn test_compress_odds_monotonically_increasing() {
+        // Higher raw odds → higher compressed odds
+        let raws = [1_800_000_000u64, 2_500_000_000, 3_500_000_000, 4_500_000_000, 5_500_000_000];
+        for i in 0..raws.len() - 1 {
+            let c_lo = compress_odds(raws[i]);
+            let c_hi = compress_odds(raws[i + 1]);
+            assert!(
+                c_hi >= c_lo,
+                "Compression not monotone: raw {:.1}x → {:.3}x, raw {:.1}x → {:.3}x",
+                raws[i] as f64 / 1e9, c_lo as f64 / 1e9,
+                raws[i+1] as f64 / 1e9, c_hi as f64 / 1e9,
+            );
+        }
+    }
+
+    #[test]
+    fn test_locked_odds_empty_pool_fallback() {
+        // Empty pools should return fallback 1.5x
+        assert_eq!(
+            calculate_locked_odds_from_seeds(0, 0, 0),
+            (FALLBACK_ODDS, FALLBACK_ODDS, FALLBACK_ODDS)
+        );
+    }
+}
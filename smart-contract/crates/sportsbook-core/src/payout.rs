@@ -0,0 +1,445 @@
+use crate::constants::*;
+use crate::{CoreError, SIMULATED_MATCHES};
+
+/// One leg of a bet, as the claim sees it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SettledLeg {
+    /// Predicted outcome: 1=Home, 2=Away, 3=Draw
+    pub predicted_outcome: u8,
+
+    /// Match result in the same encoding (0 = pending, RESULT_VOID = void)
+    pub result: u8,
+
+    /// Stake allocated to this leg's outcome pool
+    pub amount_in_pool: u64,
+
+    /// Locked odds of the predicted outcome
+    pub odds: u64,
+
+    /// Whether the match's odds were locked at seeding
+    pub odds_locked: bool,
+}
+
+/// Result of settling a bet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BetPayout {
+    /// Every leg came in
+    pub won: bool,
+
+    /// Σ(amount_in_pool × odds), before the parlay multiplier
+    pub base_payout: u64,
+
+    /// What the bettor is owed
+    pub final_payout: u64,
+}
+
+/// Settle a bet: all legs must win, then pay the stored potential payout,
+/// or for legacy bets (`potential_payout == 0`) recompute it from the
+/// locked multiplier and apply the per-bet cap
+///
+/// Void legs neither win nor lose; they count at odds 1.0, and since the
+/// stored payout assumed every leg at its locked odds, a bet with a void
+/// leg is always recomputed. Fully void bets are refunded instead (see
+/// `is_fully_void`).
+pub fn calculate_bet_payout(
+    legs: &[SettledLeg],
+    locked_multiplier: u64,
+    potential_payout: u64,
+) -> Result<BetPayout, CoreError> {
+    let mut total_base_payout = 0u64;
+    let mut has_void_leg = false;
+
+    for leg in legs {
+        if leg.result == RESULT_VOID {
+            has_void_leg = true;
+            total_base_payout = total_base_payout
+                .checked_add(leg.amount_in_pool)
+                .ok_or(CoreError::Overflow)?;
+            continue;
+        }
+
+        // Anything but 1-3 counts as a pending prediction
+        let predicted_outcome = if (1..=3).contains(&leg.predicted_outcome) {
+            leg.predicted_outcome
+        } else {
+            0
+        };
+
+        if leg.result != predicted_outcome {
+            return Ok(BetPayout::default());
+        }
+
+        // Use locked odds for payout calculation
+        if !leg.odds_locked {
+            return Err(CoreError::OddsNotLocked);
+        }
+
+        // Simple multiplication: amount × locked odds
+        let leg_payout = (leg.amount_in_pool as u128)
+            .checked_mul(leg.odds as u128)
+            .ok_or(CoreError::Overflow)?
+            .checked_div(ODDS_SCALE as u128)
+            .ok_or(CoreError::Overflow)? as u64;
+
+        total_base_payout = total_base_payout
+            .checked_add(leg_payout)
+            .ok_or(CoreError::Overflow)?;
+    }
+
+    // Payout fixed at placement
+    if potential_payout > 0 && !has_void_leg {
+        return Ok(BetPayout {
+            won: true,
+            base_payout: total_base_payout,
+            final_payout: potential_payout,
+        });
+    }
+
+    // Legacy bets: apply locked parlay multiplier
+    let total_final_payout = (total_base_payout as u128)
+        .checked_mul(locked_multiplier as u128)
+        .ok_or(CoreError::Overflow)?
+        .checked_div(ODDS_SCALE as u128)
+        .ok_or(CoreError::Overflow)? as u64;
+
+    Ok(BetPayout {
+        won: true,
+        base_payout: total_base_payout,
+        final_payout: total_final_payout.min(MAX_PAYOUT_PER_BET),
+    })
+}
+
+/// Every leg's match was voided: the bet is refunded its original stake
+/// rather than settled
+pub fn is_fully_void(legs: &[SettledLeg]) -> bool {
+    !legs.is_empty() && legs.iter().all(|leg| leg.result == RESULT_VOID)
+}
+
+/// A parlay that missed by a single leg: exactly one leg lost and every
+/// other leg won or was voided (what bet insurance refunds)
+pub fn is_one_leg_short(legs: &[SettledLeg]) -> bool {
+    if legs.len() < 2 {
+        return false;
+    }
+
+    let mut lost = 0;
+    for leg in legs {
+        if leg.result == RESULT_VOID || leg.result == leg.predicted_outcome {
+            continue;
+        }
+        // A pending match means the bet isn't decided yet
+        if leg.result == 0 {
+            return false;
+        }
+        lost += 1;
+    }
+    lost == 1
+}
+
+/// Lowest bit of each match's three outcome bits in a leg or results bitmap
+const MATCH_LOW_BITS: u32 = 0x0924_9249;
+
+/// Bit for `outcome` (1-3) of match `match_index` in a leg or results
+/// bitmap: three bits per match, home/away/draw from the low end
+pub fn outcome_bit(match_index: u8, outcome: u8) -> u32 {
+    if !(1..=3).contains(&outcome) || match_index as usize >= SIMULATED_MATCHES {
+        return 0;
+    }
+    1 << (match_index as u32 * 3 + outcome as u32 - 1)
+}
+
+/// A bet's (match_index, predicted outcome) legs as one bitmap
+pub fn leg_bitmap(legs: impl IntoIterator<Item = (u8, u8)>) -> u32 {
+    legs.into_iter()
+        .fold(0, |bits, (match_index, outcome)| bits | outcome_bit(match_index, outcome))
+}
+
+/// A round's results as one bitmap: the winning outcome's bit for each
+/// decided match, nothing for pending or voided ones
+pub fn results_bitmap(results: &[u8]) -> u32 {
+    results
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (match_index, result)| bits | outcome_bit(match_index as u8, *result))
+}
+
+/// Whether a bet has lost, from bitmaps alone: some leg is on a decided
+/// match and isn't its result. A false doesn't mean the bet won; pending
+/// and voided legs are left to calculate_bet_payout.
+pub fn is_lost_by_bitmap(leg_bits: u32, result_bits: u32) -> bool {
+    let decided_matches =
+        (result_bits | result_bits >> 1 | result_bits >> 2) & MATCH_LOW_BITS;
+    // All three bits of every decided match; the spread can't carry
+    let decided_outcomes = decided_matches * 0b111;
+    leg_bits & decided_outcomes & !result_bits != 0
+}
+
+/// Combined odds of a bet: product of leg odds × parlay multiplier (scaled by 1e9)
+pub fn calculate_combined_odds(leg_odds: &[u64], parlay_multiplier: u64) -> Option<u64> {
+    let mut combined = parlay_multiplier as u128;
+    for odds in leg_odds {
+        combined = combined
+            .checked_mul(*odds as u128)?
+            .checked_div(ODDS_SCALE as u128)?;
+    }
+    u64::try_from(combined).ok()
+}
+
+/// Payout a bet receives if every leg wins, before the per-bet cap
+///
+/// Same arithmetic as claim time: Σ(amount_in_pool × odds) × multiplier.
+/// `legs` are (amount_in_pool, locked odds) pairs.
+pub fn calculate_potential_payout(legs: &[(u64, u64)], parlay_multiplier: u64) -> Option<u64> {
+    let mut base_payout = 0u64;
+    for (amount_in_pool, odds) in legs {
+        let leg_payout = (*amount_in_pool as u128)
+            .checked_mul(*odds as u128)?
+            .checked_div(ODDS_SCALE as u128)?;
+        base_payout = base_payout.checked_add(u64::try_from(leg_payout).ok()?)?;
+    }
+
+    let final_payout = (base_payout as u128)
+        .checked_mul(parlay_multiplier as u128)?
+        .checked_div(ODDS_SCALE as u128)?;
+    u64::try_from(final_payout).ok()
+}
+
+/// Payout of a stake riding every leg in sequence: stake × Π(odds) ×
+/// multiplier (scaled by 1e9)
+pub fn calculate_accumulator_potential_payout(
+    stake: u64,
+    leg_odds: &[u64],
+    parlay_multiplier: u64,
+) -> Option<u64> {
+    let combined_odds = calculate_combined_odds(leg_odds, parlay_multiplier)?;
+    let payout = (stake as u128)
+        .checked_mul(combined_odds as u128)?
+        .checked_div(ODDS_SCALE as u128)?;
+    u64::try_from(payout).ok()
+}
+
+/// Settle a cross-round accumulator, whose whole after-fee `stake` rides
+/// every leg rather than being split across them (`amount_in_pool` is
+/// ignored)
+///
+/// All legs must win. Void legs count at odds 1.0, so a bet with one is
+/// recomputed from the remaining legs and never pays more than
+/// `potential_payout`; fully void accumulators are refunded instead (see
+/// `is_fully_void`).
+pub fn calculate_accumulator_payout(
+    legs: &[SettledLeg],
+    stake: u64,
+    locked_multiplier: u64,
+    potential_payout: u64,
+) -> Result<BetPayout, CoreError> {
+    let mut live_odds = Vec::with_capacity(legs.len());
+
+    for leg in legs {
+        if leg.result == RESULT_VOID {
+            continue;
+        }
+        if !(1..=3).contains(&leg.predicted_outcome) || leg.result != leg.predicted_outcome {
+            return Ok(BetPayout::default());
+        }
+        if !leg.odds_locked {
+            return Err(CoreError::OddsNotLocked);
+        }
+        live_odds.push(leg.odds);
+    }
+
+    let base_payout = calculate_accumulator_potential_payout(stake, &live_odds, ODDS_SCALE)
+        .ok_or(CoreError::Overflow)?;
+    if live_odds.len() == legs.len() {
+        return Ok(BetPayout { won: true, base_payout, final_payout: potential_payout });
+    }
+
+    let final_payout = calculate_accumulator_potential_payout(stake, &live_odds, locked_multiplier)
+        .ok_or(CoreError::Overflow)?;
+    Ok(BetPayout {
+        won: true,
+        base_payout,
+        final_payout: final_payout.min(potential_payout).min(MAX_PAYOUT_PER_BET),
+    })
+}
+
+/// Split a payout claimed by a third party: 90% to bettor, 10% bounty
+///
+/// Returns (bettor_amount, bounty_amount); rounding favours the bettor.
+pub fn split_bounty(payout: u64) -> Result<(u64, u64), CoreError> {
+    let bounty = (payout as u128)
+        .checked_mul(BOUNTY_BPS as u128)
+        .ok_or(CoreError::Overflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CoreError::Overflow)? as u64;
+
+    Ok((payout.saturating_sub(bounty), bounty))
+}
+
+/// Leader's success fee on a winning copied bet: `fee_bps` of the profit
+/// (what the follower receives above their stake), nothing on a loss
+pub fn calculate_success_fee(received: u64, stake: u64, fee_bps: u16) -> Result<u64, CoreError> {
+    let profit = received.saturating_sub(stake);
+    let fee = (profit as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(CoreError::Overflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CoreError::Overflow)? as u64;
+
+    Ok(fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(predicted_outcome: u8, result: u8, amount_in_pool: u64, odds: u64) -> SettledLeg {
+        SettledLeg { predicted_outcome, result, amount_in_pool, odds, odds_locked: true }
+    }
+
+    #[test]
+    fn test_bet_payout_all_legs_win() {
+        // 100 @ 1.5x + 50 @ 2.0x = 250, × 1.2 = 300
+        let legs = [leg(1, 1, 100 * ODDS_SCALE, 1_500_000_000), leg(3, 3, 50 * ODDS_SCALE, 2_000_000_000)];
+        let payout = calculate_bet_payout(&legs, 1_200_000_000, 0).unwrap();
+        assert_eq!(
+            payout,
+            BetPayout { won: true, base_payout: 250 * ODDS_SCALE, final_payout: 300 * ODDS_SCALE }
+        );
+
+        // A stored payout wins over recomputation
+        let payout = calculate_bet_payout(&legs, 1_200_000_000, 280 * ODDS_SCALE).unwrap();
+        assert_eq!(payout.final_payout, 280 * ODDS_SCALE);
+    }
+
+    #[test]
+    fn test_bet_payout_one_leg_loses() {
+        let legs = [leg(1, 1, ODDS_SCALE, 1_500_000_000), leg(2, 3, ODDS_SCALE, 2_000_000_000)];
+        assert_eq!(calculate_bet_payout(&legs, ODDS_SCALE, 0).unwrap(), BetPayout::default());
+    }
+
+    #[test]
+    fn test_bet_payout_void_legs_count_at_even_odds() {
+        // Void leg: 100 @ 1.0 + 50 @ 2.0x = 200, × 1.2 = 240, stored payout ignored
+        let legs = [leg(1, RESULT_VOID, 100 * ODDS_SCALE, 1_500_000_000), leg(3, 3, 50 * ODDS_SCALE, 2_000_000_000)];
+        let payout = calculate_bet_payout(&legs, 1_200_000_000, 300 * ODDS_SCALE).unwrap();
+        assert_eq!(
+            payout,
+            BetPayout { won: true, base_payout: 200 * ODDS_SCALE, final_payout: 240 * ODDS_SCALE }
+        );
+        assert!(!is_fully_void(&legs));
+
+        let void_legs = [leg(1, RESULT_VOID, ODDS_SCALE, 1_500_000_000), leg(2, RESULT_VOID, ODDS_SCALE, 2_000_000_000)];
+        assert!(is_fully_void(&void_legs));
+        assert!(!is_fully_void(&[]));
+    }
+
+    #[test]
+    fn test_bet_payout_requires_locked_odds() {
+        let mut unlocked = leg(1, 1, ODDS_SCALE, 1_500_000_000);
+        unlocked.odds_locked = false;
+        assert_eq!(calculate_bet_payout(&[unlocked], ODDS_SCALE, 0), Err(CoreError::OddsNotLocked));
+    }
+
+    #[test]
+    fn test_bet_payout_capped() {
+        let legs = [leg(1, 1, MAX_PAYOUT_PER_BET, 2_000_000_000)];
+        assert_eq!(calculate_bet_payout(&legs, ODDS_SCALE, 0).unwrap().final_payout, MAX_PAYOUT_PER_BET);
+    }
+
+    #[test]
+    fn test_one_leg_short() {
+        let won = leg(1, 1, ODDS_SCALE, 1_500_000_000);
+        let lost = leg(2, 3, ODDS_SCALE, 2_000_000_000);
+        let void = leg(3, RESULT_VOID, ODDS_SCALE, 2_000_000_000);
+        assert!(is_one_leg_short(&[won, lost]));
+        assert!(is_one_leg_short(&[won, void, lost]));
+        assert!(!is_one_leg_short(&[won, won]));
+        assert!(!is_one_leg_short(&[lost, lost]));
+        // Singles aren't parlays
+        assert!(!is_one_leg_short(&[lost]));
+        assert!(!is_one_leg_short(&[lost, leg(1, 0, ODDS_SCALE, 1_500_000_000)]));
+    }
+
+    #[test]
+    fn test_lost_by_bitmap() {
+        // Match 0 home, 1 away, 2 void, 3 draw, the rest pending
+        let results = results_bitmap(&[1, 2, RESULT_VOID, 3, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(results, 0b100_000_010_001);
+
+        assert!(!is_lost_by_bitmap(leg_bitmap([(0, 1), (1, 2), (3, 3)]), results));
+        assert!(is_lost_by_bitmap(leg_bitmap([(0, 1), (1, 1)]), results));
+        assert!(is_lost_by_bitmap(leg_bitmap([(3, 1)]), results));
+        // Void and pending legs are never a loss here
+        assert!(!is_lost_by_bitmap(leg_bitmap([(2, 1), (9, 3)]), results));
+        // Old bets and rounds without bitmaps never short-circuit
+        assert!(!is_lost_by_bitmap(0, results));
+        assert!(!is_lost_by_bitmap(leg_bitmap([(0, 2)]), 0));
+
+        // Last match uses the top bits
+        let results = results_bitmap(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
+        assert!(is_lost_by_bitmap(leg_bitmap([(9, 2)]), results));
+        assert!(!is_lost_by_bitmap(leg_bitmap([(9, 3)]), results));
+    }
+
+    #[test]
+    fn test_combined_odds() {
+        // 1.5x × 2.0x with a 1.1x multiplier = 3.3x
+        let combined = calculate_combined_odds(&[1_500_000_000, 2_000_000_000], 1_100_000_000);
+        assert_eq!(combined, Some(3_300_000_000));
+
+        // Single leg, no boost
+        assert_eq!(calculate_combined_odds(&[1_800_000_000], ODDS_SCALE), Some(1_800_000_000));
+    }
+
+    #[test]
+    fn test_potential_payout() {
+        // 100 @ 1.5x + 50 @ 2.0x = 250, × 1.2 = 300
+        let payout = calculate_potential_payout(
+            &[(100_000_000_000, 1_500_000_000), (50_000_000_000, 2_000_000_000)],
+            1_200_000_000,
+        );
+        assert_eq!(payout, Some(300_000_000_000));
+
+        assert_eq!(calculate_potential_payout(&[(u64::MAX, u64::MAX)], ODDS_SCALE), None);
+    }
+
+    #[test]
+    fn test_accumulator_payout() {
+        // 10 riding 1.5x then 2.0x with a 1.1x multiplier = 33
+        let potential = calculate_accumulator_potential_payout(10 * ODDS_SCALE, &[1_500_000_000, 2_000_000_000], 1_100_000_000);
+        assert_eq!(potential, Some(33 * ODDS_SCALE));
+
+        let legs = [leg(1, 1, 0, 1_500_000_000), leg(3, 3, 0, 2_000_000_000)];
+        let payout = calculate_accumulator_payout(&legs, 10 * ODDS_SCALE, 1_100_000_000, 33 * ODDS_SCALE).unwrap();
+        assert_eq!(
+            payout,
+            BetPayout { won: true, base_payout: 30 * ODDS_SCALE, final_payout: 33 * ODDS_SCALE }
+        );
+
+        // One leg loses
+        let legs = [leg(1, 1, 0, 1_500_000_000), leg(3, 2, 0, 2_000_000_000)];
+        let payout = calculate_accumulator_payout(&legs, 10 * ODDS_SCALE, 1_100_000_000, 33 * ODDS_SCALE).unwrap();
+        assert_eq!(payout, BetPayout::default());
+
+        // Void leg drops out: 10 × 2.0 × 1.1 = 22
+        let legs = [leg(1, RESULT_VOID, 0, 1_500_000_000), leg(3, 3, 0, 2_000_000_000)];
+        let payout = calculate_accumulator_payout(&legs, 10 * ODDS_SCALE, 1_100_000_000, 33 * ODDS_SCALE).unwrap();
+        assert_eq!(payout.final_payout, 22 * ODDS_SCALE);
+    }
+
+    #[test]
+    fn test_split_bounty_is_90_10() {
+        assert_eq!(split_bounty(1_000 * ODDS_SCALE).unwrap(), (900 * ODDS_SCALE, 100 * ODDS_SCALE));
+        // Rounding favours the bettor
+        assert_eq!(split_bounty(19).unwrap(), (18, 1));
+        assert_eq!(split_bounty(0).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_success_fee_is_on_profit_only() {
+        // 1000 staked, 3000 received: 10% of the 2000 profit
+        assert_eq!(calculate_success_fee(3_000, 1_000, 1_000).unwrap(), 200);
+        assert_eq!(calculate_success_fee(900, 1_000, 1_000).unwrap(), 0);
+        assert_eq!(calculate_success_fee(3_000, 1_000, 0).unwrap(), 0);
+    }
+}
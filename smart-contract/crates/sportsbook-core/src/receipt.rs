@@ -0,0 +1,97 @@
+//! Canonical hash of a bet's terms
+//!
+//! The program writes it to the Bet when the bet is placed, with the odds
+//! locked at that moment. A bettor who keeps the terms can then prove to a
+//! third party exactly what they wagered: anyone recomputes the hash here
+//! and compares it with the on-chain account, without trusting a UI.
+//!
+//! ```text
+//! sha256(BET_TERMS_HASH_DOMAIN
+//!        || bettor (32) || round_id LE || stake LE || locked_multiplier LE
+//!        || leg count (1)
+//!        || per leg: match_index (1) || outcome (1) || locked odds LE)
+//! ```
+
+use sha2::{Digest, Sha256};
+
+/// Domain tag so a terms hash can't be confused with any other hash
+pub const BET_TERMS_HASH_DOMAIN: &[u8] = b"sportsbook:bet-terms:v1";
+
+/// One leg of a bet as it was locked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetLegTerms {
+    pub match_index: u8,
+    /// 1 home, 2 away, 3 draw
+    pub outcome: u8,
+    /// Odds for `outcome` when the bet was placed, scaled by `ODDS_SCALE`
+    pub odds: u64,
+}
+
+/// Everything a bet commits to when it is placed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetTerms<'a> {
+    pub bettor: [u8; 32],
+    pub round_id: u64,
+    /// Stake before fees
+    pub stake: u64,
+    pub locked_multiplier: u64,
+    /// In the order the bet lists them
+    pub legs: &'a [BetLegTerms],
+}
+
+impl BetTerms<'_> {
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(BET_TERMS_HASH_DOMAIN);
+        hasher.update(self.bettor);
+        hasher.update(self.round_id.to_le_bytes());
+        hasher.update(self.stake.to_le_bytes());
+        hasher.update(self.locked_multiplier.to_le_bytes());
+        hasher.update([self.legs.len() as u8]);
+        for leg in self.legs {
+            hasher.update([leg.match_index, leg.outcome]);
+            hasher.update(leg.odds.to_le_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terms_hash_commits_to_every_term() {
+        let legs = [
+            BetLegTerms { match_index: 0, outcome: 1, odds: 1_750_000_000 },
+            BetLegTerms { match_index: 3, outcome: 3, odds: 3_200_000_000 },
+        ];
+        let terms = BetTerms {
+            bettor: [7; 32],
+            round_id: 42,
+            stake: 10_000_000_000,
+            locked_multiplier: 1_050_000_000,
+            legs: &legs,
+        };
+        let hash = terms.hash();
+        assert_eq!(hash, terms.hash());
+
+        let mut other_legs = legs;
+        other_legs[1].odds += 1;
+        let changed = [
+            BetTerms { bettor: [8; 32], ..terms },
+            BetTerms { round_id: 43, ..terms },
+            BetTerms { stake: terms.stake + 1, ..terms },
+            BetTerms { locked_multiplier: 1_100_000_000, ..terms },
+            BetTerms { legs: &legs[..1], ..terms },
+            BetTerms { legs: &other_legs, ..terms },
+        ];
+        for other in changed {
+            assert_ne!(other.hash(), hash, "{:?}", other);
+        }
+
+        // Leg order is part of the terms
+        let swapped = [legs[1], legs[0]];
+        assert_ne!(BetTerms { legs: &swapped, ..terms }.hash(), hash);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::constants::*;
+use crate::CoreError;
+
+/// How a finalized round's money is divided
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RevenueSplit {
+    /// Added to the season reward pool
+    pub season_share: u64,
+
+    /// Operating profit (user deposits − payouts), 0 on a loss
+    pub protocol_revenue: u64,
+}
+
+/// Protocol fee taken from a stake
+///
+/// Returns (protocol_fee, amount_after_fee).
+pub fn calculate_protocol_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64), CoreError> {
+    let protocol_fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(CoreError::Overflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CoreError::Overflow)? as u64;
+
+    Ok((protocol_fee, amount.saturating_sub(protocol_fee)))
+}
+
+/// Floor `amount` to a whole number of `unit` base units
+///
+/// Returns (rounded, dust). A unit of 0 or 1 means no rounding.
+pub fn round_down_to_unit(amount: u64, unit: u64) -> (u64, u64) {
+    if unit <= 1 {
+        return (amount, 0);
+    }
+    let dust = amount % unit;
+    (amount - dust, dust)
+}
+
+/// Fee for a bettor with the given rolling volume
+///
+/// `tiers` are (min_volume, fee_bps) pairs; the tier with the highest
+/// `min_volume` not above `rolling_volume` wins. Tiers with a `min_volume`
+/// of 0 are unused, and `base_fee_bps` applies below the first tier.
+pub fn tiered_fee_bps(rolling_volume: u64, base_fee_bps: u16, tiers: &[(u64, u16)]) -> u16 {
+    tiers
+        .iter()
+        .filter(|(min_volume, _)| *min_volume > 0 && *min_volume <= rolling_volume)
+        .max_by_key(|(min_volume, _)| *min_volume)
+        .map_or(base_fee_bps, |(_, fee_bps)| *fee_bps)
+}
+
+/// Split a round's result between the season pool and protocol revenue
+///
+/// The season pool gets `season_pool_share_bps` of user stakes before fee,
+/// capped at what the pool still holds. Protocol revenue is the operating
+/// profit excluding seed capital; a loss shows as 0 (it came out of seed).
+pub fn calculate_revenue_split(
+    user_deposits: u64,
+    protocol_fee_collected: u64,
+    total_paid_out: u64,
+    season_pool_share_bps: u16,
+    available_balance: u64,
+) -> Result<RevenueSplit, CoreError> {
+    let mut season_share = 0u64;
+
+    if user_deposits > 0 {
+        let total_user_bets_before_fee = user_deposits.saturating_add(protocol_fee_collected);
+
+        season_share = (total_user_bets_before_fee as u128)
+            .checked_mul(season_pool_share_bps as u128)
+            .ok_or(CoreError::Overflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(CoreError::Overflow)? as u64;
+
+        // Cap season share to what's actually available
+        season_share = season_share.min(available_balance);
+    }
+
+    Ok(RevenueSplit {
+        season_share,
+        protocol_revenue: user_deposits.saturating_sub(total_paid_out),
+    })
+}
+
+/// Creator's cut of a branded round's protocol revenue
+///
+/// Only profit is shared; a round that made nothing pays the creator nothing.
+pub fn calculate_creator_share(protocol_revenue: u64, share_bps: u16) -> Result<u64, CoreError> {
+    let share = (protocol_revenue as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(CoreError::Overflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CoreError::Overflow)? as u64;
+
+    Ok(share.min(protocol_revenue))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_fee() {
+        assert_eq!(calculate_protocol_fee(1_000, 500).unwrap(), (50, 950));
+        assert_eq!(calculate_protocol_fee(1_000, 0).unwrap(), (0, 1_000));
+    }
+
+    #[test]
+    fn test_round_down_to_unit() {
+        assert_eq!(round_down_to_unit(1_234_567, 1_000), (1_234_000, 567));
+        assert_eq!(round_down_to_unit(999, 1_000), (0, 999));
+        assert_eq!(round_down_to_unit(1_234_567, 0), (1_234_567, 0));
+        assert_eq!(round_down_to_unit(1_234_567, 1), (1_234_567, 0));
+    }
+
+    #[test]
+    fn test_tiered_fee() {
+        let tiers = [(10_000, 250), (100_000, 200), (0, 0)];
+        assert_eq!(tiered_fee_bps(0, 300, &tiers), 300);
+        assert_eq!(tiered_fee_bps(9_999, 300, &tiers), 300);
+        assert_eq!(tiered_fee_bps(10_000, 300, &tiers), 250);
+        assert_eq!(tiered_fee_bps(250_000, 300, &tiers), 200);
+
+        // Order of the slots doesn't matter; no tiers means the base fee
+        assert_eq!(tiered_fee_bps(250_000, 300, &[(100_000, 200), (10_000, 250)]), 200);
+        assert_eq!(tiered_fee_bps(250_000, 300, &[]), 300);
+    }
+
+    #[test]
+    fn test_revenue_split_profit() {
+        // 950 deposited after a 50 fee, 400 paid out, 2% season share of 1000
+        let split = calculate_revenue_split(950, 50, 400, 200, 10_000).unwrap();
+        assert_eq!(split, RevenueSplit { season_share: 20, protocol_revenue: 550 });
+    }
+
+    #[test]
+    fn test_revenue_split_loss_and_cap() {
+        let split = calculate_revenue_split(950, 50, 2_000, 200, 5).unwrap();
+        assert_eq!(split, RevenueSplit { season_share: 5, protocol_revenue: 0 });
+
+        // Nothing staked, nothing to share
+        assert_eq!(calculate_revenue_split(0, 0, 0, 200, 100).unwrap(), RevenueSplit::default());
+    }
+
+    #[test]
+    fn test_creator_share() {
+        assert_eq!(calculate_creator_share(10_000, 2_500).unwrap(), 2_500);
+        assert_eq!(calculate_creator_share(0, 2_500).unwrap(), 0);
+        // Never more than the revenue itself
+        assert_eq!(calculate_creator_share(10_000, 20_000).unwrap(), 10_000);
+    }
+}
@@ -0,0 +1,106 @@
+//! Double round-robin fixture generation (circle method)
+//!
+//! With N teams (even) a single round-robin takes N − 1 matchdays of N / 2
+//! matches. Team N − 1 stays fixed while the others rotate; the second half
+//! of the season replays the first with home and away swapped, so every
+//! ordered (home, away) pair occurs exactly once.
+
+/// Most teams a league can have (one matchday fills a 10-match round)
+pub const MAX_LEAGUE_TEAMS: u8 = 20;
+
+/// Fewest teams a league can have
+pub const MIN_LEAGUE_TEAMS: u8 = 4;
+
+/// Whether `num_teams` can be scheduled
+pub fn is_valid_league_size(num_teams: u8) -> bool {
+    (MIN_LEAGUE_TEAMS..=MAX_LEAGUE_TEAMS).contains(&num_teams) && num_teams & 1 == 0
+}
+
+/// Matchdays in a double round-robin
+pub fn double_round_robin_matchdays(num_teams: u8) -> u16 {
+    2 * (num_teams as u16).saturating_sub(1)
+}
+
+/// (home, away) team indices for one matchday, or None if the league size
+/// or matchday is out of range
+pub fn round_robin_fixtures(num_teams: u8, matchday: u16) -> Option<Vec<(u8, u8)>> {
+    if !is_valid_league_size(num_teams) || matchday >= double_round_robin_matchdays(num_teams) {
+        return None;
+    }
+
+    let rotating = num_teams as u16 - 1;
+    let (round, second_half) = if matchday < rotating {
+        (matchday, false)
+    } else {
+        (matchday - rotating, true)
+    };
+
+    let fixtures = (0..num_teams as u16 / 2)
+        .map(|i| {
+            let first = ((round + i) % rotating) as u8;
+            let second = if i == 0 {
+                rotating as u8
+            } else {
+                ((round + rotating - i) % rotating) as u8
+            };
+
+            // Alternate the fixed team between home and away
+            let (home, away) = if i == 0 && round & 1 == 1 {
+                (second, first)
+            } else {
+                (first, second)
+            };
+
+            if second_half {
+                (away, home)
+            } else {
+                (home, away)
+            }
+        })
+        .collect();
+
+    Some(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_ordered_pair_once() {
+        for num_teams in (MIN_LEAGUE_TEAMS..=MAX_LEAGUE_TEAMS).step_by(2) {
+            let n = num_teams as usize;
+            let mut seen = vec![vec![0u8; n]; n];
+
+            for matchday in 0..double_round_robin_matchdays(num_teams) {
+                let fixtures = round_robin_fixtures(num_teams, matchday).unwrap();
+                assert_eq!(fixtures.len(), n / 2);
+
+                // Each team plays exactly once per matchday
+                let mut playing = vec![false; n];
+                for (home, away) in fixtures {
+                    assert_ne!(home, away);
+                    assert!(!playing[home as usize] && !playing[away as usize]);
+                    playing[home as usize] = true;
+                    playing[away as usize] = true;
+                    seen[home as usize][away as usize] += 1;
+                }
+            }
+
+            for (home, row) in seen.iter().enumerate() {
+                for (away, count) in row.iter().enumerate() {
+                    assert_eq!(*count, (home != away) as u8, "{} teams", num_teams);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert!(round_robin_fixtures(7, 0).is_none());
+        assert!(round_robin_fixtures(2, 0).is_none());
+        assert!(round_robin_fixtures(22, 0).is_none());
+        assert!(round_robin_fixtures(20, 38).is_none());
+        assert_eq!(double_round_robin_matchdays(20), 38);
+    }
+}
@@ -0,0 +1,262 @@
+//! Rating-weighted phantom match simulation
+//!
+//! Each side's goals are Poisson with a mean that grows with its rating
+//! advantage: `base_goals × e^(advantage / rating_scale)`, where the home
+//! side's advantage includes `home_advantage`. Goals are drawn by inverse
+//! CDF from 8 bytes of randomness per side. Everything is integer fixed
+//! point, so any verifier gets bit-identical scores from the same inputs.
+
+/// Rating a team starts with
+pub const DEFAULT_TEAM_RATING: u16 = 1500;
+
+/// Fixed-point scale of exp and Poisson probabilities
+const PROBABILITY_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// exp(x) is evaluated as (1 + x / 2^EXP_SQUARINGS)^(2^EXP_SQUARINGS)
+const EXP_SQUARINGS: u32 = 10;
+
+/// Rating advantage is clamped to ±MAX_ADVANTAGE_EXPONENT × rating_scale
+const MAX_ADVANTAGE_EXPONENT: i128 = 2;
+
+/// Bounds on a model's base goal expectation (milli-goals)
+pub const MIN_BASE_GOALS_MILLI: u16 = 100;
+pub const MAX_BASE_GOALS_MILLI: u16 = 5_000;
+
+/// Most goals one side can be drawn to score
+pub const MAX_SIMULATED_GOALS: u8 = 15;
+
+/// Matches in a simulated round, each drawn from 32 bytes of randomness
+pub const SIMULATED_MATCHES: usize = 10;
+
+/// Randomness a simulated round consumes
+pub const ROUND_RANDOMNESS_LEN: usize = SIMULATED_MATCHES * 32;
+
+/// Parameters of the goal model
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GoalModel {
+    /// Expected goals for either side of an even match, in milli-goals
+    pub base_goals_milli: u16,
+
+    /// Rating points added to the home side
+    pub home_advantage: u16,
+
+    /// Rating points of advantage that multiply expected goals by e
+    pub rating_scale: u16,
+
+    /// Goals are capped here (the tail mass lands on the cap)
+    pub max_goals: u8,
+}
+
+impl GoalModel {
+    pub const DEFAULT: GoalModel = GoalModel {
+        base_goals_milli: 1_350,
+        home_advantage: 60,
+        rating_scale: 400,
+        max_goals: 10,
+    };
+
+    pub fn is_valid(&self) -> bool {
+        (MIN_BASE_GOALS_MILLI..=MAX_BASE_GOALS_MILLI).contains(&self.base_goals_milli)
+            && self.rating_scale > 0
+            && (1..=MAX_SIMULATED_GOALS).contains(&self.max_goals)
+    }
+
+    /// Expected goals (milli-goals) for a side `advantage` rating points
+    /// stronger than its opponent
+    pub fn expected_goals_milli(&self, advantage: i32) -> u64 {
+        let scale = self.rating_scale.max(1) as i128;
+        let exponent = (advantage as i128 * PROBABILITY_SCALE as i128 / scale).clamp(
+            -MAX_ADVANTAGE_EXPONENT * PROBABILITY_SCALE as i128,
+            MAX_ADVANTAGE_EXPONENT * PROBABILITY_SCALE as i128,
+        );
+        (self.base_goals_milli as u128 * exp_fixed(exponent) / PROBABILITY_SCALE) as u64
+    }
+
+    /// (home_goals, away_goals) for one match
+    ///
+    /// `randomness` must be at least 16 bytes: the first 8 draw the home
+    /// side's goals, the next 8 the away side's.
+    pub fn simulate_match(&self, home_rating: u16, away_rating: u16, randomness: &[u8]) -> (u8, u8) {
+        let advantage = home_rating as i32 + self.home_advantage as i32 - away_rating as i32;
+        let home_draw = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
+        let away_draw = u64::from_le_bytes(randomness[8..16].try_into().unwrap());
+
+        (
+            poisson_sample(self.expected_goals_milli(advantage), home_draw, self.max_goals),
+            poisson_sample(self.expected_goals_milli(-advantage), away_draw, self.max_goals),
+        )
+    }
+}
+
+/// One match of a simulated round
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SimulatedMatch {
+    /// (home_goals, away_goals), or None for a match without a fixture
+    pub score: Option<(u8, u8)>,
+
+    /// 1 = home win, 2 = away win, 3 = draw
+    pub outcome: u8,
+}
+
+/// Simulate a whole round
+///
+/// Match `i` uses `randomness[32 * i..32 * (i + 1)]`. Matches with a
+/// (home_rating, away_rating) entry in `fixtures` are played with the goal
+/// model; the rest fall back to `unweighted_outcome`.
+pub fn simulate_round(
+    model: &GoalModel,
+    fixtures: &[(u16, u16)],
+    randomness: &[u8; ROUND_RANDOMNESS_LEN],
+) -> [SimulatedMatch; SIMULATED_MATCHES] {
+    let mut matches = [SimulatedMatch::default(); SIMULATED_MATCHES];
+    for (i, (simulated, match_randomness)) in matches
+        .iter_mut()
+        .zip(randomness.chunks_exact(32))
+        .enumerate()
+    {
+        *simulated = match fixtures.get(i) {
+            Some(&(home_rating, away_rating)) => {
+                let (home_goals, away_goals) =
+                    model.simulate_match(home_rating, away_rating, match_randomness);
+                SimulatedMatch {
+                    score: Some((home_goals, away_goals)),
+                    outcome: outcome_from_score(home_goals, away_goals),
+                }
+            }
+            None => SimulatedMatch {
+                score: None,
+                outcome: unweighted_outcome(match_randomness),
+            },
+        };
+    }
+    matches
+}
+
+/// Outcome with equal odds for each result, from the first 8 bytes
+pub fn unweighted_outcome(randomness: &[u8]) -> u8 {
+    let value = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
+    ((value % 3) + 1) as u8
+}
+
+/// Outcome code of a score (1 = home win, 2 = away win, 3 = draw)
+pub fn outcome_from_score(home_goals: u8, away_goals: u8) -> u8 {
+    match home_goals.cmp(&away_goals) {
+        core::cmp::Ordering::Greater => 1,
+        core::cmp::Ordering::Less => 2,
+        core::cmp::Ordering::Equal => 3,
+    }
+}
+
+/// e^x for x scaled by PROBABILITY_SCALE (|x| well below 2^EXP_SQUARINGS)
+fn exp_fixed(x: i128) -> u128 {
+    let step = x / (1i128 << EXP_SQUARINGS);
+    let mut value = (PROBABILITY_SCALE as i128 + step).max(0) as u128;
+    for _ in 0..EXP_SQUARINGS {
+        value = value * value / PROBABILITY_SCALE;
+    }
+    value
+}
+
+/// Goals drawn by inverse CDF from a Poisson with mean `mean_milli` / 1000
+fn poisson_sample(mean_milli: u64, draw: u64, max_goals: u8) -> u8 {
+    let target = draw as u128 % PROBABILITY_SCALE;
+    let mean = mean_milli as u128 * (PROBABILITY_SCALE / 1_000);
+
+    let mut probability = exp_fixed(-(mean as i128));
+    let mut cumulative = probability;
+    for goals in 0..max_goals {
+        if target < cumulative {
+            return goals;
+        }
+        probability = probability * mean_milli as u128 / (1_000 * (goals as u128 + 1));
+        cumulative += probability;
+    }
+    max_goals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exp_fixed_is_close() {
+        // e ≈ 2.71828, e^-1 ≈ 0.36788, within the approximation's ~0.1%
+        let e = exp_fixed(PROBABILITY_SCALE as i128);
+        assert!((2_714_000_000_000_000_000..=2_719_000_000_000_000_000).contains(&e));
+        let inverse = exp_fixed(-(PROBABILITY_SCALE as i128));
+        assert!((367_000_000_000_000_000..=368_500_000_000_000_000).contains(&inverse));
+        assert_eq!(exp_fixed(0), PROBABILITY_SCALE);
+    }
+
+    #[test]
+    fn test_stronger_side_expects_more_goals() {
+        let model = GoalModel::DEFAULT;
+        assert_eq!(model.expected_goals_milli(0), 1_350);
+        assert!(model.expected_goals_milli(200) > model.expected_goals_milli(0));
+        assert!(model.expected_goals_milli(-200) < model.expected_goals_milli(0));
+
+        // Advantage is clamped at 2 × rating_scale
+        assert_eq!(model.expected_goals_milli(800), model.expected_goals_milli(5_000));
+    }
+
+    #[test]
+    fn test_ratings_shift_outcomes() {
+        let model = GoalModel::DEFAULT;
+        let mut strong_home_wins = 0;
+        let mut even_home_wins = 0;
+
+        for seed in 0u64..2_000 {
+            let mut randomness = [0u8; 16];
+            let mixed = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            randomness[0..8].copy_from_slice(&mixed.to_le_bytes());
+            randomness[8..16].copy_from_slice(&mixed.rotate_left(29).wrapping_mul(31).to_le_bytes());
+
+            let (home, away) = model.simulate_match(1900, 1300, &randomness);
+            assert!(home <= model.max_goals && away <= model.max_goals);
+            if outcome_from_score(home, away) == 1 {
+                strong_home_wins += 1;
+            }
+
+            let (home, away) = model.simulate_match(1500, 1500, &randomness);
+            if outcome_from_score(home, away) == 1 {
+                even_home_wins += 1;
+            }
+        }
+
+        assert!(strong_home_wins > even_home_wins);
+        assert!(strong_home_wins > 1_400);
+    }
+
+    #[test]
+    fn test_simulate_round_mixes_fixtures_and_fallback() {
+        let mut randomness = [0u8; ROUND_RANDOMNESS_LEN];
+        for (i, byte) in randomness.iter_mut().enumerate() {
+            *byte = (i as u8).wrapping_mul(37).wrapping_add(11);
+        }
+
+        let fixtures = [(1600, 1400), (1400, 1600)];
+        let matches = simulate_round(&GoalModel::DEFAULT, &fixtures, &randomness);
+        assert_eq!(matches, simulate_round(&GoalModel::DEFAULT, &fixtures, &randomness));
+
+        for (i, simulated) in matches.iter().enumerate() {
+            match simulated.score {
+                Some((home, away)) => {
+                    assert!(i < fixtures.len());
+                    assert_eq!(simulated.outcome, outcome_from_score(home, away));
+                }
+                None => {
+                    assert!(i >= fixtures.len());
+                    assert_eq!(simulated.outcome, unweighted_outcome(&randomness[i * 32..]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_model_validation() {
+        assert!(GoalModel::DEFAULT.is_valid());
+        assert!(!GoalModel { rating_scale: 0, ..GoalModel::DEFAULT }.is_valid());
+        assert!(!GoalModel { max_goals: 0, ..GoalModel::DEFAULT }.is_valid());
+        assert!(!GoalModel { base_goals_milli: 50, ..GoalModel::DEFAULT }.is_valid());
+    }
+}
@@ -0,0 +1,181 @@
+//! Ed25519 Result Attestation Module
+//!
+//! The runtime cannot verify signatures inside a program cheaply, so the
+//! submitter puts a native Ed25519 program instruction in the same
+//! transaction and we check, via the instructions sysvar, that it verified
+//! the expected key over the expected message.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::errors::SportsbookError;
+
+/// Domain separator for result attestations
+pub const RESULTS_ATTESTATION_DOMAIN: &[u8] = b"sportsbook:results:v1";
+
+/// Ed25519 instruction layout (see solana_sdk::ed25519_instruction)
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+
+/// Message an attestor signs for a round's results
+pub fn results_message(betting_pool: &Pubkey, round_id: u64, packed_results: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RESULTS_ATTESTATION_DOMAIN.len() + 32 + 8 + packed_results.len());
+    message.extend_from_slice(RESULTS_ATTESTATION_DOMAIN);
+    message.extend_from_slice(betting_pool.as_ref());
+    message.extend_from_slice(&round_id.to_le_bytes());
+    message.extend_from_slice(packed_results);
+    message
+}
+
+/// Check that the instruction right before this one is an Ed25519 program
+/// instruction verifying `signer`'s signature over exactly `message`
+pub fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    attested_signature(instructions_sysvar, signer, message).map(|_| ())
+}
+
+/// Like `verify_ed25519_attestation`, and additionally require the verified
+/// signature to be exactly `signature`
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LEN],
+) -> Result<()> {
+    let attested = attested_signature(instructions_sysvar, signer, message)?;
+    require!(attested == signature.as_slice(), SportsbookError::InvalidAttestation);
+    Ok(())
+}
+
+/// Signature of the preceding Ed25519 instruction, after checking it
+/// verified `signer` over `message`
+fn attested_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, SportsbookError::InvalidAttestation);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID,
+        SportsbookError::InvalidAttestation
+    );
+    require!(ed25519_ix.accounts.is_empty(), SportsbookError::InvalidAttestation);
+
+    let (attested_signature, attested_key, attested_message) =
+        parse_ed25519_instruction(&ed25519_ix.data)?;
+    require!(attested_key == signer.as_ref(), SportsbookError::InvalidAttestation);
+    require!(attested_message == message, SportsbookError::InvalidAttestation);
+
+    Ok(attested_signature.to_vec())
+}
+
+/// Extract (signature, pubkey, message) from a single-signature Ed25519 instruction
+///
+/// All offsets must point into the Ed25519 instruction itself; otherwise the
+/// native program would have verified data we never see here.
+fn parse_ed25519_instruction(data: &[u8]) -> Result<(&[u8], &[u8], &[u8])> {
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN,
+        SportsbookError::InvalidAttestation
+    );
+    require!(data[0] == 1, SportsbookError::InvalidAttestation);
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN];
+    let read = |i: usize| u16::from_le_bytes([offsets[i], offsets[i + 1]]);
+
+    let signature_offset = read(0) as usize;
+    let signature_ix_index = read(2);
+    let pubkey_offset = read(4) as usize;
+    let pubkey_ix_index = read(6);
+    let message_offset = read(8) as usize;
+    let message_size = read(10) as usize;
+    let message_ix_index = read(12);
+
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        SportsbookError::InvalidAttestation
+    );
+
+    let signature = data
+        .get(signature_offset..signature_offset + SIGNATURE_LEN)
+        .ok_or(SportsbookError::InvalidAttestation)?;
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(SportsbookError::InvalidAttestation)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(SportsbookError::InvalidAttestation)?;
+
+    Ok((signature, pubkey, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build Ed25519 instruction data in the layout produced by
+    /// solana_sdk::ed25519_instruction::new_ed25519_instruction
+    fn make_ed25519_data(pubkey: &[u8; 32], message: &[u8], ix_index: u16) -> Vec<u8> {
+        let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+        let signature_offset = pubkey_offset + PUBKEY_LEN;
+        let message_offset = signature_offset + 64;
+
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset as u16,
+            ix_index,
+            pubkey_offset as u16,
+            ix_index,
+            message_offset as u16,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey);
+        data.extend_from_slice(&[7u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let pool = Pubkey::new_unique();
+        let message = results_message(&pool, 42, &[0x49, 0x92, 0x24, 0x09]);
+        let data = make_ed25519_data(&[9u8; 32], &message, u16::MAX);
+
+        let (signature, pubkey, parsed_message) = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(signature, &[7u8; 64]);
+        assert_eq!(pubkey, &[9u8; 32]);
+        assert_eq!(parsed_message, message.as_slice());
+    }
+
+    #[test]
+    fn test_parse_rejects_external_offsets() {
+        // Pointing at data in another instruction would let an attacker
+        // get a valid signature checked over bytes we don't inspect
+        let data = make_ed25519_data(&[9u8; 32], b"msg", 0);
+        assert!(parse_ed25519_instruction(&data).is_err());
+    }
+
+    #[test]
+    fn test_results_message_binds_pool_and_round() {
+        let pool = Pubkey::new_unique();
+        let packed = [0x49, 0x92, 0x24, 0x09];
+        assert_ne!(results_message(&pool, 1, &packed), results_message(&pool, 2, &packed));
+        assert_ne!(
+            results_message(&pool, 1, &packed),
+            results_message(&Pubkey::new_unique(), 1, &packed)
+        );
+    }
+}
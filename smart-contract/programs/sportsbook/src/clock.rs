@@ -0,0 +1,63 @@
+//! Time Source Module
+//!
+//! Claim-window and bounty rules read time through `TimeSource` rather than
+//! the Clock sysvar, so they can be unit-tested with a fixed timestamp.
+//! Builds with the `mock-clock` feature (devnet/localnet only) additionally
+//! let the authority pin the pool's clock for end-to-end tests.
+
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+
+/// Something that knows the current unix time
+pub trait TimeSource {
+    fn unix_timestamp(&self) -> Result<i64>;
+}
+
+/// Cluster time from the Clock sysvar
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SysvarClock;
+
+impl TimeSource for SysvarClock {
+    fn unix_timestamp(&self) -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp)
+    }
+}
+
+/// A fixed point in time (tests and mock-clock mode)
+#[derive(Clone, Copy, Debug)]
+pub struct FixedClock(pub i64);
+
+impl TimeSource for FixedClock {
+    fn unix_timestamp(&self) -> Result<i64> {
+        Ok(self.0)
+    }
+}
+
+/// Clock a pool's handlers should use
+#[derive(Clone, Copy, Debug)]
+pub enum PoolClock {
+    Sysvar(SysvarClock),
+    Mock(FixedClock),
+}
+
+impl TimeSource for PoolClock {
+    fn unix_timestamp(&self) -> Result<i64> {
+        match self {
+            PoolClock::Sysvar(clock) => clock.unix_timestamp(),
+            PoolClock::Mock(clock) => clock.unix_timestamp(),
+        }
+    }
+}
+
+/// Whether this build honours `BettingPool::mock_unix_timestamp`
+pub const MOCK_CLOCK_ENABLED: bool = cfg!(feature = "mock-clock");
+
+/// The pool's mock time if mock-clock mode is compiled in and set,
+/// otherwise the Clock sysvar
+pub fn pool_clock(betting_pool: &BettingPool) -> PoolClock {
+    if MOCK_CLOCK_ENABLED && betting_pool.mock_unix_timestamp != 0 {
+        PoolClock::Mock(FixedClock(betting_pool.mock_unix_timestamp))
+    } else {
+        PoolClock::Sysvar(SysvarClock)
+    }
+}
@@ -1,18 +1,27 @@
-/// Constants for the sportsbook betting contract
-///
-/// All constants are defined to match the Solidity contract behavior
-/// Odds and multipliers are scaled by 1e9 on Solana (vs 1e18 on Ethereum)
+//! Constants for the sportsbook betting contract
+//!
+//! All constants are defined to match the Solidity contract behavior
+//! Odds and multipliers are scaled by 1e9 on Solana (vs 1e18 on Ethereum)
+//!
+//! Items marked `#[constant]` are exported in the IDL so generated clients
+//! read the exact values instead of hard-coding them.
+
+use anchor_lang::prelude::*;
 
 /// BASIS POINTS = 10000 (100%)
+#[constant]
 pub const BPS_DENOMINATOR: u64 = 10000;
 
 /// Protocol fee on all bets (5%)
+#[constant]
 pub const DEFAULT_PROTOCOL_FEE_BPS: u16 = 500;
 
 /// Winner share distributed (25%)
+#[constant]
 pub const DEFAULT_WINNER_SHARE_BPS: u16 = 2500;
 
 /// Season pool share (2%)
+#[constant]
 pub const DEFAULT_SEASON_POOL_SHARE_BPS: u16 = 200;
 
 /// MULTIBET STAKE BONUS RATES (basis points)
@@ -23,6 +32,7 @@ pub const BONUS_4_PLUS: u64 = 2000;   // 20%
 
 /// PARLAY PAYOUT MULTIPLIERS (1e9 scale, reduced for LP safety)
 /// Linear progression: 1.05x (2 matches) to 1.25x (10 matches)
+#[constant]
 pub const ODDS_SCALE: u64 = 1_000_000_000; // 1e9 (vs 1e18 on Ethereum)
 
 pub const PARLAY_MULTIPLIER_1_MATCH: u64 = ODDS_SCALE;            // 1.0x
@@ -82,29 +92,312 @@ pub const TIER_4_DECAY: u64 = 6400;  // 64% (36% decay)
 
 /// RISK MANAGEMENT CAPS
 /// Max bet amount (10,000 tokens with 9 decimals)
+#[constant]
 pub const MAX_BET_AMOUNT: u64 = 10_000_000_000_000;
 
 /// Max payout per bet (100,000 tokens with 9 decimals)
+#[constant]
 pub const MAX_PAYOUT_PER_BET: u64 = 100_000_000_000_000;
 
 /// Max round payouts (500,000 tokens with 9 decimals)
+#[constant]
 pub const MAX_ROUND_PAYOUTS: u64 = 500_000_000_000_000;
 
+/// Largest payout quick_claim handles (100 tokens with 9 decimals)
+#[constant]
+pub const QUICK_CLAIM_MAX_PAYOUT: u64 = 100_000_000_000;
+
+/// Claim shards a round's counters can be split over (bet_id % CLAIM_SHARDS)
+#[constant]
+pub const CLAIM_SHARDS: u8 = 4;
+
+/// Hard ceiling on the share of pool liquidity that may be lent out (30%)
+#[constant]
+pub const MAX_LENDING_DEPLOY_BPS: u16 = 3000;
+
+/// Delay before a new lending program, or a higher lending share, takes effect (48 hours)
+#[constant]
+pub const LENDING_PROGRAM_TIMELOCK_SECONDS: i64 = 172_800;
+
+/// Delay before a newly proposed outflow destination can receive funds (48 hours)
+#[constant]
+pub const OUTFLOW_DESTINATION_TIMELOCK_SECONDS: i64 = 172_800;
+
+/// Solend lending program; idle liquidity may be lent to it
+pub const SOLEND_PROGRAM_ID: Pubkey = pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+/// Kamino lending program; idle liquidity may be lent to it
+pub const KAMINO_LENDING_PROGRAM_ID: Pubkey = pubkey!("KLend2g3cP87fffoy8q1mQqGKjrxjC8boSyAYavgmjD");
+
+/// Claim window after settlement; after this anyone can claim for a bounty (24 hours)
+#[constant]
+pub const CLAIM_WINDOW_SECONDS: i64 = 86_400;
+
+/// Longest a round's claim window can be extended in total, by guardian
+/// pauses and outage extensions together (72 hours)
+#[constant]
+pub const MAX_CLAIMS_PAUSE_SECONDS: i64 = 259_200;
+
+/// Most one extend_claim_deadline call can add to a round's claim window
+/// (24 hours)
+#[constant]
+pub const MAX_CLAIM_DEADLINE_EXTENSION_SECONDS: i64 = 86_400;
+
 /// Number of matches per round
+#[constant]
 pub const MATCHES_PER_ROUND: usize = 10;
 
+/// Most legs a cross-round accumulator can have
+#[constant]
+pub const MAX_ACCUMULATOR_LEGS: usize = 10;
+
+/// Match result code for a voided match (1-3 are home, away, draw)
+#[constant]
+pub const RESULT_VOID: u8 = 4;
+
+// Payout and odds math runs in sportsbook-core; its copies of the values
+// above and below must never drift from what the program exports
+const _: () = {
+    assert!(BPS_DENOMINATOR == sportsbook_core::BPS_DENOMINATOR);
+    assert!(ODDS_SCALE == sportsbook_core::ODDS_SCALE);
+    assert!(MAX_PAYOUT_PER_BET == sportsbook_core::MAX_PAYOUT_PER_BET);
+    assert!(MIN_COMPRESSED_ODDS == sportsbook_core::MIN_COMPRESSED_ODDS);
+    assert!(MAX_COMPRESSED_ODDS == sportsbook_core::MAX_COMPRESSED_ODDS);
+    assert!(RAW_ODDS_MIN == sportsbook_core::RAW_ODDS_MIN);
+    assert!(RAW_ODDS_MAX == sportsbook_core::RAW_ODDS_MAX);
+    assert!(RESULT_VOID == sportsbook_core::RESULT_VOID);
+};
+
 /// Odds compression constants (compress raw odds to 1.2x - 2.2x range)
+#[constant]
 pub const MIN_COMPRESSED_ODDS: u64 = 1_200_000_000;  // 1.2x (strong favorites)
+#[constant]
 pub const MAX_COMPRESSED_ODDS: u64 = 2_200_000_000;  // 2.2x (heavy underdogs)
 pub const RAW_ODDS_MIN: u64 = 1_800_000_000;         // 1.8x raw (maps to 1.2x)
 pub const RAW_ODDS_MAX: u64 = 5_500_000_000;         // 5.5x raw (maps to 2.2x)
 
 /// TEAM TOKEN HOLDER BENEFITS
 /// Reduced protocol fee for team token holders (2% instead of 5%)
+#[constant]
 pub const TEAM_TOKEN_FEE_BPS: u16 = 200;  // 2%
 
 /// Odds boost for team token holders (5% better multiplier)
+#[constant]
 pub const TEAM_TOKEN_ODDS_BOOST_BPS: u64 = 500;  // 5%
 
 /// Minimum team token balance required for benefits (1 token with 9 decimals)
+#[constant]
 pub const MIN_TEAM_TOKEN_BALANCE: u64 = 1_000_000_000;
+
+/// COPY BETTING
+/// Highest success fee a follower can grant a leader (20% of profit)
+#[constant]
+pub const MAX_COPY_SUCCESS_FEE_BPS: u16 = 2000;
+
+/// CREATOR ROUNDS
+/// Highest share of a branded round's protocol revenue a creator can get (50%)
+#[constant]
+pub const MAX_CREATOR_REVENUE_SHARE_BPS: u16 = 5000;
+
+/// CONSOLATION LOTTERY
+/// Highest share of a round's protocol revenue set aside for lottery prizes (5%)
+#[constant]
+pub const MAX_LOTTERY_SHARE_BPS: u16 = 500;
+
+/// One lottery per week
+#[constant]
+pub const LOTTERY_PERIOD_SECONDS: i64 = 604_800;
+
+/// Tickets can still be entered this long after the week closes (24 hours)
+#[constant]
+pub const LOTTERY_ENTRY_GRACE_SECONDS: i64 = 86_400;
+
+/// BET INSURANCE
+/// Highest premium a pool can charge for insuring a parlay (20% of stake)
+#[constant]
+pub const MAX_INSURANCE_PREMIUM_BPS: u16 = 2000;
+
+/// SEASON CLOSE
+/// Longest a season's rewards can be kept claimable after it ends (90 days)
+#[constant]
+pub const MAX_SEASON_CLAIM_PERIOD_SECONDS: i64 = 7_776_000;
+
+/// BRIDGE EXPORT
+/// Wormhole consistency level for result messages (1 = finalized)
+#[constant]
+pub const BRIDGE_CONSISTENCY_LEVEL: u8 = 1;
+
+/// PDA SEEDS
+/// Every program-derived address starts with one of these prefixes:
+///   betting_pool      [BETTING_POOL_SEED]
+///   liquidity_pool    [LIQUIDITY_POOL_SEED, betting_pool]
+///   round             [ROUND_SEED, betting_pool, round_id (u64 LE)]
+///   open_liability    [OPEN_LIABILITY_SEED, betting_pool, round_id (u64 LE)]
+///   round_archive     [ROUND_ARCHIVE_SEED, betting_pool, round_id (u64 LE)]
+///   round_attestation [ROUND_ATTESTATION_SEED, betting_pool, round_id (u64 LE)]
+///   daily_volume      [DAILY_VOLUME_SEED, betting_pool]
+///   active_bettors    [ACTIVE_BETTORS_SEED, betting_pool]
+///   pending_claims    [PENDING_CLAIMS_SEED, betting_pool]
+///   vrf_request       [VRF_REQUEST_SEED, betting_pool, round_id (u64 LE)]
+///   vrf_proof         [VRF_PROOF_SEED, betting_pool, round_id (u64 LE)]
+///   round_kickoffs    [ROUND_KICKOFFS_SEED, betting_pool, round_id (u64 LE)]
+///   round_metadata    [ROUND_METADATA_SEED, betting_pool, round_id (u64 LE)]
+///   round_vault       [ROUND_VAULT_SEED, betting_pool, round_id (u64 LE)]
+///   odds_history      [ODDS_HISTORY_SEED, betting_pool, round_id (u64 LE), match_index (u8)]
+///   lottery_config    [LOTTERY_CONFIG_SEED, betting_pool]
+///   lottery           [LOTTERY_SEED, betting_pool, lottery_id (u64 LE)]
+///   lottery_ticket    [LOTTERY_TICKET_SEED, lottery, bet]
+///   results_export    [RESULTS_EXPORT_SEED, betting_pool, round_id (u64 LE)]
+///   bridge_emitter    [BRIDGE_EMITTER_SEED]
+///   market_maker      [MARKET_MAKER_SEED, betting_pool, wallet]
+///   leg_fee_schedule  [LEG_FEE_SCHEDULE_SEED, betting_pool]
+///   stake_policy      [STAKE_POLICY_SEED, betting_pool]
+///   claim_reminder    [CLAIM_REMINDER_SEED, bet]
+///   claim_shard       [CLAIM_SHARD_SEED, betting_pool, round_id (u64 LE), shard_index]
+///   access_control    [ACCESS_CONTROL_SEED, betting_pool]
+///   odds_update_limits [ODDS_UPDATE_LIMITS_SEED, betting_pool]
+///   round_odds_updates [ROUND_ODDS_UPDATES_SEED, betting_pool, round_id (u64 LE)]
+///   accumulator       [ACCUMULATOR_SEED, betting_pool, bet_id (u64 LE)]
+///   insurance_fund    [INSURANCE_FUND_SEED, betting_pool]
+///   insurance_vault   [INSURANCE_VAULT_SEED, betting_pool]
+///   round_summary     [ROUND_SUMMARY_SEED, betting_pool, round_id (u64 LE)]
+///   season_policy     [SEASON_POLICY_SEED, betting_pool]
+///   notifications     [NOTIFICATIONS_SEED, betting_pool]
+///   pn_ledger         [PN_LEDGER_SEED, betting_pool]
+///   reconcile_cursor  [RECONCILE_CURSOR_SEED, betting_pool]
+///   lending_proposal  [LENDING_PROPOSAL_SEED, betting_pool]
+///   outflow_allowlist [OUTFLOW_ALLOWLIST_SEED, betting_pool]
+///   bet (pool-wide)   [BET_SEED, betting_pool, bet_id (u64 LE)]
+///   bet (per-user)    [BET_SEED, betting_pool, bettor, user_nonce (u64 LE)]
+///   bet_record        [BET_RECORD_SEED, bet]
+///   user_bet_counter  [USER_BET_COUNTER_SEED, betting_pool, bettor]
+///   user_volume       [USER_VOLUME_SEED, betting_pool, bettor]
+///   user_stats        [USER_STATS_SEED, betting_pool, bettor]
+///   credit_vault      [CREDIT_VAULT_SEED, betting_pool, bettor]
+///   promo             [PROMO_SEED, betting_pool, code_hash]
+///   promo_redemption  [PROMO_REDEMPTION_SEED, promo, wallet]
+///   copy_config       [COPY_CONFIG_SEED, betting_pool, leader, follower]
+///   creator           [CREATOR_SEED, betting_pool, wallet]
+///   branded_round     [BRANDED_ROUND_SEED, betting_pool, round_id (u64 LE)]
+///   league_schedule   [LEAGUE_SCHEDULE_SEED, betting_pool, season_id (u64 LE)]
+///   round_fixtures    [ROUND_FIXTURES_SEED, betting_pool, round_id (u64 LE)]
+///   season_table      [SEASON_TABLE_SEED, league_schedule]
+///   season_registry   [SEASON_REGISTRY_SEED, authority, season_id (u64 LE)]
+///   season_points     [SEASON_POINTS_SEED, season_registry, user]
+///   season_reporter   [SEASON_REPORTER_SEED]
+///   season_prediction [SEASON_PREDICTION_SEED, betting_pool, season_id (u64 LE), user]
+///   prediction_nft    [PREDICTION_NFT_SEED, betting_pool, season_id (u64 LE), user]
+#[constant]
+pub const BETTING_POOL_SEED: &[u8] = b"betting_pool";
+#[constant]
+pub const LIQUIDITY_POOL_SEED: &[u8] = b"liquidity_pool";
+#[constant]
+pub const ROUND_SEED: &[u8] = b"round";
+#[constant]
+pub const OPEN_LIABILITY_SEED: &[u8] = b"open_liability";
+#[constant]
+pub const ROUND_ARCHIVE_SEED: &[u8] = b"round_archive";
+#[constant]
+pub const ROUND_ATTESTATION_SEED: &[u8] = b"round_attestation";
+#[constant]
+pub const DAILY_VOLUME_SEED: &[u8] = b"daily_volume";
+#[constant]
+pub const ACTIVE_BETTORS_SEED: &[u8] = b"active_bettors";
+#[constant]
+pub const PENDING_CLAIMS_SEED: &[u8] = b"pending_claims";
+#[constant]
+pub const VRF_REQUEST_SEED: &[u8] = b"vrf_request";
+#[constant]
+pub const VRF_PROOF_SEED: &[u8] = b"vrf_proof";
+#[constant]
+pub const ROUND_KICKOFFS_SEED: &[u8] = b"round_kickoffs";
+#[constant]
+pub const ROUND_METADATA_SEED: &[u8] = b"round_metadata";
+#[constant]
+pub const ROUND_VAULT_SEED: &[u8] = b"round_vault";
+#[constant]
+pub const ODDS_HISTORY_SEED: &[u8] = b"odds_history";
+#[constant]
+pub const LOTTERY_CONFIG_SEED: &[u8] = b"lottery_config";
+#[constant]
+pub const LOTTERY_SEED: &[u8] = b"lottery";
+#[constant]
+pub const LOTTERY_TICKET_SEED: &[u8] = b"lottery_ticket";
+#[constant]
+pub const RESULTS_EXPORT_SEED: &[u8] = b"results_export";
+#[constant]
+pub const BRIDGE_EMITTER_SEED: &[u8] = b"emitter";
+#[constant]
+pub const MARKET_MAKER_SEED: &[u8] = b"market_maker";
+#[constant]
+pub const LEG_FEE_SCHEDULE_SEED: &[u8] = b"leg_fees";
+#[constant]
+pub const STAKE_POLICY_SEED: &[u8] = b"stake_policy";
+#[constant]
+pub const CLAIM_REMINDER_SEED: &[u8] = b"claim_reminder";
+#[constant]
+pub const CLAIM_SHARD_SEED: &[u8] = b"claim_shard";
+#[constant]
+pub const ACCESS_CONTROL_SEED: &[u8] = b"access_control";
+#[constant]
+pub const ODDS_UPDATE_LIMITS_SEED: &[u8] = b"odds_update_limits";
+#[constant]
+pub const ROUND_ODDS_UPDATES_SEED: &[u8] = b"round_odds_updates";
+#[constant]
+pub const ACCUMULATOR_SEED: &[u8] = b"accumulator";
+#[constant]
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+#[constant]
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+#[constant]
+pub const ROUND_SUMMARY_SEED: &[u8] = b"round_summary";
+#[constant]
+pub const SEASON_POLICY_SEED: &[u8] = b"season_policy";
+#[constant]
+pub const NOTIFICATIONS_SEED: &[u8] = b"notifications";
+#[constant]
+pub const PN_LEDGER_SEED: &[u8] = b"pn_ledger";
+#[constant]
+pub const RECONCILE_CURSOR_SEED: &[u8] = b"reconcile_cursor";
+#[constant]
+pub const LENDING_PROPOSAL_SEED: &[u8] = b"lending_proposal";
+#[constant]
+pub const OUTFLOW_ALLOWLIST_SEED: &[u8] = b"outflow_allowlist";
+#[constant]
+pub const BET_SEED: &[u8] = b"bet";
+#[constant]
+pub const BET_RECORD_SEED: &[u8] = b"bet_record";
+#[constant]
+pub const USER_BET_COUNTER_SEED: &[u8] = b"user_bet_counter";
+#[constant]
+pub const USER_VOLUME_SEED: &[u8] = b"user_volume";
+#[constant]
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+#[constant]
+pub const CREDIT_VAULT_SEED: &[u8] = b"credit_vault";
+#[constant]
+pub const PROMO_SEED: &[u8] = b"promo";
+#[constant]
+pub const PROMO_REDEMPTION_SEED: &[u8] = b"promo_redemption";
+#[constant]
+pub const COPY_CONFIG_SEED: &[u8] = b"copy_config";
+#[constant]
+pub const CREATOR_SEED: &[u8] = b"creator";
+#[constant]
+pub const BRANDED_ROUND_SEED: &[u8] = b"branded_round";
+#[constant]
+pub const LEAGUE_SCHEDULE_SEED: &[u8] = b"league_schedule";
+#[constant]
+pub const ROUND_FIXTURES_SEED: &[u8] = b"round_fixtures";
+#[constant]
+pub const SEASON_TABLE_SEED: &[u8] = b"season_table";
+#[constant]
+pub const SEASON_REGISTRY_SEED: &[u8] = b"season_registry";
+#[constant]
+pub const SEASON_POINTS_SEED: &[u8] = b"season_points";
+#[constant]
+pub const SEASON_REPORTER_SEED: &[u8] = b"season_reporter";
+#[constant]
+pub const SEASON_PREDICTION_SEED: &[u8] = b"season_prediction";
+#[constant]
+pub const PREDICTION_NFT_SEED: &[u8] = b"prediction_nft";
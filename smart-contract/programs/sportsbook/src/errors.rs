@@ -1,5 +1,20 @@
 use anchor_lang::prelude::*;
 
+/// `require!` that also emits a context event when the check fails
+///
+/// Program logs survive a failed transaction, so the values behind the
+/// failure (amounts, deadlines, liabilities) can be read from the logs
+/// instead of being reconstructed from account state after the fact.
+#[macro_export]
+macro_rules! require_with_context {
+    ($cond:expr, $err:expr, $event:expr $(,)?) => {
+        if !($cond) {
+            anchor_lang::prelude::emit!($event);
+            return Err(anchor_lang::error!($err));
+        }
+    };
+}
+
 #[error_code]
 pub enum SportsbookError {
     #[msg("Invalid match index (must be 0-9)")]
@@ -85,4 +100,412 @@ pub enum SportsbookError {
 
     #[msg("Numerical overflow")]
     NumericalOverflow,
+
+    #[msg("Betting pool token balance does not cover outstanding liabilities")]
+    PoolInsolvent,
+
+    #[msg("Account is not a round of this betting pool")]
+    InvalidRoundAccount,
+
+    #[msg("Duplicate account passed")]
+    DuplicateAccount,
+
+    #[msg("Lending is not enabled for this pool")]
+    LendingDisabled,
+
+    #[msg("Lending program is not whitelisted")]
+    LendingProgramNotWhitelisted,
+
+    #[msg("Lending share exceeds the configured limit")]
+    LendingLimitExceeded,
+
+    #[msg("Token movement did not match the requested lending amount")]
+    LendingTransferMismatch,
+
+    #[msg("Liquidity must be recalled from the lending program first")]
+    LiquidityStillDeployed,
+
+    #[msg("Price feed is missing, unknown or not trading")]
+    InvalidPriceFeed,
+
+    #[msg("Price feed is stale")]
+    StalePrice,
+
+    #[msg("Odds can no longer change once bets have been placed")]
+    OddsUpdateAfterBets,
+
+    #[msg("Invalid packed odds payload")]
+    InvalidOddsPayload,
+
+    #[msg("Results attestor not configured")]
+    AttestorNotSet,
+
+    #[msg("Missing or invalid Ed25519 attestation")]
+    InvalidAttestation,
+
+    #[msg("Bet account does not match its PDA")]
+    InvalidBetAccount,
+
+    #[msg("Round revenue has not been finalized")]
+    RevenueNotFinalized,
+
+    #[msg("Account type cannot be resized")]
+    AccountNotResizable,
+
+    #[msg("Claimer token account must be the claimer's initialized ATA for the pool mint")]
+    InvalidClaimerTokenAccount,
+
+    #[msg("Bet won; use claim_winnings")]
+    BetNotLost,
+
+    #[msg("Bet has more legs than the pool allows")]
+    TooManyLegs,
+
+    #[msg("Leg odds below the pool minimum")]
+    LegOddsTooLow,
+
+    #[msg("Combined odds above the pool maximum")]
+    CombinedOddsTooHigh,
+
+    #[msg("Potential payout exceeds the per-bet cap")]
+    PayoutExceedsCap,
+
+    #[msg("Signer is not the guardian")]
+    InvalidGuardian,
+
+    #[msg("Claims are paused for this round")]
+    ClaimsPaused,
+
+    #[msg("Claims are not paused for this round")]
+    ClaimsNotPaused,
+
+    #[msg("Total claims pause would exceed the maximum")]
+    PauseTooLong,
+
+    #[msg("Mock clock is not available in this build")]
+    MockClockDisabled,
+
+    #[msg("This pool settles with Merkle proofs; use claim_winnings_with_proof")]
+    OddsProofRequired,
+
+    #[msg("Odds proof does not match the round's locked odds root")]
+    InvalidOddsProof,
+
+    #[msg("Fee tiers must have distinct volumes and fees that never rise with volume")]
+    InvalidFeeTiers,
+
+    #[msg("Promo bonus must be 1-10000 bps with at least one redemption per wallet")]
+    InvalidPromo,
+
+    #[msg("Promo has expired")]
+    PromoExpired,
+
+    #[msg("Promo budget cannot cover this bonus")]
+    PromoBudgetExhausted,
+
+    #[msg("Wallet has reached this promo's redemption limit")]
+    PromoRedemptionLimitReached,
+
+    #[msg("A promo has already been applied to this bet")]
+    PromoAlreadyApplied,
+
+    #[msg("Copy config needs a different leader, a 1-10000 bps stake and a success fee within the cap")]
+    InvalidCopyConfig,
+
+    #[msg("Leader bet was already copied (or an earlier one was skipped past)")]
+    BetAlreadyCopied,
+
+    #[msg("Follower's copy budget for this round is used up")]
+    CopyBudgetExhausted,
+
+    #[msg("Copied bet needs the leader's token account for the success fee")]
+    InvalidLeaderTokenAccount,
+
+    #[msg("Creator revenue share exceeds the maximum")]
+    InvalidCreatorShare,
+
+    #[msg("Creator is not whitelisted or has been deactivated")]
+    CreatorNotActive,
+
+    #[msg("Branded round needs its BrandedRound and creator token account")]
+    BrandedRoundAccountsMissing,
+
+    #[msg("League needs an even number of teams between 4 and 20")]
+    InvalidLeagueSize,
+
+    #[msg("League has started; its teams can no longer change")]
+    LeagueAlreadyStarted,
+
+    #[msg("Every matchday of this league has been materialized")]
+    LeagueFinished,
+
+    #[msg("Previous league round must be passed in and settled first")]
+    PreviousRoundNotSettled,
+
+    #[msg("Scores for this round were already recorded")]
+    ScoresAlreadyRecorded,
+
+    #[msg("Score contradicts the settled match result")]
+    ScoreContradictsResult,
+
+    #[msg("Round is not this league's next matchday to tabulate")]
+    InvalidLeagueRound,
+
+    #[msg("Round's randomness is not disputed")]
+    RoundNotDisputed,
+
+    #[msg("Team is not registered in this league")]
+    InvalidLeagueTeam,
+
+    #[msg("Goal model parameters are out of range")]
+    InvalidGoalModel,
+
+    #[msg("League round needs its RoundFixtures to simulate results")]
+    RoundFixturesMissing,
+
+    #[msg("Season registry is not configured or can't take this pool")]
+    InvalidSeasonRegistry,
+
+    #[msg("Season registry has ended")]
+    SeasonRegistryEnded,
+
+    #[msg("Reporter is not a registered pool's season reporter")]
+    InvalidSeasonReporter,
+
+    #[msg("Bet's round started before the season registry opened")]
+    BetBeforeSeason,
+
+    #[msg("Bet was already reported for season points")]
+    SeasonPointsAlreadyAccrued,
+
+    #[msg("Token mint account is missing or isn't the pool mint")]
+    InvalidTokenMint,
+
+    #[msg("Pool mint has a transfer hook: pass the hook program, its extra-account-metas account and the hook's accounts in remaining_accounts")]
+    TransferHookAccountsMissing,
+
+    #[msg("Pool mint's transfer hook could not run the payout transfer")]
+    TransferHookRejected,
+
+    #[msg("Quick claims take single-leg, non-copied bets paying at most QUICK_CLAIM_MAX_PAYOUT")]
+    NotQuickClaimable,
+
+    #[msg("Kickoff time must not be negative")]
+    InvalidKickoffTime,
+
+    #[msg("Match hasn't kicked off yet")]
+    KickoffNotReached,
+
+    #[msg("Match market is already closed at kickoff")]
+    MatchAlreadyLocked,
+
+    #[msg("Metadata URI must be 1-200 bytes with a non-zero content hash")]
+    InvalidMetadataUri,
+
+    #[msg("Round metadata can no longer change once bets have been placed")]
+    MetadataUpdateAfterBets,
+
+    #[msg("Round keeps its funds in its own vault; pass the round vault")]
+    RoundVaultRequired,
+
+    #[msg("Round was not created with round vaults enabled")]
+    RoundNotIsolated,
+
+    #[msg("Odds history account doesn't belong to an updated match of this round")]
+    InvalidOddsHistory,
+
+    #[msg("Lottery share exceeds the maximum or winners per draw is out of range")]
+    InvalidLotteryConfig,
+
+    #[msg("Lottery is not accepting tickets")]
+    LotteryEntryClosed,
+
+    #[msg("Bet is not a losing bet from a round that ended in this lottery's week")]
+    BetNotLotteryEligible,
+
+    #[msg("Lottery can't be drawn until ticket entry closes")]
+    LotteryNotClosed,
+
+    #[msg("Lottery has already been drawn")]
+    LotteryAlreadyDrawn,
+
+    #[msg("Ticket did not win this lottery")]
+    NotLotteryWinner,
+
+    #[msg("Lottery prize has already been claimed")]
+    LotteryPrizeClaimed,
+
+    #[msg("Round results are under dispute")]
+    RoundDisputed,
+
+    #[msg("Market maker bounds are invalid")]
+    InvalidMarketMakerBounds,
+
+    #[msg("Market maker is inactive or not assigned this match")]
+    MatchNotAssigned,
+
+    #[msg("Market maker quoted again before its update interval")]
+    QuoteTooFrequent,
+
+    #[msg("Quoted odds are out of range or move too far from the current odds")]
+    QuoteOutOfBounds,
+
+    #[msg("Bet can't be swept: not a winning bet, or a copied or voided one")]
+    NotSweepable,
+
+    #[msg("Leg fees must be below 100% and not rise with the number of legs")]
+    InvalidLegFees,
+
+    #[msg("Pool charges fees by parlay length; pass the leg fee schedule")]
+    LegFeeScheduleRequired,
+
+    #[msg("Minimum stake must be within the bet cap and cover one rounding unit")]
+    InvalidStakePolicy,
+
+    #[msg("Pool has a stake policy; pass the StakePolicy account")]
+    StakePolicyRequired,
+
+    #[msg("Stake is below the pool minimum")]
+    StakeBelowMinimum,
+
+    #[msg("Reminder lead time must be positive and within the claim window")]
+    InvalidReminderLeadTime,
+
+    #[msg("Bet's payout is reserved; claim it with claim_reserved")]
+    PayoutReserved,
+
+    #[msg("Bet's payout hasn't been reserved")]
+    PayoutNotReserved,
+
+    #[msg("Round has open claim shards; claim through them or merge them first")]
+    ClaimShardsOpen,
+
+    #[msg("Claim shard index out of range or not the bet's shard")]
+    InvalidClaimShard,
+
+    #[msg("Funds account balance moved differently from round accounting")]
+    FundsNotConserved,
+
+    #[msg("Role holder can't be the default pubkey")]
+    InvalidRoleHolder,
+
+    #[msg("Pool has odds update limits; pass OddsUpdateLimits and RoundOddsUpdates")]
+    OddsUpdateLimitsRequired,
+
+    #[msg("Odds update limits are out of range")]
+    InvalidOddsUpdateLimits,
+
+    #[msg("Match odds updated again before the pool's update interval")]
+    OddsUpdateTooFrequent,
+
+    #[msg("Odds update moves a line further than the pool allows")]
+    OddsMoveTooLarge,
+
+    #[msg("Accumulator legs must span at least two rounds; use place_bet within one round")]
+    AccumulatorNeedsTwoRounds,
+
+    #[msg("Accumulator anchor round must be the latest round among its legs")]
+    InvalidAnchorRound,
+
+    #[msg("Pass exactly the RoundAccounting of each other round the accumulator's legs are in")]
+    InvalidAccumulatorRounds,
+
+    #[msg("Accumulator has two legs on the same match")]
+    DuplicateAccumulatorLeg,
+
+    #[msg("A round of this accumulator hasn't settled yet")]
+    AccumulatorRoundsNotSettled,
+
+    #[msg("Accumulator already settled")]
+    AccumulatorAlreadySettled,
+
+    #[msg("Round has unsettled cross-round accumulators")]
+    AccumulatorsOpen,
+
+    #[msg("Accumulators can't include rounds that keep funds in their own vault")]
+    AccumulatorIsolatedRound,
+
+    #[msg("Insured bets need the pool's insurance fund and vault")]
+    InsuranceFundRequired,
+
+    #[msg("This pool doesn't offer bet insurance")]
+    InsuranceNotOffered,
+
+    #[msg("Only parlays can be insured")]
+    InsuranceNeedsParlay,
+
+    #[msg("Insurance premium above the maximum")]
+    InvalidInsurancePremium,
+
+    #[msg("Insurance vault can't cover the refund")]
+    InsufficientInsuranceFund,
+
+    #[msg("Insured bet lost by one leg: claim its refund with claim_winnings")]
+    InsuranceRefundDue,
+
+    #[msg("Season claim period above the maximum")]
+    InvalidSeasonClaimPeriod,
+
+    #[msg("Season hasn't ended")]
+    SeasonNotEnded,
+
+    #[msg("Season end wasn't recorded by the season policy")]
+    SeasonEndNotRecorded,
+
+    #[msg("Season rewards are still claimable")]
+    SeasonClaimsOpen,
+
+    #[msg("Returning season rewards needs the protocol treasury token account")]
+    TreasuryAccountRequired,
+
+    #[msg("Claim deadline extension must be positive and within the per-call and total limits")]
+    InvalidClaimExtension,
+
+    #[msg("UserStats and credit vault are required to bet with credit")]
+    UserStatsRequired,
+
+    #[msg("Not enough betting credit")]
+    InsufficientCredit,
+
+    #[msg("Auto-compounding claims need the bettor's credit vault")]
+    CreditVaultRequired,
+
+    #[msg("Every unclosed round must be passed, in round order, up to the pool's next round")]
+    IncompleteRoundSet,
+
+    #[msg("No lending program change is queued")]
+    NoLendingProposal,
+
+    #[msg("The lending program change is still timelocked")]
+    LendingTimelockActive,
+
+    #[msg("Accounts don't match the lending program's instruction layout")]
+    InvalidLendingAccounts,
+
+    #[msg("Reserved payouts of the round are still unclaimed")]
+    ReservedPayoutsUnpaid,
+
+    #[msg("Destination isn't on the pool's outflow allowlist, or its timelock hasn't passed")]
+    DestinationNotAllowlisted,
+
+    #[msg("Destination is already on the outflow allowlist")]
+    DestinationAlreadyListed,
+
+    #[msg("The outflow allowlist is full")]
+    OutflowAllowlistFull,
+
+    #[msg("This outflow needs the pool's outflow allowlist")]
+    OutflowAllowlistRequired,
+
+    #[msg("This outflow needs the pool's LotteryConfig address")]
+    LotteryConfigRequired,
+}
+
+impl From<sportsbook_core::CoreError> for SportsbookError {
+    fn from(error: sportsbook_core::CoreError) -> Self {
+        match error {
+            sportsbook_core::CoreError::Overflow => SportsbookError::CalculationOverflow,
+            sportsbook_core::CoreError::OddsNotLocked => SportsbookError::OddsNotLocked,
+        }
+    }
 }
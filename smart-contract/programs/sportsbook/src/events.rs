@@ -0,0 +1,378 @@
+use anchor_lang::prelude::*;
+use crate::state::{LockedOdds, SeasonRollover};
+
+/// Emitted every time `reconcile_pool` runs
+///
+/// Records the liabilities that were summed and the balance they were
+/// checked against, so indexers can chart solvency over time.
+#[event]
+pub struct PoolReconciled {
+    pub betting_pool: Pubkey,
+
+    /// Number of round accounts included in the check
+    pub rounds_checked: u32,
+
+    /// Sum of `total_reserved_for_winners` across the open rounds
+    pub total_reserved: u64,
+
+    /// Sum of `total_claimed` across the open rounds
+    pub total_claimed: u64,
+
+    /// Winnings still owed (reserved - claimed, per round)
+    pub outstanding_winnings: u64,
+
+    /// Season rewards held in the same token account
+    pub season_reward_pool: u64,
+
+    /// Lottery reserve and unclaimed prizes held in the same token account
+    pub lottery_funds: u64,
+
+    /// outstanding_winnings + season_reward_pool + lottery_funds
+    pub required_balance: u64,
+
+    /// Live balance of the betting pool token account
+    pub token_balance: u64,
+
+    pub timestamp: i64,
+}
+
+/// Emitted when the betting pool token account cannot cover its liabilities
+#[event]
+pub struct SolvencyDiscrepancy {
+    pub betting_pool: Pubkey,
+    pub required_balance: u64,
+    pub token_balance: u64,
+
+    /// required_balance - token_balance
+    pub shortfall: u64,
+
+    pub timestamp: i64,
+}
+
+/// Emitted when a bet is placed
+///
+/// Carries the counter-assigned bet ID so clients don't have to
+/// re-read the pool to find the bet they just created.
+#[event]
+pub struct BetPlaced {
+    pub betting_pool: Pubkey,
+    pub bet: Pubkey,
+    pub bet_id: u64,
+    pub bettor: Pubkey,
+    pub round_id: u64,
+    pub amount: u64,
+    pub num_predictions: u8,
+}
+
+/// A bet was paid for by a wallet other than its owner (place_gift_bet)
+#[event]
+pub struct BetGifted {
+    pub bet: Pubkey,
+    pub bet_id: u64,
+    pub payer: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+/// Emitted whenever a round's odds table is locked or replaced
+///
+/// `odds_hash` is also stored on the round, so the odds later used for
+/// payouts can be checked against what was shown at lock time.
+#[event]
+pub struct OddsLocked {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+
+    /// Full locked odds table (10 matches)
+    pub locked_odds: [LockedOdds; 10],
+
+    /// hash_locked_odds(betting_pool, round_id, locked_odds)
+    pub odds_hash: [u8; 32],
+
+    /// Merkle root over the same table, for claim proofs
+    pub odds_root: [u8; 32],
+
+    pub timestamp: i64,
+}
+
+/// Context for a failed claim-window check (ClaimsPaused / NotBettor)
+#[event]
+pub struct ClaimWindowViolation {
+    pub bet: Pubkey,
+    pub bettor: Pubkey,
+    pub claimer: Pubkey,
+    pub claim_deadline: i64,
+    pub claims_paused_until: i64,
+    pub current_time: i64,
+}
+
+/// Context for a failed payout check (PayoutBelowMinimum / PayoutExceedsCap /
+/// RoundPayoutLimitReached)
+#[event]
+pub struct PayoutCheckFailed {
+    pub bet: Pubkey,
+    pub round_id: u64,
+
+    /// Payout the bet would receive
+    pub payout: u64,
+
+    /// Bound it was checked against (min_payout or a cap)
+    pub limit: u64,
+
+    /// Round payouts so far (for the round cap check)
+    pub round_paid_out: u64,
+}
+
+/// Context for a failed liquidity or bet-size check
+/// (InsufficientProtocolLiquidity / BetExceedsMaximum)
+#[event]
+pub struct LiquidityCheckFailed {
+    pub betting_pool: Pubkey,
+
+    /// Amount that had to be covered or stayed under
+    pub required: u64,
+
+    /// Amount actually available / allowed
+    pub available: u64,
+}
+
+/// Permanent summary written for a finalized round
+#[event]
+pub struct RoundAttested {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub results_hash: [u8; 32],
+    pub total_bet_volume: u64,
+    pub pnl: i64,
+}
+
+/// A promo code was redeemed against a bet
+#[event]
+pub struct PromoRedeemed {
+    pub promo: Pubkey,
+    pub bet: Pubkey,
+    pub wallet: Pubkey,
+    pub bonus: u64,
+    pub remaining_budget: u64,
+}
+
+/// A follower's bet was mirrored from a leader's (copy_bet)
+#[event]
+pub struct BetCopied {
+    pub bet: Pubkey,
+    pub leader_bet: Pubkey,
+    pub leader: Pubkey,
+    pub follower: Pubkey,
+    pub amount: u64,
+}
+
+/// A round's settled results don't follow from its stored VRF proof
+#[event]
+pub struct RoundRandomnessDisputed {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub verifier: Pubkey,
+    pub expected_results: [u8; 10],
+    pub settled_results: [u8; 10],
+}
+
+/// A bet's stake was added to a wallet's cross-pool season points
+#[event]
+pub struct SeasonPointsRecorded {
+    pub season_registry: Pubkey,
+    pub pool_program: Pubkey,
+    pub user: Pubkey,
+    pub points: u64,
+    pub total_points: u64,
+}
+
+/// A match's market closed at kickoff, with its exposure at that moment
+#[event]
+pub struct MatchOddsLocked {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub match_index: u8,
+    pub odds: LockedOdds,
+
+    /// User stake on the match across all outcomes
+    pub stake: u64,
+
+    /// Largest payout owed if any single outcome comes in
+    pub max_payout: u64,
+
+    pub kickoff_time: i64,
+    pub locked_at: i64,
+}
+
+/// A round's off-chain metadata was set
+#[event]
+pub struct RoundMetadataSet {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub uri: String,
+
+    /// SHA-256 of the document at `uri`
+    pub content_hash: [u8; 32],
+}
+
+/// A weekly lottery was drawn
+#[event]
+pub struct LotteryDrawn {
+    pub betting_pool: Pubkey,
+    pub lottery_id: u64,
+    pub tickets: u32,
+    pub winning_tickets: Vec<u32>,
+    pub prize_per_winner: u64,
+}
+
+/// A winning lottery ticket was paid
+#[event]
+pub struct LotteryPrizeClaimed {
+    pub lottery: Pubkey,
+    pub lottery_id: u64,
+    pub ticket_number: u32,
+    pub bettor: Pubkey,
+    pub prize: u64,
+}
+
+/// A round's results were packaged for a cross-chain message
+#[event]
+pub struct RoundResultsExported {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub results_export: Pubkey,
+
+    /// keccak256 of the message payload
+    pub payload_digest: [u8; 32],
+}
+
+/// A market maker moved a match's odds
+#[event]
+pub struct MarketMakerQuoted {
+    pub betting_pool: Pubkey,
+    pub market_maker: Pubkey,
+    pub round_id: u64,
+    pub match_index: u8,
+    pub home_odds: u64,
+    pub away_odds: u64,
+    pub draw_odds: u64,
+}
+
+/// A bounty hunter claimed a batch of expired winning bets
+#[event]
+pub struct BountySwept {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub claimer: Pubkey,
+    pub bets: u32,
+
+    /// Payouts of the swept bets, bounties included
+    pub total_payout: u64,
+
+    /// Sent to the claimer in one transfer
+    pub total_bounty: u64,
+}
+
+/// A settled round's claim deadline was written to bets and reminders
+#[event]
+pub struct ClaimDeadlinesStamped {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub claim_deadline: i64,
+    pub bets: u32,
+    pub reminders: u32,
+}
+
+/// Winning and voided bets of a round were counted ahead of their claims
+#[event]
+pub struct PayoutsReserved {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub bets: u32,
+    pub total_payout: u64,
+    pub total_refunded: u64,
+}
+
+/// A parlay was insured at placement
+#[event]
+pub struct BetInsured {
+    pub betting_pool: Pubkey,
+    pub bet: Pubkey,
+    pub bet_id: u64,
+    pub premium: u64,
+}
+
+/// An insured parlay that lost by one leg had its stake refunded
+#[event]
+pub struct InsuranceRefunded {
+    pub betting_pool: Pubkey,
+    pub bet: Pubkey,
+    pub bet_id: u64,
+    pub bettor: Pubkey,
+    pub refund: u64,
+}
+
+/// A cross-round accumulator was placed
+#[event]
+pub struct AccumulatorPlaced {
+    pub betting_pool: Pubkey,
+    pub accumulator: Pubkey,
+    pub bet_id: u64,
+    pub bettor: Pubkey,
+    pub anchor_round_id: u64,
+    pub amount: u64,
+    pub num_legs: u8,
+    pub potential_payout: u64,
+}
+
+/// A cross-round accumulator was settled after its last round
+#[event]
+pub struct AccumulatorSettled {
+    pub betting_pool: Pubkey,
+    pub accumulator: Pubkey,
+    pub bet_id: u64,
+    pub bettor: Pubkey,
+
+    /// Winnings, or the stake if every leg was voided (0 = lost)
+    pub paid_out: u64,
+}
+
+/// A season was closed and its unclaimed rewards settled by the pool's
+/// SeasonPolicy
+#[event]
+pub struct SeasonClosed {
+    pub betting_pool: Pubkey,
+    pub season_id: u64,
+    pub rollover: SeasonRollover,
+    /// Reward pool left unclaimed at close
+    pub unclaimed: u64,
+}
+
+/// A round's claim deadline was pushed back after an outage
+#[event]
+pub struct ClaimDeadlineExtended {
+    pub betting_pool: Pubkey,
+    pub round_id: u64,
+    pub previous_deadline: i64,
+    pub claim_deadline: i64,
+}
+
+/// A claim held the bettor's winnings as betting credit (compounding)
+#[event]
+pub struct WinningsCompounded {
+    pub betting_pool: Pubkey,
+    pub bet: Pubkey,
+    pub bettor: Pubkey,
+    pub amount: u64,
+    pub credit: u64,
+}
+
+/// Lent principal was written off: the final recall came back short, or
+/// the authority recognized a loss the lending program won't return
+#[event]
+pub struct LendingLossRecognized {
+    pub betting_pool: Pubkey,
+    pub loss: u64,
+    /// `lending_deployed` after the write-off
+    pub still_deployed: u64,
+    pub recognized_by: Pubkey,
+}
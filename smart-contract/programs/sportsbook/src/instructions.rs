@@ -1,3 +1,8 @@
+// Every instruction module has its own `handler`, which lib.rs only calls
+// by path; the globs are there to bring the Accounts structs (and the
+// modules Anchor generates for them) into the crate root
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize;
 pub mod initialize_round;
 pub mod seed_round;
@@ -7,8 +12,55 @@ pub mod claim_winnings;
 pub mod finalize_revenue;
 pub mod vrf_request;
 pub mod vrf_fulfill;
+pub mod vrf_verify;
 pub mod season_prediction;
 pub mod end_season;
+pub mod reconcile_pool;
+pub mod idle_liquidity;
+pub mod price_feed;
+pub mod bulk_set_odds;
+pub mod bulk_submit_results;
+pub mod archive_round;
+pub mod resize_account;
+pub mod settle_lost_bets;
+pub mod bet_limits;
+pub mod guardian;
+pub mod mock_clock;
+pub mod settlement_mode;
+pub mod attest_round;
+pub mod metrics;
+pub mod fee_tiers;
+pub mod promo;
+pub mod copy_betting;
+pub mod creator_rounds;
+pub mod league_schedule;
+pub mod season_registry;
+pub mod lock_odds;
+pub mod round_metadata;
+pub mod round_vault;
+pub mod odds_history;
+pub mod lottery;
+pub mod export_results;
+pub mod market_maker;
+pub mod bounty_mode;
+pub mod bounty_sweep;
+pub mod leg_fees;
+pub mod stake_policy;
+pub mod claim_reminder;
+pub mod validate_settlement;
+pub mod reserve_payouts;
+pub mod claim_shard;
+pub mod fund_liquidity;
+pub mod access_control;
+pub mod odds_update_limits;
+pub mod accumulator;
+pub mod bet_insurance;
+pub mod round_summary;
+pub mod close_season;
+pub mod notifications;
+pub mod pn_ledger;
+pub mod auto_compound;
+pub mod outflow_allowlist;
 
 pub use initialize::*;
 pub use initialize_round::*;
@@ -19,5 +71,52 @@ pub use claim_winnings::*;
 pub use finalize_revenue::*;
 pub use vrf_request::*;
 pub use vrf_fulfill::*;
+pub use vrf_verify::*;
 pub use season_prediction::*;
 pub use end_season::*;
+pub use reconcile_pool::*;
+pub use idle_liquidity::*;
+pub use price_feed::*;
+pub use bulk_set_odds::*;
+pub use bulk_submit_results::*;
+pub use archive_round::*;
+pub use resize_account::*;
+pub use settle_lost_bets::*;
+pub use bet_limits::*;
+pub use guardian::*;
+pub use mock_clock::*;
+pub use settlement_mode::*;
+pub use attest_round::*;
+pub use metrics::*;
+pub use fee_tiers::*;
+pub use promo::*;
+pub use copy_betting::*;
+pub use creator_rounds::*;
+pub use league_schedule::*;
+pub use season_registry::*;
+pub use lock_odds::*;
+pub use round_metadata::*;
+pub use round_vault::*;
+pub use odds_history::*;
+pub use lottery::*;
+pub use export_results::*;
+pub use market_maker::*;
+pub use bounty_mode::*;
+pub use bounty_sweep::*;
+pub use leg_fees::*;
+pub use stake_policy::*;
+pub use claim_reminder::*;
+pub use validate_settlement::*;
+pub use reserve_payouts::*;
+pub use claim_shard::*;
+pub use fund_liquidity::*;
+pub use access_control::*;
+pub use odds_update_limits::*;
+pub use accumulator::*;
+pub use bet_insurance::*;
+pub use round_summary::*;
+pub use close_season::*;
+pub use notifications::*;
+pub use pn_ledger::*;
+pub use auto_compound::*;
+pub use outflow_allowlist::*;
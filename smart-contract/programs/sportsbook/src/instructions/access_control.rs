@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, AccessControl, Role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Hand an operational role to a key, replacing any previous holder
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AccessControl::LEN,
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_role_handler(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+    require!(holder != Pubkey::default(), SportsbookError::InvalidRoleHolder);
+
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.betting_pool = ctx.accounts.betting_pool.key();
+    access_control.bump = ctx.bumps.access_control;
+    access_control.set_holder(role, holder);
+
+    msg!("{:?} granted to {}", role, holder);
+
+    Ok(())
+}
+
+/// Take an operational role away; only the authority can act as it after
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Account<'info, AccessControl>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let previous = access_control.holder(role);
+    access_control.set_holder(role, Pubkey::default());
+
+    msg!("{:?} revoked from {}", role, previous);
+
+    Ok(())
+}
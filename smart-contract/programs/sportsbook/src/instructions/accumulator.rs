@@ -0,0 +1,503 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{
+    BettingPool, RoundAccounting, Accumulator, AccumulatorLeg, LegFeeSchedule, StakePolicy, leg_round_ids,
+};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::utils::{calculate_combined_odds, get_base_parlay_multiplier};
+use crate::events::{AccumulatorPlaced, AccumulatorSettled};
+use crate::clock::{pool_clock, TimeSource};
+use crate::invariants::FundsCheck;
+use sportsbook_core::{
+    calculate_accumulator_payout, calculate_accumulator_potential_payout, calculate_protocol_fee,
+    is_fully_void, round_down_to_unit, SettledLeg,
+};
+
+/// Place a parlay whose legs are matches of different rounds
+///
+/// `anchor_round_id` must be the latest round among the legs; it takes the
+/// stake and pays out. The RoundAccounting of every other round with a leg
+/// goes in `remaining_accounts` (writable), once each. Rounds must share
+/// the pool token account (no round vaults). The fee is the pool's base fee
+/// (by leg count when it has a leg fee schedule); volume tiers and team
+/// token perks don't apply.
+#[derive(Accounts)]
+#[instruction(anchor_round_id: u64)]
+pub struct PlaceAccumulator<'info> {
+    #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), anchor_round_id.to_le_bytes().as_ref()],
+        bump = anchor_round.bump,
+        constraint = anchor_round.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !anchor_round.settled @ SportsbookError::RoundAlreadySettled,
+        constraint = !anchor_round.isolated @ SportsbookError::AccumulatorIsolatedRound,
+    )]
+    pub anchor_round: Box<Account<'info, RoundAccounting>>,
+
+    /// Accumulator PDA, seeded with the pool's bet counter
+    #[account(
+        init,
+        payer = bettor,
+        space = Accumulator::LEN,
+        seeds = [
+            ACCUMULATOR_SEED,
+            betting_pool.key().as_ref(),
+            betting_pool.next_bet_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub accumulator: Box<Account<'info, Accumulator>>,
+
+    /// Bettor's token account
+    #[account(mut)]
+    pub bettor_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Betting pool's token account (receives the stake)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Required when the pool charges fees by parlay length
+    #[account(
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump = leg_fee_schedule.bump,
+    )]
+    pub leg_fee_schedule: Option<Box<Account<'info, LegFeeSchedule>>>,
+
+    /// Required when the pool has a stake policy
+    #[account(
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = stake_policy.bump,
+    )]
+    pub stake_policy: Option<Box<Account<'info, StakePolicy>>>,
+
+    /// Protocol treasury token account (receives fees)
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub protocol_treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn place_accumulator_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceAccumulator<'info>>,
+    anchor_round_id: u64,
+    round_ids: Vec<u64>,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, SportsbookError::InvalidAmount);
+    require!(amount <= MAX_BET_AMOUNT, SportsbookError::BetExceedsMaximum);
+    require!(
+        round_ids.len() == match_indices.len() && match_indices.len() == outcomes.len(),
+        SportsbookError::ArrayLengthMismatch
+    );
+    let num_legs = round_ids.len();
+    require!(
+        (2..=MAX_ACCUMULATOR_LEGS).contains(&num_legs),
+        SportsbookError::InvalidBetCount
+    );
+    let max_parlay_legs = match ctx.accounts.betting_pool.max_parlay_legs {
+        0 => MAX_ACCUMULATOR_LEGS,
+        legs => legs as usize,
+    };
+    require!(num_legs <= max_parlay_legs, SportsbookError::TooManyLegs);
+
+    let distinct_rounds = leg_round_ids(round_ids.iter().copied());
+    require!(distinct_rounds.len() >= 2, SportsbookError::AccumulatorNeedsTwoRounds);
+    require!(
+        distinct_rounds.last() == Some(&anchor_round_id),
+        SportsbookError::InvalidAnchorRound
+    );
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let mut other_rounds = load_other_rounds(
+        ctx.remaining_accounts,
+        &betting_pool_key,
+        anchor_round_id,
+        &distinct_rounds,
+    )?;
+    for round in other_rounds.iter() {
+        require!(round.seeded, SportsbookError::RoundNotSeeded);
+        require!(!round.settled, SportsbookError::RoundAlreadySettled);
+        require!(!round.isolated, SportsbookError::AccumulatorIsolatedRound);
+    }
+
+    // Legs, at the odds locked in each round right now
+    let min_leg_odds = ctx.accounts.betting_pool.min_leg_odds;
+    let mut legs = [AccumulatorLeg::default(); MAX_ACCUMULATOR_LEGS];
+    for i in 0..num_legs {
+        let (round_id, match_index, outcome) = (round_ids[i], match_indices[i], outcomes[i]);
+        require!(
+            (match_index as usize) < MATCHES_PER_ROUND,
+            SportsbookError::InvalidMatchIndex
+        );
+        require!((1..=3).contains(&outcome), SportsbookError::InvalidOutcome);
+        require!(
+            !legs[..i]
+                .iter()
+                .any(|leg| leg.round_id == round_id && leg.match_index == match_index),
+            SportsbookError::DuplicateAccumulatorLeg
+        );
+
+        let round = leg_round(&ctx.accounts.anchor_round, &other_rounds, round_id)?;
+        require!(
+            !round.is_kickoff_locked(match_index as usize),
+            SportsbookError::MatchAlreadyLocked
+        );
+        let locked_odds = &round.locked_odds[match_index as usize];
+        require!(locked_odds.locked, SportsbookError::OddsNotLocked);
+        let odds = locked_odds.get_odds(outcome);
+        require!(odds >= min_leg_odds, SportsbookError::LegOddsTooLow);
+
+        legs[i] = AccumulatorLeg { round_id, match_index, predicted_outcome: outcome, odds };
+    }
+    let leg_odds: Vec<u64> = legs[..num_legs].iter().map(|leg| leg.odds).collect();
+
+    // Pool minimum stake, and the unit the fee is floored to
+    let rounding_unit = if ctx.accounts.betting_pool.stake_policy {
+        let stake_policy = ctx
+            .accounts
+            .stake_policy
+            .as_ref()
+            .ok_or(SportsbookError::StakePolicyRequired)?;
+        require!(amount >= stake_policy.min_stake, SportsbookError::StakeBelowMinimum);
+        stake_policy.rounding_unit
+    } else {
+        0
+    };
+
+    let fee_bps = if ctx.accounts.betting_pool.leg_fees {
+        ctx.accounts
+            .leg_fee_schedule
+            .as_ref()
+            .ok_or(SportsbookError::LegFeeScheduleRequired)?
+            .base_fee_bps(num_legs, ctx.accounts.betting_pool.protocol_fee_bps)
+    } else {
+        ctx.accounts.betting_pool.protocol_fee_bps
+    };
+    let (protocol_fee, _) = calculate_protocol_fee(amount, fee_bps).map_err(SportsbookError::from)?;
+    let (protocol_fee, _) = round_down_to_unit(protocol_fee, rounding_unit);
+    let amount_after_fee = amount - protocol_fee;
+
+    let parlay_multiplier = get_base_parlay_multiplier(num_legs as u8);
+    let max_combined_odds = ctx.accounts.betting_pool.max_combined_odds;
+    if max_combined_odds > 0 {
+        let combined_odds = calculate_combined_odds(&leg_odds, parlay_multiplier)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        require!(
+            combined_odds <= max_combined_odds,
+            SportsbookError::CombinedOddsTooHigh
+        );
+    }
+
+    let potential_payout = calculate_accumulator_potential_payout(amount_after_fee, &leg_odds, parlay_multiplier)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    require!(
+        potential_payout <= MAX_PAYOUT_PER_BET,
+        SportsbookError::PayoutExceedsCap
+    );
+    require!(
+        ctx.accounts.betting_pool_token_account.amount >= potential_payout,
+        SportsbookError::InsufficientProtocolLiquidity
+    );
+
+    let funds_check = FundsCheck::start(
+        &ctx.accounts.anchor_round,
+        Some(ctx.accounts.betting_pool_token_account.to_account_info()),
+    )?;
+
+    // Stake in, fee out to the treasury
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.bettor_token_account.to_account_info(),
+        to: ctx.accounts.betting_pool_token_account.to_account_info(),
+        authority: ctx.accounts.bettor.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.betting_pool_token_account.to_account_info(),
+        to: ctx.accounts.protocol_treasury_token_account.to_account_info(),
+        authority: ctx.accounts.betting_pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token::transfer(cpi_ctx, protocol_fee)?;
+
+    // Stake is booked in the anchor round; every round holds its revenue
+    // until the accumulator settles
+    let anchor_round = &mut ctx.accounts.anchor_round;
+    anchor_round.protocol_fee_collected += protocol_fee;
+    anchor_round.total_bet_volume += amount_after_fee;
+    anchor_round.total_user_deposits += amount_after_fee;
+    anchor_round.bets_placed = anchor_round.bets_placed.saturating_add(1);
+    anchor_round.open_accumulators = anchor_round
+        .open_accumulators
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    for round in other_rounds.iter_mut() {
+        round.open_accumulators = round
+            .open_accumulators
+            .checked_add(1)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        round.exit(&crate::ID)?;
+    }
+
+    let bet_id = ctx.accounts.betting_pool.next_bet_id;
+    ctx.accounts.betting_pool.next_bet_id = bet_id
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    let accumulator = &mut ctx.accounts.accumulator;
+    accumulator.bettor = ctx.accounts.bettor.key();
+    accumulator.bet_id = bet_id;
+    accumulator.anchor_round_id = anchor_round_id;
+    accumulator.amount = amount;
+    accumulator.amount_after_fee = amount_after_fee;
+    accumulator.fee_bps = fee_bps;
+    accumulator.locked_multiplier = parlay_multiplier;
+    accumulator.potential_payout = potential_payout;
+    accumulator.num_legs = num_legs as u8;
+    accumulator.legs = legs;
+    accumulator.settled = false;
+    accumulator.paid_out = 0;
+    accumulator.bump = ctx.bumps.accumulator;
+
+    funds_check.finish(&ctx.accounts.anchor_round, 0)?;
+
+    emit!(AccumulatorPlaced {
+        betting_pool: betting_pool_key,
+        accumulator: ctx.accounts.accumulator.key(),
+        bet_id,
+        bettor: ctx.accounts.bettor.key(),
+        anchor_round_id,
+        amount,
+        num_legs: num_legs as u8,
+        potential_payout,
+    });
+
+    msg!(
+        "Accumulator {}: {} legs over rounds {:?}, pays {} if all win",
+        bet_id,
+        num_legs,
+        distinct_rounds,
+        potential_payout
+    );
+
+    Ok(())
+}
+
+/// Settle an accumulator once all of its rounds have settled
+///
+/// Anyone can call it: winnings (or the original stake, if every leg was
+/// voided) go to the bettor's token account, and a loss just closes the
+/// accumulator out. Void legs count at odds 1.0. The RoundAccounting of
+/// every round other than the anchor goes in `remaining_accounts`
+/// (writable), as for placement.
+#[derive(Accounts)]
+pub struct SettleAccumulator<'info> {
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), accumulator.anchor_round_id.to_le_bytes().as_ref()],
+        bump = anchor_round.bump,
+    )]
+    pub anchor_round: Box<Account<'info, RoundAccounting>>,
+
+    #[account(
+        mut,
+        seeds = [ACCUMULATOR_SEED, betting_pool.key().as_ref(), accumulator.bet_id.to_le_bytes().as_ref()],
+        bump = accumulator.bump,
+        constraint = !accumulator.settled @ SportsbookError::AccumulatorAlreadySettled,
+    )]
+    pub accumulator: Box<Account<'info, Accumulator>>,
+
+    /// Bettor's token account (receives any payout)
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == accumulator.bettor @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Betting pool's token account (pays out)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn settle_accumulator_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleAccumulator<'info>>,
+) -> Result<()> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let anchor_round_id = ctx.accounts.accumulator.anchor_round_id;
+    let mut other_rounds = load_other_rounds(
+        ctx.remaining_accounts,
+        &betting_pool_key,
+        anchor_round_id,
+        &ctx.accounts.accumulator.round_ids(),
+    )?;
+
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    for round in std::iter::once(&**ctx.accounts.anchor_round).chain(other_rounds.iter().map(|round| &**round)) {
+        require!(round.settled, SportsbookError::AccumulatorRoundsNotSettled);
+        require!(!round.claims_blocked(current_time), SportsbookError::ClaimsPaused);
+    }
+
+    let accumulator = &ctx.accounts.accumulator;
+    let legs = accumulator
+        .get_legs()
+        .iter()
+        .map(|leg| {
+            let round = leg_round(&ctx.accounts.anchor_round, &other_rounds, leg.round_id)?;
+            Ok(SettledLeg {
+                predicted_outcome: leg.predicted_outcome,
+                result: round.match_results[leg.match_index as usize] as u8,
+                amount_in_pool: 0,
+                odds: leg.odds,
+                odds_locked: true,
+            })
+        })
+        .collect::<Result<Vec<SettledLeg>>>()?;
+
+    // Fully void: refunded as placed, fee included
+    let refund = is_fully_void(&legs).then_some(accumulator.amount);
+    let payout = match refund {
+        Some(refund) => refund,
+        None => {
+            calculate_accumulator_payout(
+                &legs,
+                accumulator.amount_after_fee,
+                accumulator.locked_multiplier,
+                accumulator.potential_payout,
+            )
+            .map_err(SportsbookError::from)?
+            .final_payout
+        }
+    };
+    if refund.is_none() {
+        require!(
+            ctx.accounts.anchor_round.total_paid_out + payout <= MAX_ROUND_PAYOUTS,
+            SportsbookError::RoundPayoutLimitReached
+        );
+    }
+
+    let funds_check = FundsCheck::start(
+        &ctx.accounts.anchor_round,
+        Some(ctx.accounts.betting_pool_token_account.to_account_info()),
+    )?;
+
+    if payout > 0 {
+        let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.betting_pool_token_account.to_account_info(),
+            to: ctx.accounts.bettor_token_account.to_account_info(),
+            authority: ctx.accounts.betting_pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, payout)?;
+    }
+
+    let anchor_round = &mut ctx.accounts.anchor_round;
+    if refund.is_some() {
+        anchor_round.total_refunded += payout;
+    } else {
+        anchor_round.total_paid_out += payout;
+    }
+    anchor_round.record_bet_resolved();
+    anchor_round.open_accumulators = anchor_round.open_accumulators.saturating_sub(1);
+    for round in other_rounds.iter_mut() {
+        round.open_accumulators = round.open_accumulators.saturating_sub(1);
+        round.exit(&crate::ID)?;
+    }
+
+    let accumulator = &mut ctx.accounts.accumulator;
+    accumulator.settled = true;
+    accumulator.paid_out = payout;
+
+    funds_check.finish(&ctx.accounts.anchor_round, 0)?;
+
+    emit!(AccumulatorSettled {
+        betting_pool: betting_pool_key,
+        accumulator: ctx.accounts.accumulator.key(),
+        bet_id: ctx.accounts.accumulator.bet_id,
+        bettor: ctx.accounts.accumulator.bettor,
+        paid_out: payout,
+    });
+
+    msg!("Accumulator {} settled, paid {}", ctx.accounts.accumulator.bet_id, payout);
+
+    Ok(())
+}
+
+/// Load the RoundAccounting of each round in `round_ids` other than the
+/// anchor round from `round_accounts`: one account per round, in any order,
+/// and nothing else
+fn load_other_rounds<'info>(
+    round_accounts: &'info [AccountInfo<'info>],
+    betting_pool: &Pubkey,
+    anchor_round_id: u64,
+    round_ids: &[u64],
+) -> Result<Vec<Account<'info, RoundAccounting>>> {
+    let mut rounds: Vec<Account<'info, RoundAccounting>> = Vec::with_capacity(round_accounts.len());
+
+    for round_info in round_accounts.iter() {
+        let round = Account::<RoundAccounting>::try_from(round_info)?;
+        require!(
+            round.betting_pool == *betting_pool
+                && round.round_id != anchor_round_id
+                && round_ids.contains(&round.round_id)
+                && !rounds.iter().any(|loaded| loaded.round_id == round.round_id),
+            SportsbookError::InvalidAccumulatorRounds
+        );
+        rounds.push(round);
+    }
+
+    require!(
+        rounds.len() + 1 == round_ids.len(),
+        SportsbookError::InvalidAccumulatorRounds
+    );
+
+    Ok(rounds)
+}
+
+/// The round a leg is in, among the anchor and the loaded other rounds
+fn leg_round<'a>(
+    anchor_round: &'a RoundAccounting,
+    other_rounds: &'a [Account<RoundAccounting>],
+    round_id: u64,
+) -> Result<&'a RoundAccounting> {
+    if round_id == anchor_round.round_id {
+        return Ok(anchor_round);
+    }
+    other_rounds
+        .iter()
+        .find(|round| round.round_id == round_id)
+        .map(|round| &**round)
+        .ok_or_else(|| error!(SportsbookError::InvalidAccumulatorRounds))
+}
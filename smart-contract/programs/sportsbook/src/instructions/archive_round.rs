@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, OpenLiability, RoundArchive};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Archive a finished round
+///
+/// Once revenue is finalized and every reserved payout claimed, the claim
+/// window is over and nothing more can be paid from the round, so its live
+/// accounts can go: a small
+/// RoundArchive keeps the final figures and the rent from RoundAccounting
+/// and OpenLiability goes back to the authority. Bets of an archived round
+/// can no longer be claimed. Run attest_round first if a permanent
+/// RoundAttestation is wanted.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ArchiveRound<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.revenue_distributed @ SportsbookError::RevenueNotFinalized,
+        constraint = round_accounting.total_reserved_unpaid == 0 @ SportsbookError::ReservedPayoutsUnpaid,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the round's OpenLiability, closed too when the round has one
+    #[account(
+        mut,
+        close = authority,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Account<'info, OpenLiability>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RoundArchive::LEN,
+        seeds = [ROUND_ARCHIVE_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_archive: Account<'info, RoundArchive>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ArchiveRound>, round_id: u64) -> Result<()> {
+    let archived_at = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.round_archive.record(
+        &ctx.accounts.round_accounting,
+        archived_at,
+        ctx.bumps.round_archive,
+    );
+
+    let round_accounting = &ctx.accounts.round_accounting;
+    if !round_accounting.all_bets_resolved() {
+        msg!(
+            "Archiving with unresolved bets: {}/{} resolved",
+            round_accounting.bets_resolved,
+            round_accounting.bets_placed
+        );
+    }
+
+    msg!("Round {} archived", round_id);
+    msg!("Total paid out: {}", ctx.accounts.round_archive.total_paid_out);
+    msg!("Protocol revenue: {}", ctx.accounts.round_archive.protocol_revenue_share);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, RoundAttestation};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::RoundAttested;
+
+/// Write the permanent RoundAttestation for a finalized round
+///
+/// Optional and permissionless: whoever wants the record pays its rent.
+/// Must run before the round is archived, since it reads RoundAccounting.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct AttestRound<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.revenue_distributed @ SportsbookError::RevenueNotFinalized,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RoundAttestation::LEN,
+        seeds = [ROUND_ATTESTATION_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_attestation: Account<'info, RoundAttestation>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AttestRound>, round_id: u64) -> Result<()> {
+    let attested_at = Clock::get()?.unix_timestamp;
+
+    let attestation = &mut ctx.accounts.round_attestation;
+    attestation.record(&ctx.accounts.round_accounting, attested_at, ctx.bumps.round_attestation);
+
+    emit!(RoundAttested {
+        betting_pool: attestation.betting_pool,
+        round_id,
+        results_hash: attestation.results_hash,
+        total_bet_volume: attestation.total_bet_volume,
+        pnl: attestation.pnl,
+    });
+
+    msg!("Round {} attested", round_id);
+    msg!("Results hash: {:?}", attestation.results_hash);
+    msg!("PnL: {}", attestation.pnl);
+
+    Ok(())
+}
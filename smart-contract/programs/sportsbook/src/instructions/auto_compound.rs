@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, UserStats};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use super::place_bet::{execute_bet, BetPlacement, BetReceipt, PlaceBet};
+
+/// Set whether the caller prefers their winnings held as betting credit,
+/// creating their UserStats and credit vault on first use
+///
+/// The preference is advisory (see UserStats): only claim_winnings calls
+/// that pass the bettor's user_stats pay the bettor's share into the
+/// credit vault. Turning it off leaves any credit in place for
+/// place_credit_bet or withdraw_credit.
+#[derive(Accounts)]
+pub struct SetCompoundPreference<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserStats::LEN,
+        seeds = [USER_STATS_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        seeds = [CREDIT_VAULT_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = user_stats,
+    )]
+    pub credit_vault: Account<'info, TokenAccount>,
+
+    #[account(address = betting_pool.token_mint @ SportsbookError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_compound_preference_handler(ctx: Context<SetCompoundPreference>, prefer_compound: bool) -> Result<()> {
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.bettor = ctx.accounts.bettor.key();
+    user_stats.betting_pool = ctx.accounts.betting_pool.key();
+    user_stats.prefer_compound = prefer_compound;
+    user_stats.vault_bump = ctx.bumps.credit_vault;
+    user_stats.bump = ctx.bumps.user_stats;
+
+    msg!(
+        "Compounding {} for {}",
+        if prefer_compound { "preferred" } else { "not preferred" },
+        user_stats.bettor
+    );
+    msg!("Credit: {}", user_stats.credit);
+
+    Ok(())
+}
+
+/// Move betting credit back to the bettor's wallet
+#[derive(Accounts)]
+pub struct WithdrawCredit<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = user_stats.bump,
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [CREDIT_VAULT_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = user_stats.vault_bump,
+    )]
+    pub credit_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenMint,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_credit_handler(ctx: Context<WithdrawCredit>, amount: u64) -> Result<()> {
+    require!(amount > 0, SportsbookError::InvalidAmount);
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let bettor_key = ctx.accounts.bettor.key();
+    let user_stats = &mut ctx.accounts.user_stats;
+    user_stats.credit = user_stats
+        .credit
+        .checked_sub(amount)
+        .ok_or(SportsbookError::InsufficientCredit)?;
+
+    let stats_bump = [user_stats.bump];
+    let seeds: &[&[u8]] = &[USER_STATS_SEED, betting_pool_key.as_ref(), bettor_key.as_ref(), &stats_bump];
+    let signer = &[seeds];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.credit_vault.to_account_info(),
+        to: ctx.accounts.bettor_token_account.to_account_info(),
+        authority: user_stats.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+    token::transfer(cpi_ctx, amount)?;
+
+    msg!("Withdrew {} credit, {} left", amount, user_stats.credit);
+
+    Ok(())
+}
+
+/// Place a bet staked from the bettor's credit instead of their wallet: as
+/// place_bet, with user_stats and credit_vault
+pub fn place_credit_bet_handler(
+    ctx: Context<PlaceBet>,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
+    let accounts = ctx.accounts;
+    let user_stats = accounts
+        .user_stats
+        .as_ref()
+        .ok_or(SportsbookError::UserStatsRequired)?;
+    require!(user_stats.credit >= amount, SportsbookError::InsufficientCredit);
+
+    let betting_pool_key = accounts.betting_pool.key();
+    let bettor_key = accounts.bettor.key();
+    let stats_bump = [user_stats.bump];
+    let stats_seeds: &[&[u8]] = &[USER_STATS_SEED, betting_pool_key.as_ref(), bettor_key.as_ref(), &stats_bump];
+    let stake_authority = user_stats.to_account_info();
+
+    let placement = BetPlacement {
+        betting_pool: accounts.betting_pool.as_mut(),
+        round_accounting: accounts.round_accounting.as_mut(),
+        open_liability: accounts.open_liability.as_deref_mut(),
+        daily_volume: accounts.daily_volume.as_deref_mut(),
+        active_bettors: accounts.active_bettors.as_deref_mut(),
+        pending_claims: accounts.pending_claims.as_deref_mut(),
+        user_volume: accounts.user_volume.as_mut(),
+        user_volume_bump: ctx.bumps.user_volume,
+        bet: accounts.bet.as_mut(),
+        bet_record: accounts.bet_record.as_mut(),
+        bettor_token_account: accounts
+            .credit_vault
+            .as_deref()
+            .ok_or(SportsbookError::UserStatsRequired)?,
+        betting_pool_token_account: accounts.betting_pool_token_account.as_ref(),
+        round_vault: accounts.round_vault.as_deref(),
+        leg_fee_schedule: accounts.leg_fee_schedule.as_deref(),
+        stake_policy: accounts.stake_policy.as_deref(),
+        protocol_treasury_token_account: accounts.protocol_treasury_token_account.as_ref(),
+        team_token_account: accounts.team_token_account.as_deref(),
+        price_feed: accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        stake_authority,
+        stake_authority_seeds: Some(stats_seeds),
+        payer: bettor_key,
+        beneficiary: bettor_key,
+        token_program: &accounts.token_program,
+        bet_bump: ctx.bumps.bet,
+        bet_record_bump: ctx.bumps.bet_record,
+        user_nonce: None,
+        copy_terms: None,
+    };
+
+    let receipt = execute_bet(placement, round_id, match_indices, outcomes, amount)?;
+
+    let user_stats = accounts
+        .user_stats
+        .as_mut()
+        .ok_or(SportsbookError::UserStatsRequired)?;
+    user_stats
+        .stake_credit(amount)
+        .ok_or(SportsbookError::InsufficientCredit)?;
+
+    msg!("Bet {} staked {} from credit, {} left", receipt.bet_id, amount, user_stats.credit);
+
+    Ok(receipt)
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, InsuranceFund, AccessControl, Role, has_role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::BetInsured;
+use super::place_bet::{place, BetReceipt, PlaceBet};
+
+/// Set the premium for insuring a parlay, creating the insurance fund and
+/// its vault on first use
+///
+/// A premium of 0 stops new insured bets; bets already insured keep their
+/// cover.
+#[derive(Accounts)]
+pub struct ConfigureInsurance<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [INSURANCE_VAULT_SEED, betting_pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = insurance_fund,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    #[account(address = betting_pool.token_mint @ SportsbookError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_insurance_handler(ctx: Context<ConfigureInsurance>, premium_bps: u16) -> Result<()> {
+    require!(
+        premium_bps <= MAX_INSURANCE_PREMIUM_BPS,
+        SportsbookError::InvalidInsurancePremium
+    );
+
+    let insurance_fund = &mut ctx.accounts.insurance_fund;
+    insurance_fund.betting_pool = ctx.accounts.betting_pool.key();
+    insurance_fund.premium_bps = premium_bps;
+    insurance_fund.vault_bump = ctx.bumps.insurance_vault;
+    insurance_fund.bump = ctx.bumps.insurance_fund;
+
+    msg!("Bet insurance premium: {} bps", premium_bps);
+
+    Ok(())
+}
+
+/// Top up the insurance vault beyond what premiums have brought in
+#[derive(Accounts)]
+pub struct FundInsurance<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [INSURANCE_FUND_SEED, betting_pool.key().as_ref()],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED, betting_pool.key().as_ref()],
+        bump = insurance_fund.vault_bump,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenMint,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Optional: the pool's roles, so a treasurer other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::Treasurer)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn fund_insurance_handler(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+    require!(amount > 0, SportsbookError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.insurance_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    msg!("Insurance vault funded with {}", amount);
+
+    Ok(())
+}
+
+/// Place a parlay with insurance: as place_bet, plus the premium
+///
+/// The bettor pays `premium_bps` of the stake on top of it into the
+/// insurance vault. The vault must already hold enough to refund this
+/// stake.
+pub fn place_insured_bet_handler(
+    ctx: Context<PlaceBet>,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
+    require!(match_indices.len() >= 2, SportsbookError::InsuranceNeedsParlay);
+
+    let accounts = ctx.accounts;
+    let premium = {
+        let insurance_fund = accounts
+            .insurance_fund
+            .as_ref()
+            .ok_or(SportsbookError::InsuranceFundRequired)?;
+        let insurance_vault = accounts
+            .insurance_vault
+            .as_ref()
+            .ok_or(SportsbookError::InsuranceFundRequired)?;
+        require!(insurance_fund.premium_bps > 0, SportsbookError::InsuranceNotOffered);
+
+        let premium = insurance_fund
+            .premium(amount)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        require!(premium > 0, SportsbookError::InvalidAmount);
+        require!(
+            insurance_vault.amount.saturating_add(premium) >= amount,
+            SportsbookError::InsufficientInsuranceFund
+        );
+        premium
+    };
+
+    let receipt = place(accounts, &ctx.bumps, round_id, match_indices, outcomes, amount)?;
+
+    // Premium on top of the stake, straight from the bettor to the vault
+    let insurance_vault = accounts
+        .insurance_vault
+        .as_ref()
+        .ok_or(SportsbookError::InsuranceFundRequired)?;
+    let cpi_accounts = Transfer {
+        from: accounts.bettor_token_account.to_account_info(),
+        to: insurance_vault.to_account_info(),
+        authority: accounts.bettor.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, premium)?;
+
+    let insurance_fund = accounts
+        .insurance_fund
+        .as_mut()
+        .ok_or(SportsbookError::InsuranceFundRequired)?;
+    insurance_fund.total_premiums = insurance_fund
+        .total_premiums
+        .checked_add(premium)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    accounts.bet.insured = true;
+
+    emit!(BetInsured {
+        betting_pool: accounts.betting_pool.key(),
+        bet: receipt.bet,
+        bet_id: receipt.bet_id,
+        premium,
+    });
+
+    msg!("Bet {} insured for premium {}", receipt.bet_id, premium);
+
+    Ok(receipt)
+}
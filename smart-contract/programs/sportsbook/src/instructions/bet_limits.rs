@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Configure placement-time bet limits
+#[derive(Accounts)]
+pub struct SetBetLimits<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetBetLimits>,
+    max_parlay_legs: u8,
+    min_leg_odds: u64,
+    max_combined_odds: u64,
+) -> Result<()> {
+    require!(
+        max_parlay_legs > 0 && max_parlay_legs as usize <= MATCHES_PER_ROUND,
+        SportsbookError::InvalidBetCount
+    );
+
+    let betting_pool = &mut ctx.accounts.betting_pool;
+    betting_pool.max_parlay_legs = max_parlay_legs;
+    betting_pool.min_leg_odds = min_leg_odds;
+    betting_pool.max_combined_odds = max_combined_odds;
+
+    msg!("Max parlay legs: {}", max_parlay_legs);
+    msg!("Min leg odds: {} (0 = none)", min_leg_odds);
+    msg!("Max combined odds: {} (0 = none)", max_combined_odds);
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+use crate::errors::SportsbookError;
+
+/// Choose who pays the late-claim bounty in rounds created from now on
+///
+/// By default the 10% bounty is cut from the winner's payout. With
+/// `protocol_funded` the winner keeps the full payout and the bounty comes
+/// on top, out of the round's protocol revenue. Rounds keep the mode they
+/// were created with.
+#[derive(Accounts)]
+pub struct SetBountyMode<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetBountyMode>, protocol_funded: bool) -> Result<()> {
+    ctx.accounts.betting_pool.protocol_funded_bounty = protocol_funded;
+
+    msg!(
+        "Late-claim bounty paid from {}",
+        if protocol_funded { "protocol revenue" } else { "winnings" }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, RoundAccounting, Bet, PendingClaims};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::BountySwept;
+use crate::clock::pool_clock;
+use crate::invariants::FundsCheck;
+use super::claim_winnings::{bounty_split, calculate_bet_payout, voided_bet_refund, ClaimWindow};
+use super::round_vault::round_funds;
+
+/// Claim many expired winning bets of one round for their bounties
+///
+/// `remaining_accounts` holds (bet, bettor token account) pairs. Every bet
+/// must be a winning, unclaimed bet past its claim deadline; each bettor
+/// is paid their share directly and the bounties go to the claimer in one
+/// transfer. Copied and voided bets need claim_winnings. SPL Token pools
+/// only, as for quick_claim.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct BountySweep<'info> {
+    #[account(constraint = !betting_pool.merkle_settlement @ SportsbookError::OddsProofRequired)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.claim_shards == 0 @ SportsbookError::ClaimShardsOpen,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Required for isolated rounds: pays out instead of the pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+
+    pub claimer: Signer<'info>,
+
+    /// Receives the summed bounties
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = claimer,
+    )]
+    pub claimer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BountySweep<'info>>,
+    round_id: u64,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        SportsbookError::ArrayLengthMismatch
+    );
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let token_mint = ctx.accounts.betting_pool.token_mint;
+    let claimer_key = ctx.accounts.claimer.key();
+    let clock = pool_clock(&ctx.accounts.betting_pool);
+    let round_accounting = &mut ctx.accounts.round_accounting;
+
+    let round_id_bytes = round_id.to_le_bytes();
+    let round_bump = [round_accounting.bump];
+    let pool_bump = [ctx.accounts.betting_pool.bump];
+    let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if round_accounting.isolated {
+        (
+            round_accounting.to_account_info(),
+            &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump],
+        )
+    } else {
+        (ctx.accounts.betting_pool.to_account_info(), &[BETTING_POOL_SEED, &pool_bump])
+    };
+    let signer = &[seeds];
+    let funds_account = round_funds(
+        round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?;
+    let mut available = funds_account.amount;
+    let funds_check = FundsCheck::start(round_accounting, Some(funds_account.to_account_info()))?;
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    let mut total_payout = 0u64;
+    let mut total_bounty = 0u64;
+
+    for pair in ctx.remaining_accounts.chunks_exact(2) {
+        let (bet_info, bettor_token_info) = (&pair[0], &pair[1]);
+        require!(!seen.contains(bet_info.key), SportsbookError::DuplicateAccount);
+        seen.push(*bet_info.key);
+
+        let mut bet = Account::<Bet>::try_from(bet_info)?;
+        require!(
+            bet.is_at_address(bet_info.key, &betting_pool_key) && bet.round_id == round_id,
+            SportsbookError::InvalidBetAccount
+        );
+        require!(!bet.claimed, SportsbookError::BetAlreadyClaimed);
+        require!(!bet.payout_reserved, SportsbookError::PayoutReserved);
+        require!(
+            bet.copy_leader == Pubkey::default()
+                && voided_bet_refund(&bet, round_accounting).is_none(),
+            SportsbookError::NotSweepable
+        );
+
+        let window = ClaimWindow::evaluate(&clock, round_accounting, claimer_key == bet.bettor)?;
+        require!(!window.paused, SportsbookError::ClaimsPaused);
+        require!(window.is_bounty_claim, SportsbookError::NotBettor);
+
+        let (won, _, payout) = calculate_bet_payout(&bet, round_accounting)?;
+        require!(won && payout > 0, SportsbookError::NotSweepable);
+        require!(
+            round_accounting.total_paid_out + payout <= MAX_ROUND_PAYOUTS,
+            SportsbookError::RoundPayoutLimitReached
+        );
+
+        let bettor_token_account = Account::<TokenAccount>::try_from(bettor_token_info)?;
+        require!(
+            bettor_token_account.owner == bet.bettor && bettor_token_account.mint == token_mint,
+            SportsbookError::InvalidTokenAccount
+        );
+
+        let (bettor_amount, bounty) =
+            bounty_split(payout, true, round_accounting.protocol_funded_bounty)?;
+        let owed = bettor_amount + bounty;
+        require!(available >= owed, SportsbookError::InsufficientProtocolLiquidity);
+        available -= owed;
+
+        if bet.claim_deadline == 0 {
+            bet.claim_deadline = window.claim_deadline;
+        }
+        bet.claimed = true;
+        bet.settled = true;
+        bet.bounty_claimer = Some(claimer_key);
+        bet.exit(&crate::ID)?;
+
+        round_accounting.record_bet_resolved();
+        round_accounting.total_claimed += payout;
+        round_accounting.total_paid_out += payout;
+        round_accounting.payout_dust += bet.payout_dust;
+        if round_accounting.protocol_funded_bounty {
+            round_accounting.protocol_bounties_paid += bounty;
+        }
+        if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+            pending_claims.record_resolution(bet.potential_payout, payout, bounty);
+        }
+
+        let cpi_accounts = Transfer {
+            from: funds_account.to_account_info(),
+            to: bettor_token_info.clone(),
+            authority: funds_authority.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, bettor_amount)?;
+
+        total_payout += payout;
+        total_bounty += bounty;
+    }
+
+    // All the bounties in one transfer
+    if total_bounty > 0 {
+        let cpi_accounts = Transfer {
+            from: funds_account.to_account_info(),
+            to: ctx.accounts.claimer_token_account.to_account_info(),
+            authority: funds_authority,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, total_bounty)?;
+    }
+    funds_check.finish(round_accounting, 0)?;
+
+    emit!(BountySwept {
+        betting_pool: betting_pool_key,
+        round_id,
+        claimer: claimer_key,
+        bets: seen.len() as u32,
+        total_payout,
+        total_bounty,
+    });
+
+    msg!(
+        "Round {}: {} swept {} bets, paid {} with {} in bounties",
+        round_id,
+        claimer_key,
+        seen.len(),
+        total_payout,
+        total_bounty
+    );
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, AccessControl, Role, has_role, OddsUpdateLimits, RoundOddsUpdates};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::utils::unpack_odds_entries;
+use super::odds_history::record_odds_updates;
+use super::odds_update_limits::{enforce_odds_update_limits, init_round_odds_updates};
+
+/// Set odds for many matches of a round in one instruction
+///
+/// Only allowed before any user bet lands in the round: payouts are
+/// computed from the round's locked odds, so changing them afterwards
+/// would rewrite the terms of existing bets. OddsHistory accounts of the
+/// updated matches may be passed in `remaining_accounts` to record the move.
+/// Pools with odds update limits bound each match's move and update rate.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct BulkSetOdds<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the pool's roles, so an odds manager other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    /// Required when the pool has odds update limits
+    #[account(
+        seeds = [ODDS_UPDATE_LIMITS_SEED, betting_pool.key().as_ref()],
+        bump = odds_update_limits.bump,
+    )]
+    pub odds_update_limits: Option<Account<'info, OddsUpdateLimits>>,
+
+    /// Required with odds_update_limits: when each match's odds last moved
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoundOddsUpdates::LEN,
+        seeds = [ROUND_ODDS_UPDATES_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_odds_updates: Option<Account<'info, RoundOddsUpdates>>,
+
+    #[account(
+        mut,
+        constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::OddsManager)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BulkSetOdds<'info>>,
+    round_id: u64,
+    payload: Vec<u8>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.round_accounting.total_user_deposits == 0,
+        SportsbookError::OddsUpdateAfterBets
+    );
+
+    let entries = unpack_odds_entries(&payload).map_err(|e| {
+        msg!("Invalid odds payload: {}", e);
+        SportsbookError::InvalidOddsPayload
+    })?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let mut round_odds_updates = ctx.accounts.round_odds_updates.as_deref_mut();
+    init_round_odds_updates(
+        round_odds_updates.as_deref_mut(),
+        betting_pool_key,
+        round_id,
+        ctx.bumps.round_odds_updates,
+    );
+    let round_accounting = &mut ctx.accounts.round_accounting;
+
+    for entry in entries.iter() {
+        require!(
+            !round_accounting.is_kickoff_locked(entry.match_index as usize),
+            SportsbookError::MatchAlreadyLocked
+        );
+        enforce_odds_update_limits(
+            &ctx.accounts.betting_pool,
+            ctx.accounts.odds_update_limits.as_deref(),
+            round_odds_updates.as_deref_mut(),
+            entry.match_index as usize,
+            &round_accounting.locked_odds[entry.match_index as usize],
+            [entry.home_odds, entry.away_odds, entry.draw_odds],
+            current_time,
+        )?;
+        let locked_odds = &mut round_accounting.locked_odds[entry.match_index as usize];
+        locked_odds.home_odds = entry.home_odds;
+        locked_odds.away_odds = entry.away_odds;
+        locked_odds.draw_odds = entry.draw_odds;
+        locked_odds.locked = true;
+
+        msg!(
+            "Match {}: odds set to {}/{}/{}",
+            entry.match_index,
+            entry.home_odds,
+            entry.away_odds,
+            entry.draw_odds
+        );
+    }
+
+    round_accounting.commit_locked_odds()?;
+
+    let updated: Vec<u8> = entries.iter().map(|entry| entry.match_index).collect();
+    record_odds_updates(ctx.remaining_accounts, round_accounting, &updated, current_time)?;
+
+    msg!("Round {}: odds set for {} matches", round_id, entries.len());
+
+    Ok(())
+}
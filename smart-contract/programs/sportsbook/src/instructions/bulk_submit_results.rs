@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{BettingPool, RoundAccounting, RoundSummary, Notifications};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::attestation::{results_message, verify_ed25519_attestation};
+use crate::pyth::report_pool_price;
+use crate::utils::unpack_match_results;
+use super::settle_round::apply_match_results;
+use super::round_summary::write_round_summary;
+
+/// Set the key whose signature authorizes packed result submissions
+#[derive(Accounts)]
+pub struct SetResultsAttestor<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_results_attestor_handler(ctx: Context<SetResultsAttestor>, results_attestor: Pubkey) -> Result<()> {
+    ctx.accounts.betting_pool.results_attestor = results_attestor;
+
+    msg!("Results attestor set to {}", results_attestor);
+
+    Ok(())
+}
+
+/// Settle a whole round from one packed, attested result payload
+///
+/// The transaction must carry an Ed25519 program instruction immediately
+/// before this one, signed by `betting_pool.results_attestor` over
+/// `results_message(betting_pool, round_id, packed_results)`. The signature
+/// is the authorization, so any account may submit it.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct BulkSubmitResults<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: Pyth price feed, snapshotted when the pool is USD-valued;
+    /// settlement goes ahead without a snapshot if it's missing or unusable
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Optional: the round's compact summary, created or refreshed here
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = RoundSummary::LEN,
+        seeds = [ROUND_SUMMARY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_summary: Option<Account<'info, RoundSummary>>,
+
+    /// Optional: the pool's operator notification queue
+    #[account(
+        mut,
+        seeds = [NOTIFICATIONS_SEED, betting_pool.key().as_ref()],
+        bump = notifications.bump,
+    )]
+    pub notifications: Option<Box<Account<'info, Notifications>>>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<BulkSubmitResults>,
+    round_id: u64,
+    packed_results: Vec<u8>,
+) -> Result<()> {
+    let results_attestor = ctx.accounts.betting_pool.results_attestor;
+    require!(
+        results_attestor != Pubkey::default(),
+        SportsbookError::AttestorNotSet
+    );
+
+    // Same precondition as settle_round
+    require!(
+        ctx.accounts.betting_pool.lending_deployed == 0,
+        SportsbookError::LiquidityStillDeployed
+    );
+
+    let message = results_message(&ctx.accounts.betting_pool.key(), round_id, &packed_results);
+    verify_ed25519_attestation(&ctx.accounts.instructions, &results_attestor, &message)?;
+
+    let match_results = unpack_match_results(&packed_results).map_err(|e| {
+        msg!("Invalid packed results: {}", e);
+        SportsbookError::InvalidOutcome
+    })?;
+
+    // Price staleness is always judged against cluster time, even when
+    // the pool runs on a mock clock
+    let current_time = Clock::get()?.unix_timestamp;
+    let settlement_price = report_pool_price(
+        &ctx.accounts.betting_pool.price_feed,
+        ctx.accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        current_time,
+    );
+
+    let settlement_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    apply_match_results(
+        &mut ctx.accounts.round_accounting,
+        &match_results,
+        settlement_time,
+        settlement_price,
+        ctx.accounts.notifications.as_deref_mut().map(|notifications| &mut **notifications),
+    )?;
+
+    let round_accounting = &ctx.accounts.round_accounting;
+    write_round_summary(
+        ctx.accounts.round_summary.as_deref_mut(),
+        round_accounting,
+        ctx.bumps.round_summary,
+        settlement_time,
+    );
+
+    msg!("Round {} settled from attested results", round_id);
+    msg!("Submitted by: {}", ctx.accounts.submitter.key());
+    msg!("Total reserved for winners: {}", round_accounting.total_reserved_for_winners);
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::{BettingPool, RoundAccounting, Bet, ClaimReminder};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::ClaimDeadlinesStamped;
+
+/// Register for a reminder before a bet's claim window closes
+#[derive(Accounts)]
+pub struct RegisterReminder<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bettor == bettor.key() @ SportsbookError::NotBettor,
+        constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = ClaimReminder::LEN,
+        seeds = [CLAIM_REMINDER_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub claim_reminder: Account<'info, ClaimReminder>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_reminder_handler(ctx: Context<RegisterReminder>, lead_time: i64) -> Result<()> {
+    require!(
+        lead_time > 0 && lead_time <= CLAIM_WINDOW_SECONDS,
+        SportsbookError::InvalidReminderLeadTime
+    );
+
+    let claim_reminder = &mut ctx.accounts.claim_reminder;
+    claim_reminder.betting_pool = ctx.accounts.betting_pool.key();
+    claim_reminder.bet = ctx.accounts.bet.key();
+    claim_reminder.bettor = ctx.accounts.bettor.key();
+    claim_reminder.round_id = ctx.accounts.bet.round_id;
+    claim_reminder.lead_time = lead_time;
+    claim_reminder.bump = ctx.bumps.claim_reminder;
+
+    // Already settled: the deadline is known now
+    let round_accounting = &ctx.accounts.round_accounting;
+    if round_accounting.settled {
+        claim_reminder.set_deadline(round_accounting.claim_deadline());
+    } else {
+        claim_reminder.claim_deadline = 0;
+        claim_reminder.notify_at = 0;
+    }
+
+    msg!(
+        "Reminder for bet {}: {}s before the claim deadline",
+        ctx.accounts.bet.bet_id,
+        lead_time
+    );
+
+    Ok(())
+}
+
+/// Write a settled round's claim deadline onto its bets and reminders
+///
+/// Permissionless crank. `remaining_accounts` holds any mix of the round's
+/// Bet and ClaimReminder accounts. A guardian pause moves the deadline, so
+/// the crank can be run again to restamp.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct StampClaimDeadlines<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn stamp_claim_deadlines_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StampClaimDeadlines<'info>>,
+    round_id: u64,
+) -> Result<()> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let claim_deadline = ctx.accounts.round_accounting.claim_deadline();
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut bets = 0u32;
+    let mut reminders = 0u32;
+
+    for info in ctx.remaining_accounts.iter() {
+        require!(!seen.contains(info.key), SportsbookError::DuplicateAccount);
+        seen.push(*info.key);
+        require!(info.owner == &crate::ID, SportsbookError::InvalidBetAccount);
+
+        let is_bet = info.try_borrow_data()?.get(..8) == Some(Bet::DISCRIMINATOR);
+        if is_bet {
+            let mut bet = Account::<Bet>::try_from(info)?;
+            require!(
+                bet.is_at_address(info.key, &betting_pool_key) && bet.round_id == round_id,
+                SportsbookError::InvalidBetAccount
+            );
+            bet.claim_deadline = claim_deadline;
+            bet.exit(&crate::ID)?;
+            bets += 1;
+        } else {
+            let mut claim_reminder = Account::<ClaimReminder>::try_from(info)?;
+            require!(
+                claim_reminder.betting_pool == betting_pool_key && claim_reminder.round_id == round_id,
+                SportsbookError::InvalidBetAccount
+            );
+            claim_reminder.set_deadline(claim_deadline);
+            claim_reminder.exit(&crate::ID)?;
+            reminders += 1;
+        }
+    }
+
+    emit!(ClaimDeadlinesStamped {
+        betting_pool: betting_pool_key,
+        round_id,
+        claim_deadline,
+        bets,
+        reminders,
+    });
+
+    msg!(
+        "Round {}: claim deadline {} stamped on {} bets, {} reminders",
+        round_id,
+        claim_deadline,
+        bets,
+        reminders
+    );
+
+    Ok(())
+}
+
+/// Close a reminder and return its rent
+#[derive(Accounts)]
+pub struct CloseReminder<'info> {
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [CLAIM_REMINDER_SEED, claim_reminder.bet.as_ref()],
+        bump = claim_reminder.bump,
+        has_one = bettor @ SportsbookError::NotBettor,
+    )]
+    pub claim_reminder: Account<'info, ClaimReminder>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+}
+
+pub fn close_reminder_handler(ctx: Context<CloseReminder>) -> Result<()> {
+    msg!("Reminder for bet {} closed", ctx.accounts.claim_reminder.bet);
+
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{BettingPool, RoundAccounting, Bet, PendingClaims, ClaimShard, AccessControl, Role, has_role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::pool_clock;
+use crate::token_hook::PoolTransfer;
+use crate::invariants::FundsCheck;
+use super::claim_winnings::{
+    bounty_split, calculate_bet_payout, copy_success_fee, verify_claimer_token_account,
+    insured_bet_refund, voided_bet_refund, ClaimWindow,
+};
+use super::round_vault::round_funds;
+
+/// Open one of a settled round's claim shards
+///
+/// Treasurer only, since once any shard is open claim_winnings,
+/// quick_claim, bounty_sweep, reserve_payouts and finalize_revenue wait
+/// until every shard is merged back. The payer gets the rent back at
+/// merge. The shard's payout cap is fixed here and held against the
+/// round's MAX_ROUND_PAYOUTS until the merge.
+#[derive(Accounts)]
+#[instruction(round_id: u64, shard_index: u8)]
+pub struct InitClaimShard<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = !round_accounting.revenue_allocated @ SportsbookError::RevenueAlreadyDistributed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ClaimShard::LEN,
+        seeds = [
+            CLAIM_SHARD_SEED,
+            betting_pool.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            &[shard_index],
+        ],
+        bump
+    )]
+    pub claim_shard: Account<'info, ClaimShard>,
+
+    /// Optional: the pool's roles, so a treasurer other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &payer.key(), Role::Treasurer)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_claim_shard_handler(ctx: Context<InitClaimShard>, round_id: u64, shard_index: u8) -> Result<()> {
+    require!(shard_index < CLAIM_SHARDS, SportsbookError::InvalidClaimShard);
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    let payout_cap = ClaimShard::payout_cap_for(
+        round_accounting.total_paid_out,
+        round_accounting.shard_payout_cap,
+        round_accounting.claim_shards,
+    );
+    round_accounting.shard_payout_cap += payout_cap;
+    round_accounting.claim_shards += 1;
+
+    let claim_shard = &mut ctx.accounts.claim_shard;
+    claim_shard.betting_pool = ctx.accounts.betting_pool.key();
+    claim_shard.round_id = round_id;
+    claim_shard.shard_index = shard_index;
+    claim_shard.payer = ctx.accounts.payer.key();
+    claim_shard.bets_resolved = 0;
+    claim_shard.lost_bets = 0;
+    claim_shard.resolved_potential_payout = 0;
+    claim_shard.total_paid_out = 0;
+    claim_shard.total_refunded = 0;
+    claim_shard.bounty_paid = 0;
+    claim_shard.protocol_bounties_paid = 0;
+    claim_shard.payout_dust = 0;
+    claim_shard.payout_cap = payout_cap;
+    claim_shard.bump = ctx.bumps.claim_shard;
+
+    msg!("Round {} claim shard {} opened, payout cap {}", round_id, shard_index, payout_cap);
+
+    Ok(())
+}
+
+/// claim_winnings against the bet's claim shard
+///
+/// Same claim window, bounty, copy fee and void refund rules; the counters
+/// go to the shard picked by `bet_id % CLAIM_SHARDS`, and the round is only
+/// read. Losing bets are settled too. SPL Token, non-Merkle pools only.
+#[derive(Accounts)]
+#[instruction(bet_id: u64)]
+pub struct ClaimSharded<'info> {
+    #[account(constraint = !betting_pool.merkle_settlement @ SportsbookError::OddsProofRequired)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_SHARD_SEED,
+            betting_pool.key().as_ref(),
+            bet.round_id.to_le_bytes().as_ref(),
+            &[(bet.bet_id % CLAIM_SHARDS as u64) as u8],
+        ],
+        bump = claim_shard.bump,
+    )]
+    pub claim_shard: Account<'info, ClaimShard>,
+
+    #[account(
+        mut,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bet_id == bet_id @ SportsbookError::InvalidBetAccount,
+        constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+        constraint = !bet.payout_reserved @ SportsbookError::PayoutReserved,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Required for isolated rounds: pays out instead of the pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bet.bettor @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    /// Bettor, or anyone after the claim deadline for the bounty
+    pub claimer: Signer<'info>,
+
+    /// Claimer's token account (receives the bounty on a third-party claim)
+    /// CHECK: For bounty claims, verified in handler to be the claimer's
+    /// initialized ATA for the pool mint
+    #[account(mut)]
+    pub claimer_token_account: UncheckedAccount<'info>,
+
+    /// Copy leader's token account; required when claiming a winning
+    /// copied bet
+    /// CHECK: Verified in handler to be bet.copy_leader's initialized ATA
+    /// for the pool mint
+    #[account(mut)]
+    pub leader_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_sharded_handler(ctx: Context<ClaimSharded>, bet_id: u64) -> Result<()> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let bet = &ctx.accounts.bet;
+    let claimer_key = ctx.accounts.claimer.key();
+    let token_mint = ctx.accounts.betting_pool.token_mint;
+    let token_program_key = ctx.accounts.token_program.key();
+
+    let window = ClaimWindow::evaluate(
+        &pool_clock(&ctx.accounts.betting_pool),
+        round_accounting,
+        claimer_key == bet.bettor,
+    )?;
+    require!(!window.paused, SportsbookError::ClaimsPaused);
+    require!(window.claimer_allowed, SportsbookError::NotBettor);
+
+    // The insurance vault isn't part of a shard; those refunds wait for
+    // claim_winnings once the shards are merged
+    require!(
+        insured_bet_refund(bet, round_accounting).is_none(),
+        SportsbookError::InsuranceRefundDue
+    );
+
+    let protocol_funded = round_accounting.protocol_funded_bounty;
+    let refund = voided_bet_refund(bet, round_accounting);
+    let payout = match refund {
+        Some(_) => 0,
+        None => calculate_bet_payout(bet, round_accounting)?.2,
+    };
+    let (bettor_amount, bounty_amount) = bounty_split(payout, window.is_bounty_claim, protocol_funded)?;
+    let leader_fee = copy_success_fee(bet, bettor_amount)?;
+    let bettor_amount = bettor_amount - leader_fee + refund.unwrap_or(0);
+    require!(
+        ctx.accounts.claim_shard.within_payout_cap(payout),
+        SportsbookError::RoundPayoutLimitReached
+    );
+
+    if bounty_amount > 0 {
+        verify_claimer_token_account(
+            &ctx.accounts.claimer_token_account,
+            &claimer_key,
+            &token_mint,
+            &token_program_key,
+        )?;
+    }
+
+    let funds_account = round_funds(
+        round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?;
+    require!(
+        funds_account.amount >= bettor_amount + bounty_amount + leader_fee,
+        SportsbookError::InsufficientProtocolLiquidity
+    );
+    let funds_check = FundsCheck::start(round_accounting, Some(funds_account.to_account_info()))?;
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let round_id_bytes = bet.round_id.to_le_bytes();
+    let round_bump = [round_accounting.bump];
+    let pool_bump = [ctx.accounts.betting_pool.bump];
+    let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if round_accounting.isolated {
+        (
+            round_accounting.to_account_info(),
+            &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump],
+        )
+    } else {
+        (ctx.accounts.betting_pool.to_account_info(), &[BETTING_POOL_SEED, &pool_bump])
+    };
+    let signer = &[seeds];
+    let pool_transfer = PoolTransfer {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: None,
+        from: funds_account.to_account_info(),
+        authority: funds_authority,
+        extra_accounts: &[],
+        signer,
+    };
+
+    if bettor_amount > 0 {
+        pool_transfer.transfer(ctx.accounts.bettor_token_account.to_account_info(), bettor_amount)?;
+    }
+    if bounty_amount > 0 {
+        pool_transfer.transfer(ctx.accounts.claimer_token_account.to_account_info(), bounty_amount)?;
+    }
+    if leader_fee > 0 {
+        let leader_token_account = ctx
+            .accounts
+            .leader_token_account
+            .as_ref()
+            .ok_or(SportsbookError::InvalidLeaderTokenAccount)?;
+        verify_claimer_token_account(leader_token_account, &bet.copy_leader, &token_mint, &token_program_key)
+            .map_err(|_| SportsbookError::InvalidLeaderTokenAccount)?;
+        pool_transfer.transfer(leader_token_account.to_account_info(), leader_fee)?;
+    }
+    funds_check.finish(round_accounting, bettor_amount + bounty_amount + leader_fee)?;
+
+    let claim_shard = &mut ctx.accounts.claim_shard;
+    claim_shard.bets_resolved += 1;
+    claim_shard.resolved_potential_payout += bet.potential_payout;
+    if let Some(refund) = refund {
+        claim_shard.total_refunded += refund;
+    } else if payout > 0 {
+        claim_shard.total_paid_out += payout;
+        claim_shard.payout_dust += bet.payout_dust;
+        claim_shard.bounty_paid += bounty_amount;
+        if protocol_funded {
+            claim_shard.protocol_bounties_paid += bounty_amount;
+        }
+    } else {
+        claim_shard.lost_bets += 1;
+    }
+
+    let bet = &mut ctx.accounts.bet;
+    if bet.claim_deadline == 0 {
+        bet.claim_deadline = window.claim_deadline;
+    }
+    bet.claimed = true;
+    bet.settled = true;
+    if bounty_amount > 0 {
+        bet.bounty_claimer = Some(claimer_key);
+    }
+
+    msg!(
+        "Bet {} claimed through shard {}: payout {}, refund {}, bounty {}",
+        bet_id,
+        claim_shard.shard_index,
+        payout,
+        refund.unwrap_or(0),
+        bounty_amount
+    );
+
+    Ok(())
+}
+
+/// Fold a claim shard's totals into its round and the pool's PendingClaims,
+/// close it and refund its rent. Permissionless
+#[derive(Accounts)]
+pub struct MergeClaimShard<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), claim_shard.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            CLAIM_SHARD_SEED,
+            betting_pool.key().as_ref(),
+            claim_shard.round_id.to_le_bytes().as_ref(),
+            &[claim_shard.shard_index],
+        ],
+        bump = claim_shard.bump,
+        has_one = payer @ SportsbookError::InvalidClaimShard,
+    )]
+    pub claim_shard: Account<'info, ClaimShard>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
+    /// Shard's rent payer
+    /// CHECK: Checked against claim_shard.payer; only receives lamports
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn merge_claim_shard_handler(ctx: Context<MergeClaimShard>) -> Result<()> {
+    let claim_shard = &ctx.accounts.claim_shard;
+    let round_accounting = &mut ctx.accounts.round_accounting;
+
+    round_accounting.total_claimed += claim_shard.total_paid_out;
+    round_accounting.total_paid_out += claim_shard.total_paid_out;
+    round_accounting.total_refunded += claim_shard.total_refunded;
+    round_accounting.protocol_bounties_paid += claim_shard.protocol_bounties_paid;
+    round_accounting.payout_dust += claim_shard.payout_dust;
+    round_accounting.bets_resolved = round_accounting
+        .bets_resolved
+        .saturating_add(claim_shard.bets_resolved);
+    round_accounting.claim_shards -= 1;
+    round_accounting.shard_payout_cap -= claim_shard.payout_cap;
+
+    if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+        pending_claims.record_shard(claim_shard);
+    }
+
+    msg!(
+        "Round {} claim shard {} merged: {} bets, {} paid out",
+        claim_shard.round_id,
+        claim_shard.shard_index,
+        claim_shard.bets_resolved,
+        claim_shard.total_paid_out
+    );
+
+    Ok(())
+}
@@ -1,8 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{BettingPool, RoundAccounting, Bet, MatchOutcome};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{BettingPool, RoundAccounting, Bet, BetRecord, PendingClaims, Prediction, InsuranceFund, UserStats};
 use crate::errors::SportsbookError;
 use crate::constants::*;
+use crate::events::{
+    ClaimWindowViolation, PayoutCheckFailed, LiquidityCheckFailed, InsuranceRefunded, WinningsCompounded,
+};
+use crate::require_with_context;
+use crate::clock::{pool_clock, TimeSource};
+use crate::merkle::verify_bet_odds_proof;
+use crate::token_hook::PoolTransfer;
+use crate::invariants::FundsCheck;
+use sportsbook_core::{
+    calculate_success_fee, is_fully_void, is_lost_by_bitmap, is_one_leg_short, split_bounty, SettledLeg,
+};
+use super::round_vault::round_funds;
 
 #[derive(Accounts)]
 #[instruction(bet_id: u64)]
@@ -12,28 +26,62 @@ pub struct ClaimWinnings<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.claim_shards == 0 @ SportsbookError::ClaimShardsOpen,
     )]
     pub round_accounting: Account<'info, RoundAccounting>,
 
     #[account(
         mut,
-        seeds = [b"bet", betting_pool.key().as_ref(), bet_id.to_le_bytes().as_ref()],
-        bump = bet.bump,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bet_id == bet_id @ SportsbookError::InvalidBetAccount,
         constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+        constraint = !bet.payout_reserved @ SportsbookError::PayoutReserved,
     )]
     pub bet: Account<'info, Bet>,
 
+    /// Optional: the bet's BetRecord, whose leg bitmap settles a lost bet early
+    #[account(
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump = bet_record.bump,
+    )]
+    pub bet_record: Option<Account<'info, BetRecord>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
     /// Betting pool's token account (protocol provides all liquidity)
-    #[account(mut)]
-    pub betting_pool_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub betting_pool_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Bettor's token account (receives winnings or 90% if bounty claim)
-    /// CHECK: Verified against bet.bettor
-    #[account(mut)]
-    pub bettor_token_account: UncheckedAccount<'info>,
+    /// Required for isolated rounds: pays out instead of the pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account (receives winnings or 90% if bounty claim,
+    /// and any void or insurance refund)
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bet.bettor @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Claimer (can be bettor or bounty hunter after 24h)
     /// If claiming within 24h, must be the bettor
@@ -42,192 +90,983 @@ pub struct ClaimWinnings<'info> {
     pub claimer: Signer<'info>,
 
     /// Claimer's token account (receives 10% bounty if third-party claim)
-    /// CHECK: Only used for bounty claims after deadline
+    /// CHECK: For bounty claims, verified in handler to be the claimer's
+    /// initialized ATA for the pool mint
     #[account(mut)]
     pub claimer_token_account: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Copy leader's token account (receives the success fee); required
+    /// when claiming a winning copied bet
+    /// CHECK: Verified in handler to be bet.copy_leader's initialized ATA
+    /// for the pool mint
+    #[account(mut)]
+    pub leader_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Pool mint; required when the pool uses Token-2022. If the mint has
+    /// a transfer hook, the hook program, its extra-account-metas account
+    /// and the hook's own accounts go in `remaining_accounts`
+    #[account(address = betting_pool.token_mint @ SportsbookError::InvalidTokenMint)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Required when claiming an insured bet that lost by one leg
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, betting_pool.key().as_ref()],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+
+    /// Required with insurance_fund: pays the insured refund
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED, betting_pool.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional: the bettor's UserStats; when it prefers compounding, a
+    /// winning bettor's share goes to credit_vault instead of their wallet.
+    /// Claims without it pay the wallet whatever the preference
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, betting_pool.key().as_ref(), bet.bettor.as_ref()],
+        bump = user_stats.bump,
+    )]
+    pub user_stats: Option<Account<'info, UserStats>>,
+
+    /// Required with a compounding user_stats: holds the credit
+    #[account(
+        mut,
+        seeds = [CREDIT_VAULT_SEED, betting_pool.key().as_ref(), bet.bettor.as_ref()],
+        bump,
+    )]
+    pub credit_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ClaimWinnings>,
+/// What a claim paid, returned as instruction return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimReceipt {
+    /// Bet PDA
+    pub bet: Pubkey,
+
+    /// All legs won
+    pub won: bool,
+
+    /// Total payout (0 if lost)
+    pub payout: u64,
+
+    /// Amount sent to the bettor
+    pub bettor_amount: u64,
+
+    /// Bettor's share held as betting credit instead (compounding)
+    pub credited: u64,
+
+    /// Amount sent to the claimer as bounty
+    pub bounty_amount: u64,
+
+    /// Success fee sent to the copy leader (copied bets only)
+    pub leader_fee: u64,
+
+    /// Original stake returned because every leg was voided
+    pub refund: u64,
+
+    /// Original stake refunded by the insurance fund (insured parlay that
+    /// lost by one leg)
+    pub insurance_refund: u64,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimWinnings<'info>>,
     bet_id: u64,
     min_payout: u64,
-) -> Result<()> {
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+) -> Result<ClaimReceipt> {
+    require!(
+        !ctx.accounts.betting_pool.merkle_settlement,
+        SportsbookError::OddsProofRequired
+    );
+
+    execute_claim(ctx, bet_id, min_payout)
+}
+
+/// Claim in a Merkle settlement pool: `proof` is the multi-proof of the
+/// bet's legs against the round's locked_odds_root
+pub fn claim_with_proof_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimWinnings<'info>>,
+    bet_id: u64,
+    min_payout: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<ClaimReceipt> {
+    verify_bet_odds_proof(&ctx.accounts.bet, &ctx.accounts.round_accounting, &proof)?;
 
+    execute_claim(ctx, bet_id, min_payout)
+}
+
+fn execute_claim<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimWinnings<'info>>,
+    bet_id: u64,
+    min_payout: u64,
+) -> Result<ClaimReceipt> {
     // Extract account infos and keys BEFORE mutable borrows
     let betting_pool_info = ctx.accounts.betting_pool.to_account_info();
     let betting_pool_bump = ctx.accounts.betting_pool.bump;
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let round_info = ctx.accounts.round_accounting.to_account_info();
+    let round_id_bytes = ctx.accounts.bet.round_id.to_le_bytes();
+    let round_bump = [ctx.accounts.round_accounting.bump];
+    let isolated = ctx.accounts.round_accounting.isolated;
 
-    // Calculate claim deadline: 24 hours after round settlement
-    // 86400 seconds = 24 hours
-    let claim_deadline = ctx.accounts.round_accounting.round_end_time + 86400;
+    let is_bettor = ctx.accounts.claimer.key() == ctx.accounts.bet.bettor;
+    let window = ClaimWindow::evaluate(
+        &pool_clock(&ctx.accounts.betting_pool),
+        &ctx.accounts.round_accounting,
+        is_bettor,
+    )?;
+    let current_time = window.current_time;
+    let claim_deadline = window.claim_deadline;
+    let is_bounty_claim = window.is_bounty_claim;
+
+    let window_context = ClaimWindowViolation {
+        bet: ctx.accounts.bet.key(),
+        bettor: ctx.accounts.bet.bettor,
+        claimer: ctx.accounts.claimer.key(),
+        claim_deadline,
+        claims_paused_until: ctx.accounts.round_accounting.claims_paused_until,
+        current_time,
+    };
+
+    require_with_context!(
+        !window.paused,
+        SportsbookError::ClaimsPaused,
+        window_context
+    );
 
     // Update bet's claim_deadline if not set yet
     if ctx.accounts.bet.claim_deadline == 0 {
         ctx.accounts.bet.claim_deadline = claim_deadline;
     }
 
-    // Check claim window and determine if this is a bounty claim
-    let is_bettor = ctx.accounts.claimer.key() == ctx.accounts.bet.bettor;
-    let is_bounty_claim = current_time > claim_deadline && !is_bettor;
-
     // If within 24h window, only bettor can claim
-    if current_time <= claim_deadline {
-        require!(is_bettor, SportsbookError::NotBettor);
+    require_with_context!(
+        window.claimer_allowed,
+        SportsbookError::NotBettor,
+        window_context
+    );
+
+    // Bounty goes to the claimer's ATA; reject anything else up front
+    // rather than risk sending it to a mistyped account
+    if is_bounty_claim {
+        verify_claimer_token_account(
+            &ctx.accounts.claimer_token_account,
+            &ctx.accounts.claimer.key(),
+            &ctx.accounts.betting_pool.token_mint,
+            &ctx.accounts.token_program.key(),
+        )?;
     }
 
+    // A losing claim on an isolated round may leave out the vault
+    let funds_check = FundsCheck::start(
+        &ctx.accounts.round_accounting,
+        round_funds(
+            isolated,
+            &ctx.accounts.betting_pool_token_account,
+            ctx.accounts.round_vault.as_ref(),
+        )
+        .ok()
+        .map(|funds_account| funds_account.to_account_info()),
+    )?;
+
+    // A bet its record shows lost needs no pricing; a fully voided bet is
+    // refunded, never settled
+    let lost = is_lost_by_record(ctx.accounts.bet_record.as_deref(), &ctx.accounts.round_accounting);
+    let refund = if lost {
+        None
+    } else {
+        voided_bet_refund(&ctx.accounts.bet, &ctx.accounts.round_accounting)
+    };
+
     // Calculate if bet won and payout amount
-    let (won, base_payout, final_payout) = calculate_bet_payout(&ctx.accounts.bet, &ctx.accounts.round_accounting)?;
+    let (won, base_payout, final_payout) = if lost || refund.is_some() {
+        (false, 0, 0)
+    } else {
+        calculate_bet_payout(&ctx.accounts.bet, &ctx.accounts.round_accounting)?
+    };
+    let insurance_refund = if won || refund.is_some() {
+        None
+    } else {
+        insured_bet_refund(&ctx.accounts.bet, &ctx.accounts.round_accounting)
+    };
 
-    // Slippage protection
-    require!(
-        final_payout >= min_payout,
-        SportsbookError::PayoutBelowMinimum
-    );
+    // Slippage protection only makes sense for a payout; a losing bet
+    // settles at zero whatever min_payout says
+    if won {
+        require_with_context!(
+            final_payout >= min_payout,
+            SportsbookError::PayoutBelowMinimum,
+            PayoutCheckFailed {
+                bet: ctx.accounts.bet.key(),
+                round_id: ctx.accounts.bet.round_id,
+                payout: final_payout,
+                limit: min_payout,
+                round_paid_out: ctx.accounts.round_accounting.total_paid_out,
+            }
+        );
+    }
 
     // Mark as claimed and settled
     ctx.accounts.bet.claimed = true;
     ctx.accounts.bet.settled = true;
+    ctx.accounts.round_accounting.record_bet_resolved();
+
+    let mut receipt = ClaimReceipt {
+        bet: ctx.accounts.bet.key(),
+        won,
+        payout: 0,
+        bettor_amount: 0,
+        credited: 0,
+        bounty_amount: 0,
+        leader_fee: 0,
+        refund: 0,
+        insurance_refund: 0,
+    };
 
     if won && final_payout > 0 {
         // Check per-round payout cap
-        require!(
+        require_with_context!(
             ctx.accounts.round_accounting.total_paid_out + final_payout <= MAX_ROUND_PAYOUTS,
-            SportsbookError::RoundPayoutLimitReached
+            SportsbookError::RoundPayoutLimitReached,
+            PayoutCheckFailed {
+                bet: ctx.accounts.bet.key(),
+                round_id: ctx.accounts.bet.round_id,
+                payout: final_payout,
+                limit: MAX_ROUND_PAYOUTS,
+                round_paid_out: ctx.accounts.round_accounting.total_paid_out,
+            }
         );
 
         // Update accounting
         ctx.accounts.round_accounting.total_claimed += final_payout;
         ctx.accounts.round_accounting.total_paid_out += final_payout;
+        ctx.accounts.round_accounting.payout_dust += ctx.accounts.bet.payout_dust;
 
         // Calculate bounty split if applicable
-        let (bettor_amount, bounty_amount) = if is_bounty_claim {
-            // 90% to bettor, 10% to claimer
-            let bounty = (final_payout as u128)
-                .checked_mul(1000)  // 10% = 1000 / 10000
-                .ok_or(SportsbookError::CalculationOverflow)?
-                .checked_div(10000)
-                .ok_or(SportsbookError::CalculationOverflow)? as u64;
-            let bettor_share = final_payout.saturating_sub(bounty);
-
+        let protocol_funded_bounty = ctx.accounts.round_accounting.protocol_funded_bounty;
+        let (bettor_amount, bounty_amount) =
+            bounty_split(final_payout, is_bounty_claim, protocol_funded_bounty)?;
+        if is_bounty_claim {
             // Record bounty claimer
             ctx.accounts.bet.bounty_claimer = Some(ctx.accounts.claimer.key());
+            if protocol_funded_bounty {
+                ctx.accounts.round_accounting.protocol_bounties_paid += bounty_amount;
+            }
 
-            msg!("Bounty claim by {}: 10% bounty = {}", ctx.accounts.claimer.key(), bounty);
-            (bettor_share, bounty)
-        } else {
-            // Bettor claims within 24h, gets 100%
-            (final_payout, 0)
-        };
+            msg!("Bounty claim by {}: 10% bounty = {}", ctx.accounts.claimer.key(), bounty_amount);
+        }
+        let total_out = bettor_amount + bounty_amount;
+
+        // Copied bets give the leader a cut of the bettor's profit
+        let leader_fee = copy_success_fee(&ctx.accounts.bet, bettor_amount)?;
+        let bettor_amount = bettor_amount - leader_fee;
 
-        let betting_pool_balance = ctx.accounts.betting_pool_token_account.amount;
+        // Isolated rounds pay only from their own vault
+        let funds_account = round_funds(
+            isolated,
+            &ctx.accounts.betting_pool_token_account,
+            ctx.accounts.round_vault.as_ref(),
+        )?;
+        let betting_pool_balance = funds_account.amount;
 
         // Ensure protocol has enough to pay (should always be true)
-        require!(
-            betting_pool_balance >= final_payout,
-            SportsbookError::InsufficientProtocolLiquidity
+        require_with_context!(
+            betting_pool_balance >= total_out,
+            SportsbookError::InsufficientProtocolLiquidity,
+            LiquidityCheckFailed {
+                betting_pool: ctx.accounts.betting_pool.key(),
+                required: total_out,
+                available: betting_pool_balance,
+            }
         );
 
-        let seeds = &[b"betting_pool".as_ref(), &[betting_pool_bump]];
-        let signer = &[&seeds[..]];
+        let pool_bump = [betting_pool_bump];
+        let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if isolated {
+            (round_info, &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump])
+        } else {
+            (betting_pool_info, &[BETTING_POOL_SEED, &pool_bump])
+        };
+        let signer = &[seeds];
+        let pool_transfer = PoolTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.token_mint.as_ref().map(|mint| mint.to_account_info()),
+            from: funds_account.to_account_info(),
+            authority: funds_authority,
+            extra_accounts: ctx.remaining_accounts,
+            signer,
+        };
 
-        // Pay bettor their share
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.betting_pool_token_account.to_account_info(),
-            to: ctx.accounts.bettor_token_account.to_account_info(),
-            authority: betting_pool_info.clone(),
+        // Pay bettor their share, or hold it as credit if they prefer to compound
+        let credited = match ctx.accounts.user_stats.as_mut() {
+            Some(user_stats) if user_stats.prefer_compound => {
+                let credit_vault = ctx
+                    .accounts
+                    .credit_vault
+                    .as_ref()
+                    .ok_or(SportsbookError::CreditVaultRequired)?;
+                pool_transfer.transfer(credit_vault.to_account_info(), bettor_amount)?;
+                user_stats
+                    .compound(bettor_amount)
+                    .ok_or(SportsbookError::CalculationOverflow)?;
+
+                emit!(WinningsCompounded {
+                    betting_pool: betting_pool_key,
+                    bet: ctx.accounts.bet.key(),
+                    bettor: ctx.accounts.bet.bettor,
+                    amount: bettor_amount,
+                    credit: user_stats.credit,
+                });
+                bettor_amount
+            }
+            _ => {
+                pool_transfer.transfer(ctx.accounts.bettor_token_account.to_account_info(), bettor_amount)?;
+                0
+            }
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, bettor_amount)?;
 
         // Pay bounty to claimer if applicable
         if bounty_amount > 0 {
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.betting_pool_token_account.to_account_info(),
-                to: ctx.accounts.claimer_token_account.to_account_info(),
-                authority: betting_pool_info.clone(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            token::transfer(cpi_ctx, bounty_amount)?;
+            pool_transfer.transfer(ctx.accounts.claimer_token_account.to_account_info(), bounty_amount)?;
+        }
+
+        // Pay the copy leader's success fee if applicable
+        if leader_fee > 0 {
+            let leader_token_account = ctx
+                .accounts
+                .leader_token_account
+                .as_ref()
+                .ok_or(SportsbookError::InvalidLeaderTokenAccount)?;
+            verify_claimer_token_account(
+                leader_token_account,
+                &ctx.accounts.bet.copy_leader,
+                &ctx.accounts.betting_pool.token_mint,
+                &ctx.accounts.token_program.key(),
+            )
+            .map_err(|_| SportsbookError::InvalidLeaderTokenAccount)?;
+
+            pool_transfer.transfer(leader_token_account.to_account_info(), leader_fee)?;
+
+            msg!("Copy leader {} success fee: {}", ctx.accounts.bet.copy_leader, leader_fee);
         }
 
         msg!("Bet {} won! Paid out {} tokens (bettor: {}, bounty: {})",
              bet_id, final_payout, bettor_amount, bounty_amount);
         msg!("Base payout: {}, Parlay multiplier: {}", base_payout, ctx.accounts.bet.locked_multiplier);
+
+        receipt.payout = final_payout;
+        receipt.bettor_amount = bettor_amount - credited;
+        receipt.credited = credited;
+        receipt.bounty_amount = bounty_amount;
+        receipt.leader_fee = leader_fee;
+    } else if let Some(refund) = refund {
+        // The whole stake goes back to the bettor whoever sends the claim:
+        // no bounty, no copy fee and no round payout cap, as nothing was won
+        ctx.accounts.round_accounting.total_refunded += refund;
+
+        let funds_account = round_funds(
+            isolated,
+            &ctx.accounts.betting_pool_token_account,
+            ctx.accounts.round_vault.as_ref(),
+        )?;
+        require_with_context!(
+            funds_account.amount >= refund,
+            SportsbookError::InsufficientProtocolLiquidity,
+            LiquidityCheckFailed {
+                betting_pool: ctx.accounts.betting_pool.key(),
+                required: refund,
+                available: funds_account.amount,
+            }
+        );
+
+        let pool_bump = [betting_pool_bump];
+        let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if isolated {
+            (round_info, &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump])
+        } else {
+            (betting_pool_info, &[BETTING_POOL_SEED, &pool_bump])
+        };
+        let signer = &[seeds];
+        let pool_transfer = PoolTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.token_mint.as_ref().map(|mint| mint.to_account_info()),
+            from: funds_account.to_account_info(),
+            authority: funds_authority,
+            extra_accounts: ctx.remaining_accounts,
+            signer,
+        };
+        pool_transfer.transfer(ctx.accounts.bettor_token_account.to_account_info(), refund)?;
+
+        msg!("Bet {} voided: refunded stake {}", bet_id, refund);
+
+        receipt.refund = refund;
+    } else if let Some(insurance_refund) = insurance_refund {
+        // Paid from the insurance vault, so the round's funds and
+        // accounting don't move; the stake goes to the bettor whoever
+        // sends the claim, as for a void refund
+        let insurance_fund = ctx
+            .accounts
+            .insurance_fund
+            .as_mut()
+            .ok_or(SportsbookError::InsuranceFundRequired)?;
+        let insurance_vault = ctx
+            .accounts
+            .insurance_vault
+            .as_ref()
+            .ok_or(SportsbookError::InsuranceFundRequired)?;
+        require!(
+            insurance_vault.amount >= insurance_refund,
+            SportsbookError::InsufficientInsuranceFund
+        );
+
+        let fund_bump = [insurance_fund.bump];
+        let seeds: &[&[u8]] = &[INSURANCE_FUND_SEED, betting_pool_key.as_ref(), &fund_bump];
+        let signer = &[seeds];
+        let vault_transfer = PoolTransfer {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.token_mint.as_ref().map(|mint| mint.to_account_info()),
+            from: insurance_vault.to_account_info(),
+            authority: insurance_fund.to_account_info(),
+            extra_accounts: ctx.remaining_accounts,
+            signer,
+        };
+        vault_transfer.transfer(ctx.accounts.bettor_token_account.to_account_info(), insurance_refund)?;
+
+        insurance_fund.total_refunded = insurance_fund
+            .total_refunded
+            .checked_add(insurance_refund)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+
+        emit!(InsuranceRefunded {
+            betting_pool: betting_pool_key,
+            bet: ctx.accounts.bet.key(),
+            bet_id,
+            bettor: ctx.accounts.bet.bettor,
+            refund: insurance_refund,
+        });
+
+        msg!("Bet {} lost by one leg: insurance refunded stake {}", bet_id, insurance_refund);
+
+        receipt.insurance_refund = insurance_refund;
     } else {
         msg!("Bet {} lost", bet_id);
     }
 
+    if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+        pending_claims.record_resolution(
+            ctx.accounts.bet.potential_payout,
+            receipt.payout + receipt.refund,
+            receipt.bounty_amount,
+        );
+    }
+    funds_check.finish(&ctx.accounts.round_accounting, 0)?;
+
+    Ok(receipt)
+}
+
+/// Mark a losing bet as settled without any token accounts
+///
+/// Permissionless and transfer-free so keepers can crank losses cheaply.
+/// Fails for winning bets and insured bets owed a refund, which must go
+/// through claim_winnings.
+#[derive(Accounts)]
+pub struct SettleLosingBet<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Optional: the bet's BetRecord, whose leg bitmap settles a lost bet early
+    #[account(
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump = bet_record.bump,
+    )]
+    pub bet_record: Option<Account<'info, BetRecord>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn settle_losing_bet_handler(ctx: Context<SettleLosingBet>) -> Result<()> {
+    require!(
+        !ctx.accounts.round_accounting.claims_blocked(
+            pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?
+        ),
+        SportsbookError::ClaimsPaused
+    );
+
+    if !is_lost_by_record(ctx.accounts.bet_record.as_deref(), &ctx.accounts.round_accounting) {
+        require!(
+            voided_bet_refund(&ctx.accounts.bet, &ctx.accounts.round_accounting).is_none(),
+            SportsbookError::BetNotLost
+        );
+        let (won, _, _) = calculate_bet_payout(&ctx.accounts.bet, &ctx.accounts.round_accounting)?;
+        require!(!won, SportsbookError::BetNotLost);
+    }
+    require!(
+        insured_bet_refund(&ctx.accounts.bet, &ctx.accounts.round_accounting).is_none(),
+        SportsbookError::InsuranceRefundDue
+    );
+
+    ctx.accounts.bet.claimed = true;
+    ctx.accounts.bet.settled = true;
+    ctx.accounts.round_accounting.record_bet_resolved();
+    if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+        pending_claims.record_resolution(ctx.accounts.bet.potential_payout, 0, 0);
+    }
+
+    msg!("Bet {} lost", ctx.accounts.bet.bet_id);
+
     Ok(())
 }
 
-/// Calculate bet payout with parlay multiplier
-fn calculate_bet_payout(
-    bet: &Bet,
-    round_accounting: &RoundAccounting,
-) -> Result<(bool, u64, u64)> {
-    let mut all_correct = true;
-    let mut total_base_payout = 0u64;
+/// Cheap claim for small single-leg payouts
+///
+/// A lean claim_winnings for aggregators packing many claims into one
+/// transaction: anyone may send it, the whole payout always goes to the
+/// bettor, and there is no bounty split, copy fee, per-round cap check or
+/// return data. Losing bets are settled as by settle_losing_bet. SPL Token
+/// pools only; Token-2022 and Merkle settlement pools use claim_winnings.
+#[derive(Accounts)]
+pub struct QuickClaim<'info> {
+    #[account(constraint = !betting_pool.merkle_settlement @ SportsbookError::OddsProofRequired)]
+    pub betting_pool: Account<'info, BettingPool>,
 
-    let predictions = bet.get_predictions();
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.claim_shards == 0 @ SportsbookError::ClaimShardsOpen,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
 
-    for prediction in predictions {
-        let match_result = &round_accounting.match_results[prediction.match_index as usize];
-        let locked_odds = &round_accounting.locked_odds[prediction.match_index as usize];
+    #[account(
+        mut,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+        constraint = !bet.payout_reserved @ SportsbookError::PayoutReserved,
+        constraint = is_quick_claimable(&bet) @ SportsbookError::NotQuickClaimable,
+    )]
+    pub bet: Account<'info, Bet>,
 
-        // Check if prediction is correct
-        let predicted_outcome = match prediction.predicted_outcome {
-            1 => MatchOutcome::HomeWin,
-            2 => MatchOutcome::AwayWin,
-            3 => MatchOutcome::Draw,
-            _ => MatchOutcome::Pending,
-        };
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
 
-        if *match_result != predicted_outcome {
-            all_correct = false;
-            break;
-        }
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, token::TokenAccount>,
 
-        // Use locked odds for payout calculation
-        require!(locked_odds.locked, SportsbookError::OddsNotLocked);
+    /// Required for isolated rounds: pays out instead of the pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, token::TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bet.bettor @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Account<'info, token::TokenAccount>,
 
-        let odds = locked_odds.get_odds(prediction.predicted_outcome);
+    pub claimer: Signer<'info>,
 
-        // Simple multiplication: amount × locked odds
-        let match_payout = (prediction.amount_in_pool as u128)
-            .checked_mul(odds as u128)
-            .ok_or(SportsbookError::CalculationOverflow)?
-            .checked_div(ODDS_SCALE as u128)
-            .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    pub token_program: Program<'info, Token>,
+}
 
-        total_base_payout += match_payout;
+pub fn quick_claim_handler(ctx: Context<QuickClaim>) -> Result<()> {
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    require!(
+        !round_accounting.claims_blocked(pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?),
+        SportsbookError::ClaimsPaused
+    );
+    let funds_check = FundsCheck::start(
+        round_accounting,
+        round_funds(
+            round_accounting.isolated,
+            &ctx.accounts.betting_pool_token_account,
+            ctx.accounts.round_vault.as_ref(),
+        )
+        .ok()
+        .map(|funds_account| funds_account.to_account_info()),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let leg = [settled_leg(&bet.predictions[0], round_accounting)];
+    // Refunds of voided bets go through claim_winnings
+    require!(!is_fully_void(&leg), SportsbookError::NotQuickClaimable);
+    let payout = sportsbook_core::calculate_bet_payout(&leg, bet.locked_multiplier, bet.potential_payout)
+        .map_err(SportsbookError::from)?;
+    require!(
+        payout.final_payout <= QUICK_CLAIM_MAX_PAYOUT,
+        SportsbookError::NotQuickClaimable
+    );
+
+    bet.claimed = true;
+    bet.settled = true;
+    round_accounting.record_bet_resolved();
+
+    let paid = if payout.won { payout.final_payout } else { 0 };
+    if paid > 0 {
+        round_accounting.total_claimed += paid;
+        round_accounting.total_paid_out += paid;
+        round_accounting.payout_dust += bet.payout_dust;
+
+        let betting_pool_key = ctx.accounts.betting_pool.key();
+        let round_id_bytes = bet.round_id.to_le_bytes();
+        let round_bump = [round_accounting.bump];
+        let pool_bump = [ctx.accounts.betting_pool.bump];
+        let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if round_accounting.isolated {
+            (
+                round_accounting.to_account_info(),
+                &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump],
+            )
+        } else {
+            (ctx.accounts.betting_pool.to_account_info(), &[BETTING_POOL_SEED, &pool_bump])
+        };
+        let signer = &[seeds];
+        let funds_account = round_funds(
+            round_accounting.isolated,
+            &ctx.accounts.betting_pool_token_account,
+            ctx.accounts.round_vault.as_ref(),
+        )?;
+        let cpi_accounts = Transfer {
+            from: funds_account.to_account_info(),
+            to: ctx.accounts.bettor_token_account.to_account_info(),
+            authority: funds_authority,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, paid)?;
     }
 
-    if !all_correct {
-        return Ok((false, 0, 0));
+    if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+        pending_claims.record_resolution(bet.potential_payout, paid, 0);
     }
+    funds_check.finish(round_accounting, 0)?;
+
+    msg!("Bet {} quick-claimed: {}", bet.bet_id, paid);
+
+    Ok(())
+}
+
+/// Single-leg bet with no copy leader to pay
+fn is_quick_claimable(bet: &Bet) -> bool {
+    bet.num_predictions == 1 && bet.copy_leader == Pubkey::default()
+}
+
+/// Dry-run of claim_winnings for `simulateTransaction`
+///
+/// Mutates nothing and needs no signer; the result comes back as return
+/// data so wallets can show the exact claim outcome.
+#[derive(Accounts)]
+#[instruction(bet_id: u64)]
+pub struct PreviewClaim<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bet_id == bet_id @ SportsbookError::InvalidBetAccount,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Who would send the claim (decides bettor vs bounty split)
+    /// CHECK: Only its key is read
+    pub claimer: UncheckedAccount<'info>,
+}
+
+/// Outcome of a claim as preview_claim computes it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimPreview {
+    /// Round settled and claims not paused
+    pub claimable: bool,
+
+    /// Bet already claimed or settled as lost
+    pub already_claimed: bool,
+
+    /// All legs won
+    pub won: bool,
+
+    /// Total payout (0 if lost)
+    pub payout: u64,
+
+    /// Share going to the bettor
+    pub bettor_amount: u64,
 
-    // Apply locked parlay multiplier
-    let total_final_payout = (total_base_payout as u128)
-        .checked_mul(bet.locked_multiplier as u128)
-        .ok_or(SportsbookError::CalculationOverflow)?
-        .checked_div(ODDS_SCALE as u128)
-        .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    /// Share going to the claimer as bounty
+    pub bounty_amount: u64,
 
-    // Cap maximum payout per bet
-    let capped_payout = if total_final_payout > MAX_PAYOUT_PER_BET {
-        MAX_PAYOUT_PER_BET
+    /// Success fee going to the copy leader (copied bets only)
+    pub leader_fee: u64,
+
+    /// Stake that would be refunded because every leg was voided
+    pub refund: u64,
+
+    /// Stake the insurance fund would refund (insured parlay that lost by
+    /// one leg)
+    pub insurance_refund: u64,
+
+    /// End of the bettor-only window
+    pub claim_deadline: i64,
+
+    /// Claimer is allowed to claim right now
+    pub claimer_allowed: bool,
+}
+
+pub fn preview_claim_handler(ctx: Context<PreviewClaim>, _bet_id: u64) -> Result<ClaimPreview> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let bet = &ctx.accounts.bet;
+
+    let settled = round_accounting.settled;
+    let window = ClaimWindow::evaluate(
+        &pool_clock(&ctx.accounts.betting_pool),
+        round_accounting,
+        ctx.accounts.claimer.key() == bet.bettor,
+    )?;
+
+    let refund = if settled { voided_bet_refund(bet, round_accounting) } else { None };
+    let (won, payout) = if settled && refund.is_none() {
+        let (won, _, payout) = calculate_bet_payout(bet, round_accounting)?;
+        (won, payout)
     } else {
-        total_final_payout
+        (false, 0)
     };
 
-    Ok((true, total_base_payout, capped_payout))
+    let insurance_refund = if settled && !won && refund.is_none() {
+        insured_bet_refund(bet, round_accounting)
+    } else {
+        None
+    };
+
+    // Reserved payouts always take the bounty out of the payout
+    let protocol_funded = round_accounting.protocol_funded_bounty && !bet.payout_reserved;
+    let (bettor_amount, bounty_amount) = bounty_split(payout, window.is_bounty_claim, protocol_funded)?;
+    let leader_fee = copy_success_fee(bet, bettor_amount)?;
+
+    Ok(ClaimPreview {
+        claimable: settled && !window.paused,
+        already_claimed: bet.claimed,
+        won,
+        payout,
+        bettor_amount: bettor_amount - leader_fee,
+        bounty_amount,
+        leader_fee,
+        refund: refund.unwrap_or(0),
+        insurance_refund: insurance_refund.unwrap_or(0),
+        claim_deadline: window.claim_deadline,
+        claimer_allowed: window.claimer_allowed,
+    })
+}
+
+/// Who may claim a settled round's bets at a given time, and on what terms
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimWindow {
+    pub current_time: i64,
+
+    /// End of the bettor-only window: settlement + 24h + pause extension
+    pub claim_deadline: i64,
+
+    /// A guardian pause is in effect
+    pub paused: bool,
+
+    /// Bettor at any time, anyone else only after the deadline
+    pub claimer_allowed: bool,
+
+    /// Third-party claim after the deadline, which earns the 10% bounty
+    pub is_bounty_claim: bool,
+}
+
+impl ClaimWindow {
+    pub fn evaluate(
+        clock: &impl TimeSource,
+        round_accounting: &RoundAccounting,
+        is_bettor: bool,
+    ) -> Result<Self> {
+        let current_time = clock.unix_timestamp()?;
+        let claim_deadline = round_accounting.claim_deadline();
+        let past_deadline = current_time > claim_deadline;
+
+        Ok(Self {
+            current_time,
+            claim_deadline,
+            paused: round_accounting.claims_blocked(current_time),
+            claimer_allowed: is_bettor || past_deadline,
+            is_bounty_claim: past_deadline && !is_bettor,
+        })
+    }
+}
+
+/// Check the bounty destination is the claimer's initialized ATA for the pool mint
+pub(crate) fn verify_claimer_token_account(
+    claimer_token_account: &AccountInfo,
+    claimer: &Pubkey,
+    token_mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<()> {
+    require!(
+        claimer_token_account.key()
+            == get_associated_token_address_with_program_id(claimer, token_mint, token_program),
+        SportsbookError::InvalidClaimerTokenAccount
+    );
+
+    // Fails if the account isn't an initialized token account
+    require!(
+        claimer_token_account.owner == token_program,
+        SportsbookError::InvalidClaimerTokenAccount
+    );
+    let data = claimer_token_account.try_borrow_data()?;
+    let token_account = TokenAccount::try_deserialize(&mut &data[..])
+        .map_err(|_| SportsbookError::InvalidClaimerTokenAccount)?;
+    require!(
+        token_account.owner == *claimer && token_account.mint == *token_mint,
+        SportsbookError::InvalidClaimerTokenAccount
+    );
+
+    Ok(())
+}
+
+/// (bettor_amount, bounty_amount) for a payout
+///
+/// A bettor's own claim pays in full. A bounty claim cuts the bounty from
+/// the payout, or in a round with protocol-funded bounties pays the full
+/// payout and the bounty on top.
+pub fn bounty_split(payout: u64, is_bounty_claim: bool, protocol_funded: bool) -> Result<(u64, u64)> {
+    if !is_bounty_claim {
+        return Ok((payout, 0));
+    }
+
+    let (bettor_share, bounty) = split_bounty(payout).map_err(SportsbookError::from)?;
+    Ok(if protocol_funded { (payout, bounty) } else { (bettor_share, bounty) })
+}
+
+/// Copy leader's cut of what the bettor receives (0 for ordinary bets)
+pub fn copy_success_fee(bet: &Bet, bettor_amount: u64) -> Result<u64> {
+    if bet.copy_leader == Pubkey::default() {
+        return Ok(0);
+    }
+
+    calculate_success_fee(bettor_amount, bet.amount, bet.copy_fee_bps)
+        .map_err(|e| SportsbookError::from(e).into())
+}
+
+/// Calculate bet payout with parlay multiplier
+///
+/// Returns (won, base_payout, final_payout); the math itself is
+/// `sportsbook_core::calculate_bet_payout`, shared with web clients.
+pub fn calculate_bet_payout(
+    bet: &Bet,
+    round_accounting: &RoundAccounting,
+) -> Result<(bool, u64, u64)> {
+    let legs: Vec<SettledLeg> = bet
+        .get_predictions()
+        .iter()
+        .map(|prediction| settled_leg(prediction, round_accounting))
+        .collect();
+
+    let payout = sportsbook_core::calculate_bet_payout(
+        &legs,
+        bet.locked_multiplier,
+        bet.potential_payout,
+    )
+    .map_err(SportsbookError::from)?;
+
+    Ok((payout.won, payout.base_payout, payout.final_payout))
+}
+
+/// Original stake, fee included, if every leg of the bet was voided
+///
+/// Refunded as placed rather than paid through calculate_bet_payout, which
+/// would count the legs at odds 1.0 on the after-fee stake and apply the
+/// parlay multiplier.
+pub fn voided_bet_refund(bet: &Bet, round_accounting: &RoundAccounting) -> Option<u64> {
+    let legs: Vec<SettledLeg> = bet
+        .get_predictions()
+        .iter()
+        .map(|prediction| settled_leg(prediction, round_accounting))
+        .collect();
+
+    is_fully_void(&legs).then_some(bet.amount)
+}
+
+/// Whether the bet's BetRecord shows it lost: one of its legs was decided
+/// the other way, per the round's result_bits
+///
+/// Most bets lose, and this settles them with one AND instead of pricing
+/// every leg. Without a record it says nothing (false), and the caller
+/// falls back to calculate_bet_payout.
+pub fn is_lost_by_record(bet_record: Option<&BetRecord>, round_accounting: &RoundAccounting) -> bool {
+    bet_record.is_some_and(|record| is_lost_by_bitmap(record.leg_bits, round_accounting.result_bits))
+}
+
+/// Original stake, fee included, if the bet is insured and lost by exactly
+/// one leg; paid from the pool's InsuranceFund, not the round
+pub fn insured_bet_refund(bet: &Bet, round_accounting: &RoundAccounting) -> Option<u64> {
+    if !bet.insured {
+        return None;
+    }
+
+    let legs: Vec<SettledLeg> = bet
+        .get_predictions()
+        .iter()
+        .map(|prediction| settled_leg(prediction, round_accounting))
+        .collect();
+
+    is_one_leg_short(&legs).then_some(bet.amount)
+}
+
+/// A prediction together with its match's result and locked odds
+fn settled_leg(prediction: &Prediction, round_accounting: &RoundAccounting) -> SettledLeg {
+    let match_index = prediction.match_index as usize;
+    let locked_odds = &round_accounting.locked_odds[match_index];
+    SettledLeg {
+        predicted_outcome: prediction.predicted_outcome,
+        result: round_accounting.match_results[match_index] as u8,
+        amount_in_pool: prediction.amount_in_pool,
+        odds: locked_odds.get_odds(prediction.predicted_outcome),
+        odds_locked: locked_odds.locked,
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -237,7 +1076,7 @@ fn calculate_bet_payout(
 mod tests {
     use super::*;
     use crate::state::{Bet, RoundAccounting, MatchPool, LockedOdds, MatchOutcome, Prediction};
-    use crate::constants::*;
+    use crate::clock::FixedClock;
     use anchor_lang::prelude::Pubkey;
 
     const SCALE: u64 = ODDS_SCALE;
@@ -269,7 +1108,20 @@ mod tests {
             claimed: false,
             claim_deadline: 0,
             bounty_claimer: None,
+            price_at_placement: Default::default(),
+            stake_usd: 0,
+            user_nonce: None,
             bump: 0,
+            potential_payout: 0,
+            fee_bps: 0,
+            promo_bonus: 0,
+            copy_leader: Pubkey::default(),
+            copy_fee_bps: 0,
+            season_points_accrued: false,
+            payout_dust: 0,
+            payout_reserved: false,
+            insured: false,
+            reserved: [0; 1],
         }
     }
 
@@ -311,7 +1163,31 @@ mod tests {
             round_start_time: 0,
             round_end_time: 0,
             settled: true,
+            total_volume_usd: 0,
+            settlement_price: Default::default(),
             bump: 0,
+            bets_placed: 0,
+            bets_resolved: 0,
+            locked_odds_hash: [0; 32],
+            claims_paused_until: 0,
+            claim_window_extension: 0,
+            locked_odds_root: [0; 32],
+            branded: false,
+            disputed: false,
+            league_round: false,
+            kickoff_locked: 0,
+            isolated: false,
+            vault_bump: 0,
+            total_refunded: 0,
+            protocol_funded_bounty: false,
+            protocol_bounties_paid: 0,
+            revenue_allocated: false,
+            payout_dust: 0,
+            claim_shards: 0,
+            open_accumulators: 0,
+            result_bits: 0,
+            total_reserved_unpaid: 0,
+            shard_payout_cap: 0,
         }
     }
 
@@ -500,6 +1376,49 @@ mod tests {
         assert!(!won, "One wrong in 10-leg parlay → full loss");
     }
 
+    #[test]
+    fn test_bitmap_early_exit_only_settles_lost_bets() {
+        use sportsbook_core::{leg_bitmap, results_bitmap};
+
+        let odds = default_odds(1_500_000_000, 2_000_000_000, 1_800_000_000);
+        let mut results = default_results();
+        results[0] = MatchOutcome::HomeWin;
+        results[1] = MatchOutcome::AwayWin;
+        results[2] = MatchOutcome::Void;
+        let mut round = make_round(results, odds);
+        round.result_bits = results_bitmap(&results.map(|result| result as u8));
+
+        // (legs, lost by the bitmap)
+        let parlays: [(&[(u8, u8)], bool); 5] = [
+            (&[(0, 1), (1, 2)], false),        // won
+            (&[(0, 1), (1, 1)], true),         // lost on match 1
+            (&[(0, 1), (2, 3)], false),        // won with a void leg
+            (&[(2, 1)], false),                // fully void
+            (&[(1, 1), (5, 3)], true),         // lost, with a pending leg
+        ];
+        for (legs, lost) in parlays {
+            let preds: Vec<_> = legs
+                .iter()
+                .map(|(match_index, outcome)| make_prediction(*match_index, *outcome, 100_000_000))
+                .collect();
+            let bet = make_bet(&preds, SCALE);
+            let record = BetRecord {
+                bet: Pubkey::default(),
+                terms_hash: [0; 32],
+                leg_bits: leg_bitmap(legs.iter().copied()),
+                bump: 0,
+            };
+
+            assert_eq!(is_lost_by_record(Some(&record), &round), lost, "{:?}", legs);
+            assert!(!is_lost_by_record(None, &round));
+            // Wherever the bitmap says lost, full pricing agrees
+            if lost {
+                assert!(!calculate_bet_payout(&bet, &round).unwrap().0, "{:?}", legs);
+                assert_eq!(voided_bet_refund(&bet, &round), None, "{:?}", legs);
+            }
+        }
+    }
+
     // ── odds range validation ─────────────────────────────────────────────────
 
     #[test]
@@ -532,6 +1451,24 @@ mod tests {
         assert_eq!(final_p, 2_200_000_000); // exactly 2.2x
     }
 
+    // ── quick claim eligibility ───────────────────────────────────────────────
+
+    #[test]
+    fn test_quick_claim_takes_single_uncopied_legs() {
+        let single = make_bet(&[make_prediction(0, 1, 1_000_000_000)], SCALE);
+        assert!(is_quick_claimable(&single));
+
+        let parlay = make_bet(
+            &[make_prediction(0, 1, 1_000_000_000), make_prediction(1, 2, 1_000_000_000)],
+            SCALE,
+        );
+        assert!(!is_quick_claimable(&parlay));
+
+        let mut copied = single;
+        copied.copy_leader = Pubkey::new_unique();
+        assert!(!is_quick_claimable(&copied));
+    }
+
     // ── team token odds boost ─────────────────────────────────────────────────
 
     #[test]
@@ -583,4 +1520,190 @@ mod tests {
         // MAX_PAYOUT_PER_BET = 100_000 tokens — cap should be applied
         assert_eq!(final_p, MAX_PAYOUT_PER_BET, "Payout must be capped at MAX_PAYOUT_PER_BET");
     }
+
+    // ── stored potential payout ──────────────────────────────────────────────
+
+    #[test]
+    fn test_stored_potential_payout_is_paid() {
+        let odds = default_odds(1_500_000_000, 2_000_000_000, 1_800_000_000);
+        let mut results = default_results();
+        results[0] = MatchOutcome::HomeWin;
+        let round = make_round(results, odds);
+
+        let mut bet = make_bet(&[make_prediction(0, 1, 100 * SCALE)], SCALE);
+        let (_, _, recomputed) = calculate_bet_payout(&bet, &round).unwrap();
+
+        bet.potential_payout = recomputed;
+        let (won, _, stored) = calculate_bet_payout(&bet, &round).unwrap();
+        assert!(won);
+        assert_eq!(stored, recomputed);
+
+        // A losing bet pays nothing whatever was stored
+        let mut lost_round = make_round(default_results(), odds);
+        lost_round.match_results[0] = MatchOutcome::AwayWin;
+        assert_eq!(calculate_bet_payout(&bet, &lost_round).unwrap(), (false, 0, 0));
+    }
+
+    // ── claim window ─────────────────────────────────────────────────────────
+
+    fn settled_round_at(round_end_time: i64) -> RoundAccounting {
+        let mut round = make_round(default_results(), default_odds(SCALE, SCALE, SCALE));
+        round.round_end_time = round_end_time;
+        round
+    }
+
+    #[test]
+    fn test_claim_window_bettor_only_before_deadline() {
+        let round = settled_round_at(1_000);
+        let deadline = 1_000 + CLAIM_WINDOW_SECONDS;
+
+        let window = ClaimWindow::evaluate(&FixedClock(deadline), &round, false).unwrap();
+        assert_eq!(window.claim_deadline, deadline);
+        assert!(!window.claimer_allowed);
+        assert!(!window.is_bounty_claim);
+
+        let window = ClaimWindow::evaluate(&FixedClock(deadline), &round, true).unwrap();
+        assert!(window.claimer_allowed);
+        assert!(!window.is_bounty_claim);
+    }
+
+    #[test]
+    fn test_claim_window_bounty_after_deadline() {
+        let round = settled_round_at(1_000);
+        let after = FixedClock(1_000 + CLAIM_WINDOW_SECONDS + 1);
+
+        let window = ClaimWindow::evaluate(&after, &round, false).unwrap();
+        assert!(window.claimer_allowed);
+        assert!(window.is_bounty_claim);
+
+        // The bettor never pays a bounty on their own claim
+        let window = ClaimWindow::evaluate(&after, &round, true).unwrap();
+        assert!(!window.is_bounty_claim);
+    }
+
+    #[test]
+    fn test_claim_window_pause_extends_deadline() {
+        let mut round = settled_round_at(1_000);
+        round.claims_paused_until = 5_000;
+        round.claim_window_extension = 3_600;
+        let deadline = 1_000 + CLAIM_WINDOW_SECONDS + 3_600;
+
+        let window = ClaimWindow::evaluate(&FixedClock(4_999), &round, true).unwrap();
+        assert!(window.paused);
+        assert_eq!(window.claim_deadline, deadline);
+
+        let window = ClaimWindow::evaluate(&FixedClock(deadline), &round, false).unwrap();
+        assert!(!window.paused);
+        assert!(!window.is_bounty_claim);
+    }
+
+    // ── voided matches ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_fully_voided_bet_refunds_original_stake() {
+        let odds = default_odds(1_500_000_000, 2_000_000_000, 1_800_000_000);
+        let mut results = default_results();
+        results[0] = MatchOutcome::Void;
+        results[1] = MatchOutcome::Void;
+        results[2] = MatchOutcome::HomeWin;
+        let round = make_round(results, odds);
+
+        // 1000 staked, 950 after fee, 1.2x parlay: the refund is the 1000
+        let mut bet = make_bet(
+            &[make_prediction(0, 1, 475 * SCALE), make_prediction(1, 2, 475 * SCALE)],
+            1_200_000_000,
+        );
+        bet.amount = 1000 * SCALE;
+        bet.amount_after_fee = 950 * SCALE;
+        bet.potential_payout = 2000 * SCALE;
+        assert_eq!(voided_bet_refund(&bet, &round), Some(1000 * SCALE));
+
+        // One leg still live: settled, void leg at 1.0 and the multiplier reapplied
+        bet.predictions[1].match_index = 2;
+        bet.predictions[1].predicted_outcome = 1;
+        assert_eq!(voided_bet_refund(&bet, &round), None);
+        let (won, base, payout) = calculate_bet_payout(&bet, &round).unwrap();
+        assert!(won);
+        assert_eq!(base, 475 * SCALE + 712_500_000_000);
+        assert_eq!(payout, base * 6 / 5);
+    }
+
+    #[test]
+    fn test_insured_parlay_refunded_when_one_leg_short() {
+        let odds = default_odds(1_500_000_000, 2_000_000_000, 1_800_000_000);
+        let mut results = default_results();
+        results[0] = MatchOutcome::HomeWin;
+        results[1] = MatchOutcome::Draw;
+        results[2] = MatchOutcome::Draw;
+        let round = make_round(results, odds);
+
+        // Second leg lost, the other two came in
+        let mut bet = make_bet(
+            &[
+                make_prediction(0, 1, 300 * SCALE),
+                make_prediction(1, 2, 300 * SCALE),
+                make_prediction(2, 3, 300 * SCALE),
+            ],
+            1_100_000_000,
+        );
+        bet.amount = 1000 * SCALE;
+        assert_eq!(insured_bet_refund(&bet, &round), None, "only insured bets");
+        bet.insured = true;
+        assert_eq!(insured_bet_refund(&bet, &round), Some(1000 * SCALE));
+
+        // Two legs lost: no cover
+        bet.predictions[2].predicted_outcome = 1;
+        assert_eq!(insured_bet_refund(&bet, &round), None);
+    }
+
+    // ── bounty funding ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_bounty_split_modes() {
+        assert_eq!(bounty_split(1000, false, false).unwrap(), (1000, 0));
+        assert_eq!(bounty_split(1000, false, true).unwrap(), (1000, 0));
+        // Cut from the winner, or paid on top by the protocol
+        assert_eq!(bounty_split(1000, true, false).unwrap(), (900, 100));
+        assert_eq!(bounty_split(1000, true, true).unwrap(), (1000, 100));
+    }
+
+    #[test]
+    fn test_reserved_payouts_stay_owed_until_paid() {
+        let mut round = make_round(default_results(), default_odds(ODDS_SCALE, ODDS_SCALE, ODDS_SCALE));
+        round.total_reserved_for_winners = 1_000;
+
+        // 400 reserved by reserve_payouts, 200 claimed directly
+        round.total_claimed = 600;
+        round.total_reserved_unpaid = 400;
+        assert_eq!(round.outstanding_liability(), 800);
+
+        // Finalization writes off unclaimed winnings, not reserved ones
+        round.revenue_distributed = true;
+        assert_eq!(round.outstanding_liability(), 400);
+
+        round.total_reserved_unpaid = 0;
+        assert_eq!(round.outstanding_liability(), 0);
+    }
+
+    // ── merkle settlement ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_bet_odds_proof() {
+        use crate::merkle::locked_odds_table;
+        use sportsbook_core::{odds_root, prove_legs};
+
+        let mut round = make_round(default_results(), default_odds(1_500_000_000, 2_000_000_000, 1_800_000_000));
+        round.locked_odds[7].draw_odds = 2_100_000_000;
+        let table = locked_odds_table(&round.locked_odds);
+        round.locked_odds_root = odds_root(&table);
+
+        let bet = make_bet(&[make_prediction(2, 1, SCALE), make_prediction(7, 3, SCALE)], SCALE);
+        let proof = prove_legs(&table, &[2, 7]);
+        assert!(verify_bet_odds_proof(&bet, &round, &proof).is_ok());
+
+        // Proof for other legs, or odds changed after the root was taken
+        assert!(verify_bet_odds_proof(&bet, &round, &prove_legs(&table, &[2, 8])).is_err());
+        round.locked_odds[7].draw_odds = 2_200_000_000;
+        assert!(verify_bet_odds_proof(&bet, &round, &proof).is_err());
+    }
 }
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, SeasonPolicy, SeasonRollover};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::events::SeasonClosed;
+
+/// Choose what happens to unclaimed season rewards and how long winners
+/// have to claim before that
+///
+/// If the current season has already ended without its end being recorded,
+/// the claim period starts now.
+#[derive(Accounts)]
+pub struct SetSeasonPolicy<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SeasonPolicy::LEN,
+        seeds = [SEASON_POLICY_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub season_policy: Account<'info, SeasonPolicy>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_season_policy_handler(
+    ctx: Context<SetSeasonPolicy>,
+    rollover: SeasonRollover,
+    claim_period_seconds: i64,
+) -> Result<()> {
+    require!(
+        (0..=MAX_SEASON_CLAIM_PERIOD_SECONDS).contains(&claim_period_seconds),
+        SportsbookError::InvalidSeasonClaimPeriod
+    );
+
+    let betting_pool = &ctx.accounts.betting_pool;
+    let season_policy = &mut ctx.accounts.season_policy;
+    season_policy.betting_pool = betting_pool.key();
+    season_policy.rollover = rollover;
+    season_policy.claim_period_seconds = claim_period_seconds;
+    season_policy.bump = ctx.bumps.season_policy;
+
+    let season_id = betting_pool.current_season_id;
+    if betting_pool.season_ended && season_policy.claims_close_at(season_id).is_none() {
+        let now = pool_clock(betting_pool).unix_timestamp()?;
+        season_policy.record_season_end(season_id, now);
+    }
+
+    msg!("Unclaimed season rewards: {:?} after {}s", rollover, claim_period_seconds);
+
+    Ok(())
+}
+
+/// Close the ended season: settle its unclaimed reward pool by the pool's
+/// SeasonPolicy, then start the next season
+///
+/// Waits out the policy's claim period. Rolled-over rewards stay in
+/// `season_reward_pool` for the next season; burned or returned rewards
+/// leave the pool token account and the reward pool starts from zero.
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEASON_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = season_policy.bump,
+    )]
+    pub season_policy: Account<'info, SeasonPolicy>,
+
+    /// Betting pool's token account, where the season reward pool is held
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = betting_pool.token_mint @ SportsbookError::InvalidTokenMint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// Optional: protocol treasury token account, needed when the policy
+    /// returns rewards to the treasury
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub protocol_treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn close_season_handler(ctx: Context<CloseSeason>) -> Result<()> {
+    let betting_pool = &ctx.accounts.betting_pool;
+    require!(betting_pool.season_ended, SportsbookError::SeasonNotEnded);
+
+    let season_id = betting_pool.current_season_id;
+    let claims_close_at = ctx
+        .accounts
+        .season_policy
+        .claims_close_at(season_id)
+        .ok_or(SportsbookError::SeasonEndNotRecorded)?;
+    let now = pool_clock(betting_pool).unix_timestamp()?;
+    require!(now >= claims_close_at, SportsbookError::SeasonClaimsOpen);
+
+    let unclaimed = betting_pool.season_reward_pool;
+    let rollover = ctx.accounts.season_policy.rollover;
+    let bump = betting_pool.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[BETTING_POOL_SEED, &[bump]]];
+
+    match rollover {
+        SeasonRollover::RollOver => {}
+        SeasonRollover::Burn if unclaimed > 0 => {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.betting_pool_token_account.to_account_info(),
+                authority: ctx.accounts.betting_pool.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::burn(cpi_ctx, unclaimed)?;
+        }
+        SeasonRollover::Treasury if unclaimed > 0 => {
+            let treasury = ctx
+                .accounts
+                .protocol_treasury_token_account
+                .as_ref()
+                .ok_or(SportsbookError::TreasuryAccountRequired)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.betting_pool_token_account.to_account_info(),
+                to: treasury.to_account_info(),
+                authority: ctx.accounts.betting_pool.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, unclaimed)?;
+        }
+        SeasonRollover::Burn | SeasonRollover::Treasury => {}
+    }
+
+    let season_policy = &mut ctx.accounts.season_policy;
+    let total = match rollover {
+        SeasonRollover::RollOver => &mut season_policy.total_rolled_over,
+        SeasonRollover::Burn => &mut season_policy.total_burned,
+        SeasonRollover::Treasury => &mut season_policy.total_returned,
+    };
+    *total = total
+        .checked_add(unclaimed)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    let betting_pool = &mut ctx.accounts.betting_pool;
+    if rollover != SeasonRollover::RollOver {
+        betting_pool.season_reward_pool = 0;
+    }
+    betting_pool.current_season_id = season_id
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    betting_pool.season_ended = false;
+    betting_pool.season_winning_team = 0;
+
+    emit!(SeasonClosed {
+        betting_pool: betting_pool.key(),
+        season_id,
+        rollover,
+        unclaimed,
+    });
+
+    msg!("Season {} closed: {} unclaimed, {:?}", season_id, unclaimed, rollover);
+    msg!("New season started: {}", betting_pool.current_season_id);
+
+    Ok(())
+}
@@ -0,0 +1,362 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{
+    BettingPool, RoundAccounting, Bet, BetRecord, OpenLiability, DailyVolume, ActiveBettors,
+    PendingClaims, UserVolume, CopyConfig, LegFeeSchedule, StakePolicy,
+};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::BetCopied;
+use super::place_bet::{execute_bet, BetPlacement, BetReceipt, CopyTerms};
+
+/// Authorize (or update) mirroring of a leader's bets
+///
+/// Copies are funded by token delegation: after this, the follower must
+/// `approve` the CopyConfig PDA as delegate on their token account for at
+/// least what they are willing to spend. Setting `budget_per_round` to 0
+/// pauses copying without closing the config.
+#[derive(Accounts)]
+#[instruction(leader: Pubkey)]
+pub struct SetCopyConfig<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = follower,
+        space = CopyConfig::LEN,
+        seeds = [COPY_CONFIG_SEED, betting_pool.key().as_ref(), leader.as_ref(), follower.key().as_ref()],
+        bump
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_copy_config_handler(
+    ctx: Context<SetCopyConfig>,
+    leader: Pubkey,
+    stake_bps: u16,
+    budget_per_round: u64,
+    success_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        leader != ctx.accounts.follower.key() && leader != Pubkey::default(),
+        SportsbookError::InvalidCopyConfig
+    );
+    require!(
+        stake_bps > 0 && stake_bps as u64 <= BPS_DENOMINATOR,
+        SportsbookError::InvalidCopyConfig
+    );
+    require!(
+        success_fee_bps <= MAX_COPY_SUCCESS_FEE_BPS,
+        SportsbookError::InvalidCopyConfig
+    );
+
+    let copy_config = &mut ctx.accounts.copy_config;
+    if copy_config.follower == Pubkey::default() {
+        copy_config.betting_pool = ctx.accounts.betting_pool.key();
+        copy_config.leader = leader;
+        copy_config.follower = ctx.accounts.follower.key();
+        copy_config.budget_round_id = 0;
+        copy_config.spent_this_round = 0;
+        copy_config.last_copied_bet_id = 0;
+        copy_config.has_copied = false;
+        copy_config.bump = ctx.bumps.copy_config;
+    }
+    copy_config.stake_bps = stake_bps;
+    copy_config.budget_per_round = budget_per_round;
+    copy_config.success_fee_bps = success_fee_bps;
+
+    msg!("Copying {} for {}", leader, copy_config.follower);
+    msg!("Stake: {}bps of leader, budget per round: {}", stake_bps, budget_per_round);
+    msg!("Success fee: {}bps", success_fee_bps);
+
+    Ok(())
+}
+
+/// Stop copying a leader and reclaim the config's rent
+#[derive(Accounts)]
+pub struct CloseCopyConfig<'info> {
+    #[account(
+        mut,
+        close = follower,
+        constraint = copy_config.follower == follower.key() @ SportsbookError::InvalidAuthority,
+    )]
+    pub copy_config: Account<'info, CopyConfig>,
+
+    #[account(mut)]
+    pub follower: Signer<'info>,
+}
+
+pub fn close_copy_config_handler(ctx: Context<CloseCopyConfig>) -> Result<()> {
+    msg!(
+        "Stopped copying {} for {}",
+        ctx.accounts.copy_config.leader,
+        ctx.accounts.follower.key()
+    );
+
+    Ok(())
+}
+
+/// Mirror one of the leader's bets for a follower
+///
+/// Permissionless crank; the cranker pays the new bet's rent. The copy has
+/// the same legs and the leader bet's locked multiplier (per-leg odds are
+/// the round's locked odds either way), its stake is `stake_bps` of the
+/// leader's, capped at what is left of the follower's round budget, and it
+/// belongs to the follower. Leader bets are copied in bet_id order; a bet
+/// skipped by the crank can't be copied afterwards.
+#[derive(Accounts)]
+pub struct CopyBet<'info> {
+    #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), leader_bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    /// Optional: the round's OpenLiability read model, updated when passed
+    #[account(
+        mut,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), leader_bet.round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Box<Account<'info, OpenLiability>>>,
+
+    /// Optional: the pool's DailyVolume read model, updated when passed
+    #[account(
+        mut,
+        seeds = [DAILY_VOLUME_SEED, betting_pool.key().as_ref()],
+        bump = daily_volume.bump,
+    )]
+    pub daily_volume: Option<Box<Account<'info, DailyVolume>>>,
+
+    /// Optional: the pool's ActiveBettors read model, updated when passed
+    #[account(
+        mut,
+        seeds = [ACTIVE_BETTORS_SEED, betting_pool.key().as_ref()],
+        bump = active_bettors.bump,
+    )]
+    pub active_bettors: Option<Box<Account<'info, ActiveBettors>>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Box<Account<'info, PendingClaims>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            COPY_CONFIG_SEED,
+            betting_pool.key().as_ref(),
+            copy_config.leader.as_ref(),
+            copy_config.follower.as_ref()
+        ],
+        bump = copy_config.bump,
+    )]
+    pub copy_config: Box<Account<'info, CopyConfig>>,
+
+    /// The leader's bet being mirrored (copies of copies are not allowed)
+    #[account(
+        constraint = leader_bet.is_at_address(&leader_bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = leader_bet.bettor == copy_config.leader @ SportsbookError::InvalidBetAccount,
+        constraint = leader_bet.copy_leader == Pubkey::default() @ SportsbookError::InvalidBetAccount,
+    )]
+    pub leader_bet: Box<Account<'info, Bet>>,
+
+    /// Follower's rolling stake volume for fee tiers
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = UserVolume::LEN,
+        seeds = [USER_VOLUME_SEED, betting_pool.key().as_ref(), copy_config.follower.as_ref()],
+        bump
+    )]
+    pub user_volume: Box<Account<'info, UserVolume>>,
+
+    /// The follower's bet, seeded with the pool's bet counter
+    #[account(
+        init,
+        payer = cranker,
+        space = Bet::LEN,
+        seeds = [
+            BET_SEED,
+            betting_pool.key().as_ref(),
+            betting_pool.next_bet_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// The follower bet's BetRecord, created here to keep its terms hash and leg bitmap
+    #[account(
+        init,
+        payer = cranker,
+        space = BetRecord::LEN,
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub bet_record: Box<Account<'info, BetRecord>>,
+
+    /// Follower's token account, with the CopyConfig PDA as delegate
+    #[account(
+        mut,
+        constraint = follower_token_account.owner == copy_config.follower @ SportsbookError::InvalidTokenAccount,
+        constraint = follower_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub follower_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Betting pool's token account (receives bet funds)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Required for isolated rounds: receives the stake instead
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), leader_bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Required when the pool charges fees by parlay length
+    #[account(
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump = leg_fee_schedule.bump,
+    )]
+    pub leg_fee_schedule: Option<Box<Account<'info, LegFeeSchedule>>>,
+
+    /// Required when the pool has a stake policy
+    #[account(
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = stake_policy.bump,
+    )]
+    pub stake_policy: Option<Box<Account<'info, StakePolicy>>>,
+
+    /// Protocol treasury token account (receives fees)
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub protocol_treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Optional: Pyth price feed, required when the pool is USD-valued
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn copy_bet_handler(ctx: Context<CopyBet>) -> Result<BetReceipt> {
+    let accounts = ctx.accounts;
+    let leader_bet = &accounts.leader_bet;
+    let round_id = leader_bet.round_id;
+
+    require!(
+        accounts.copy_config.can_copy(leader_bet.bet_id),
+        SportsbookError::BetAlreadyCopied
+    );
+
+    let desired = (leader_bet.amount as u128)
+        .checked_mul(accounts.copy_config.stake_bps as u128)
+        .ok_or(SportsbookError::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    let amount = desired.min(accounts.copy_config.remaining_budget(round_id));
+    require!(amount > 0, SportsbookError::CopyBudgetExhausted);
+
+    let (match_indices, outcomes): (Vec<u8>, Vec<u8>) = leader_bet
+        .get_predictions()
+        .iter()
+        .map(|prediction| (prediction.match_index, prediction.predicted_outcome))
+        .unzip();
+    let leader_bet_key = leader_bet.key();
+    let leader_bet_id = leader_bet.bet_id;
+    let terms = CopyTerms {
+        leader: accounts.copy_config.leader,
+        locked_multiplier: leader_bet.locked_multiplier,
+        success_fee_bps: accounts.copy_config.success_fee_bps,
+    };
+
+    let betting_pool_key = accounts.betting_pool.key();
+    let leader = accounts.copy_config.leader;
+    let follower = accounts.copy_config.follower;
+    let copy_config_bump = [accounts.copy_config.bump];
+    let copy_config_seeds: &[&[u8]] = &[
+        COPY_CONFIG_SEED,
+        betting_pool_key.as_ref(),
+        leader.as_ref(),
+        follower.as_ref(),
+        &copy_config_bump,
+    ];
+
+    let placement = BetPlacement {
+        betting_pool: accounts.betting_pool.as_mut(),
+        round_accounting: accounts.round_accounting.as_mut(),
+        open_liability: accounts.open_liability.as_deref_mut(),
+        daily_volume: accounts.daily_volume.as_deref_mut(),
+        active_bettors: accounts.active_bettors.as_deref_mut(),
+        pending_claims: accounts.pending_claims.as_deref_mut(),
+        user_volume: accounts.user_volume.as_mut(),
+        user_volume_bump: ctx.bumps.user_volume,
+        bet: accounts.bet.as_mut(),
+        bet_record: accounts.bet_record.as_mut(),
+        bettor_token_account: accounts.follower_token_account.as_ref(),
+        betting_pool_token_account: accounts.betting_pool_token_account.as_ref(),
+        round_vault: accounts.round_vault.as_deref(),
+        leg_fee_schedule: accounts.leg_fee_schedule.as_deref(),
+        stake_policy: accounts.stake_policy.as_deref(),
+        protocol_treasury_token_account: accounts.protocol_treasury_token_account.as_ref(),
+        team_token_account: None,
+        price_feed: accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        stake_authority: accounts.copy_config.to_account_info(),
+        stake_authority_seeds: Some(copy_config_seeds),
+        payer: follower,
+        beneficiary: follower,
+        token_program: &accounts.token_program,
+        bet_bump: ctx.bumps.bet,
+        bet_record_bump: ctx.bumps.bet_record,
+        user_nonce: None,
+        copy_terms: Some(terms),
+    };
+
+    let receipt = execute_bet(placement, round_id, match_indices, outcomes, amount)?;
+
+    accounts.copy_config.record_copy(round_id, leader_bet_id, amount);
+
+    emit!(BetCopied {
+        bet: receipt.bet,
+        leader_bet: leader_bet_key,
+        leader,
+        follower,
+        amount,
+    });
+
+    msg!("Copied leader bet {} for {}", leader_bet_id, follower);
+    msg!(
+        "Round {} copy spend: {}/{}",
+        round_id,
+        accounts.copy_config.spent_this_round,
+        accounts.copy_config.budget_per_round
+    );
+
+    Ok(receipt)
+}
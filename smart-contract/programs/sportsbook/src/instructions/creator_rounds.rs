@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, OpenLiability, Creator, BrandedRound};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use super::initialize_round::init_round;
+
+/// Whitelist a creator (or update / deactivate one)
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetCreator<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Creator::LEN,
+        seeds = [CREATOR_SEED, betting_pool.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub creator: Account<'info, Creator>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_creator_handler(
+    ctx: Context<SetCreator>,
+    wallet: Pubkey,
+    revenue_share_bps: u16,
+    active: bool,
+) -> Result<()> {
+    require!(
+        revenue_share_bps <= MAX_CREATOR_REVENUE_SHARE_BPS,
+        SportsbookError::InvalidCreatorShare
+    );
+
+    let creator = &mut ctx.accounts.creator;
+    if creator.wallet == Pubkey::default() {
+        creator.betting_pool = ctx.accounts.betting_pool.key();
+        creator.wallet = wallet;
+        creator.rounds_created = 0;
+        creator.bump = ctx.bumps.creator;
+    }
+    creator.revenue_share_bps = revenue_share_bps;
+    creator.active = active;
+
+    msg!("Creator {}: {}bps revenue share, active: {}", wallet, revenue_share_bps, active);
+
+    Ok(())
+}
+
+/// Start the pool's next round under a creator's brand
+///
+/// Same as initialize_round, but signed and paid for by an active
+/// whitelisted creator. The round runs on the shared bankroll (the operator
+/// still seeds it); at finalize_revenue the creator receives
+/// `revenue_share_bps` of the round's protocol revenue.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct InitializeBrandedRound<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_SEED, betting_pool.key().as_ref(), creator_wallet.key().as_ref()],
+        bump = creator.bump,
+        constraint = creator.active @ SportsbookError::CreatorNotActive,
+    )]
+    pub creator: Account<'info, Creator>,
+
+    #[account(
+        init,
+        payer = creator_wallet,
+        space = RoundAccounting::LEN,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    #[account(
+        init,
+        payer = creator_wallet,
+        space = OpenLiability::LEN,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_liability: Box<Account<'info, OpenLiability>>,
+
+    #[account(
+        init,
+        payer = creator_wallet,
+        space = BrandedRound::LEN,
+        seeds = [BRANDED_ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub branded_round: Account<'info, BrandedRound>,
+
+    #[account(mut)]
+    pub creator_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_branded_round_handler(
+    ctx: Context<InitializeBrandedRound>,
+    round_id: u64,
+    brand: [u8; 32],
+) -> Result<()> {
+    init_round(
+        &mut ctx.accounts.betting_pool,
+        &mut ctx.accounts.round_accounting,
+        &mut ctx.accounts.open_liability,
+        round_id,
+        ctx.bumps.round_accounting,
+        ctx.bumps.open_liability,
+    )?;
+    ctx.accounts.round_accounting.branded = true;
+
+    let creator = &mut ctx.accounts.creator;
+    creator.rounds_created = creator.rounds_created.saturating_add(1);
+
+    let branded_round = &mut ctx.accounts.branded_round;
+    branded_round.betting_pool = ctx.accounts.betting_pool.key();
+    branded_round.round_id = round_id;
+    branded_round.creator = creator.wallet;
+    branded_round.brand = brand;
+    branded_round.revenue_share_bps = creator.revenue_share_bps;
+    branded_round.creator_revenue = 0;
+    branded_round.bump = ctx.bumps.branded_round;
+
+    msg!("Round {} initialized for creator {}", round_id, creator.wallet);
+    msg!("Creator revenue share: {}bps", creator.revenue_share_bps);
+
+    Ok(())
+}
@@ -1,12 +1,23 @@
 use anchor_lang::prelude::*;
-use crate::state::BettingPool;
+use crate::state::{BettingPool, SeasonPolicy};
 use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
 
 #[derive(Accounts)]
 pub struct EndSeason<'info> {
     #[account(mut)]
     pub betting_pool: Account<'info, BettingPool>,
 
+    /// Optional: the pool's season policy, to start the reward claim period
+    /// close_season waits out
+    #[account(
+        mut,
+        seeds = [SEASON_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = season_policy.bump,
+    )]
+    pub season_policy: Option<Account<'info, SeasonPolicy>>,
+
     #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
     pub authority: Signer<'info>,
 }
@@ -31,6 +42,11 @@ pub fn handler(
     ctx.accounts.betting_pool.season_ended = true;
     ctx.accounts.betting_pool.season_winning_team = winning_team;
 
+    if let Some(season_policy) = ctx.accounts.season_policy.as_mut() {
+        let now = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+        season_policy.record_season_end(ctx.accounts.betting_pool.current_season_id, now);
+    }
+
     msg!("Season {} ended!", ctx.accounts.betting_pool.current_season_id);
     msg!("Winning team: {}", winning_team);
     msg!("Season reward pool: {}", ctx.accounts.betting_pool.season_reward_pool);
@@ -60,7 +76,8 @@ pub fn start_new_season_handler(
     ctx.accounts.betting_pool.current_season_id += 1;
     ctx.accounts.betting_pool.season_ended = false;
     ctx.accounts.betting_pool.season_winning_team = 0;
-    // Note: season_reward_pool carries over to new season
+    // Note: season_reward_pool carries over to new season; close_season
+    // applies the pool's SeasonPolicy instead
 
     msg!("New season started: {}", ctx.accounts.betting_pool.current_season_id);
 
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::state::{BettingPool, RoundAccounting, ResultsExport, hash_round_result};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::RoundResultsExported;
+use sportsbook_core::RoundResultsPayload;
+
+/// Package a settled round's results for a mirror deployment on another chain
+///
+/// Permissionless; the payer covers rent. The message account holds the
+/// payload exactly as the bridge emitter posts it. Disputed rounds can't be
+/// exported, since their results may still change.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ExportRoundResults<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = !round_accounting.disputed @ SportsbookError::RoundDisputed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ResultsExport::LEN,
+        seeds = [RESULTS_EXPORT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub results_export: Account<'info, ResultsExport>,
+
+    /// CHECK: PDA that posts bridge messages; holds nothing
+    #[account(seeds = [BRIDGE_EMITTER_SEED], bump)]
+    pub bridge_emitter: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExportRoundResults>, round_id: u64) -> Result<()> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let payload = RoundResultsPayload {
+        program_id: crate::ID.to_bytes(),
+        betting_pool: round_accounting.betting_pool.to_bytes(),
+        round_id,
+        match_results: round_accounting.match_results.map(|result| result as u8),
+        round_end_time: round_accounting.round_end_time,
+        results_hash: hash_round_result(round_accounting),
+    }
+    .encode();
+    let payload_digest = keccak::hash(&payload).to_bytes();
+
+    let results_export = &mut ctx.accounts.results_export;
+    results_export.betting_pool = ctx.accounts.betting_pool.key();
+    results_export.round_id = round_id;
+    results_export.emitter = ctx.accounts.bridge_emitter.key();
+    results_export.nonce = round_id as u32;
+    results_export.consistency_level = BRIDGE_CONSISTENCY_LEVEL;
+    results_export.payload = payload;
+    results_export.payload_digest = payload_digest;
+    results_export.exported_at = Clock::get()?.unix_timestamp;
+    results_export.bump = ctx.bumps.results_export;
+
+    // NOTE: Posting to the Wormhole core bridge needs the wormhole-anchor-sdk
+    // crate; until it's wired in, a relayer posts `payload` from this account
+    // with the emitter's signature. With the SDK this becomes:
+    // ```
+    // wormhole::post_message(
+    //     CpiContext::new_with_signer(wormhole_program, PostMessage { .. }, &[emitter_seeds]),
+    //     results_export.nonce,
+    //     payload.to_vec(),
+    //     wormhole::Finality::Finalized,
+    // )?;
+    // ```
+
+    emit!(RoundResultsExported {
+        betting_pool: results_export.betting_pool,
+        round_id,
+        results_export: results_export.key(),
+        payload_digest,
+    });
+
+    msg!("Round {} results exported for bridging", round_id);
+
+    Ok(())
+}
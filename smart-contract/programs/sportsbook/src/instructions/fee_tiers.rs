@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, FeeTier, FEE_TIER_COUNT};
+use crate::errors::SportsbookError;
+
+/// Configure volume-based protocol fee tiers
+///
+/// Each tier applies to bettors whose stake over the last 30 days reaches
+/// its `min_volume`; unused slots have `min_volume` 0. Tiers are discounts,
+/// so each must charge no more than the one below it, starting from
+/// `protocol_fee_bps`.
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeTiers>, fee_tiers: [FeeTier; FEE_TIER_COUNT]) -> Result<()> {
+    let betting_pool = &mut ctx.accounts.betting_pool;
+
+    let mut active: Vec<FeeTier> = fee_tiers
+        .iter()
+        .copied()
+        .filter(|tier| tier.min_volume > 0)
+        .collect();
+    active.sort_by_key(|tier| tier.min_volume);
+
+    let mut previous_fee_bps = betting_pool.protocol_fee_bps;
+    let mut previous_min_volume = 0u64;
+    for tier in &active {
+        require!(
+            tier.min_volume > previous_min_volume && tier.fee_bps <= previous_fee_bps,
+            SportsbookError::InvalidFeeTiers
+        );
+        previous_min_volume = tier.min_volume;
+        previous_fee_bps = tier.fee_bps;
+    }
+
+    betting_pool.fee_tiers = fee_tiers;
+
+    msg!("Base fee: {}bps", betting_pool.protocol_fee_bps);
+    for tier in &active {
+        msg!("From volume {}: {}bps", tier.min_volume, tier.fee_bps);
+    }
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{BettingPool, RoundAccounting};
+use crate::state::{
+    BettingPool, RoundAccounting, BrandedRound, LotteryConfig, AccessControl, Role, has_role, PnLedger, PnlEntry,
+};
 use crate::errors::SportsbookError;
 use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use sportsbook_core::{calculate_creator_share, calculate_revenue_split, RevenueSplit};
+use super::round_vault::round_funds;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
@@ -12,7 +17,7 @@ pub struct FinalizeRoundRevenue<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
         constraint = !round_accounting.revenue_distributed @ SportsbookError::RevenueAlreadyDistributed,
@@ -20,24 +25,79 @@ pub struct FinalizeRoundRevenue<'info> {
     pub round_accounting: Account<'info, RoundAccounting>,
 
     /// Betting pool's token account (protocol holds all funds)
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
     pub betting_pool_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut, constraint = authority.key() == betting_pool.authority)]
+    /// Required for isolated rounds: its balance is swept back into the
+    /// pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Required for branded rounds: the round's creator terms
+    #[account(
+        mut,
+        seeds = [BRANDED_ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = branded_round.bump,
+    )]
+    pub branded_round: Option<Account<'info, BrandedRound>>,
+
+    /// Required for branded rounds: receives the creator's revenue share
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Lottery settings; when passed, the lottery takes its share of revenue
+    #[account(
+        mut,
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump = lottery_config.bump,
+    )]
+    pub lottery_config: Option<Account<'info, LotteryConfig>>,
+
+    /// Optional: the pool's roles, so a treasurer other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    /// Optional: the pool's PnL ledger, which records the round once its
+    /// revenue is allocated
+    #[account(
+        mut,
+        seeds = [PN_LEDGER_SEED, betting_pool.key().as_ref()],
+        bump = pn_ledger.bump,
+    )]
+    pub pn_ledger: Option<Box<Account<'info, PnLedger>>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::Treasurer)
+            @ SportsbookError::InvalidAuthority,
+    )]
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<FinalizeRoundRevenue>, round_id: u64) -> Result<()> {
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+pub fn handler(mut ctx: Context<FinalizeRoundRevenue>, round_id: u64) -> Result<()> {
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
 
     // IMPORTANT: With multi-match parlays, we CANNOT calculate total_reserved_for_winners
     // without iterating through all bets (which defeats the purpose of O(10) accounting).
     //
     // Instead, we use time-based finalization:
-    // - Winners have 24 hours to claim (or lose to bounty hunters)
+    // - Winners have 24 hours to claim (or lose to bounty hunters),
+    //   extended by however long claims were paused
     // - After 24h + buffer (e.g., 1 hour), protocol can finalize revenue
     // - Any unclaimed winnings after this deadline become protocol profit
     //
@@ -46,23 +106,67 @@ pub fn handler(ctx: Context<FinalizeRoundRevenue>, round_id: u64) -> Result<()>
     // 2. Bounty hunters have incentive to claim for winners (get 10%)
     // 3. After 25 hours, extremely unlikely any unclaimed winners remain
 
-    let claim_deadline = ctx.accounts.round_accounting.round_end_time + 86400; // 24 hours
+    let claim_deadline = ctx.accounts.round_accounting.claim_deadline();
     let finalize_buffer = 3600; // 1 hour buffer after claim deadline
     let earliest_finalize_time = claim_deadline + finalize_buffer;
 
+    require!(
+        !ctx.accounts.round_accounting.claims_blocked(current_time),
+        SportsbookError::ClaimsPaused
+    );
     require!(
         current_time >= earliest_finalize_time,
         SportsbookError::RevenueDistributedBeforeClaims
     );
+    require!(
+        ctx.accounts.round_accounting.claim_shards == 0,
+        SportsbookError::ClaimShardsOpen
+    );
+    require!(
+        ctx.accounts.round_accounting.open_accumulators == 0,
+        SportsbookError::AccumulatorsOpen
+    );
 
+    // Allocation only ever runs once: a retry after the transfer phase
+    // failed finds the shares already recorded and goes straight to it
+    if !ctx.accounts.round_accounting.revenue_allocated {
+        allocate_revenue(&mut ctx, current_time)?;
+    }
+    transfer_revenue(&ctx, round_id)?;
+
+    ctx.accounts.round_accounting.revenue_distributed = true;
+
+    msg!("Round {} revenue finalized", round_id);
+    msg!("Protocol seed: {} (stays in pool)", ctx.accounts.round_accounting.protocol_seed_amount);
+    msg!("Season share: {}", ctx.accounts.round_accounting.season_revenue_share);
+    msg!("Protocol revenue: {}", ctx.accounts.round_accounting.protocol_revenue_share);
+
+    Ok(())
+}
+
+/// Compute/allocate phase: split the round's result and credit the season
+/// pool, creator and lottery shares in account state, without moving tokens
+fn allocate_revenue(ctx: &mut Context<FinalizeRoundRevenue>, current_time: i64) -> Result<()> {
     // Extract season pool share
     let season_pool_share_bps = ctx.accounts.betting_pool.season_pool_share_bps;
 
-    // Check actual balance remaining in betting pool
-    let remaining_in_contract = ctx.accounts.betting_pool_token_account.amount;
-    let protocol_seed = ctx.accounts.round_accounting.protocol_seed_amount;
+    // Check actual balance remaining in betting pool (or the round's vault)
+    let remaining_in_contract = round_funds(
+        ctx.accounts.round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?
+    .amount;
     let user_deposits = ctx.accounts.round_accounting.total_user_deposits;
-    let total_paid = ctx.accounts.round_accounting.total_paid_out;
+    // Voided bets got their stake and fee back, and protocol-funded
+    // bounties were paid on top of winnings; that money left the round like
+    // a payout, so it comes off the operating profit too
+    let total_paid = ctx
+        .accounts
+        .round_accounting
+        .total_paid_out
+        .saturating_add(ctx.accounts.round_accounting.total_refunded)
+        .saturating_add(ctx.accounts.round_accounting.protocol_bounties_paid);
 
     // CORRECT ACCOUNTING:
     // Operating profit/loss = user_deposits - total_paid (can be negative!)
@@ -71,55 +175,158 @@ pub fn handler(ctx: Context<FinalizeRoundRevenue>, round_id: u64) -> Result<()>
     //
     // If operating_profit is negative, protocol loses from seed capital
 
-    let mut season_share = 0u64;
-    let mut operating_profit = 0i64; // Can be negative!
+    // Season pool gets exactly 2% of ACTUAL USER DEPOSITS (before fee);
+    // a negative operating profit shows as 0 revenue (seed capital covered it)
+    let RevenueSplit { season_share, protocol_revenue } = calculate_revenue_split(
+        user_deposits,
+        ctx.accounts.round_accounting.protocol_fee_collected,
+        total_paid,
+        season_pool_share_bps,
+        remaining_in_contract,
+    )
+    .map_err(SportsbookError::from)?;
+
+    // Rounding dust kept back from payouts is swept into the season pool
+    // rather than left in the operating profit
+    let dust = ctx
+        .accounts
+        .round_accounting
+        .payout_dust
+        .min(remaining_in_contract - season_share);
+    let season_share = season_share + dust;
+    let protocol_revenue = protocol_revenue.saturating_sub(dust);
 
-    if user_deposits > 0 {
-        // Season pool gets exactly 2% of ACTUAL USER DEPOSITS (before fee)
-        let total_user_bets_before_fee = user_deposits
-            .saturating_add(ctx.accounts.round_accounting.protocol_fee_collected);
+    // Allocate season pool share (stays in betting pool for season rewards)
+    if season_share > 0 {
+        ctx.accounts.betting_pool.season_reward_pool += season_share;
+    }
+
+    // Branded rounds route part of the profit to their creator; paid in
+    // the transfer phase
+    let mut creator_share = 0u64;
+    if ctx.accounts.round_accounting.branded {
+        let branded_round = ctx
+            .accounts
+            .branded_round
+            .as_mut()
+            .ok_or(SportsbookError::BrandedRoundAccountsMissing)?;
 
-        season_share = (total_user_bets_before_fee as u128)
-            .checked_mul(season_pool_share_bps as u128)
+        creator_share = calculate_creator_share(protocol_revenue, branded_round.revenue_share_bps)
+            .map_err(SportsbookError::from)?;
+        branded_round.creator_revenue = creator_share;
+    }
+
+    // Lottery prizes come out of what's left for the protocol; the tokens
+    // stay in the pool account until a winner claims
+    let mut lottery_share = 0u64;
+    if let Some(lottery_config) = ctx.accounts.lottery_config.as_mut() {
+        lottery_share = ((protocol_revenue - creator_share) as u128)
+            .checked_mul(lottery_config.share_bps as u128)
             .ok_or(SportsbookError::CalculationOverflow)?
             .checked_div(BPS_DENOMINATOR as u128)
             .ok_or(SportsbookError::CalculationOverflow)? as u64;
+        lottery_config.reserve = lottery_config.reserve.saturating_add(lottery_share);
+        msg!("Lottery share: {}", lottery_share);
+    }
 
-        // Cap season share to what's actually available
-        if season_share > remaining_in_contract {
-            season_share = remaining_in_contract;
-        }
+    let operating_profit = user_deposits as i64 - total_paid as i64;
 
-        // Allocate season pool share (stays in betting pool for season rewards)
-        if season_share > 0 {
-            ctx.accounts.betting_pool.season_reward_pool += season_share;
-        }
-    }
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    round_accounting.protocol_revenue_share = protocol_revenue - creator_share - lottery_share;
+    round_accounting.season_revenue_share = season_share;
+    round_accounting.revenue_allocated = true;
 
-    // Calculate operating profit (EXCLUDING seed capital)
-    // This can be negative if protocol paid out more than users deposited
-    operating_profit = user_deposits as i64 - total_paid as i64;
-
-    // Store profit (note: if negative, this represents a loss)
-    // For u64 storage, we'll store the absolute value and track sign separately
-    // or accept that negative profits show as 0
-    let protocol_revenue = if operating_profit > 0 {
-        operating_profit as u64
-    } else {
-        0 // Loss - protocol used seed capital
-    };
-
-    ctx.accounts.round_accounting.protocol_revenue_share = protocol_revenue;
-    ctx.accounts.round_accounting.season_revenue_share = season_share;
-    ctx.accounts.round_accounting.revenue_distributed = true;
+    if let Some(pn_ledger) = ctx.accounts.pn_ledger.as_mut() {
+        pn_ledger.record(PnlEntry {
+            round_id: round_accounting.round_id,
+            volume: user_deposits.saturating_add(round_accounting.protocol_fee_collected),
+            payouts: total_paid,
+            fees: round_accounting.protocol_fee_collected,
+            profit: operating_profit,
+            season_share,
+            protocol_revenue: round_accounting.protocol_revenue_share,
+            finalized_at: current_time,
+        });
+    }
 
-    msg!("Round {} revenue finalized", round_id);
-    msg!("Protocol seed: {} (stays in pool)", protocol_seed);
     msg!("User deposits: {}", user_deposits);
     msg!("Total paid: {}", total_paid);
     msg!("Operating profit: {} (negative = loss from seed)", operating_profit);
     msg!("Remaining balance: {}", remaining_in_contract);
-    msg!("Season share: {}", season_share);
+
+    Ok(())
+}
+
+/// Transfer phase: move the tokens the allocation recorded. Safe to rerun;
+/// it only reads the allocated amounts and never changes them
+fn transfer_revenue(ctx: &Context<FinalizeRoundRevenue>, round_id: u64) -> Result<()> {
+    // Isolated round: return what's left in its vault to the pool, where
+    // the season share and protocol revenue are held from here on
+    if ctx.accounts.round_accounting.isolated {
+        let round_vault = ctx
+            .accounts
+            .round_vault
+            .as_ref()
+            .ok_or(SportsbookError::RoundVaultRequired)?;
+        let remaining_in_vault = round_vault.amount;
+
+        if remaining_in_vault > 0 {
+            let betting_pool_key = ctx.accounts.betting_pool.key();
+            let round_id_bytes = round_id.to_le_bytes();
+            let seeds = &[
+                ROUND_SEED,
+                betting_pool_key.as_ref(),
+                round_id_bytes.as_ref(),
+                &[ctx.accounts.round_accounting.bump],
+            ];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: round_vault.to_account_info(),
+                to: ctx.accounts.betting_pool_token_account.to_account_info(),
+                authority: ctx.accounts.round_accounting.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, remaining_in_vault)?;
+
+            msg!("Round vault swept: {} returned to the pool", remaining_in_vault);
+        }
+    }
+
+    if ctx.accounts.round_accounting.branded {
+        let branded_round = ctx
+            .accounts
+            .branded_round
+            .as_ref()
+            .ok_or(SportsbookError::BrandedRoundAccountsMissing)?;
+        let creator_token_account = ctx
+            .accounts
+            .creator_token_account
+            .as_ref()
+            .ok_or(SportsbookError::BrandedRoundAccountsMissing)?;
+        require!(
+            creator_token_account.owner == branded_round.creator,
+            SportsbookError::InvalidTokenAccount
+        );
+
+        let creator_share = branded_round.creator_revenue;
+        if creator_share > 0 {
+            let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+            let signer = &[&seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.betting_pool_token_account.to_account_info(),
+                to: creator_token_account.to_account_info(),
+                authority: ctx.accounts.betting_pool.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, creator_share)?;
+        }
+
+        msg!("Creator {} revenue share: {}", branded_round.creator, creator_share);
+    }
 
     Ok(())
 }
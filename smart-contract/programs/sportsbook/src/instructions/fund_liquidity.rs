@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, LiquidityPool, AccessControl, Role, has_role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Move protocol seed capital into the LP pool, which funds round seeding
+///
+/// Tokens go to the LP pool PDA's associated token account, the one
+/// seed_round_pools draws from; the shares issued stay with the protocol.
+#[derive(Accounts)]
+pub struct FundLiquidityPool<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POOL_SEED, betting_pool.key().as_ref()],
+        bump = liquidity_pool.bump,
+    )]
+    pub liquidity_pool: Account<'info, LiquidityPool>,
+
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = liquidity_pool,
+    )]
+    pub lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenMint,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    /// Optional: the pool's roles, so a treasurer other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::Treasurer)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FundLiquidityPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, SportsbookError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.authority_token_account.to_account_info(),
+        to: ctx.accounts.lp_token_account.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    let shares = ctx.accounts.liquidity_pool.add_liquidity(amount)?;
+
+    msg!("LP pool funded with {} ({} shares)", amount, shares);
+    msg!("Available liquidity: {}", ctx.accounts.liquidity_pool.available_liquidity);
+
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, AccessControl, Role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::events::ClaimDeadlineExtended;
+
+/// Appoint (or remove) the guardian
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_guardian_handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    ctx.accounts.betting_pool.guardian = guardian;
+
+    msg!("Guardian set to {}", guardian);
+
+    Ok(())
+}
+
+/// Pause or resume claims on one round
+///
+/// Only the guardian can pause. Either the guardian or the authority can
+/// resume early; otherwise the pause lapses on its own. Paused time is
+/// added to the round's claim window, and the total is capped at
+/// MAX_CLAIMS_PAUSE_SECONDS so funds can't be held indefinitely.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SetRoundClaimsPause<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = !round_accounting.revenue_distributed @ SportsbookError::RevenueAlreadyDistributed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the pool's roles, so the pauser can pause and resume
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    pub signer: Signer<'info>,
+}
+
+pub fn pause_handler(ctx: Context<SetRoundClaimsPause>, round_id: u64, duration: i64) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let guardian = ctx.accounts.betting_pool.guardian;
+    require!(
+        (guardian != Pubkey::default() && signer == guardian)
+            || is_pauser(&ctx.accounts.access_control, &signer),
+        SportsbookError::InvalidGuardian
+    );
+    require!(duration > 0, SportsbookError::InvalidAmount);
+
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    let round_accounting = &mut ctx.accounts.round_accounting;
+
+    // Only the part that goes beyond an existing pause is new paused time
+    let paused_from = current_time.max(round_accounting.claims_paused_until);
+    let paused_until = current_time
+        .checked_add(duration)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    let added = paused_until.saturating_sub(paused_from);
+
+    let extension = round_accounting
+        .claim_window_extension
+        .checked_add(added)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    require!(
+        extension <= MAX_CLAIMS_PAUSE_SECONDS,
+        SportsbookError::PauseTooLong
+    );
+
+    round_accounting.claims_paused_until = round_accounting.claims_paused_until.max(paused_until);
+    round_accounting.claim_window_extension = extension;
+
+    msg!("Round {} claims paused until {}", round_id, round_accounting.claims_paused_until);
+    msg!("Claim deadline extended to {}", round_accounting.claim_deadline());
+
+    Ok(())
+}
+
+pub fn unpause_handler(ctx: Context<SetRoundClaimsPause>, round_id: u64) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    require!(
+        signer == ctx.accounts.betting_pool.authority
+            || (ctx.accounts.betting_pool.guardian != Pubkey::default()
+                && signer == ctx.accounts.betting_pool.guardian)
+            || is_pauser(&ctx.accounts.access_control, &signer),
+        SportsbookError::InvalidGuardian
+    );
+
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    require!(
+        round_accounting.claims_paused(current_time),
+        SportsbookError::ClaimsNotPaused
+    );
+
+    // Give back the pause time that won't be used
+    let unused = round_accounting.claims_paused_until - current_time;
+    round_accounting.claim_window_extension = round_accounting
+        .claim_window_extension
+        .saturating_sub(unused)
+        .max(0);
+    round_accounting.claims_paused_until = current_time;
+
+    msg!("Round {} claims resumed", round_id);
+    msg!("Claim deadline: {}", round_accounting.claim_deadline());
+
+    Ok(())
+}
+
+/// Push a round's claim deadline back after an RPC or cluster outage
+/// (authority only)
+///
+/// Bettors who couldn't reach the chain keep their bettor-only window, so
+/// bounty hunters don't collect on winnings the outage held up. Each call
+/// adds at most MAX_CLAIM_DEADLINE_EXTENSION_SECONDS, and the extension
+/// shares the MAX_CLAIMS_PAUSE_SECONDS total with guardian pauses.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ExtendClaimDeadline<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = !round_accounting.revenue_distributed @ SportsbookError::RevenueAlreadyDistributed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn extend_claim_deadline_handler(
+    ctx: Context<ExtendClaimDeadline>,
+    round_id: u64,
+    seconds: i64,
+) -> Result<()> {
+    require!(
+        (1..=MAX_CLAIM_DEADLINE_EXTENSION_SECONDS).contains(&seconds),
+        SportsbookError::InvalidClaimExtension
+    );
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    let previous_deadline = round_accounting.claim_deadline();
+    let extension = round_accounting
+        .claim_window_extension
+        .checked_add(seconds)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    require!(
+        extension <= MAX_CLAIMS_PAUSE_SECONDS,
+        SportsbookError::InvalidClaimExtension
+    );
+    round_accounting.claim_window_extension = extension;
+
+    emit!(ClaimDeadlineExtended {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        round_id,
+        previous_deadline,
+        claim_deadline: round_accounting.claim_deadline(),
+    });
+
+    msg!("Round {} claim deadline extended by {}s", round_id, seconds);
+    msg!("Claim deadline: {}", round_accounting.claim_deadline());
+
+    Ok(())
+}
+
+/// Lift a randomness dispute raised by verify_round_randomness (guardian only)
+///
+/// Claims stay held while a round is disputed, so the claim window doesn't
+/// stop running; a guardian pause can be added to give the time back.
+pub fn resolve_dispute_handler(ctx: Context<SetRoundClaimsPause>, round_id: u64) -> Result<()> {
+    let guardian = ctx.accounts.betting_pool.guardian;
+    require!(
+        guardian != Pubkey::default() && ctx.accounts.signer.key() == guardian,
+        SportsbookError::InvalidGuardian
+    );
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    require!(round_accounting.disputed, SportsbookError::RoundNotDisputed);
+    round_accounting.disputed = false;
+
+    msg!("Round {} randomness dispute resolved", round_id);
+
+    Ok(())
+}
+
+/// Whether `signer` holds the pauser role in the passed AccessControl
+fn is_pauser(access_control: &Option<Account<AccessControl>>, signer: &Pubkey) -> bool {
+    access_control
+        .as_ref()
+        .is_some_and(|access_control| access_control.holds(Role::Pauser, signer))
+}
@@ -0,0 +1,643 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, TokenAccount};
+use crate::state::{BettingPool, AccessControl, Role, has_role, LendingProposal, ReconcileCursor, OutflowAllowlist};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::LendingLossRecognized;
+use super::reconcile_pool::{lottery_funds_held, tally_unclosed_rounds, FIRST_ROUND_ID};
+
+/// Change the lending program (Solend or Kamino) idle liquidity may go to,
+/// or the share of liquidity it may take
+///
+/// Disabling lending (`Pubkey::default()`) or lowering the share on the
+/// current program applies at once. Anything else is queued in the pool's
+/// LendingProposal for LENDING_PROGRAM_TIMELOCK_SECONDS and made live by
+/// apply_lending_program. Each call replaces whatever was queued.
+#[derive(Accounts)]
+pub struct SetLendingProgram<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LendingProposal::LEN,
+        seeds = [LENDING_PROPOSAL_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub lending_proposal: Account<'info, LendingProposal>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_lending_program_handler(
+    ctx: Context<SetLendingProgram>,
+    lending_program: Pubkey,
+    max_lending_bps: u16,
+) -> Result<()> {
+    require!(
+        max_lending_bps <= MAX_LENDING_DEPLOY_BPS,
+        SportsbookError::LendingLimitExceeded
+    );
+    require!(
+        lending_program == Pubkey::default() || is_known_lending_program(&lending_program),
+        SportsbookError::LendingProgramNotWhitelisted
+    );
+
+    let betting_pool = &mut ctx.accounts.betting_pool;
+    let lending_proposal = &mut ctx.accounts.lending_proposal;
+    lending_proposal.betting_pool = betting_pool.key();
+    lending_proposal.bump = ctx.bumps.lending_proposal;
+
+    let tightening = lending_program == Pubkey::default()
+        || (lending_program == betting_pool.lending_program && max_lending_bps <= betting_pool.max_lending_bps);
+    if tightening {
+        if lending_program != betting_pool.lending_program {
+            require!(
+                betting_pool.lending_deployed == 0,
+                SportsbookError::LiquidityStillDeployed
+            );
+        }
+        betting_pool.lending_program = lending_program;
+        betting_pool.max_lending_bps = max_lending_bps;
+        lending_proposal.lending_program = Pubkey::default();
+        lending_proposal.max_lending_bps = 0;
+        lending_proposal.activates_at = 0;
+
+        msg!("Lending program set to {}", lending_program);
+        msg!("Max lending share: {}bps", max_lending_bps);
+        return Ok(());
+    }
+
+    // Cluster time, not the pool clock: the authority sets the mock clock
+    let activates_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(LENDING_PROGRAM_TIMELOCK_SECONDS)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    lending_proposal.lending_program = lending_program;
+    lending_proposal.max_lending_bps = max_lending_bps;
+    lending_proposal.activates_at = activates_at;
+
+    msg!("Lending program {} queued, live from {}", lending_program, activates_at);
+    msg!("Max lending share: {}bps", max_lending_bps);
+
+    Ok(())
+}
+
+/// Make a queued lending program change live once its timelock has passed
+///
+/// The program must also be a live entry on the pool's outflow allowlist.
+#[derive(Accounts)]
+pub struct ApplyLendingProgram<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LENDING_PROPOSAL_SEED, betting_pool.key().as_ref()],
+        bump = lending_proposal.bump,
+        constraint = lending_proposal.is_pending() @ SportsbookError::NoLendingProposal,
+    )]
+    pub lending_proposal: Account<'info, LendingProposal>,
+
+    #[account(
+        seeds = [OUTFLOW_ALLOWLIST_SEED, betting_pool.key().as_ref()],
+        bump = outflow_allowlist.bump,
+    )]
+    pub outflow_allowlist: Account<'info, OutflowAllowlist>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn apply_lending_program_handler(ctx: Context<ApplyLendingProgram>) -> Result<()> {
+    let lending_proposal = &mut ctx.accounts.lending_proposal;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= lending_proposal.activates_at, SportsbookError::LendingTimelockActive);
+    require!(
+        ctx.accounts.outflow_allowlist.allows(&lending_proposal.lending_program, now),
+        SportsbookError::DestinationNotAllowlisted
+    );
+
+    // Switching programs with funds still out would orphan the position
+    let betting_pool = &mut ctx.accounts.betting_pool;
+    if lending_proposal.lending_program != betting_pool.lending_program {
+        require!(
+            betting_pool.lending_deployed == 0,
+            SportsbookError::LiquidityStillDeployed
+        );
+    }
+
+    betting_pool.lending_program = lending_proposal.lending_program;
+    betting_pool.max_lending_bps = lending_proposal.max_lending_bps;
+    lending_proposal.activates_at = 0;
+
+    msg!("Lending program set to {}", betting_pool.lending_program);
+    msg!("Max lending share: {}bps", betting_pool.max_lending_bps);
+
+    Ok(())
+}
+
+/// Move idle liquidity into / out of the whitelisted lending program
+///
+/// The lending protocol's accounts are passed in `remaining_accounts`, in
+/// the order of its deposit or redeem instruction (see `LendingShape`).
+/// A deposit follows them with every unclosed round, as for
+/// reconcile_pool, so the winnings they still owe stay in the pool along
+/// with season rewards and lottery funds, and may only go to a reserve on
+/// the pool's outflow allowlist.
+/// The instruction data is built here from the amount, and the betting pool
+/// PDA signs only as the instruction's owner/transfer authority. Both the
+/// pool token account and the pool's collateral account are re-read
+/// afterwards to verify the token movement.
+#[derive(Accounts)]
+pub struct MoveIdleLiquidity<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    /// Betting pool's token account (source of deposits, target of recalls)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Whitelisted lending program
+    /// CHECK: Address checked against betting_pool.lending_program
+    #[account(
+        executable,
+        constraint = lending_program.key() == betting_pool.lending_program @ SportsbookError::LendingProgramNotWhitelisted,
+    )]
+    pub lending_program: UncheckedAccount<'info>,
+
+    /// Optional: the pool's roles, so a treasurer other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    /// Optional: the pool's reconcile cursor; deposits needn't pass rounds below it
+    #[account(
+        seeds = [RECONCILE_CURSOR_SEED, betting_pool.key().as_ref()],
+        bump = reconcile_cursor.bump,
+    )]
+    pub reconcile_cursor: Option<Account<'info, ReconcileCursor>>,
+
+    /// Optional: the pool's outflow allowlist; deposits need it
+    #[account(
+        seeds = [OUTFLOW_ALLOWLIST_SEED, betting_pool.key().as_ref()],
+        bump = outflow_allowlist.bump,
+    )]
+    pub outflow_allowlist: Option<Account<'info, OutflowAllowlist>>,
+
+    /// Optional: the pool's LotteryConfig address, whether or not the pool
+    /// runs a lottery; deposits need it
+    /// CHECK: Address checked by seeds; read by lottery_funds_held
+    #[account(
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump,
+    )]
+    pub lottery_config: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::Treasurer)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Which way liquidity moves
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum LendingAction {
+    /// Pool liquidity in, collateral tokens out
+    Deposit,
+    /// Collateral tokens in, liquidity (principal + interest) out
+    Redeem,
+}
+
+/// Layout of one lending instruction the pool is allowed to call
+#[derive(Debug, PartialEq, Eq)]
+struct LendingShape {
+    /// Instruction data ahead of the u64 amount
+    data_prefix: &'static [u8],
+
+    /// Number of accounts the instruction takes
+    account_count: usize,
+
+    /// Owner / transfer authority slot: the betting pool, and the only signer
+    authority_index: usize,
+
+    /// Slot of the lending reserve the liquidity goes to or comes from
+    reserve_index: usize,
+
+    /// Slot of the pool token account
+    liquidity_index: usize,
+
+    /// Slot of the pool's collateral token account
+    collateral_index: usize,
+
+    /// Slots that must hold the SPL Token program
+    token_program_indices: &'static [usize],
+}
+
+/// Solend: deposit_reserve_liquidity (tag 4) and redeem_reserve_collateral
+/// (tag 5), with a trailing token program
+const SOLEND_DEPOSIT: LendingShape = LendingShape {
+    data_prefix: &[4],
+    account_count: 9,
+    authority_index: 7,
+    reserve_index: 2,
+    liquidity_index: 0,
+    collateral_index: 1,
+    token_program_indices: &[8],
+};
+const SOLEND_REDEEM: LendingShape = LendingShape {
+    data_prefix: &[5],
+    account_count: 9,
+    authority_index: 7,
+    reserve_index: 2,
+    liquidity_index: 1,
+    collateral_index: 0,
+    token_program_indices: &[8],
+};
+
+/// Kamino: Anchor instructions deposit_reserve_liquidity and
+/// redeem_reserve_collateral, with separate collateral and liquidity token
+/// programs and the instructions sysvar last
+const KAMINO_DEPOSIT: LendingShape = LendingShape {
+    data_prefix: &[169, 201, 30, 126, 6, 205, 102, 68],
+    account_count: 12,
+    authority_index: 0,
+    reserve_index: 1,
+    liquidity_index: 7,
+    collateral_index: 8,
+    token_program_indices: &[9, 10],
+};
+const KAMINO_REDEEM: LendingShape = LendingShape {
+    data_prefix: &[234, 117, 181, 125, 185, 142, 220, 29],
+    account_count: 12,
+    authority_index: 0,
+    reserve_index: 2,
+    liquidity_index: 8,
+    collateral_index: 7,
+    token_program_indices: &[9, 10],
+};
+
+/// Whether idle liquidity may be lent to `program`
+fn is_known_lending_program(program: &Pubkey) -> bool {
+    *program == SOLEND_PROGRAM_ID || *program == KAMINO_LENDING_PROGRAM_ID
+}
+
+/// The instruction layout for `action` on `program`, if it's a known lending program
+fn lending_shape(program: &Pubkey, action: LendingAction) -> Option<&'static LendingShape> {
+    match (*program, action) {
+        (SOLEND_PROGRAM_ID, LendingAction::Deposit) => Some(&SOLEND_DEPOSIT),
+        (SOLEND_PROGRAM_ID, LendingAction::Redeem) => Some(&SOLEND_REDEEM),
+        (KAMINO_LENDING_PROGRAM_ID, LendingAction::Deposit) => Some(&KAMINO_DEPOSIT),
+        (KAMINO_LENDING_PROGRAM_ID, LendingAction::Redeem) => Some(&KAMINO_REDEEM),
+        _ => None,
+    }
+}
+
+pub fn deposit_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MoveIdleLiquidity<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, SportsbookError::InvalidAmount);
+    require!(
+        ctx.accounts.betting_pool.lending_program != Pubkey::default(),
+        SportsbookError::LendingDisabled
+    );
+
+    let balance_before = ctx.accounts.betting_pool_token_account.amount;
+    let deployed = ctx.accounts.betting_pool.lending_deployed;
+
+    // Season rewards, lottery funds and the winnings settled rounds still
+    // owe never leave the pool; the rounds follow the lending accounts
+    let shape = lending_shape(&ctx.accounts.lending_program.key(), LendingAction::Deposit)
+        .ok_or(SportsbookError::LendingProgramNotWhitelisted)?;
+
+    // Liquidity only goes to a reserve that has sat out the allowlist timelock
+    let reserve = ctx
+        .remaining_accounts
+        .get(shape.reserve_index)
+        .ok_or(SportsbookError::InvalidLendingAccounts)?;
+    let outflow_allowlist = ctx
+        .accounts
+        .outflow_allowlist
+        .as_ref()
+        .ok_or(SportsbookError::OutflowAllowlistRequired)?;
+    require!(
+        outflow_allowlist.allows(reserve.key, Clock::get()?.unix_timestamp),
+        SportsbookError::DestinationNotAllowlisted
+    );
+    let round_infos = ctx
+        .remaining_accounts
+        .get(shape.account_count..)
+        .ok_or(SportsbookError::InvalidLendingAccounts)?;
+    let first_round_id = ctx
+        .accounts
+        .reconcile_cursor
+        .as_ref()
+        .map_or(FIRST_ROUND_ID, |cursor| cursor.closed_below);
+    let outstanding_winnings =
+        tally_unclosed_rounds(&ctx.accounts.betting_pool, first_round_id, round_infos)?.outstanding_winnings;
+    let lottery_config = ctx
+        .accounts
+        .lottery_config
+        .as_ref()
+        .ok_or(SportsbookError::LotteryConfigRequired)?;
+    let lottery_funds = lottery_funds_held(lottery_config)?;
+    let required_balance = ctx
+        .accounts
+        .betting_pool
+        .season_reward_pool
+        .checked_add(outstanding_winnings)
+        .and_then(|required| required.checked_add(lottery_funds))
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    require!(
+        balance_before.saturating_sub(amount) >= required_balance,
+        SportsbookError::LendingLimitExceeded
+    );
+
+    // Deployed principal may not exceed max_lending_bps of total liquidity
+    let new_deployed = deployed
+        .checked_add(amount)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    let total_liquidity = balance_before
+        .checked_add(deployed)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    let max_deployed = (total_liquidity as u128)
+        .checked_mul(ctx.accounts.betting_pool.max_lending_bps as u128)
+        .ok_or(SportsbookError::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    require!(
+        new_deployed <= max_deployed,
+        SportsbookError::LendingLimitExceeded
+    );
+
+    let (collateral_before, collateral_after) = invoke_lending_program(&ctx, LendingAction::Deposit, amount)?;
+    require!(
+        collateral_after > collateral_before,
+        SportsbookError::LendingTransferMismatch
+    );
+
+    ctx.accounts.betting_pool_token_account.reload()?;
+    let balance_after = ctx.accounts.betting_pool_token_account.amount;
+    require!(
+        balance_before.checked_sub(balance_after) == Some(amount),
+        SportsbookError::LendingTransferMismatch
+    );
+
+    ctx.accounts.betting_pool.lending_deployed = new_deployed;
+
+    msg!("Deposited {} idle tokens into lending", amount);
+    msg!("Total deployed: {}", new_deployed);
+
+    Ok(())
+}
+
+/// Redeem `collateral_amount` of the pool's collateral tokens for liquidity
+pub fn recall_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MoveIdleLiquidity<'info>>,
+    collateral_amount: u64,
+) -> Result<()> {
+    require!(collateral_amount > 0, SportsbookError::InvalidAmount);
+    require!(
+        ctx.accounts.betting_pool.lending_deployed > 0,
+        SportsbookError::InvalidAmount
+    );
+
+    let balance_before = ctx.accounts.betting_pool_token_account.amount;
+
+    let (collateral_before, collateral_after) =
+        invoke_lending_program(&ctx, LendingAction::Redeem, collateral_amount)?;
+    require!(
+        collateral_before.checked_sub(collateral_after) == Some(collateral_amount),
+        SportsbookError::LendingTransferMismatch
+    );
+
+    ctx.accounts.betting_pool_token_account.reload()?;
+    let balance_after = ctx.accounts.betting_pool_token_account.amount;
+    let received = balance_after
+        .checked_sub(balance_before)
+        .ok_or(SportsbookError::LendingTransferMismatch)?;
+    require!(received > 0, SportsbookError::LendingTransferMismatch);
+
+    let deployed = ctx.accounts.betting_pool.lending_deployed;
+    let recall = split_recall(deployed, received, collateral_after);
+    ctx.accounts.betting_pool.lending_deployed = recall.still_deployed;
+
+    msg!("Recalled {} tokens from lending (interest: {})", received, recall.interest);
+    msg!("Still deployed: {}", recall.still_deployed);
+
+    if recall.loss > 0 {
+        emit!(LendingLossRecognized {
+            betting_pool: ctx.accounts.betting_pool.key(),
+            loss: recall.loss,
+            still_deployed: recall.still_deployed,
+            recognized_by: ctx.accounts.authority.key(),
+        });
+        msg!("Collateral exhausted, wrote off {} lost principal", recall.loss);
+    }
+
+    Ok(())
+}
+
+/// How a recall's proceeds split against the deployed principal
+#[derive(Debug, PartialEq, Eq)]
+struct RecallSplit {
+    still_deployed: u64,
+    interest: u64,
+    loss: u64,
+}
+
+/// Anything received above the principal is interest. Once the pool holds
+/// no more collateral, principal that didn't come back never will, so it
+/// is written off rather than left blocking settlement.
+fn split_recall(deployed: u64, received: u64, collateral_left: u64) -> RecallSplit {
+    let principal_returned = received.min(deployed);
+    let interest = received - principal_returned;
+    let unreturned = deployed - principal_returned;
+    if collateral_left == 0 {
+        RecallSplit { still_deployed: 0, interest, loss: unreturned }
+    } else {
+        RecallSplit { still_deployed: unreturned, interest, loss: 0 }
+    }
+}
+
+/// Write off deployed principal the lending program will not return
+/// (a haircut, a frozen reserve, collateral lost outside a recall)
+///
+/// Settlement waits for `lending_deployed` to reach 0, so a loss that no
+/// recall can realize has to be recognized here by the authority.
+#[derive(Accounts)]
+pub struct RecognizeLendingLoss<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn recognize_loss_handler(ctx: Context<RecognizeLendingLoss>, loss: u64) -> Result<()> {
+    let betting_pool = &mut ctx.accounts.betting_pool;
+    require!(
+        loss > 0 && loss <= betting_pool.lending_deployed,
+        SportsbookError::InvalidAmount
+    );
+
+    betting_pool.lending_deployed -= loss;
+
+    emit!(LendingLossRecognized {
+        betting_pool: betting_pool.key(),
+        loss,
+        still_deployed: betting_pool.lending_deployed,
+        recognized_by: ctx.accounts.authority.key(),
+    });
+
+    msg!("Wrote off {} lost lending principal", loss);
+    msg!("Still deployed: {}", betting_pool.lending_deployed);
+
+    Ok(())
+}
+
+/// CPI into the whitelisted lending program's deposit or redeem
+/// instruction, with the betting pool PDA signing as its authority
+///
+/// The accounts in `remaining_accounts` have to fit the instruction's
+/// shape: the pool in the authority slot and nowhere else, the pool token
+/// account in the liquidity slot, a pool-owned collateral account (not of
+/// the pool's own mint) in the collateral slot and SPL Token in the token
+/// program slots. Returns the collateral balance before and after.
+fn invoke_lending_program<'info>(
+    ctx: &Context<'_, '_, '_, 'info, MoveIdleLiquidity<'info>>,
+    action: LendingAction,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let lending_program = ctx.accounts.lending_program.key();
+    let shape = lending_shape(&lending_program, action).ok_or(SportsbookError::LendingProgramNotWhitelisted)?;
+
+    require!(
+        ctx.remaining_accounts.len() >= shape.account_count,
+        SportsbookError::InvalidLendingAccounts
+    );
+    let lending_accounts = &ctx.remaining_accounts[..shape.account_count];
+    require!(
+        *lending_accounts[shape.liquidity_index].key == ctx.accounts.betting_pool_token_account.key(),
+        SportsbookError::InvalidLendingAccounts
+    );
+    for &index in shape.token_program_indices {
+        require!(
+            *lending_accounts[index].key == token::ID,
+            SportsbookError::InvalidLendingAccounts
+        );
+    }
+
+    let collateral_info = &lending_accounts[shape.collateral_index];
+    let collateral_before = load_collateral(collateral_info, &ctx.accounts.betting_pool)?;
+
+    let mut accounts = Vec::with_capacity(shape.account_count);
+    for (index, account) in lending_accounts.iter().enumerate() {
+        let is_authority = index == shape.authority_index;
+        require!(
+            is_authority == (*account.key == betting_pool_key),
+            SportsbookError::InvalidLendingAccounts
+        );
+        accounts.push(if account.is_writable {
+            AccountMeta::new(*account.key, is_authority)
+        } else {
+            AccountMeta::new_readonly(*account.key, is_authority)
+        });
+    }
+
+    let mut data = shape.data_prefix.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    let ix = Instruction {
+        program_id: lending_program,
+        accounts,
+        data,
+    };
+
+    let mut account_infos = lending_accounts.to_vec();
+    account_infos.push(ctx.accounts.lending_program.to_account_info());
+
+    let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+    invoke_signed(&ix, &account_infos, &[&seeds[..]])?;
+
+    let collateral_after = load_collateral(collateral_info, &ctx.accounts.betting_pool)?;
+
+    Ok((collateral_before, collateral_after))
+}
+
+/// Balance of the pool's collateral token account: an SPL token account
+/// owned by the pool, holding something other than the pool's own mint
+fn load_collateral(collateral_info: &AccountInfo, betting_pool: &Account<BettingPool>) -> Result<u64> {
+    require!(
+        collateral_info.owner == &token::ID,
+        SportsbookError::InvalidLendingAccounts
+    );
+    let data = collateral_info.try_borrow_data()?;
+    let collateral = TokenAccount::try_deserialize(&mut &data[..])?;
+    require!(
+        collateral.owner == betting_pool.key() && collateral.mint != betting_pool.token_mint,
+        SportsbookError::InvalidLendingAccounts
+    );
+
+    Ok(collateral.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_known_lending_programs_have_shapes() {
+        for action in [LendingAction::Deposit, LendingAction::Redeem] {
+            assert!(lending_shape(&SOLEND_PROGRAM_ID, action).is_some());
+            assert!(lending_shape(&KAMINO_LENDING_PROGRAM_ID, action).is_some());
+            assert_eq!(lending_shape(&token::ID, action), None);
+            assert_eq!(lending_shape(&Pubkey::default(), action), None);
+        }
+        assert!(!is_known_lending_program(&token::ID));
+    }
+
+    #[test]
+    fn test_lending_shapes_keep_the_pool_slots_apart() {
+        for shape in [&SOLEND_DEPOSIT, &SOLEND_REDEEM, &KAMINO_DEPOSIT, &KAMINO_REDEEM] {
+            let mut slots = vec![
+                shape.authority_index,
+                shape.reserve_index,
+                shape.liquidity_index,
+                shape.collateral_index,
+            ];
+            slots.extend_from_slice(shape.token_program_indices);
+            assert!(slots.iter().all(|&slot| slot < shape.account_count));
+
+            slots.sort_unstable();
+            slots.dedup();
+            assert_eq!(slots.len(), 4 + shape.token_program_indices.len());
+        }
+    }
+
+    #[test]
+    fn test_recall_writes_off_principal_once_collateral_is_gone() {
+        // Interest on top of a full recall
+        assert_eq!(split_recall(1_000, 1_050, 0), RecallSplit { still_deployed: 0, interest: 50, loss: 0 });
+
+        // Partial recall with collateral left: the rest is still out there
+        assert_eq!(split_recall(1_000, 400, 600), RecallSplit { still_deployed: 600, interest: 0, loss: 0 });
+
+        // Haircut on the final recall: the shortfall is a loss, not deployed
+        assert_eq!(split_recall(1_000, 900, 0), RecallSplit { still_deployed: 0, interest: 0, loss: 100 });
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
-use crate::state::{BettingPool, LiquidityPool};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{BettingPool, LiquidityPool, FeeTier, FEE_TIER_COUNT};
+use crate::constants::MATCHES_PER_ROUND;
+use crate::constants::*;
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -8,7 +11,7 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = BettingPool::LEN,
-        seeds = [b"betting_pool"],
+        seeds = [BETTING_POOL_SEED],
         bump
     )]
     pub betting_pool: Account<'info, BettingPool>,
@@ -17,7 +20,7 @@ pub struct Initialize<'info> {
         init,
         payer = authority,
         space = LiquidityPool::LEN,
-        seeds = [b"liquidity_pool", betting_pool.key().as_ref()],
+        seeds = [LIQUIDITY_POOL_SEED, betting_pool.key().as_ref()],
         bump
     )]
     pub liquidity_pool: Account<'info, LiquidityPool>,
@@ -28,10 +31,22 @@ pub struct Initialize<'info> {
     /// SPL token mint for betting token (e.g., LEAGUE)
     pub token_mint: Account<'info, Mint>,
 
+    /// The pool's own ATA for token_mint; every instruction that moves
+    /// pool funds requires exactly this account
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
     /// Protocol treasury for fee collection
     /// CHECK: Treasury address is set by authority
     pub protocol_treasury: UncheckedAccount<'info>,
 
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -58,7 +73,28 @@ pub fn handler(
     ctx.accounts.betting_pool.season_reward_pool = 0;
     ctx.accounts.betting_pool.next_bet_id = 1;
     ctx.accounts.betting_pool.next_round_id = 1;
+    ctx.accounts.betting_pool.lending_program = Pubkey::default();
+    ctx.accounts.betting_pool.max_lending_bps = 0;
+    ctx.accounts.betting_pool.lending_deployed = 0;
+    ctx.accounts.betting_pool.price_feed = Pubkey::default();
+    ctx.accounts.betting_pool.token_decimals = ctx.accounts.token_mint.decimals;
+    ctx.accounts.betting_pool.max_bet_usd = 0;
+    ctx.accounts.betting_pool.results_attestor = Pubkey::default();
     ctx.accounts.betting_pool.bump = betting_pool_bump;
+    ctx.accounts.betting_pool.max_parlay_legs = MATCHES_PER_ROUND as u8;
+    ctx.accounts.betting_pool.min_leg_odds = 0;
+    ctx.accounts.betting_pool.max_combined_odds = 0;
+    ctx.accounts.betting_pool.guardian = Pubkey::default();
+    ctx.accounts.betting_pool.mock_unix_timestamp = 0;
+    ctx.accounts.betting_pool.merkle_settlement = false;
+    ctx.accounts.betting_pool.fee_tiers = [FeeTier::default(); FEE_TIER_COUNT];
+    ctx.accounts.betting_pool.season_registry = Pubkey::default();
+    ctx.accounts.betting_pool.round_vaults = false;
+    ctx.accounts.betting_pool.protocol_funded_bounty = false;
+    ctx.accounts.betting_pool.leg_fees = false;
+    ctx.accounts.betting_pool.stake_policy = false;
+    ctx.accounts.betting_pool.odds_update_limits = false;
+    ctx.accounts.betting_pool.reserved = [0; 3];
 
     // Initialize liquidity pool
     ctx.accounts.liquidity_pool.betting_pool = betting_pool_key;
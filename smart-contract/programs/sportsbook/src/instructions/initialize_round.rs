@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{BettingPool, RoundAccounting};
+use crate::state::{BettingPool, RoundAccounting, OpenLiability};
 use crate::errors::SportsbookError;
+use crate::constants::*;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
@@ -12,11 +13,20 @@ pub struct InitializeRound<'info> {
         init,
         payer = authority,
         space = RoundAccounting::LEN,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump
     )]
     pub round_accounting: Account<'info, RoundAccounting>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = OpenLiability::LEN,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_liability: Account<'info, OpenLiability>,
+
     #[account(mut, constraint = authority.key() == betting_pool.authority)]
     pub authority: Signer<'info>,
 
@@ -24,20 +34,41 @@ pub struct InitializeRound<'info> {
 }
 
 pub fn handler(ctx: Context<InitializeRound>, round_id: u64) -> Result<()> {
+    init_round(
+        &mut ctx.accounts.betting_pool,
+        &mut ctx.accounts.round_accounting,
+        &mut ctx.accounts.open_liability,
+        round_id,
+        ctx.bumps.round_accounting,
+        ctx.bumps.open_liability,
+    )?;
+
+    msg!("Round {} initialized", round_id);
+
+    Ok(())
+}
+
+/// Fill in a freshly created round and its liability tracker
+pub(crate) fn init_round(
+    betting_pool: &mut Account<BettingPool>,
+    round_accounting: &mut Account<RoundAccounting>,
+    open_liability: &mut Account<OpenLiability>,
+    round_id: u64,
+    round_bump: u8,
+    open_liability_bump: u8,
+) -> Result<()> {
     // Validate round_id is sequential
     require!(
-        round_id == ctx.accounts.betting_pool.next_round_id,
+        round_id == betting_pool.next_round_id,
         SportsbookError::InvalidRoundId
     );
 
     // Increment next_round_id for future rounds
-    ctx.accounts.betting_pool.next_round_id += 1;
-
-    let round_accounting = &mut ctx.accounts.round_accounting;
+    betting_pool.next_round_id += 1;
 
     // Initialize round
     round_accounting.round_id = round_id;
-    round_accounting.betting_pool = ctx.accounts.betting_pool.key();
+    round_accounting.betting_pool = betting_pool.key();
     round_accounting.match_pools = [Default::default(); 10];
     round_accounting.locked_odds = [Default::default(); 10];
     round_accounting.match_results = [Default::default(); 10];
@@ -58,9 +89,37 @@ pub fn handler(ctx: Context<InitializeRound>, round_id: u64) -> Result<()> {
     round_accounting.round_start_time = Clock::get()?.unix_timestamp;
     round_accounting.round_end_time = 0;
     round_accounting.settled = false;
-    round_accounting.bump = ctx.bumps.round_accounting;
+    round_accounting.total_volume_usd = 0;
+    round_accounting.settlement_price = Default::default();
+    round_accounting.bump = round_bump;
+    round_accounting.bets_placed = 0;
+    round_accounting.bets_resolved = 0;
+    round_accounting.locked_odds_hash = [0; 32];
+    round_accounting.claims_paused_until = 0;
+    round_accounting.claim_window_extension = 0;
+    round_accounting.locked_odds_root = [0; 32];
+    round_accounting.branded = false;
+    round_accounting.disputed = false;
+    round_accounting.league_round = false;
+    round_accounting.kickoff_locked = 0;
+    round_accounting.isolated = betting_pool.round_vaults;
+    round_accounting.vault_bump = 0;
+    round_accounting.total_refunded = 0;
+    round_accounting.protocol_funded_bounty = betting_pool.protocol_funded_bounty;
+    round_accounting.protocol_bounties_paid = 0;
+    round_accounting.revenue_allocated = false;
+    round_accounting.payout_dust = 0;
+    round_accounting.claim_shards = 0;
+    round_accounting.shard_payout_cap = 0;
+    round_accounting.open_accumulators = 0;
+    round_accounting.result_bits = 0;
 
-    msg!("Round {} initialized", round_id);
+    open_liability.betting_pool = betting_pool.key();
+    open_liability.round_id = round_id;
+    open_liability.matches = [Default::default(); 10];
+    open_liability.total_stake = 0;
+    open_liability.total_potential_payout = 0;
+    open_liability.bump = open_liability_bump;
 
     Ok(())
 }
@@ -0,0 +1,395 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    BettingPool, RoundAccounting, OpenLiability, LeagueSchedule, RoundFixtures, Fixture,
+    GoalModelParams, SeasonTable, score_matches_outcome,
+};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use super::initialize_round::init_round;
+use sportsbook_core::{
+    double_round_robin_matchdays, is_valid_league_size, round_robin_fixtures, GoalModel,
+    DEFAULT_TEAM_RATING, MAX_LEAGUE_TEAMS,
+};
+
+/// Open a phantom league for a season
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct CreateLeague<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LeagueSchedule::LEN,
+        seeds = [LEAGUE_SCHEDULE_SEED, betting_pool.key().as_ref(), season_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub league: Box<Account<'info, LeagueSchedule>>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_league_handler(ctx: Context<CreateLeague>, season_id: u64) -> Result<()> {
+    let league = &mut ctx.accounts.league;
+    league.betting_pool = ctx.accounts.betting_pool.key();
+    league.season_id = season_id;
+    league.teams = [[0; 16]; MAX_LEAGUE_TEAMS as usize];
+    league.num_teams = 0;
+    league.ratings = [DEFAULT_TEAM_RATING; MAX_LEAGUE_TEAMS as usize];
+    league.goal_model = GoalModel::DEFAULT.into();
+    league.total_matchdays = 0;
+    league.next_matchday = 0;
+    league.last_round_id = 0;
+    league.bump = ctx.bumps.league;
+
+    msg!("League created for season {}", season_id);
+
+    Ok(())
+}
+
+/// Add a team to a league that hasn't started yet
+#[derive(Accounts)]
+pub struct RegisterLeagueTeam<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LEAGUE_SCHEDULE_SEED, betting_pool.key().as_ref(), league.season_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+        constraint = !league.started() @ SportsbookError::LeagueAlreadyStarted,
+    )]
+    pub league: Box<Account<'info, LeagueSchedule>>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn register_league_team_handler(ctx: Context<RegisterLeagueTeam>, name: [u8; 16]) -> Result<()> {
+    let league = &mut ctx.accounts.league;
+    require!(
+        league.num_teams < MAX_LEAGUE_TEAMS,
+        SportsbookError::InvalidLeagueSize
+    );
+
+    let team = league.num_teams;
+    league.teams[team as usize] = name;
+    league.ratings[team as usize] = DEFAULT_TEAM_RATING;
+    league.num_teams += 1;
+
+    msg!("Team {} registered ({} teams)", team, league.num_teams);
+
+    Ok(())
+}
+
+/// Change a team's rating or the league's goal model
+///
+/// Allowed at any point of the season; rounds already materialized keep
+/// the values they locked.
+#[derive(Accounts)]
+pub struct SetLeagueModel<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LEAGUE_SCHEDULE_SEED, betting_pool.key().as_ref(), league.season_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+    )]
+    pub league: Box<Account<'info, LeagueSchedule>>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_team_rating_handler(ctx: Context<SetLeagueModel>, team: u8, rating: u16) -> Result<()> {
+    let league = &mut ctx.accounts.league;
+    require!(team < league.num_teams, SportsbookError::InvalidLeagueTeam);
+
+    league.ratings[team as usize] = rating;
+
+    msg!("Team {} rating set to {}", team, rating);
+
+    Ok(())
+}
+
+pub fn set_goal_model_handler(ctx: Context<SetLeagueModel>, goal_model: GoalModelParams) -> Result<()> {
+    require!(goal_model.to_core().is_valid(), SportsbookError::InvalidGoalModel);
+
+    ctx.accounts.league.goal_model = goal_model;
+
+    msg!(
+        "Goal model: base {} milli-goals, home advantage {}, rating scale {}, max goals {}",
+        goal_model.base_goals_milli,
+        goal_model.home_advantage,
+        goal_model.rating_scale,
+        goal_model.max_goals
+    );
+
+    Ok(())
+}
+
+/// Turn the league's next matchday into a pool round
+///
+/// Permissionless crank; the caller pays rent for the round and its
+/// fixtures. The first call freezes the team list. Each later call needs the
+/// previous matchday's round, which must be settled, so the league plays
+/// out one round at a time. The round still has to be seeded like any
+/// other before it takes bets. Current team ratings and the goal model are
+/// locked into the round's fixtures for its simulation.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct MaterializeLeagueRound<'info> {
+    #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [LEAGUE_SCHEDULE_SEED, betting_pool.key().as_ref(), league.season_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+        constraint = !league.finished() @ SportsbookError::LeagueFinished,
+    )]
+    pub league: Box<Account<'info, LeagueSchedule>>,
+
+    /// Round of the previous matchday (omit for the first matchday)
+    pub previous_round: Option<Box<Account<'info, RoundAccounting>>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = RoundAccounting::LEN,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = OpenLiability::LEN,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub open_liability: Box<Account<'info, OpenLiability>>,
+
+    #[account(
+        init,
+        payer = cranker,
+        space = RoundFixtures::LEN,
+        seeds = [ROUND_FIXTURES_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_fixtures: Box<Account<'info, RoundFixtures>>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn materialize_league_round_handler(ctx: Context<MaterializeLeagueRound>, round_id: u64) -> Result<()> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let league = &mut ctx.accounts.league;
+
+    if !league.started() {
+        require!(
+            is_valid_league_size(league.num_teams),
+            SportsbookError::InvalidLeagueSize
+        );
+        league.total_matchdays = double_round_robin_matchdays(league.num_teams);
+    } else {
+        let previous_round = ctx
+            .accounts
+            .previous_round
+            .as_ref()
+            .ok_or(SportsbookError::PreviousRoundNotSettled)?;
+        require!(
+            previous_round.betting_pool == betting_pool_key
+                && previous_round.round_id == league.last_round_id
+                && previous_round.settled,
+            SportsbookError::PreviousRoundNotSettled
+        );
+    }
+
+    let matchday = league.next_matchday;
+    let pairings = round_robin_fixtures(league.num_teams, matchday)
+        .ok_or(SportsbookError::InvalidLeagueSize)?;
+
+    init_round(
+        &mut ctx.accounts.betting_pool,
+        &mut ctx.accounts.round_accounting,
+        &mut ctx.accounts.open_liability,
+        round_id,
+        ctx.bumps.round_accounting,
+        ctx.bumps.open_liability,
+    )?;
+    ctx.accounts.round_accounting.league_round = true;
+
+    let round_fixtures = &mut ctx.accounts.round_fixtures;
+    round_fixtures.betting_pool = betting_pool_key;
+    round_fixtures.round_id = round_id;
+    round_fixtures.league = league.key();
+    round_fixtures.matchday = matchday;
+    round_fixtures.fixtures = [Fixture::default(); MATCHES_PER_ROUND];
+    for (slot, (home_team, away_team)) in round_fixtures.fixtures.iter_mut().zip(pairings.iter()) {
+        *slot = Fixture {
+            home_team: *home_team,
+            away_team: *away_team,
+            home_rating: league.ratings[*home_team as usize],
+            away_rating: league.ratings[*away_team as usize],
+            ..Default::default()
+        };
+    }
+    round_fixtures.num_fixtures = pairings.len() as u8;
+    round_fixtures.goal_model = league.goal_model;
+    round_fixtures.scores_recorded = false;
+    round_fixtures.bump = ctx.bumps.round_fixtures;
+
+    league.next_matchday = matchday + 1;
+    league.last_round_id = round_id;
+
+    msg!(
+        "League season {}: matchday {}/{} is round {}",
+        league.season_id,
+        matchday + 1,
+        league.total_matchdays,
+        round_id
+    );
+
+    Ok(())
+}
+
+/// Record the scores of a settled league round
+///
+/// Scores feed goal difference in the SeasonTable and must agree with the
+/// round's settled outcomes. Record them before the round is tabulated;
+/// a round tabulated without scores counts points only.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct RecordFixtureScores<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_FIXTURES_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_fixtures.bump,
+        constraint = !round_fixtures.scores_recorded @ SportsbookError::ScoresAlreadyRecorded,
+    )]
+    pub round_fixtures: Box<Account<'info, RoundFixtures>>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+/// `scores` holds (home_goals, away_goals) byte pairs, one per fixture
+pub fn record_fixture_scores_handler(
+    ctx: Context<RecordFixtureScores>,
+    round_id: u64,
+    scores: Vec<u8>,
+) -> Result<()> {
+    let round_fixtures = &mut ctx.accounts.round_fixtures;
+    let num_fixtures = round_fixtures.num_fixtures as usize;
+    require!(
+        scores.len() == num_fixtures * 2,
+        SportsbookError::ArrayLengthMismatch
+    );
+
+    for (i, score) in scores.chunks_exact(2).enumerate() {
+        let outcome = ctx.accounts.round_accounting.match_results[i];
+        require!(
+            score_matches_outcome(score[0], score[1], outcome),
+            SportsbookError::ScoreContradictsResult
+        );
+        round_fixtures.fixtures[i].home_goals = score[0];
+        round_fixtures.fixtures[i].away_goals = score[1];
+    }
+    round_fixtures.scores_recorded = true;
+
+    msg!("Round {}: scores recorded for {} fixtures", round_id, num_fixtures);
+
+    Ok(())
+}
+
+/// Apply the league's next settled matchday to its SeasonTable
+///
+/// Permissionless crank; the first call creates the table. Matchdays are
+/// applied in order, each exactly once.
+#[derive(Accounts)]
+pub struct UpdateSeasonTable<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [LEAGUE_SCHEDULE_SEED, betting_pool.key().as_ref(), league.season_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+    )]
+    pub league: Box<Account<'info, LeagueSchedule>>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = SeasonTable::LEN,
+        seeds = [SEASON_TABLE_SEED, league.key().as_ref()],
+        bump
+    )]
+    pub season_table: Box<Account<'info, SeasonTable>>,
+
+    #[account(
+        seeds = [ROUND_FIXTURES_SEED, betting_pool.key().as_ref(), round_fixtures.round_id.to_le_bytes().as_ref()],
+        bump = round_fixtures.bump,
+        constraint = round_fixtures.league == league.key() @ SportsbookError::InvalidLeagueRound,
+        constraint = round_fixtures.matchday == season_table.matchdays_tabulated @ SportsbookError::InvalidLeagueRound,
+    )]
+    pub round_fixtures: Box<Account<'info, RoundFixtures>>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_fixtures.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_season_table_handler(ctx: Context<UpdateSeasonTable>) -> Result<()> {
+    let season_table = &mut ctx.accounts.season_table;
+    if season_table.league == Pubkey::default() {
+        season_table.league = ctx.accounts.league.key();
+        season_table.num_teams = ctx.accounts.league.num_teams;
+        season_table.bump = ctx.bumps.season_table;
+    }
+
+    let round_fixtures = &ctx.accounts.round_fixtures;
+    let fixtures = &round_fixtures.fixtures[..round_fixtures.num_fixtures as usize];
+    for (i, fixture) in fixtures.iter().enumerate() {
+        season_table.apply_result(
+            fixture,
+            ctx.accounts.round_accounting.match_results[i],
+            round_fixtures.scores_recorded,
+        );
+    }
+    season_table.matchdays_tabulated += 1;
+
+    msg!(
+        "Season table: matchday {}/{} applied (round {})",
+        season_table.matchdays_tabulated,
+        ctx.accounts.league.total_matchdays,
+        round_fixtures.round_id
+    );
+    if let Some(winner) = season_table.winner(ctx.accounts.league.total_matchdays) {
+        msg!("League complete, winner: team {}", winner);
+    }
+
+    Ok(())
+}
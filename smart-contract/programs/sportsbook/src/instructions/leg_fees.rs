@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, LegFeeSchedule};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Set the protocol fee per parlay length
+///
+/// `fee_bps[n - 1]` is the base fee on an `n`-leg bet (0 = the pool's
+/// `protocol_fee_bps`). Longer parlays already carry a compounded house
+/// edge, so configured entries must not rise with the number of legs.
+/// An all-zero schedule turns it off.
+#[derive(Accounts)]
+pub struct SetLegFees<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LegFeeSchedule::LEN,
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub leg_fee_schedule: Account<'info, LegFeeSchedule>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetLegFees>, fee_bps: [u16; MATCHES_PER_ROUND]) -> Result<()> {
+    require!(
+        fee_bps.iter().all(|fee| (*fee as u64) < BPS_DENOMINATOR) && LegFeeSchedule::is_sliding(&fee_bps),
+        SportsbookError::InvalidLegFees
+    );
+
+    let leg_fee_schedule = &mut ctx.accounts.leg_fee_schedule;
+    leg_fee_schedule.betting_pool = ctx.accounts.betting_pool.key();
+    leg_fee_schedule.fee_bps = fee_bps;
+    leg_fee_schedule.bump = ctx.bumps.leg_fee_schedule;
+
+    let enabled = fee_bps.iter().any(|fee| *fee > 0);
+    ctx.accounts.betting_pool.leg_fees = enabled;
+
+    if enabled {
+        for (legs, fee) in fee_bps.iter().enumerate().filter(|(_, fee)| **fee > 0) {
+            msg!("{} legs: {}bps", legs + 1, fee);
+        }
+    } else {
+        msg!("Leg fee schedule disabled");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, RoundKickoffs, OpenLiability, LockedOdds, AccessControl, Role, has_role};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::MatchOddsLocked;
+use crate::utils::calculate_market_odds;
+use super::odds_history::record_odds_updates;
+
+/// Set the kickoff time of each match in a round
+///
+/// Matches already closed at kickoff keep their time. 0 leaves a match
+/// unscheduled (its market stays open until settlement).
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SetMatchKickoffs<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoundKickoffs::LEN,
+        seeds = [ROUND_KICKOFFS_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_kickoffs: Account<'info, RoundKickoffs>,
+
+    /// Optional: the pool's roles, so an odds manager other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::OddsManager)
+            @ SportsbookError::InvalidAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_match_kickoffs_handler(
+    ctx: Context<SetMatchKickoffs>,
+    round_id: u64,
+    kickoff_times: [i64; 10],
+) -> Result<()> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let round_kickoffs = &mut ctx.accounts.round_kickoffs;
+    round_kickoffs.betting_pool = ctx.accounts.betting_pool.key();
+    round_kickoffs.round_id = round_id;
+    round_kickoffs.bump = ctx.bumps.round_kickoffs;
+
+    for (match_index, kickoff) in kickoff_times.iter().enumerate() {
+        require!(*kickoff >= 0, SportsbookError::InvalidKickoffTime);
+        if !round_accounting.is_kickoff_locked(match_index) {
+            round_kickoffs.kickoff_times[match_index] = *kickoff;
+        }
+    }
+
+    msg!("Round {}: kickoff times set", round_id);
+
+    Ok(())
+}
+
+/// Close a match's market once its kickoff has passed
+///
+/// Permissionless, so the lock lands as soon as any keeper sees the
+/// kickoff. Odds already quoted stay as they are: bets were priced at them
+/// and settle at them. A match with no quoted odds, which can't have bets,
+/// is locked at the current market odds of its pool. The match's open
+/// liability at lock time is emitted with the odds. Odds set here are
+/// recorded in the match's OddsHistory if it's passed in `remaining_accounts`.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct LockOdds<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        seeds = [ROUND_KICKOFFS_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_kickoffs.bump,
+    )]
+    pub round_kickoffs: Account<'info, RoundKickoffs>,
+
+    /// Optional: the round's OpenLiability read model, for the stake and
+    /// exposure reported in MatchOddsLocked
+    #[account(
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Account<'info, OpenLiability>>,
+}
+
+pub fn lock_odds_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, LockOdds<'info>>,
+    round_id: u64,
+    match_index: u8,
+) -> Result<()> {
+    let index = match_index as usize;
+    require!(index < MATCHES_PER_ROUND, SportsbookError::InvalidMatchIndex);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.round_kickoffs.has_kicked_off(index, current_time),
+        SportsbookError::KickoffNotReached
+    );
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    require!(
+        !round_accounting.is_kickoff_locked(index),
+        SportsbookError::MatchAlreadyLocked
+    );
+
+    let liability = ctx
+        .accounts
+        .open_liability
+        .as_ref()
+        .map(|open_liability| open_liability.matches[index])
+        .unwrap_or_default();
+    let stake = liability.home.stake + liability.away.stake + liability.draw.stake;
+
+    // OpenLiability is only updated when placements pass it, so whether
+    // bets are in comes from the round itself
+    if !round_accounting.locked_odds[index].locked {
+        require!(
+            round_accounting.total_user_deposits == 0,
+            SportsbookError::OddsUpdateAfterBets
+        );
+        let pool = &round_accounting.match_pools[index];
+        round_accounting.locked_odds[index] = LockedOdds {
+            home_odds: calculate_market_odds(pool, 1),
+            away_odds: calculate_market_odds(pool, 2),
+            draw_odds: calculate_market_odds(pool, 3),
+            locked: true,
+        };
+        round_accounting.commit_locked_odds()?;
+        record_odds_updates(ctx.remaining_accounts, round_accounting, &[match_index], current_time)?;
+    }
+    round_accounting.kickoff_locked |= 1 << index;
+
+    let max_payout = liability
+        .home
+        .potential_payout
+        .max(liability.away.potential_payout)
+        .max(liability.draw.potential_payout);
+
+    emit!(MatchOddsLocked {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        round_id,
+        match_index,
+        odds: round_accounting.locked_odds[index],
+        stake,
+        max_payout,
+        kickoff_time: ctx.accounts.round_kickoffs.kickoff_times[index],
+        locked_at: current_time,
+    });
+
+    msg!("Round {} match {}: market closed at kickoff ({} staked)", round_id, match_index, stake);
+
+    Ok(())
+}
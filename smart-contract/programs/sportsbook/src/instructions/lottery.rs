@@ -0,0 +1,358 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{
+    BettingPool, Bet, RoundAccounting, LotteryConfig, Lottery, LotteryTicket, MAX_LOTTERY_WINNERS,
+    draw_winning_tickets,
+};
+use crate::vrf::{VRF_PROOF_LEN, lottery_seed, randomness_from_proof};
+use crate::attestation::verify_ed25519_signature;
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::events::{LotteryDrawn, LotteryPrizeClaimed};
+use super::claim_winnings::calculate_bet_payout;
+
+/// Set the lottery's cut of protocol revenue and winners per draw
+///
+/// A share of 0 stops funding new prizes; what's already in the reserve
+/// still goes to the next draw.
+#[derive(Accounts)]
+pub struct ConfigureLottery<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LotteryConfig::LEN,
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub lottery_config: Account<'info, LotteryConfig>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_lottery_handler(
+    ctx: Context<ConfigureLottery>,
+    share_bps: u16,
+    winners_per_draw: u8,
+) -> Result<()> {
+    require!(
+        share_bps <= MAX_LOTTERY_SHARE_BPS
+            && winners_per_draw >= 1
+            && winners_per_draw as usize <= MAX_LOTTERY_WINNERS,
+        SportsbookError::InvalidLotteryConfig
+    );
+
+    let lottery_config = &mut ctx.accounts.lottery_config;
+    lottery_config.betting_pool = ctx.accounts.betting_pool.key();
+    lottery_config.share_bps = share_bps;
+    lottery_config.winners_per_draw = winners_per_draw;
+    lottery_config.bump = ctx.bumps.lottery_config;
+
+    msg!("Lottery: {} bps of protocol revenue, {} winners per draw", share_bps, winners_per_draw);
+
+    Ok(())
+}
+
+/// Open the lottery for week `lottery_id` (permissionless)
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct OpenLottery<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Lottery::LEN,
+        seeds = [LOTTERY_SEED, betting_pool.key().as_ref(), lottery_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_lottery_handler(ctx: Context<OpenLottery>, lottery_id: u64) -> Result<()> {
+    let opens_at = (lottery_id as i64)
+        .checked_mul(LOTTERY_PERIOD_SECONDS)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.betting_pool = ctx.accounts.betting_pool.key();
+    lottery.lottery_id = lottery_id;
+    lottery.opens_at = opens_at;
+    lottery.closes_at = opens_at + LOTTERY_PERIOD_SECONDS;
+    lottery.tickets = 0;
+    lottery.prize_pool = 0;
+    lottery.prize_per_winner = 0;
+    lottery.winning_tickets = [0; MAX_LOTTERY_WINNERS];
+    lottery.num_winners = 0;
+    lottery.drawn = false;
+    lottery.proof = [0; VRF_PROOF_LEN];
+    lottery.bump = ctx.bumps.lottery;
+
+    msg!("Lottery {} open for rounds ending {}..{}", lottery_id, lottery.opens_at, lottery.closes_at);
+
+    Ok(())
+}
+
+/// Enter a losing bet as a lottery ticket (one ticket per bet)
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct EnterLottery<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, betting_pool.key().as_ref(), lottery_id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = !lottery.drawn @ SportsbookError::LotteryAlreadyDrawn,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bettor == bettor.key() @ SportsbookError::InvalidBetAccount,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = LotteryTicket::LEN,
+        seeds = [LOTTERY_TICKET_SEED, lottery.key().as_ref(), bet.key().as_ref()],
+        bump
+    )]
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn enter_lottery_handler(ctx: Context<EnterLottery>, lottery_id: u64) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    require!(
+        pool_clock(&ctx.accounts.betting_pool).unix_timestamp()? < lottery.closes_at + LOTTERY_ENTRY_GRACE_SECONDS,
+        SportsbookError::LotteryEntryClosed
+    );
+
+    let round_end_time = ctx.accounts.round_accounting.round_end_time;
+    let (won, _, _) = calculate_bet_payout(&ctx.accounts.bet, &ctx.accounts.round_accounting)?;
+    require!(
+        !won && round_end_time >= lottery.opens_at && round_end_time < lottery.closes_at,
+        SportsbookError::BetNotLotteryEligible
+    );
+
+    let ticket = &mut ctx.accounts.lottery_ticket;
+    ticket.lottery = lottery.key();
+    ticket.bet = ctx.accounts.bet.key();
+    ticket.bettor = ctx.accounts.bettor.key();
+    ticket.ticket_number = lottery.tickets;
+    ticket.claimed = false;
+    ticket.bump = ctx.bumps.lottery_ticket;
+
+    lottery.tickets = lottery
+        .tickets
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    msg!("Bet {} entered in lottery {} as ticket {}", ctx.accounts.bet.bet_id, lottery_id, ticket.ticket_number);
+
+    Ok(())
+}
+
+/// Draw a closed lottery with the oracle's signature over its seed
+///
+/// Same oracle and Ed25519 precompile check as fulfill_vrf_request. The
+/// whole config reserve becomes the prize pool, split evenly between the
+/// winners; with no tickets nothing is drawn from the reserve.
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct DrawLottery<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump = lottery_config.bump,
+    )]
+    pub lottery_config: Account<'info, LotteryConfig>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_SEED, betting_pool.key().as_ref(), lottery_id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = !lottery.drawn @ SportsbookError::LotteryAlreadyDrawn,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn draw_lottery_handler(
+    ctx: Context<DrawLottery>,
+    lottery_id: u64,
+    proof: [u8; VRF_PROOF_LEN],
+) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    require!(
+        pool_clock(&ctx.accounts.betting_pool).unix_timestamp()? >= lottery.closes_at + LOTTERY_ENTRY_GRACE_SECONDS,
+        SportsbookError::LotteryNotClosed
+    );
+
+    let oracle = ctx.accounts.betting_pool.results_attestor;
+    require!(oracle != Pubkey::default(), SportsbookError::AttestorNotSet);
+    let seed = lottery_seed(&ctx.accounts.betting_pool.key(), lottery_id, lottery.closes_at);
+    verify_ed25519_signature(&ctx.accounts.instructions, &oracle, &seed, &proof)?;
+
+    let lottery_config = &mut ctx.accounts.lottery_config;
+    let winners = draw_winning_tickets(
+        &randomness_from_proof(&proof),
+        lottery.tickets,
+        lottery_config.winners_per_draw as usize,
+    );
+    for (slot, ticket) in lottery.winning_tickets.iter_mut().zip(winners.iter()) {
+        *slot = *ticket;
+    }
+    lottery.num_winners = winners.len() as u8;
+    lottery.proof = proof;
+    lottery.drawn = true;
+
+    if !winners.is_empty() {
+        let prize_per_winner = lottery_config.reserve / winners.len() as u64;
+        let prize_pool = prize_per_winner * winners.len() as u64;
+        lottery_config.reserve -= prize_pool;
+        lottery_config.prizes_unclaimed = lottery_config
+            .prizes_unclaimed
+            .checked_add(prize_pool)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        lottery.prize_pool = prize_pool;
+        lottery.prize_per_winner = prize_per_winner;
+    }
+
+    emit!(LotteryDrawn {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        lottery_id,
+        tickets: lottery.tickets,
+        winning_tickets: winners.clone(),
+        prize_per_winner: lottery.prize_per_winner,
+    });
+
+    msg!(
+        "Lottery {} drawn: {} winners of {} tickets, {} each",
+        lottery_id,
+        winners.len(),
+        lottery.tickets,
+        lottery.prize_per_winner
+    );
+
+    Ok(())
+}
+
+/// Pay a winning ticket its prize from the pool token account
+#[derive(Accounts)]
+#[instruction(lottery_id: u64)]
+pub struct ClaimLotteryPrize<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump = lottery_config.bump,
+    )]
+    pub lottery_config: Account<'info, LotteryConfig>,
+
+    #[account(
+        seeds = [LOTTERY_SEED, betting_pool.key().as_ref(), lottery_id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        constraint = lottery_ticket.lottery == lottery.key() @ SportsbookError::NotLotteryWinner,
+        constraint = lottery_ticket.bettor == bettor.key() @ SportsbookError::NotLotteryWinner,
+        constraint = !lottery_ticket.claimed @ SportsbookError::LotteryPrizeClaimed,
+    )]
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bettor.key() @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_lottery_prize_handler(ctx: Context<ClaimLotteryPrize>, lottery_id: u64) -> Result<()> {
+    let lottery = &ctx.accounts.lottery;
+    let ticket = &mut ctx.accounts.lottery_ticket;
+    require!(lottery.is_winner(ticket.ticket_number), SportsbookError::NotLotteryWinner);
+
+    ticket.claimed = true;
+    let prize = lottery.prize_per_winner;
+    let lottery_config = &mut ctx.accounts.lottery_config;
+    lottery_config.prizes_unclaimed = lottery_config
+        .prizes_unclaimed
+        .checked_sub(prize)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    if prize > 0 {
+        let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.betting_pool_token_account.to_account_info(),
+            to: ctx.accounts.bettor_token_account.to_account_info(),
+            authority: ctx.accounts.betting_pool.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, prize)?;
+    }
+
+    emit!(LotteryPrizeClaimed {
+        lottery: lottery.key(),
+        lottery_id,
+        ticket_number: ticket.ticket_number,
+        bettor: ticket.bettor,
+        prize,
+    });
+
+    msg!("Lottery {} ticket {} claimed {}", lottery_id, ticket.ticket_number, prize);
+
+    Ok(())
+}
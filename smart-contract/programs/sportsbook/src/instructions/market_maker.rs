@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, MarketMaker, OddsUpdateLimits, RoundOddsUpdates};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::MarketMakerQuoted;
+use super::odds_history::record_odds_updates;
+use super::odds_update_limits::{enforce_odds_update_limits, init_round_odds_updates};
+
+/// Register or update a market maker's assignment and bounds
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetMarketMaker<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MarketMaker::LEN,
+        seeds = [MARKET_MAKER_SEED, betting_pool.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_market_maker_handler(
+    ctx: Context<SetMarketMaker>,
+    wallet: Pubkey,
+    match_mask: u16,
+    max_deviation_bps: u16,
+    min_update_interval: i64,
+    active: bool,
+) -> Result<()> {
+    require!(
+        match_mask >> MATCHES_PER_ROUND == 0
+            && max_deviation_bps as u64 <= BPS_DENOMINATOR
+            && min_update_interval >= 0,
+        SportsbookError::InvalidMarketMakerBounds
+    );
+
+    let market_maker = &mut ctx.accounts.market_maker;
+    market_maker.betting_pool = ctx.accounts.betting_pool.key();
+    market_maker.wallet = wallet;
+    market_maker.match_mask = match_mask;
+    market_maker.max_deviation_bps = max_deviation_bps;
+    market_maker.min_update_interval = min_update_interval;
+    market_maker.active = active;
+    market_maker.bump = ctx.bumps.market_maker;
+
+    msg!(
+        "Market maker {}: matches {:#012b}, max move {} bps, every {}s, {}",
+        wallet,
+        match_mask,
+        max_deviation_bps,
+        min_update_interval,
+        if active { "active" } else { "inactive" }
+    );
+
+    Ok(())
+}
+
+/// Quote new odds for one assigned match
+///
+/// Signed by the maker's wallet. Same window as bulk_set_odds: the round is
+/// seeded, has no user bets yet and the match hasn't kicked off. Every
+/// outcome must stay in the compressed odds range and within the maker's
+/// deviation bound of the current odds, and pass the pool's odds update
+/// limits if it has any. The match's OddsHistory may be passed in
+/// `remaining_accounts`.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct QuoteOdds<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+        constraint = round_accounting.total_user_deposits == 0 @ SportsbookError::OddsUpdateAfterBets,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_MAKER_SEED, betting_pool.key().as_ref(), wallet.key().as_ref()],
+        bump = market_maker.bump,
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    /// Required when the pool has odds update limits
+    #[account(
+        seeds = [ODDS_UPDATE_LIMITS_SEED, betting_pool.key().as_ref()],
+        bump = odds_update_limits.bump,
+    )]
+    pub odds_update_limits: Option<Account<'info, OddsUpdateLimits>>,
+
+    /// Required with odds_update_limits: when each match's odds last moved
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = RoundOddsUpdates::LEN,
+        seeds = [ROUND_ODDS_UPDATES_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_odds_updates: Option<Account<'info, RoundOddsUpdates>>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn quote_odds_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QuoteOdds<'info>>,
+    round_id: u64,
+    match_index: u8,
+    home_odds: u64,
+    away_odds: u64,
+    draw_odds: u64,
+) -> Result<()> {
+    let index = match_index as usize;
+    require!(index < MATCHES_PER_ROUND, SportsbookError::InvalidMatchIndex);
+
+    let market_maker = &mut ctx.accounts.market_maker;
+    require!(market_maker.may_quote(index), SportsbookError::MatchNotAssigned);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        market_maker.interval_elapsed(current_time),
+        SportsbookError::QuoteTooFrequent
+    );
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    require!(
+        !round_accounting.is_kickoff_locked(index),
+        SportsbookError::MatchAlreadyLocked
+    );
+
+    let mut round_odds_updates = ctx.accounts.round_odds_updates.as_deref_mut();
+    init_round_odds_updates(
+        round_odds_updates.as_deref_mut(),
+        ctx.accounts.betting_pool.key(),
+        round_id,
+        ctx.bumps.round_odds_updates,
+    );
+    enforce_odds_update_limits(
+        &ctx.accounts.betting_pool,
+        ctx.accounts.odds_update_limits.as_deref(),
+        round_odds_updates,
+        index,
+        &round_accounting.locked_odds[index],
+        [home_odds, away_odds, draw_odds],
+        current_time,
+    )?;
+
+    let locked_odds = &mut round_accounting.locked_odds[index];
+    require!(locked_odds.locked, SportsbookError::OddsNotLocked);
+    let quoted = [
+        (locked_odds.home_odds, home_odds),
+        (locked_odds.away_odds, away_odds),
+        (locked_odds.draw_odds, draw_odds),
+    ];
+    require!(
+        quoted.iter().all(|(current, new)| {
+            (MIN_COMPRESSED_ODDS..=MAX_COMPRESSED_ODDS).contains(new)
+                && market_maker.within_deviation(*current, *new)
+        }),
+        SportsbookError::QuoteOutOfBounds
+    );
+
+    locked_odds.home_odds = home_odds;
+    locked_odds.away_odds = away_odds;
+    locked_odds.draw_odds = draw_odds;
+    round_accounting.commit_locked_odds()?;
+    record_odds_updates(ctx.remaining_accounts, round_accounting, &[match_index], current_time)?;
+
+    market_maker.last_update = current_time;
+    market_maker.quotes = market_maker.quotes.saturating_add(1);
+
+    emit!(MarketMakerQuoted {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        market_maker: market_maker.wallet,
+        round_id,
+        match_index,
+        home_odds,
+        away_odds,
+        draw_odds,
+    });
+
+    msg!(
+        "Round {} match {}: {} quoted {}/{}/{}",
+        round_id,
+        match_index,
+        market_maker.wallet,
+        home_odds,
+        away_odds,
+        draw_odds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, DailyVolume, ActiveBettors, PendingClaims};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Create the pool's dashboard read models
+///
+/// Bet placement and claim handlers keep these up to date incrementally, so
+/// an operator can poll three small accounts instead of scanning every Bet.
+/// Counting starts from creation; nothing is backfilled.
+#[derive(Accounts)]
+pub struct InitializeMetrics<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DailyVolume::LEN,
+        seeds = [DAILY_VOLUME_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub daily_volume: Box<Account<'info, DailyVolume>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ActiveBettors::LEN,
+        seeds = [ACTIVE_BETTORS_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub active_bettors: Account<'info, ActiveBettors>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PendingClaims::LEN,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub pending_claims: Account<'info, PendingClaims>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeMetrics>) -> Result<()> {
+    let betting_pool = ctx.accounts.betting_pool.key();
+
+    let daily_volume = &mut ctx.accounts.daily_volume;
+    daily_volume.betting_pool = betting_pool;
+    daily_volume.bump = ctx.bumps.daily_volume;
+
+    let active_bettors = &mut ctx.accounts.active_bettors;
+    active_bettors.betting_pool = betting_pool;
+    active_bettors.bump = ctx.bumps.active_bettors;
+
+    let pending_claims = &mut ctx.accounts.pending_claims;
+    pending_claims.betting_pool = betting_pool;
+    pending_claims.bump = ctx.bumps.pending_claims;
+
+    msg!("Metrics initialized for pool {}", betting_pool);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+use crate::errors::SportsbookError;
+use crate::clock::MOCK_CLOCK_ENABLED;
+
+/// Pin the pool's clock to a fixed unix time
+///
+/// Only compiled to do anything with the `mock-clock` feature, which is for
+/// devnet/localnet builds: it lets end-to-end tests walk through claim
+/// windows and bounty deadlines without waiting on real time.
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMockClock>, unix_timestamp: i64) -> Result<()> {
+    require!(MOCK_CLOCK_ENABLED, SportsbookError::MockClockDisabled);
+    require!(unix_timestamp >= 0, SportsbookError::InvalidAmount);
+
+    ctx.accounts.betting_pool.mock_unix_timestamp = unix_timestamp;
+
+    if unix_timestamp == 0 {
+        msg!("Mock clock cleared, using cluster time");
+    } else {
+        msg!("Mock clock pinned to {}", unix_timestamp);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, Notifications, NotificationKind};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Create the pool's operator notification queue, or change its
+/// low-liquidity threshold
+///
+/// Settlement, seeding and randomness verification append to the queue
+/// whenever they are given it; see `NotificationKind` for what is queued.
+#[derive(Accounts)]
+pub struct ConfigureNotifications<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Notifications::LEN,
+        seeds = [NOTIFICATIONS_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub notifications: Box<Account<'info, Notifications>>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_notifications_handler(
+    ctx: Context<ConfigureNotifications>,
+    liquidity_low_threshold: u64,
+) -> Result<()> {
+    let notifications = &mut ctx.accounts.notifications;
+    notifications.betting_pool = ctx.accounts.betting_pool.key();
+    notifications.liquidity_low_threshold = liquidity_low_threshold;
+    notifications.bump = ctx.bumps.notifications;
+
+    msg!("Notifications: liquidity low below {} (0 = off)", liquidity_low_threshold);
+
+    Ok(())
+}
+
+/// Queue a notification, when the instruction was given the queue
+pub(crate) fn notify(
+    notifications: Option<&mut Notifications>,
+    kind: NotificationKind,
+    round_id: u64,
+    subject: Pubkey,
+    amount: u64,
+    timestamp: i64,
+) {
+    if let Some(notifications) = notifications {
+        notifications.push(kind, round_id, subject, amount, timestamp);
+    }
+}
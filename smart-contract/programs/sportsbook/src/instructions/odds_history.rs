@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, OddsHistory, OddsPoint, ODDS_HISTORY_LEN};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Start recording a match's odds updates
+#[derive(Accounts)]
+#[instruction(round_id: u64, match_index: u8)]
+pub struct InitOddsHistory<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OddsHistory::LEN,
+        seeds = [
+            ODDS_HISTORY_SEED,
+            betting_pool.key().as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            &[match_index],
+        ],
+        bump
+    )]
+    pub odds_history: Account<'info, OddsHistory>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_odds_history_handler(ctx: Context<InitOddsHistory>, round_id: u64, match_index: u8) -> Result<()> {
+    require!(
+        (match_index as usize) < MATCHES_PER_ROUND,
+        SportsbookError::InvalidMatchIndex
+    );
+
+    let odds_history = &mut ctx.accounts.odds_history;
+    odds_history.betting_pool = ctx.accounts.betting_pool.key();
+    odds_history.round_id = round_id;
+    odds_history.match_index = match_index;
+    odds_history.points = [OddsPoint::default(); ODDS_HISTORY_LEN];
+    odds_history.total_updates = 0;
+    odds_history.bump = ctx.bumps.odds_history;
+
+    // Odds already set before the history existed are its first point
+    let locked_odds = &ctx.accounts.round_accounting.locked_odds[match_index as usize];
+    if locked_odds.locked {
+        odds_history.record(locked_odds, Clock::get()?.unix_timestamp);
+    }
+
+    msg!("Round {} match {}: odds history started", round_id, match_index);
+
+    Ok(())
+}
+
+/// Record the round's current odds in the OddsHistory accounts passed in
+/// `history_accounts`, one per match in `updated`
+///
+/// Every history passed must belong to this round and to a match that was
+/// just updated; matches without a history account are not recorded.
+pub(crate) fn record_odds_updates<'info>(
+    history_accounts: &'info [AccountInfo<'info>],
+    round_accounting: &RoundAccounting,
+    updated: &[u8],
+    timestamp: i64,
+) -> Result<()> {
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(history_accounts.len());
+
+    for history_info in history_accounts.iter() {
+        require!(!seen.contains(history_info.key), SportsbookError::DuplicateAccount);
+        seen.push(*history_info.key);
+        require!(history_info.owner == &crate::ID, SportsbookError::InvalidOddsHistory);
+
+        let mut odds_history = Account::<OddsHistory>::try_from(history_info)?;
+        require!(
+            odds_history.betting_pool == round_accounting.betting_pool
+                && odds_history.round_id == round_accounting.round_id
+                && updated.contains(&odds_history.match_index),
+            SportsbookError::InvalidOddsHistory
+        );
+
+        let match_index = odds_history.match_index as usize;
+        odds_history.record(&round_accounting.locked_odds[match_index], timestamp);
+        odds_history.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
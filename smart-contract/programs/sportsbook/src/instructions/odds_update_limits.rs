@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, LockedOdds, OddsUpdateLimits, RoundOddsUpdates};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Bound how far and how often odds can move (both 0 = off)
+///
+/// Once on, bulk_set_odds and quote_odds must pass the OddsUpdateLimits
+/// account and the round's RoundOddsUpdates. Held by the authority alone so
+/// an odds manager can't lift the limits it is bound by.
+#[derive(Accounts)]
+pub struct SetOddsUpdateLimits<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OddsUpdateLimits::LEN,
+        seeds = [ODDS_UPDATE_LIMITS_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub odds_update_limits: Account<'info, OddsUpdateLimits>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_odds_update_limits_handler(
+    ctx: Context<SetOddsUpdateLimits>,
+    max_move_bps: u16,
+    min_update_interval: i64,
+) -> Result<()> {
+    require!(
+        max_move_bps as u64 <= BPS_DENOMINATOR && min_update_interval >= 0,
+        SportsbookError::InvalidOddsUpdateLimits
+    );
+
+    let odds_update_limits = &mut ctx.accounts.odds_update_limits;
+    odds_update_limits.betting_pool = ctx.accounts.betting_pool.key();
+    odds_update_limits.max_move_bps = max_move_bps;
+    odds_update_limits.min_update_interval = min_update_interval;
+    odds_update_limits.bump = ctx.bumps.odds_update_limits;
+
+    ctx.accounts.betting_pool.odds_update_limits = max_move_bps > 0 || min_update_interval > 0;
+
+    msg!("Max odds move per update: {} bps (0 = unbounded)", max_move_bps);
+    msg!("Min seconds between updates of a match: {}", min_update_interval);
+
+    Ok(())
+}
+
+/// Fill in a RoundOddsUpdates created by init_if_needed in this instruction
+pub(crate) fn init_round_odds_updates(
+    round_odds_updates: Option<&mut RoundOddsUpdates>,
+    betting_pool: Pubkey,
+    round_id: u64,
+    bump: Option<u8>,
+) {
+    if let (Some(round_odds_updates), Some(bump)) = (round_odds_updates, bump) {
+        if round_odds_updates.betting_pool == Pubkey::default() {
+            round_odds_updates.betting_pool = betting_pool;
+            round_odds_updates.round_id = round_id;
+            round_odds_updates.bump = bump;
+        }
+    }
+}
+
+/// Check a move of `match_index` from `current` to `new_odds` (home, away,
+/// draw) against the pool's odds update limits and stamp its update time.
+/// Does nothing when the pool has no limits.
+pub(crate) fn enforce_odds_update_limits(
+    betting_pool: &BettingPool,
+    odds_update_limits: Option<&OddsUpdateLimits>,
+    round_odds_updates: Option<&mut RoundOddsUpdates>,
+    match_index: usize,
+    current: &LockedOdds,
+    new_odds: [u64; 3],
+    current_time: i64,
+) -> Result<()> {
+    if !betting_pool.odds_update_limits {
+        return Ok(());
+    }
+    let (Some(limits), Some(updates)) = (odds_update_limits, round_odds_updates) else {
+        return err!(SportsbookError::OddsUpdateLimitsRequired);
+    };
+
+    require!(
+        updates.interval_elapsed(limits, match_index, current_time),
+        SportsbookError::OddsUpdateTooFrequent
+    );
+    let current_odds = [current.home_odds, current.away_odds, current.draw_odds];
+    require!(
+        current_odds
+            .iter()
+            .zip(new_odds.iter())
+            .all(|(current, new)| limits.allows_move(*current, *new)),
+        SportsbookError::OddsMoveTooLarge
+    );
+
+    updates.last_update[match_index] = current_time;
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, OutflowAllowlist};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Propose a destination pool liquidity may be sent to, creating the
+/// pool's OutflowAllowlist on first use
+///
+/// The destination can receive funds OUTFLOW_DESTINATION_TIMELOCK_SECONDS
+/// from now. Only the authority can propose; a treasurer can't.
+#[derive(Accounts)]
+pub struct ProposeOutflowDestination<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OutflowAllowlist::LEN,
+        seeds = [OUTFLOW_ALLOWLIST_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub outflow_allowlist: Account<'info, OutflowAllowlist>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_outflow_destination_handler(
+    ctx: Context<ProposeOutflowDestination>,
+    destination: Pubkey,
+) -> Result<()> {
+    require!(destination != Pubkey::default(), SportsbookError::InvalidTokenAccount);
+
+    let outflow_allowlist = &mut ctx.accounts.outflow_allowlist;
+    outflow_allowlist.betting_pool = ctx.accounts.betting_pool.key();
+    outflow_allowlist.bump = ctx.bumps.outflow_allowlist;
+    require!(
+        !outflow_allowlist.destinations.iter().any(|entry| entry.destination == destination),
+        SportsbookError::DestinationAlreadyListed
+    );
+
+    // Cluster time, not the pool clock: the authority sets the mock clock
+    let allowed_from = Clock::get()?
+        .unix_timestamp
+        .checked_add(OUTFLOW_DESTINATION_TIMELOCK_SECONDS)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    outflow_allowlist
+        .propose(destination, allowed_from)
+        .ok_or(SportsbookError::OutflowAllowlistFull)?;
+
+    msg!("Outflow destination {} allowed from {}", destination, allowed_from);
+
+    Ok(())
+}
+
+/// Take a destination off the pool's outflow allowlist, effective at once
+#[derive(Accounts)]
+pub struct RemoveOutflowDestination<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [OUTFLOW_ALLOWLIST_SEED, betting_pool.key().as_ref()],
+        bump = outflow_allowlist.bump,
+    )]
+    pub outflow_allowlist: Account<'info, OutflowAllowlist>,
+
+    #[account(constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn remove_outflow_destination_handler(
+    ctx: Context<RemoveOutflowDestination>,
+    destination: Pubkey,
+) -> Result<()> {
+    require!(
+        ctx.accounts.outflow_allowlist.remove(&destination),
+        SportsbookError::DestinationNotAllowlisted
+    );
+
+    msg!("Outflow destination {} removed", destination);
+
+    Ok(())
+}
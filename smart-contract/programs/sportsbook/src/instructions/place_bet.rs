@@ -1,9 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{BettingPool, RoundAccounting, Bet, Prediction};
+use crate::state::{
+    BettingPool, RoundAccounting, Bet, BetRecord, Prediction, UserBetCounter, OpenLiability,
+    DailyVolume, ActiveBettors, PendingClaims, UserVolume, LegFeeSchedule, StakePolicy,
+    InsuranceFund, UserStats,
+};
 use crate::errors::SportsbookError;
 use crate::constants::*;
-use crate::utils::{calculate_parlay_multiplier_dynamic, calculate_odds_weighted_allocations, calculate_max_payout};
+use crate::utils::{
+    calculate_parlay_multiplier_dynamic, calculate_odds_weighted_allocations, calculate_max_payout,
+    calculate_combined_odds, calculate_potential_payout,
+};
+use crate::events::{BetPlaced, BetGifted, PayoutCheckFailed, LiquidityCheckFailed};
+use crate::require_with_context;
+use crate::pyth::{load_pool_price, token_amount_to_usd};
+use sportsbook_core::{calculate_protocol_fee, leg_bitmap, round_down_to_unit, BetLegTerms, BetTerms};
+use crate::invariants::FundsCheck;
+use super::round_vault::round_funds;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
@@ -13,19 +26,62 @@ pub struct PlaceBet<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
         constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
     )]
     pub round_accounting: Box<Account<'info, RoundAccounting>>,
 
+    /// Optional: the round's OpenLiability read model, updated when passed
+    #[account(
+        mut,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Box<Account<'info, OpenLiability>>>,
+
+    /// Optional: the pool's DailyVolume read model, updated when passed
+    #[account(
+        mut,
+        seeds = [DAILY_VOLUME_SEED, betting_pool.key().as_ref()],
+        bump = daily_volume.bump,
+    )]
+    pub daily_volume: Option<Box<Account<'info, DailyVolume>>>,
+
+    /// Optional: the pool's ActiveBettors read model, updated when passed
+    #[account(
+        mut,
+        seeds = [ACTIVE_BETTORS_SEED, betting_pool.key().as_ref()],
+        bump = active_bettors.bump,
+    )]
+    pub active_bettors: Option<Box<Account<'info, ActiveBettors>>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Box<Account<'info, PendingClaims>>>,
+
+    /// Bettor's rolling stake volume for fee tiers (created on first bet)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserVolume::LEN,
+        seeds = [USER_VOLUME_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub user_volume: Box<Account<'info, UserVolume>>,
+
+    /// Bet PDA, seeded with the pool's bet counter
     #[account(
         init,
         payer = bettor,
         space = Bet::LEN,
         seeds = [
-            b"bet",
+            BET_SEED,
             betting_pool.key().as_ref(),
             betting_pool.next_bet_id.to_le_bytes().as_ref()
         ],
@@ -33,22 +89,399 @@ pub struct PlaceBet<'info> {
     )]
     pub bet: Box<Account<'info, Bet>>,
 
+    /// The bet's BetRecord, created here to keep its terms hash and leg bitmap
+    #[account(
+        init,
+        payer = bettor,
+        space = BetRecord::LEN,
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub bet_record: Box<Account<'info, BetRecord>>,
+
     /// Bettor's token account
     #[account(mut)]
     pub bettor_token_account: Box<Account<'info, TokenAccount>>,
 
     /// Betting pool's token account (receives bet funds)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Required for isolated rounds: receives the stake instead
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Required when the pool charges fees by parlay length
+    #[account(
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump = leg_fee_schedule.bump,
+    )]
+    pub leg_fee_schedule: Option<Box<Account<'info, LegFeeSchedule>>>,
+
+    /// Required when the pool has a stake policy
+    #[account(
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = stake_policy.bump,
+    )]
+    pub stake_policy: Option<Box<Account<'info, StakePolicy>>>,
+
+    /// Protocol treasury token account (receives fees)
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub protocol_treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Optional: User's team token account (for fee discount + odds boost)
+    /// If provided and has balance, user gets benefits
+    pub team_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Optional: Pyth price feed, required when the pool is USD-valued
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Required by place_insured_bet: the pool's insurance terms
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED, betting_pool.key().as_ref()],
+        bump = insurance_fund.bump,
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// Required by place_insured_bet: receives the premium
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED, betting_pool.key().as_ref()],
+        bump,
+    )]
+    pub insurance_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Required by place_credit_bet: the bettor's credit balance
+    #[account(
+        mut,
+        seeds = [USER_STATS_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = user_stats.bump,
+    )]
+    pub user_stats: Option<Box<Account<'info, UserStats>>>,
+
+    /// Required by place_credit_bet: pays the stake instead of
+    /// bettor_token_account
+    #[account(
+        mut,
+        seeds = [CREDIT_VAULT_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump,
+    )]
+    pub credit_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Place a bet in the caller's own namespace
+///
+/// The bet PDA is `[BET_SEED, pool, bettor, user_nonce]`, where `user_nonce`
+/// is the bettor's own counter, so users never contend for each other's
+/// bet addresses and a user's bets can be enumerated by derivation
+/// (nonce 0..user_bet_counter.next_nonce).
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct PlaceUserBet<'info> {
     #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    /// Optional: the round's OpenLiability read model, updated when passed
+    #[account(
+        mut,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Box<Account<'info, OpenLiability>>>,
+
+    /// Optional: the pool's DailyVolume read model, updated when passed
+    #[account(
+        mut,
+        seeds = [DAILY_VOLUME_SEED, betting_pool.key().as_ref()],
+        bump = daily_volume.bump,
+    )]
+    pub daily_volume: Option<Box<Account<'info, DailyVolume>>>,
+
+    /// Optional: the pool's ActiveBettors read model, updated when passed
+    #[account(
+        mut,
+        seeds = [ACTIVE_BETTORS_SEED, betting_pool.key().as_ref()],
+        bump = active_bettors.bump,
+    )]
+    pub active_bettors: Option<Box<Account<'info, ActiveBettors>>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Box<Account<'info, PendingClaims>>>,
+
+    /// Bettor's rolling stake volume for fee tiers (created on first bet)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserVolume::LEN,
+        seeds = [USER_VOLUME_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub user_volume: Box<Account<'info, UserVolume>>,
+
+    /// Per-user bet counter (created on the user's first namespaced bet)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserBetCounter::LEN,
+        seeds = [USER_BET_COUNTER_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub user_bet_counter: Box<Account<'info, UserBetCounter>>,
+
+    /// Bet PDA, seeded with the bettor's own nonce
+    #[account(
+        init,
+        payer = bettor,
+        space = Bet::LEN,
+        seeds = [
+            BET_SEED,
+            betting_pool.key().as_ref(),
+            bettor.key().as_ref(),
+            user_bet_counter.next_nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// The bet's BetRecord, created here to keep its terms hash and leg bitmap
+    #[account(
+        init,
+        payer = bettor,
+        space = BetRecord::LEN,
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub bet_record: Box<Account<'info, BetRecord>>,
+
+    /// Bettor's token account
+    #[account(mut)]
+    pub bettor_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Betting pool's token account (receives bet funds)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
     pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
 
+    /// Required for isolated rounds: receives the stake instead
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Required when the pool charges fees by parlay length
+    #[account(
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump = leg_fee_schedule.bump,
+    )]
+    pub leg_fee_schedule: Option<Box<Account<'info, LegFeeSchedule>>>,
+
+    /// Required when the pool has a stake policy
+    #[account(
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = stake_policy.bump,
+    )]
+    pub stake_policy: Option<Box<Account<'info, StakePolicy>>>,
+
     /// Protocol treasury token account (receives fees)
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub protocol_treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Optional: User's team token account (for fee discount + odds boost)
+    pub team_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Optional: Pyth price feed, required when the pool is USD-valued
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Place a bet on behalf of another wallet
+///
+/// The signer funds the stake but the bet belongs to `beneficiary`: it is
+/// recorded as `bet.bettor`, so winnings go to the beneficiary's token
+/// account and only they can claim before the bounty window opens. The
+/// payer keeps no claim on the bet. Uses the pool-wide bet ID namespace.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct PlaceGiftBet<'info> {
+    #[account(mut)]
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    /// Optional: the round's OpenLiability read model, updated when passed
+    #[account(
+        mut,
+        seeds = [OPEN_LIABILITY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = open_liability.bump,
+    )]
+    pub open_liability: Option<Box<Account<'info, OpenLiability>>>,
+
+    /// Optional: the pool's DailyVolume read model, updated when passed
+    #[account(
+        mut,
+        seeds = [DAILY_VOLUME_SEED, betting_pool.key().as_ref()],
+        bump = daily_volume.bump,
+    )]
+    pub daily_volume: Option<Box<Account<'info, DailyVolume>>>,
+
+    /// Optional: the pool's ActiveBettors read model, updated when passed
+    #[account(
+        mut,
+        seeds = [ACTIVE_BETTORS_SEED, betting_pool.key().as_ref()],
+        bump = active_bettors.bump,
+    )]
+    pub active_bettors: Option<Box<Account<'info, ActiveBettors>>>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Box<Account<'info, PendingClaims>>>,
+
+    /// Payer's rolling stake volume for fee tiers (created on first bet)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserVolume::LEN,
+        seeds = [USER_VOLUME_SEED, betting_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub user_volume: Box<Account<'info, UserVolume>>,
+
+    /// Bet PDA, seeded with the pool's bet counter
+    #[account(
+        init,
+        payer = bettor,
+        space = Bet::LEN,
+        seeds = [
+            BET_SEED,
+            betting_pool.key().as_ref(),
+            betting_pool.next_bet_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// The bet's BetRecord, created here to keep its terms hash and leg bitmap
+    #[account(
+        init,
+        payer = bettor,
+        space = BetRecord::LEN,
+        seeds = [BET_RECORD_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub bet_record: Box<Account<'info, BetRecord>>,
+
+    /// Payer's token account (funds the stake)
     #[account(mut)]
+    pub bettor_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Betting pool's token account (receives bet funds)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Required for isolated rounds: receives the stake instead
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Required when the pool charges fees by parlay length
+    #[account(
+        seeds = [LEG_FEE_SCHEDULE_SEED, betting_pool.key().as_ref()],
+        bump = leg_fee_schedule.bump,
+    )]
+    pub leg_fee_schedule: Option<Box<Account<'info, LegFeeSchedule>>>,
+
+    /// Required when the pool has a stake policy
+    #[account(
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump = stake_policy.bump,
+    )]
+    pub stake_policy: Option<Box<Account<'info, StakePolicy>>>,
+
+    /// Protocol treasury token account (receives fees)
+    #[account(
+        mut,
+        address = betting_pool.protocol_treasury @ SportsbookError::InvalidTokenAccount,
+    )]
     pub protocol_treasury_token_account: Box<Account<'info, TokenAccount>>,
 
     /// Optional: User's team token account (for fee discount + odds boost)
     /// If provided and has balance, user gets benefits
     pub team_token_account: Option<Box<Account<'info, TokenAccount>>>,
 
+    /// Optional: Pyth price feed, required when the pool is USD-valued
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Wallet the bet is placed for; becomes `bet.bettor` and is the only
+    /// one who can claim within the claim window
+    pub beneficiary: SystemAccount<'info>,
+
+    /// Pays the stake and fee (and the bet's rent)
     #[account(mut)]
     pub bettor: Signer<'info>,
 
@@ -56,22 +489,313 @@ pub struct PlaceBet<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Accounts and PDA details shared by the bet placement paths
+pub(crate) struct BetPlacement<'a, 'info> {
+    pub betting_pool: &'a mut Account<'info, BettingPool>,
+    pub round_accounting: &'a mut Account<'info, RoundAccounting>,
+    /// Dashboard read models, updated when passed
+    pub open_liability: Option<&'a mut Account<'info, OpenLiability>>,
+    pub daily_volume: Option<&'a mut Account<'info, DailyVolume>>,
+    pub active_bettors: Option<&'a mut Account<'info, ActiveBettors>>,
+    pub pending_claims: Option<&'a mut Account<'info, PendingClaims>>,
+    pub user_volume: &'a mut Account<'info, UserVolume>,
+    pub user_volume_bump: u8,
+    pub bet: &'a mut Account<'info, Bet>,
+    /// Gets the bet's terms hash and leg bitmap
+    pub bet_record: &'a mut Account<'info, BetRecord>,
+    pub bettor_token_account: &'a Account<'info, TokenAccount>,
+    pub betting_pool_token_account: &'a Account<'info, TokenAccount>,
+    /// The round's own vault, for isolated rounds
+    pub round_vault: Option<&'a Account<'info, TokenAccount>>,
+    /// The pool's fee by parlay length, when it has one
+    pub leg_fee_schedule: Option<&'a Account<'info, LegFeeSchedule>>,
+    /// The pool's minimum stake and rounding unit, when it has them
+    pub stake_policy: Option<&'a Account<'info, StakePolicy>>,
+    pub protocol_treasury_token_account: &'a Account<'info, TokenAccount>,
+    pub team_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub price_feed: Option<&'a AccountInfo<'info>>,
+    /// Authority over bettor_token_account: the signer, or the CopyConfig
+    /// PDA acting as the follower's token delegate
+    pub stake_authority: AccountInfo<'info>,
+    /// Signer seeds when stake_authority is a PDA
+    pub stake_authority_seeds: Option<&'a [&'a [u8]]>,
+    /// Wallet whose tokens fund the stake (owns user_volume)
+    pub payer: Pubkey,
+    /// Owner of the bet (the payer, except for gift bets)
+    pub beneficiary: Pubkey,
+    pub token_program: &'a Program<'info, Token>,
+    pub bet_bump: u8,
+    pub bet_record_bump: u8,
+    pub user_nonce: Option<u64>,
+    /// Set when mirroring a leader's bet (copy_bet)
+    pub copy_terms: Option<CopyTerms>,
+}
+
+/// How a copied bet follows its leader's bet
+#[derive(Clone, Copy)]
+pub(crate) struct CopyTerms {
+    pub leader: Pubkey,
+    /// The leader bet's multiplier, used instead of recomputing
+    pub locked_multiplier: u64,
+    pub success_fee_bps: u16,
+}
+
+/// What a placement locked in, returned as instruction return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BetReceipt {
+    /// Bet PDA
+    pub bet: Pubkey,
+
+    /// Pool-wide bet ID
+    pub bet_id: u64,
+
+    /// Nonce in the bettor's namespace (place_user_bet only)
+    pub user_nonce: Option<u64>,
+
+    /// Stake after protocol fee
+    pub amount_after_fee: u64,
+
+    /// Protocol fee charged, in basis points
+    pub fee_bps: u16,
+
+    /// Parlay multiplier locked on the bet
+    pub locked_multiplier: u64,
+
+    /// Locked odds of each leg, in leg order (0 past num_predictions)
+    pub leg_odds: [u64; 10],
+
+    /// sportsbook_core::BetTerms hash, also stored in the BetRecord if one was passed
+    pub terms_hash: [u8; 32],
+
+    /// Payout if every leg wins
+    pub potential_payout: u64,
+}
+
 pub fn handler(
     ctx: Context<PlaceBet>,
     round_id: u64,
     match_indices: Vec<u8>,
     outcomes: Vec<u8>,
     amount: u64,
-) -> Result<()> {
+) -> Result<BetReceipt> {
+    place(ctx.accounts, &ctx.bumps, round_id, match_indices, outcomes, amount)
+}
+
+/// place_bet on its validated accounts; place_insured_bet goes on to
+/// charge the premium on the same accounts
+pub(crate) fn place(
+    accounts: &mut PlaceBet,
+    bumps: &PlaceBetBumps,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
+    let placement = BetPlacement {
+        betting_pool: accounts.betting_pool.as_mut(),
+        round_accounting: accounts.round_accounting.as_mut(),
+        open_liability: accounts.open_liability.as_deref_mut(),
+        daily_volume: accounts.daily_volume.as_deref_mut(),
+        active_bettors: accounts.active_bettors.as_deref_mut(),
+        pending_claims: accounts.pending_claims.as_deref_mut(),
+        user_volume: accounts.user_volume.as_mut(),
+        user_volume_bump: bumps.user_volume,
+        bet: accounts.bet.as_mut(),
+        bet_record: accounts.bet_record.as_mut(),
+        bettor_token_account: accounts.bettor_token_account.as_ref(),
+        betting_pool_token_account: accounts.betting_pool_token_account.as_ref(),
+        round_vault: accounts.round_vault.as_deref(),
+        leg_fee_schedule: accounts.leg_fee_schedule.as_deref(),
+        stake_policy: accounts.stake_policy.as_deref(),
+        protocol_treasury_token_account: accounts.protocol_treasury_token_account.as_ref(),
+        team_token_account: accounts.team_token_account.as_deref(),
+        price_feed: accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        stake_authority: accounts.bettor.to_account_info(),
+        stake_authority_seeds: None,
+        payer: accounts.bettor.key(),
+        beneficiary: accounts.bettor.key(),
+        token_program: &accounts.token_program,
+        bet_bump: bumps.bet,
+        bet_record_bump: bumps.bet_record,
+        user_nonce: None,
+        copy_terms: None,
+    };
+
+    execute_bet(placement, round_id, match_indices, outcomes, amount)
+}
+
+pub fn place_user_bet_handler(
+    ctx: Context<PlaceUserBet>,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
+    let accounts = ctx.accounts;
+
+    // First namespaced bet for this user: fill in the counter
+    if accounts.user_bet_counter.bettor == Pubkey::default() {
+        accounts.user_bet_counter.bettor = accounts.bettor.key();
+        accounts.user_bet_counter.betting_pool = accounts.betting_pool.key();
+        accounts.user_bet_counter.next_nonce = 0;
+        accounts.user_bet_counter.bump = ctx.bumps.user_bet_counter;
+    }
+
+    let user_nonce = accounts.user_bet_counter.next_nonce;
+    accounts.user_bet_counter.next_nonce = user_nonce
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+
+    let placement = BetPlacement {
+        betting_pool: accounts.betting_pool.as_mut(),
+        round_accounting: accounts.round_accounting.as_mut(),
+        open_liability: accounts.open_liability.as_deref_mut(),
+        daily_volume: accounts.daily_volume.as_deref_mut(),
+        active_bettors: accounts.active_bettors.as_deref_mut(),
+        pending_claims: accounts.pending_claims.as_deref_mut(),
+        user_volume: accounts.user_volume.as_mut(),
+        user_volume_bump: ctx.bumps.user_volume,
+        bet: accounts.bet.as_mut(),
+        bet_record: accounts.bet_record.as_mut(),
+        bettor_token_account: accounts.bettor_token_account.as_ref(),
+        betting_pool_token_account: accounts.betting_pool_token_account.as_ref(),
+        round_vault: accounts.round_vault.as_deref(),
+        leg_fee_schedule: accounts.leg_fee_schedule.as_deref(),
+        stake_policy: accounts.stake_policy.as_deref(),
+        protocol_treasury_token_account: accounts.protocol_treasury_token_account.as_ref(),
+        team_token_account: accounts.team_token_account.as_deref(),
+        price_feed: accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        stake_authority: accounts.bettor.to_account_info(),
+        stake_authority_seeds: None,
+        payer: accounts.bettor.key(),
+        beneficiary: accounts.bettor.key(),
+        token_program: &accounts.token_program,
+        bet_bump: ctx.bumps.bet,
+        bet_record_bump: ctx.bumps.bet_record,
+        user_nonce: Some(user_nonce),
+        copy_terms: None,
+    };
+
+    execute_bet(placement, round_id, match_indices, outcomes, amount)
+}
+
+pub fn place_gift_bet_handler(
+    ctx: Context<PlaceGiftBet>,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
+    let accounts = ctx.accounts;
+    let payer = accounts.bettor.key();
+    let beneficiary = accounts.beneficiary.key();
+
+    let placement = BetPlacement {
+        betting_pool: accounts.betting_pool.as_mut(),
+        round_accounting: accounts.round_accounting.as_mut(),
+        open_liability: accounts.open_liability.as_deref_mut(),
+        daily_volume: accounts.daily_volume.as_deref_mut(),
+        active_bettors: accounts.active_bettors.as_deref_mut(),
+        pending_claims: accounts.pending_claims.as_deref_mut(),
+        user_volume: accounts.user_volume.as_mut(),
+        user_volume_bump: ctx.bumps.user_volume,
+        bet: accounts.bet.as_mut(),
+        bet_record: accounts.bet_record.as_mut(),
+        bettor_token_account: accounts.bettor_token_account.as_ref(),
+        betting_pool_token_account: accounts.betting_pool_token_account.as_ref(),
+        round_vault: accounts.round_vault.as_deref(),
+        leg_fee_schedule: accounts.leg_fee_schedule.as_deref(),
+        stake_policy: accounts.stake_policy.as_deref(),
+        protocol_treasury_token_account: accounts.protocol_treasury_token_account.as_ref(),
+        team_token_account: accounts.team_token_account.as_deref(),
+        price_feed: accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        stake_authority: accounts.bettor.to_account_info(),
+        stake_authority_seeds: None,
+        payer,
+        beneficiary,
+        token_program: &accounts.token_program,
+        bet_bump: ctx.bumps.bet,
+        bet_record_bump: ctx.bumps.bet_record,
+        user_nonce: None,
+        copy_terms: None,
+    };
+
+    let receipt = execute_bet(placement, round_id, match_indices, outcomes, amount)?;
+
+    emit!(BetGifted {
+        bet: receipt.bet,
+        bet_id: receipt.bet_id,
+        payer,
+        beneficiary,
+    });
+
+    msg!("Gifted by {} to {}", payer, beneficiary);
+
+    Ok(receipt)
+}
+
+pub(crate) fn execute_bet(
+    mut ctx: BetPlacement,
+    round_id: u64,
+    match_indices: Vec<u8>,
+    outcomes: Vec<u8>,
+    amount: u64,
+) -> Result<BetReceipt> {
     // Validate inputs
     require!(amount > 0, SportsbookError::InvalidAmount);
-    require!(amount <= MAX_BET_AMOUNT, SportsbookError::BetExceedsMaximum);
+
+    // Pool minimum stake, and the unit fees and payouts are floored to
+    let rounding_unit = if ctx.betting_pool.stake_policy {
+        let stake_policy = ctx.stake_policy.ok_or(SportsbookError::StakePolicyRequired)?;
+        require!(amount >= stake_policy.min_stake, SportsbookError::StakeBelowMinimum);
+        stake_policy.rounding_unit
+    } else {
+        0
+    };
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Value the stake in USD for non-stablecoin pools
+    let price_snapshot = load_pool_price(
+        &ctx.betting_pool.price_feed,
+        ctx.price_feed,
+        current_time,
+    )?;
+    let stake_usd = match price_snapshot {
+        Some(ref snapshot) => token_amount_to_usd(amount, ctx.betting_pool.token_decimals, snapshot)
+            .ok_or(SportsbookError::CalculationOverflow)?,
+        None => 0,
+    };
+
+    // Priced pools cap bets in USD, stablecoin pools in raw token amount
+    let max_bet_usd = ctx.betting_pool.max_bet_usd;
+    if price_snapshot.is_some() && max_bet_usd > 0 {
+        require_with_context!(
+            stake_usd <= max_bet_usd,
+            SportsbookError::BetExceedsMaximum,
+            LiquidityCheckFailed {
+                betting_pool: ctx.betting_pool.key(),
+                required: stake_usd,
+                available: max_bet_usd,
+            }
+        );
+    } else {
+        require_with_context!(
+            amount <= MAX_BET_AMOUNT,
+            SportsbookError::BetExceedsMaximum,
+            LiquidityCheckFailed {
+                betting_pool: ctx.betting_pool.key(),
+                required: amount,
+                available: MAX_BET_AMOUNT,
+            }
+        );
+    }
     require!(
         match_indices.len() == outcomes.len(),
         SportsbookError::ArrayLengthMismatch
     );
     require!(
-        match_indices.len() > 0 && match_indices.len() <= MATCHES_PER_ROUND,
+        !match_indices.is_empty() && match_indices.len() <= MATCHES_PER_ROUND,
         SportsbookError::InvalidBetCount
     );
 
@@ -85,15 +809,71 @@ pub fn handler(
             outcomes[i] >= 1 && outcomes[i] <= 3,
             SportsbookError::InvalidOutcome
         );
+        require!(
+            !ctx.round_accounting.is_kickoff_locked(match_indices[i] as usize),
+            SportsbookError::MatchAlreadyLocked
+        );
     }
 
+    // Pool-configured bet limits
+    let max_parlay_legs = match ctx.betting_pool.max_parlay_legs {
+        0 => MATCHES_PER_ROUND,
+        legs => legs as usize,
+    };
+    require!(
+        match_indices.len() <= max_parlay_legs,
+        SportsbookError::TooManyLegs
+    );
+
+    let leg_odds: Vec<u64> = match_indices
+        .iter()
+        .zip(outcomes.iter())
+        .map(|(match_index, outcome)| {
+            ctx.round_accounting.locked_odds[*match_index as usize].get_odds(*outcome)
+        })
+        .collect();
+
+    let min_leg_odds = ctx.betting_pool.min_leg_odds;
+    require!(
+        leg_odds.iter().all(|odds| *odds >= min_leg_odds),
+        SportsbookError::LegOddsTooLow
+    );
+
     // Extract all account infos, keys, and bumps BEFORE any mutable borrows
-    let betting_pool_info = ctx.accounts.betting_pool.to_account_info();
-    let betting_pool_bump = ctx.accounts.betting_pool.bump;
-    let betting_pool_fee_bps = ctx.accounts.betting_pool.protocol_fee_bps;
+    let betting_pool_info = ctx.betting_pool.to_account_info();
+    let betting_pool_bump = ctx.betting_pool.bump;
+    let betting_pool_key = ctx.betting_pool.key();
+    let round_info = ctx.round_accounting.to_account_info();
+    let round_id_bytes = round_id.to_le_bytes();
+    let round_bump = ctx.round_accounting.bump;
+
+    // Isolated rounds take stakes into, and pay fees out of, their own vault
+    let isolated = ctx.round_accounting.isolated;
+    let funds_account = round_funds(isolated, ctx.betting_pool_token_account, ctx.round_vault)?;
+    let funds_check = FundsCheck::start(ctx.round_accounting, Some(funds_account.to_account_info()))?;
+
+    // First bet by this bettor: fill in their volume account
+    let new_bettor = ctx.user_volume.bettor == Pubkey::default();
+    if new_bettor {
+        ctx.user_volume.bettor = ctx.payer;
+        ctx.user_volume.betting_pool = ctx.betting_pool.key();
+        ctx.user_volume.bump = ctx.user_volume_bump;
+    }
+
+    // Base fee by parlay length, then the tier picked from volume before
+    // this bet
+    let base_fee_bps = if ctx.betting_pool.leg_fees {
+        ctx.leg_fee_schedule
+            .ok_or(SportsbookError::LegFeeScheduleRequired)?
+            .base_fee_bps(match_indices.len(), ctx.betting_pool.protocol_fee_bps)
+    } else {
+        ctx.betting_pool.protocol_fee_bps
+    };
+    let rolling_volume = ctx.user_volume.rolling_volume(current_time);
+    let tier_fee_bps = ctx.betting_pool.fee_bps_for_bet(rolling_volume, base_fee_bps);
 
     // Check if user holds team tokens for benefits
-    let has_team_tokens = if let Some(ref team_token_account) = ctx.accounts.team_token_account {
+    let has_team_tokens = if let Some(team_token_account) = ctx.team_token_account {
         team_token_account.amount >= MIN_TEAM_TOKEN_BALANCE
     } else {
         false
@@ -101,58 +881,69 @@ pub fn handler(
 
     // Transfer user's stake
     let cpi_accounts = Transfer {
-        from: ctx.accounts.bettor_token_account.to_account_info(),
-        to: ctx.accounts.betting_pool_token_account.to_account_info(),
-        authority: ctx.accounts.bettor.to_account_info(),
+        from: ctx.bettor_token_account.to_account_info(),
+        to: funds_account.to_account_info(),
+        authority: ctx.stake_authority.clone(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
+    let cpi_program = ctx.token_program.to_account_info();
+    match ctx.stake_authority_seeds {
+        Some(seeds) => {
+            let signer = &[seeds];
+            token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+        }
+        None => token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?,
+    }
 
-    // Deduct protocol fee (reduced for team token holders)
+    // Deduct protocol fee (volume tier, or the team token rate if lower)
     let fee_bps = if has_team_tokens {
-        TEAM_TOKEN_FEE_BPS
+        tier_fee_bps.min(TEAM_TOKEN_FEE_BPS)
     } else {
-        betting_pool_fee_bps
+        tier_fee_bps
     };
 
-    let protocol_fee = (amount as u128)
-        .checked_mul(fee_bps as u128)
-        .ok_or(SportsbookError::CalculationOverflow)?
-        .checked_div(BPS_DENOMINATOR as u128)
-        .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    // A fee remainder below the rounding unit stays with the stake
+    let (protocol_fee, _) = calculate_protocol_fee(amount, fee_bps).map_err(SportsbookError::from)?;
+    let (protocol_fee, _) = round_down_to_unit(protocol_fee, rounding_unit);
+    let amount_after_fee = amount - protocol_fee;
 
-    let amount_after_fee = amount.saturating_sub(protocol_fee);
-
-    // Transfer fee to treasury
-    let seeds = &[
-        b"betting_pool".as_ref(),
-        &[betting_pool_bump],
-    ];
-    let signer = &[&seeds[..]];
+    // Transfer fee to treasury, signed by whichever PDA owns the funds
+    let pool_bump = [betting_pool_bump];
+    let round_bump = [round_bump];
+    let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if isolated {
+        (round_info, &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump])
+    } else {
+        (betting_pool_info, &[BETTING_POOL_SEED, &pool_bump])
+    };
+    let signer = &[seeds];
 
     let cpi_accounts = Transfer {
-        from: ctx.accounts.betting_pool_token_account.to_account_info(),
-        to: ctx.accounts.protocol_treasury_token_account.to_account_info(),
-        authority: betting_pool_info.clone(),
+        from: funds_account.to_account_info(),
+        to: ctx.protocol_treasury_token_account.to_account_info(),
+        authority: funds_authority,
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = ctx.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
     token::transfer(cpi_ctx, protocol_fee)?;
 
-    ctx.accounts.round_accounting.protocol_fee_collected += protocol_fee;
-    ctx.accounts.round_accounting.total_bet_volume += amount_after_fee;
-    ctx.accounts.round_accounting.total_user_deposits += amount_after_fee;
+    ctx.round_accounting.protocol_fee_collected += protocol_fee;
+    ctx.round_accounting.total_bet_volume += amount_after_fee;
+    ctx.round_accounting.total_user_deposits += amount_after_fee;
+    ctx.round_accounting.total_volume_usd = ctx.round_accounting
+        .total_volume_usd
+        .saturating_add(stake_usd);
 
     // Determine if this is a parlay
     let is_parlay = match_indices.len() > 1;
 
-    // Calculate dynamic parlay multiplier
-    let mut parlay_multiplier = calculate_parlay_multiplier_dynamic(
-        &ctx.accounts.round_accounting,
-        &match_indices,
-        match_indices.len() as u8,
-    );
+    // Calculate dynamic parlay multiplier (copies take their leader's)
+    let mut parlay_multiplier = match ctx.copy_terms {
+        Some(terms) => terms.locked_multiplier,
+        None => calculate_parlay_multiplier_dynamic(
+            ctx.round_accounting,
+            &match_indices,
+            match_indices.len() as u8,
+        ),
+    };
 
     // Apply odds boost for team token holders (5% better multiplier)
     if has_team_tokens {
@@ -167,6 +958,16 @@ pub fn handler(
         msg!("Team token holder: odds boost applied (+{})", boost);
     }
 
+    let max_combined_odds = ctx.betting_pool.max_combined_odds;
+    if max_combined_odds > 0 {
+        let combined_odds = calculate_combined_odds(&leg_odds, parlay_multiplier)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        require!(
+            combined_odds <= max_combined_odds,
+            SportsbookError::CombinedOddsTooHigh
+        );
+    }
+
     // CRITICAL: Check protocol has enough capital to cover potential payout
     // This prevents insolvency if multiple large parlays win
     let max_possible_payout = calculate_max_payout(
@@ -175,21 +976,28 @@ pub fn handler(
         parlay_multiplier,
     );
 
-    let current_balance = ctx.accounts.betting_pool_token_account.amount;
-    require!(
+    let current_balance = funds_account.amount;
+    require_with_context!(
         current_balance >= max_possible_payout,
-        SportsbookError::InsufficientProtocolLiquidity
+        SportsbookError::InsufficientProtocolLiquidity,
+        LiquidityCheckFailed {
+            betting_pool: ctx.betting_pool.key(),
+            required: max_possible_payout,
+            available: current_balance,
+        }
     );
 
+    ctx.round_accounting.bets_placed = ctx.round_accounting.bets_placed.saturating_add(1);
+
     // Increment parlay count (FOMO mechanism)
     if is_parlay {
-        ctx.accounts.round_accounting.parlay_count += 1;
+        ctx.round_accounting.parlay_count += 1;
     }
 
     // Calculate odds-weighted allocations
     // Note: Protocol provides all liquidity, no borrowing needed
     let (allocations, total_allocated, _lp_borrowed) = calculate_odds_weighted_allocations(
-        &ctx.accounts.round_accounting,
+        ctx.round_accounting,
         &match_indices,
         &outcomes,
         amount_after_fee,
@@ -197,25 +1005,62 @@ pub fn handler(
     )
     .map_err(|_| SportsbookError::CalculationOverflow)?;
 
-    // Get bet ID and increment
-    let bet_id = ctx.accounts.betting_pool.next_bet_id;
-    ctx.accounts.betting_pool.next_bet_id += 1;
+    // Reject now rather than have the per-bet cap silently truncate at claim
+    let legs: Vec<(u64, u64)> = allocations
+        .iter()
+        .copied()
+        .zip(leg_odds.iter().copied())
+        .collect();
+    let potential_payout = calculate_potential_payout(&legs, parlay_multiplier)
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    let (potential_payout, payout_dust) = round_down_to_unit(potential_payout, rounding_unit);
+    require_with_context!(
+        potential_payout <= MAX_PAYOUT_PER_BET,
+        SportsbookError::PayoutExceedsCap,
+        PayoutCheckFailed {
+            bet: ctx.bet.key(),
+            round_id,
+            payout: potential_payout,
+            limit: MAX_PAYOUT_PER_BET,
+            round_paid_out: 0,
+        }
+    );
+
+    // Bet ID comes from the pool counter (the bet PDA is seeded with it),
+    // never from the caller
+    let bet_id = ctx.betting_pool.next_bet_id;
+    ctx.betting_pool.next_bet_id = bet_id
+        .checked_add(1)
+        .ok_or(SportsbookError::CalculationOverflow)?;
 
     // Store bet
-    ctx.accounts.bet.bettor = ctx.accounts.bettor.key();
-    ctx.accounts.bet.round_id = round_id;
-    ctx.accounts.bet.bet_id = bet_id;
-    ctx.accounts.bet.amount = amount;
-    ctx.accounts.bet.amount_after_fee = amount_after_fee;
-    ctx.accounts.bet.allocated_amount = total_allocated;
-    ctx.accounts.bet.bonus = 0; // No bonus
-    ctx.accounts.bet.locked_multiplier = parlay_multiplier;
-    ctx.accounts.bet.num_predictions = match_indices.len() as u8;
-    ctx.accounts.bet.settled = false;
-    ctx.accounts.bet.claimed = false;
-    ctx.accounts.bet.claim_deadline = 0; // Will be set when round is settled
-    ctx.accounts.bet.bounty_claimer = None;
-    ctx.accounts.bet.bump = ctx.bumps.bet;
+    ctx.bet.bettor = ctx.beneficiary;
+    ctx.bet.round_id = round_id;
+    ctx.bet.bet_id = bet_id;
+    ctx.bet.amount = amount;
+    ctx.bet.amount_after_fee = amount_after_fee;
+    ctx.bet.allocated_amount = total_allocated;
+    ctx.bet.bonus = 0; // No bonus
+    ctx.bet.locked_multiplier = parlay_multiplier;
+    ctx.bet.num_predictions = match_indices.len() as u8;
+    ctx.bet.settled = false;
+    ctx.bet.claimed = false;
+    ctx.bet.claim_deadline = 0; // Will be set when round is settled
+    ctx.bet.bounty_claimer = None;
+    ctx.bet.price_at_placement = price_snapshot.unwrap_or_default();
+    ctx.bet.stake_usd = stake_usd;
+    ctx.bet.user_nonce = ctx.user_nonce;
+    ctx.bet.bump = ctx.bet_bump;
+    ctx.bet.potential_payout = potential_payout;
+    ctx.bet.fee_bps = fee_bps;
+    ctx.bet.promo_bonus = 0;
+    ctx.bet.copy_leader = ctx.copy_terms.map_or(Pubkey::default(), |terms| terms.leader);
+    ctx.bet.copy_fee_bps = ctx.copy_terms.map_or(0, |terms| terms.success_fee_bps);
+    ctx.bet.season_points_accrued = false;
+    ctx.bet.payout_dust = payout_dust;
+    ctx.bet.payout_reserved = false;
+    ctx.bet.insured = false;
+    ctx.bet.reserved = [0; 1];
 
     // Add predictions and update pools
     let mut predictions = [Prediction {
@@ -223,6 +1068,7 @@ pub fn handler(
         predicted_outcome: 0,
         amount_in_pool: 0,
     }; 10];
+    let mut leg_odds = [0u64; 10];
 
     for i in 0..match_indices.len() {
         let match_index = match_indices[i];
@@ -236,16 +1082,91 @@ pub fn handler(
         };
 
         // Add to appropriate match pool (with overflow protection)
-        let pool = &mut ctx.accounts.round_accounting.match_pools[match_index as usize];
+        let pool = &mut ctx.round_accounting.match_pools[match_index as usize];
         pool.add_to_pool(outcome, allocation)?;
+
+        // Track what this leg pays if its outcome comes in
+        let odds = ctx.round_accounting.locked_odds[match_index as usize].get_odds(outcome);
+        leg_odds[i] = odds;
+        let leg_payout = (allocation as u128)
+            .checked_mul(odds as u128)
+            .ok_or(SportsbookError::CalculationOverflow)?
+            .checked_mul(parlay_multiplier as u128)
+            .ok_or(SportsbookError::CalculationOverflow)?
+            .checked_div((ODDS_SCALE as u128) * (ODDS_SCALE as u128))
+            .ok_or(SportsbookError::CalculationOverflow)? as u64;
+        if let Some(open_liability) = ctx.open_liability.as_deref_mut() {
+            open_liability.record_leg(match_index, outcome, allocation, leg_payout)?;
+        }
     }
 
-    ctx.accounts.bet.predictions = predictions;
+    ctx.bet.predictions = predictions;
+    if let Some(open_liability) = ctx.open_liability.as_deref_mut() {
+        open_liability.record_bet(potential_payout)?;
+    }
+
+    let leg_terms: Vec<BetLegTerms> = predictions[..match_indices.len()]
+        .iter()
+        .zip(leg_odds)
+        .map(|(prediction, odds)| BetLegTerms {
+            match_index: prediction.match_index,
+            outcome: prediction.predicted_outcome,
+            odds,
+        })
+        .collect();
+    let terms_hash = BetTerms {
+        bettor: ctx.beneficiary.to_bytes(),
+        round_id,
+        stake: amount,
+        locked_multiplier: parlay_multiplier,
+        legs: &leg_terms,
+    }
+    .hash();
+    ctx.bet_record.bet = ctx.bet.key();
+    ctx.bet_record.terms_hash = terms_hash;
+    ctx.bet_record.leg_bits = leg_bitmap(match_indices.iter().copied().zip(outcomes.iter().copied()));
+    ctx.bet_record.bump = ctx.bet_record_bump;
+
+    // Dashboard read models
+    if let Some(daily_volume) = ctx.daily_volume {
+        daily_volume.record_bet(current_time, amount, protocol_fee, stake_usd);
+    }
+    if let Some(active_bettors) = ctx.active_bettors {
+        active_bettors.record_bet(ctx.beneficiary, new_bettor, current_time);
+    }
+    if let Some(pending_claims) = ctx.pending_claims {
+        pending_claims.record_bet(potential_payout);
+    }
+    funds_check.finish(ctx.round_accounting, 0)?;
+
+    // Counts towards the bettor's fee tier from the next bet on
+    ctx.user_volume.record_bet(current_time, amount);
+
+    emit!(BetPlaced {
+        betting_pool: ctx.betting_pool.key(),
+        bet: ctx.bet.key(),
+        bet_id,
+        bettor: ctx.beneficiary,
+        round_id,
+        amount,
+        num_predictions: match_indices.len() as u8,
+    });
 
     msg!("Bet {} placed successfully", bet_id);
-    msg!("Amount: {}, After fee: {}", amount, amount_after_fee);
+    msg!("Amount: {}, After fee: {} ({}bps)", amount, amount_after_fee, fee_bps);
     msg!("Parlay multiplier: {}", parlay_multiplier);
     msg!("Allocated: {}", total_allocated);
+    msg!("Potential payout: {}", potential_payout);
 
-    Ok(())
+    Ok(BetReceipt {
+        bet: ctx.bet.key(),
+        bet_id,
+        user_nonce: ctx.user_nonce,
+        amount_after_fee,
+        fee_bps,
+        locked_multiplier: parlay_multiplier,
+        leg_odds,
+        terms_hash,
+        potential_payout,
+    })
 }
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, PnLedger};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Create the pool's protocol PnL ledger
+///
+/// Rounds are recorded from the first finalize_round_revenue that is given
+/// the ledger; rounds finalized before it existed aren't backfilled.
+#[derive(Accounts)]
+pub struct CreatePnLedger<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PnLedger::LEN,
+        seeds = [PN_LEDGER_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub pn_ledger: Box<Account<'info, PnLedger>>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_pn_ledger_handler(ctx: Context<CreatePnLedger>) -> Result<()> {
+    let pn_ledger = &mut ctx.accounts.pn_ledger;
+    pn_ledger.betting_pool = ctx.accounts.betting_pool.key();
+    pn_ledger.bump = ctx.bumps.pn_ledger;
+
+    msg!("PnL ledger created for pool {}", pn_ledger.betting_pool);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+use crate::errors::SportsbookError;
+use crate::pyth::{is_pyth_oracle_program, parse_price_account};
+
+/// Configure USD valuation for a non-stablecoin pool
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    /// Pyth price account for the betting token
+    /// Pass the system program to switch USD valuation off (stablecoin pool)
+    /// CHECK: Owner checked against the Pyth oracle program and layout
+    /// validated by parse_price_account when enabling
+    pub price_feed: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPriceFeed>, max_bet_usd: u64) -> Result<()> {
+    let price_feed_key = ctx.accounts.price_feed.key();
+
+    if price_feed_key == System::id() {
+        ctx.accounts.betting_pool.price_feed = Pubkey::default();
+        ctx.accounts.betting_pool.max_bet_usd = 0;
+
+        msg!("USD valuation disabled (stablecoin pool)");
+        return Ok(());
+    }
+
+    // Make sure it really is a Pyth price account before trusting it:
+    // the layout alone can be copied into any account
+    require!(
+        is_pyth_oracle_program(ctx.accounts.price_feed.owner),
+        SportsbookError::InvalidPriceFeed
+    );
+    {
+        let data = ctx.accounts.price_feed.try_borrow_data()?;
+        parse_price_account(&data)?;
+    }
+
+    ctx.accounts.betting_pool.price_feed = price_feed_key;
+    ctx.accounts.betting_pool.max_bet_usd = max_bet_usd;
+
+    msg!("Price feed set to {}", price_feed_key);
+    msg!("Max bet: {} USD (6 decimals)", max_bet_usd);
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{BettingPool, RoundAccounting, Bet, Promo, PromoRedemption};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::events::PromoRedeemed;
+
+/// Create a promo funded from the pool's liquidity
+///
+/// `code_hash` is sha256 of the plaintext code, so the code itself only
+/// becomes public when someone redeems it.
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePromo<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Promo::LEN,
+        seeds = [PROMO_SEED, betting_pool.key().as_ref(), code_hash.as_ref()],
+        bump
+    )]
+    pub promo: Account<'info, Promo>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_promo_handler(
+    ctx: Context<CreatePromo>,
+    code_hash: [u8; 32],
+    budget: u64,
+    bonus_bps: u16,
+    expiry: i64,
+    max_redemptions_per_wallet: u8,
+) -> Result<()> {
+    require!(budget > 0, SportsbookError::InvalidAmount);
+    require!(
+        bonus_bps > 0 && bonus_bps as u64 <= BPS_DENOMINATOR,
+        SportsbookError::InvalidPromo
+    );
+    require!(max_redemptions_per_wallet > 0, SportsbookError::InvalidPromo);
+    require!(
+        expiry > pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?,
+        SportsbookError::PromoExpired
+    );
+
+    let promo = &mut ctx.accounts.promo;
+    promo.betting_pool = ctx.accounts.betting_pool.key();
+    promo.code_hash = code_hash;
+    promo.budget = budget;
+    promo.spent = 0;
+    promo.bonus_bps = bonus_bps;
+    promo.expiry = expiry;
+    promo.max_redemptions_per_wallet = max_redemptions_per_wallet;
+    promo.redemptions = 0;
+    promo.bump = ctx.bumps.promo;
+
+    msg!("Promo {} created", promo.key());
+    msg!("Budget: {}, bonus: {}bps, expires: {}", budget, bonus_bps, expiry);
+    msg!("Max redemptions per wallet: {}", max_redemptions_per_wallet);
+
+    Ok(())
+}
+
+/// Redeem a promo code against one of the caller's open bets
+///
+/// Pays `bonus_bps` of the bet's stake from the pool to the bettor. Each bet
+/// can carry one promo, each wallet redeems a promo at most
+/// `max_redemptions_per_wallet` times, and a redemption that would take the
+/// promo over budget fails.
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RedeemPromo<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [PROMO_SEED, betting_pool.key().as_ref(), hash(code.as_bytes()).as_ref()],
+        bump = promo.bump,
+    )]
+    pub promo: Account<'info, Promo>,
+
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = PromoRedemption::LEN,
+        seeds = [PROMO_REDEMPTION_SEED, promo.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub promo_redemption: Account<'info, PromoRedemption>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bettor == bettor.key() @ SportsbookError::InvalidBetAccount,
+        constraint = bet.promo_bonus == 0 @ SportsbookError::PromoAlreadyApplied,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Betting pool's token account (funds the bonus)
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Bettor's token account (receives the bonus)
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bettor.key() @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn redeem_promo_handler(ctx: Context<RedeemPromo>, _code: String) -> Result<()> {
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    require!(
+        current_time <= ctx.accounts.promo.expiry,
+        SportsbookError::PromoExpired
+    );
+
+    // First redemption by this wallet: fill in the record
+    let redemption = &mut ctx.accounts.promo_redemption;
+    if redemption.wallet == Pubkey::default() {
+        redemption.promo = ctx.accounts.promo.key();
+        redemption.wallet = ctx.accounts.bettor.key();
+        redemption.bump = ctx.bumps.promo_redemption;
+    }
+    require!(
+        redemption.count < ctx.accounts.promo.max_redemptions_per_wallet,
+        SportsbookError::PromoRedemptionLimitReached
+    );
+
+    let bonus = (ctx.accounts.bet.amount as u128)
+        .checked_mul(ctx.accounts.promo.bonus_bps as u128)
+        .ok_or(SportsbookError::CalculationOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(SportsbookError::CalculationOverflow)? as u64;
+    require!(bonus > 0, SportsbookError::InvalidAmount);
+    require!(
+        bonus <= ctx.accounts.promo.remaining_budget(),
+        SportsbookError::PromoBudgetExhausted
+    );
+
+    let seeds = &[BETTING_POOL_SEED, &[ctx.accounts.betting_pool.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.betting_pool_token_account.to_account_info(),
+        to: ctx.accounts.bettor_token_account.to_account_info(),
+        authority: ctx.accounts.betting_pool.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, bonus)?;
+
+    let promo = &mut ctx.accounts.promo;
+    promo.spent = promo.spent.saturating_add(bonus);
+    promo.redemptions = promo.redemptions.saturating_add(1);
+
+    let redemption = &mut ctx.accounts.promo_redemption;
+    redemption.count += 1;
+    redemption.total_bonus = redemption.total_bonus.saturating_add(bonus);
+
+    ctx.accounts.bet.promo_bonus = bonus;
+
+    emit!(PromoRedeemed {
+        promo: promo.key(),
+        bet: ctx.accounts.bet.key(),
+        wallet: ctx.accounts.bettor.key(),
+        bonus,
+        remaining_budget: promo.remaining_budget(),
+    });
+
+    msg!("Promo redeemed on bet {}", ctx.accounts.bet.bet_id);
+    msg!("Bonus: {}, remaining budget: {}", bonus, promo.remaining_budget());
+
+    Ok(())
+}
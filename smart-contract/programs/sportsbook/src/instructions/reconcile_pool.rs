@@ -0,0 +1,329 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{BettingPool, RoundAccounting, RoundArchive, ReconcileCursor, LotteryConfig};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::{PoolReconciled, SolvencyDiscrepancy};
+
+/// Round IDs start here (see initialize)
+pub(crate) const FIRST_ROUND_ID: u64 = 1;
+
+/// Create the pool's reconcile cursor, starting at the first round
+///
+/// Permissionless: the cursor only ever moves past rounds that are shown
+/// to be closed, so whoever pays its rent gains no control over it.
+#[derive(Accounts)]
+pub struct InitializeReconcileCursor<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReconcileCursor::LEN,
+        seeds = [RECONCILE_CURSOR_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub reconcile_cursor: Account<'info, ReconcileCursor>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_reconcile_cursor_handler(ctx: Context<InitializeReconcileCursor>) -> Result<()> {
+    let reconcile_cursor = &mut ctx.accounts.reconcile_cursor;
+    reconcile_cursor.betting_pool = ctx.accounts.betting_pool.key();
+    reconcile_cursor.closed_below = FIRST_ROUND_ID;
+    reconcile_cursor.bump = ctx.bumps.reconcile_cursor;
+
+    msg!("Reconcile cursor created for {}", reconcile_cursor.betting_pool);
+
+    Ok(())
+}
+
+/// Proof-of-solvency check for the betting pool
+///
+/// Permissionless. `remaining_accounts` must hold every round from the
+/// cursor's `closed_below` (the first round without a cursor) up to
+/// `betting_pool.next_round_id`, in round order: the live RoundAccounting,
+/// or the RoundArchive of a round that has been archived. A set with a
+/// round missing is rejected, so an insolvent round can't be left out.
+/// The outstanding winnings of those rounds plus the season reward pool
+/// and the lottery's funds are compared against the live token balance. Isolated rounds are
+/// skipped: their winnings are held in their vaults.
+#[derive(Accounts)]
+pub struct ReconcilePool<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    /// Betting pool's token account (holds stakes, seed and season rewards)
+    #[account(
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Optional: the pool's reconcile cursor; rounds below it needn't be
+    /// passed, and it moves past the leading closed rounds that are
+    #[account(
+        mut,
+        seeds = [RECONCILE_CURSOR_SEED, betting_pool.key().as_ref()],
+        bump = reconcile_cursor.bump,
+    )]
+    pub reconcile_cursor: Option<Account<'info, ReconcileCursor>>,
+
+    /// The pool's LotteryConfig address, passed whether or not the pool
+    /// runs a lottery
+    /// CHECK: Address checked by seeds; read by lottery_funds_held
+    #[account(
+        seeds = [LOTTERY_CONFIG_SEED, betting_pool.key().as_ref()],
+        bump,
+    )]
+    pub lottery_config: UncheckedAccount<'info>,
+}
+
+/// Lottery reserve and unclaimed prizes held in the pool token account,
+/// read from the pool's LotteryConfig PDA (0 if no lottery is configured)
+pub(crate) fn lottery_funds_held(lottery_config_info: &AccountInfo) -> Result<u64> {
+    if lottery_config_info.data_is_empty() {
+        return Ok(0);
+    }
+
+    require!(
+        lottery_config_info.owner == &crate::ID,
+        SportsbookError::InvalidLotteryConfig
+    );
+    let data = lottery_config_info.try_borrow_data()?;
+    let lottery_config = LotteryConfig::try_deserialize(&mut &data[..])?;
+
+    Ok(lottery_config
+        .funds_held()
+        .ok_or(SportsbookError::CalculationOverflow)?)
+}
+
+/// Winnings owed by a pool's unclosed rounds, from a complete round set
+pub(crate) struct RoundTally {
+    /// Number of rounds in the set
+    pub rounds_checked: u32,
+
+    /// Sum of `total_reserved_for_winners` across rounds that still owe
+    pub total_reserved: u64,
+
+    /// Sum of `total_claimed` across rounds that still owe
+    pub total_claimed: u64,
+
+    /// Winnings still owed out of the pool token account
+    pub outstanding_winnings: u64,
+
+    /// Every round below this is closed
+    pub closed_below: u64,
+}
+
+/// Sum what the pool's unclosed rounds owe, requiring `round_infos` to be
+/// exactly the rounds `first_round_id..next_round_id`, each either its
+/// live RoundAccounting or its RoundArchive
+pub(crate) fn tally_unclosed_rounds(
+    betting_pool: &Account<BettingPool>,
+    first_round_id: u64,
+    round_infos: &[AccountInfo],
+) -> Result<RoundTally> {
+    let betting_pool_key = betting_pool.key();
+    let round_count = betting_pool
+        .next_round_id
+        .checked_sub(first_round_id)
+        .ok_or(SportsbookError::IncompleteRoundSet)?;
+    require!(
+        round_infos.len() as u64 == round_count,
+        SportsbookError::IncompleteRoundSet
+    );
+
+    let mut tally = RoundTally {
+        rounds_checked: round_infos.len() as u32,
+        total_reserved: 0,
+        total_claimed: 0,
+        outstanding_winnings: 0,
+        closed_below: first_round_id,
+    };
+
+    for (round_id, round_info) in (first_round_id..).zip(round_infos.iter()) {
+        let is_archive = round_info.try_borrow_data()?.get(..8) == Some(RoundArchive::DISCRIMINATOR);
+        if is_archive {
+            // Archived rounds were finalized before their accounts closed
+            load_archive(round_info, &betting_pool_key, round_id)?;
+            if tally.closed_below == round_id {
+                tally.closed_below = round_id + 1;
+            }
+            continue;
+        }
+
+        let round = load_round(round_info, &betting_pool_key)?;
+        require!(
+            round.round_id == round_id,
+            SportsbookError::IncompleteRoundSet
+        );
+
+        let owes = round.outstanding_liability();
+        if tally.closed_below == round_id && round.revenue_distributed && owes == 0 {
+            tally.closed_below = round_id + 1;
+        }
+
+        // Rounds that have been finalized owe nothing anymore, and
+        // isolated rounds pay from their own vault, not this account
+        if owes == 0 || round.isolated {
+            continue;
+        }
+
+        tally.total_reserved = tally
+            .total_reserved
+            .checked_add(round.total_reserved_for_winners)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        tally.total_claimed = tally
+            .total_claimed
+            .checked_add(round.total_claimed)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        tally.outstanding_winnings = tally
+            .outstanding_winnings
+            .checked_add(owes)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+    }
+
+    Ok(tally)
+}
+
+pub fn handler(ctx: Context<ReconcilePool>, strict: bool) -> Result<()> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+
+    let first_round_id = ctx
+        .accounts
+        .reconcile_cursor
+        .as_ref()
+        .map_or(FIRST_ROUND_ID, |cursor| cursor.closed_below);
+    let RoundTally {
+        rounds_checked,
+        total_reserved,
+        total_claimed,
+        outstanding_winnings,
+        closed_below,
+    } = tally_unclosed_rounds(&ctx.accounts.betting_pool, first_round_id, ctx.remaining_accounts)?;
+
+    if let Some(reconcile_cursor) = ctx.accounts.reconcile_cursor.as_mut() {
+        if closed_below > reconcile_cursor.closed_below {
+            reconcile_cursor.closed_below = closed_below;
+            msg!("Reconcile cursor moved to round {}", closed_below);
+        }
+    }
+
+    let season_reward_pool = ctx.accounts.betting_pool.season_reward_pool;
+    let lottery_funds = lottery_funds_held(&ctx.accounts.lottery_config)?;
+    let required_balance = outstanding_winnings
+        .checked_add(season_reward_pool)
+        .and_then(|required| required.checked_add(lottery_funds))
+        .ok_or(SportsbookError::CalculationOverflow)?;
+    let token_balance = ctx.accounts.betting_pool_token_account.amount;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    emit!(PoolReconciled {
+        betting_pool: betting_pool_key,
+        rounds_checked,
+        total_reserved,
+        total_claimed,
+        outstanding_winnings,
+        season_reward_pool,
+        lottery_funds,
+        required_balance,
+        token_balance,
+        timestamp,
+    });
+
+    if token_balance < required_balance {
+        let shortfall = required_balance - token_balance;
+
+        emit!(SolvencyDiscrepancy {
+            betting_pool: betting_pool_key,
+            required_balance,
+            token_balance,
+            shortfall,
+            timestamp,
+        });
+
+        msg!("Pool shortfall: {} (required {}, balance {})", shortfall, required_balance, token_balance);
+
+        // In strict mode the transaction fails, so the check can be composed
+        // as a guard in front of other instructions
+        if strict {
+            return err!(SportsbookError::PoolInsolvent);
+        }
+    } else {
+        msg!("Pool solvent: required {}, balance {}", required_balance, token_balance);
+    }
+
+    Ok(())
+}
+
+/// Deserialize a round passed through `remaining_accounts` and check it
+/// belongs to this betting pool
+fn load_round(round_info: &AccountInfo, betting_pool_key: &Pubkey) -> Result<RoundAccounting> {
+    require!(
+        round_info.owner == &crate::ID,
+        SportsbookError::InvalidRoundAccount
+    );
+
+    let data = round_info.try_borrow_data()?;
+    let round = RoundAccounting::try_deserialize(&mut &data[..])?;
+
+    require!(
+        round.betting_pool == *betting_pool_key,
+        SportsbookError::InvalidRoundAccount
+    );
+
+    let expected_key = Pubkey::create_program_address(
+        &[
+            ROUND_SEED,
+            betting_pool_key.as_ref(),
+            round.round_id.to_le_bytes().as_ref(),
+            &[round.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| SportsbookError::InvalidRoundAccount)?;
+    require!(
+        expected_key == *round_info.key,
+        SportsbookError::InvalidRoundAccount
+    );
+
+    Ok(round)
+}
+
+/// Check an account passed in place of round `round_id` is that round's
+/// RoundArchive
+fn load_archive(archive_info: &AccountInfo, betting_pool_key: &Pubkey, round_id: u64) -> Result<()> {
+    require!(
+        archive_info.owner == &crate::ID,
+        SportsbookError::InvalidRoundAccount
+    );
+
+    let data = archive_info.try_borrow_data()?;
+    let archive = RoundArchive::try_deserialize(&mut &data[..])?;
+
+    require!(
+        archive.betting_pool == *betting_pool_key && archive.round_id == round_id,
+        SportsbookError::IncompleteRoundSet
+    );
+
+    let expected_key = Pubkey::create_program_address(
+        &[
+            ROUND_ARCHIVE_SEED,
+            betting_pool_key.as_ref(),
+            round_id.to_le_bytes().as_ref(),
+            &[archive.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| SportsbookError::InvalidRoundAccount)?;
+    require!(
+        expected_key == *archive_info.key,
+        SportsbookError::InvalidRoundAccount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{BettingPool, RoundAccounting, Bet, PendingClaims};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::PayoutsReserved;
+use crate::clock::pool_clock;
+use crate::token_hook::PoolTransfer;
+use crate::invariants::FundsCheck;
+use super::claim_winnings::{
+    bounty_split, calculate_bet_payout, copy_success_fee, verify_claimer_token_account,
+    voided_bet_refund, ClaimWindow,
+};
+use super::round_vault::round_funds;
+
+/// Count a settled round's winning and voided bets into its accounting
+/// ahead of their claims
+///
+/// Permissionless crank. `remaining_accounts` holds bets of the round; each
+/// winning bet's payout (or voided bet's refund) is added to the round's
+/// paid-out totals and to `total_reserved_unpaid`, and the bet is marked
+/// `payout_reserved`. Losing bets are skipped and go through
+/// settle_losing_bet as before. Reserved bets are then paid by
+/// claim_reserved, which skips the payout calculation and the round's
+/// payout limit checks.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ReservePayouts<'info> {
+    #[account(constraint = !betting_pool.merkle_settlement @ SportsbookError::OddsProofRequired)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = round_accounting.claim_shards == 0 @ SportsbookError::ClaimShardsOpen,
+        constraint = !round_accounting.revenue_allocated @ SportsbookError::RevenueAlreadyDistributed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn reserve_payouts_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReservePayouts<'info>>,
+    round_id: u64,
+) -> Result<()> {
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let round_accounting = &mut ctx.accounts.round_accounting;
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut reserved_bets = 0u32;
+    let mut total_payout = 0u64;
+    let mut total_refunded = 0u64;
+
+    for bet_info in ctx.remaining_accounts.iter() {
+        require!(!seen.contains(bet_info.key), SportsbookError::DuplicateAccount);
+        seen.push(*bet_info.key);
+
+        let mut bet = Account::<Bet>::try_from(bet_info)?;
+        require!(
+            bet.is_at_address(bet_info.key, &betting_pool_key) && bet.round_id == round_id,
+            SportsbookError::InvalidBetAccount
+        );
+        require!(!bet.claimed, SportsbookError::BetAlreadyClaimed);
+        if bet.payout_reserved {
+            continue;
+        }
+
+        if let Some(refund) = voided_bet_refund(&bet, round_accounting) {
+            round_accounting.total_refunded += refund;
+            round_accounting.total_reserved_unpaid += refund;
+            total_refunded += refund;
+            if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+                pending_claims.record_resolution(bet.potential_payout, refund, 0);
+            }
+        } else {
+            let (won, _, payout) = calculate_bet_payout(&bet, round_accounting)?;
+            if !won || payout == 0 {
+                continue;
+            }
+            require!(
+                round_accounting.total_paid_out + payout <= MAX_ROUND_PAYOUTS,
+                SportsbookError::RoundPayoutLimitReached
+            );
+
+            round_accounting.total_claimed += payout;
+            round_accounting.total_paid_out += payout;
+            round_accounting.payout_dust += bet.payout_dust;
+            round_accounting.total_reserved_unpaid += payout;
+            total_payout += payout;
+            if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+                pending_claims.record_resolution(bet.potential_payout, payout, 0);
+            }
+        }
+
+        round_accounting.record_bet_resolved();
+        bet.settled = true;
+        bet.payout_reserved = true;
+        bet.exit(&crate::ID)?;
+        reserved_bets += 1;
+    }
+
+    emit!(PayoutsReserved {
+        betting_pool: betting_pool_key,
+        round_id,
+        bets: reserved_bets,
+        total_payout,
+        total_refunded,
+    });
+
+    msg!(
+        "Round {}: reserved {} bets, {} in payouts and {} in refunds",
+        round_id,
+        reserved_bets,
+        total_payout,
+        total_refunded
+    );
+
+    Ok(())
+}
+
+/// Pay out a bet whose payout reserve_payouts already counted
+///
+/// Same claim window and bounty rules as claim_winnings, except that the
+/// bounty always comes out of the payout: the reservation has nothing set
+/// aside for a protocol-funded one. Of the round, only
+/// `total_reserved_unpaid` is written. SPL Token pools only.
+#[derive(Accounts)]
+#[instruction(bet_id: u64)]
+pub struct ClaimReserved<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        mut,
+        constraint = bet.is_at_address(&bet.key(), &betting_pool.key()) @ SportsbookError::InvalidBetAccount,
+        constraint = bet.bet_id == bet_id @ SportsbookError::InvalidBetAccount,
+        constraint = !bet.claimed @ SportsbookError::BetAlreadyClaimed,
+        constraint = bet.payout_reserved @ SportsbookError::PayoutNotReserved,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Required for isolated rounds: pays out instead of the pool account
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bettor_token_account.owner == bet.bettor @ SportsbookError::InvalidTokenAccount,
+        constraint = bettor_token_account.mint == betting_pool.token_mint @ SportsbookError::InvalidTokenAccount,
+    )]
+    pub bettor_token_account: Account<'info, TokenAccount>,
+
+    /// Bettor, or anyone after the claim deadline for the bounty
+    pub claimer: Signer<'info>,
+
+    /// Claimer's token account (receives the bounty on a third-party claim)
+    /// CHECK: For bounty claims, verified in handler to be the claimer's
+    /// initialized ATA for the pool mint
+    #[account(mut)]
+    pub claimer_token_account: UncheckedAccount<'info>,
+
+    /// Copy leader's token account; required when claiming a winning
+    /// copied bet
+    /// CHECK: Verified in handler to be bet.copy_leader's initialized ATA
+    /// for the pool mint
+    #[account(mut)]
+    pub leader_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_reserved_handler(ctx: Context<ClaimReserved>, bet_id: u64) -> Result<()> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let bet = &ctx.accounts.bet;
+    let claimer_key = ctx.accounts.claimer.key();
+    let token_mint = ctx.accounts.betting_pool.token_mint;
+    let token_program_key = ctx.accounts.token_program.key();
+
+    let window = ClaimWindow::evaluate(
+        &pool_clock(&ctx.accounts.betting_pool),
+        round_accounting,
+        claimer_key == bet.bettor,
+    )?;
+    require!(!window.paused, SportsbookError::ClaimsPaused);
+    require!(window.claimer_allowed, SportsbookError::NotBettor);
+
+    // The round's results can't change after settlement, so this is the
+    // amount reserve_payouts counted
+    let refund = voided_bet_refund(bet, round_accounting);
+    let (bettor_amount, bounty_amount, leader_fee) = match refund {
+        Some(refund) => (refund, 0, 0),
+        None => {
+            let (_, _, payout) = calculate_bet_payout(bet, round_accounting)?;
+            let (bettor_amount, bounty_amount) = bounty_split(payout, window.is_bounty_claim, false)?;
+            let leader_fee = copy_success_fee(bet, bettor_amount)?;
+            (bettor_amount - leader_fee, bounty_amount, leader_fee)
+        }
+    };
+
+    if bounty_amount > 0 {
+        verify_claimer_token_account(
+            &ctx.accounts.claimer_token_account,
+            &claimer_key,
+            &token_mint,
+            &token_program_key,
+        )?;
+    }
+
+    let funds_account = round_funds(
+        round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?;
+    require!(
+        funds_account.amount >= bettor_amount + bounty_amount + leader_fee,
+        SportsbookError::InsufficientProtocolLiquidity
+    );
+    let funds_check = FundsCheck::start(round_accounting, Some(funds_account.to_account_info()))?;
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let round_id_bytes = bet.round_id.to_le_bytes();
+    let round_bump = [round_accounting.bump];
+    let pool_bump = [ctx.accounts.betting_pool.bump];
+    let (funds_authority, seeds): (AccountInfo, &[&[u8]]) = if round_accounting.isolated {
+        (
+            round_accounting.to_account_info(),
+            &[ROUND_SEED, betting_pool_key.as_ref(), &round_id_bytes, &round_bump],
+        )
+    } else {
+        (ctx.accounts.betting_pool.to_account_info(), &[BETTING_POOL_SEED, &pool_bump])
+    };
+    let signer = &[seeds];
+    let pool_transfer = PoolTransfer {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: None,
+        from: funds_account.to_account_info(),
+        authority: funds_authority,
+        extra_accounts: &[],
+        signer,
+    };
+
+    pool_transfer.transfer(ctx.accounts.bettor_token_account.to_account_info(), bettor_amount)?;
+    if bounty_amount > 0 {
+        pool_transfer.transfer(ctx.accounts.claimer_token_account.to_account_info(), bounty_amount)?;
+    }
+    if leader_fee > 0 {
+        let leader_token_account = ctx
+            .accounts
+            .leader_token_account
+            .as_ref()
+            .ok_or(SportsbookError::InvalidLeaderTokenAccount)?;
+        verify_claimer_token_account(leader_token_account, &bet.copy_leader, &token_mint, &token_program_key)
+            .map_err(|_| SportsbookError::InvalidLeaderTokenAccount)?;
+        pool_transfer.transfer(leader_token_account.to_account_info(), leader_fee)?;
+    }
+    funds_check.finish(round_accounting, bettor_amount + bounty_amount + leader_fee)?;
+
+    let round_accounting = &mut ctx.accounts.round_accounting;
+    round_accounting.total_reserved_unpaid = round_accounting
+        .total_reserved_unpaid
+        .saturating_sub(bettor_amount + bounty_amount + leader_fee);
+
+    let bet = &mut ctx.accounts.bet;
+    if bet.claim_deadline == 0 {
+        bet.claim_deadline = window.claim_deadline;
+    }
+    bet.claimed = true;
+    if bounty_amount > 0 {
+        bet.bounty_claimer = Some(claimer_key);
+    }
+
+    msg!(
+        "Bet {} reserved claim: bettor {}, bounty {}, leader {}",
+        bet_id,
+        bettor_amount,
+        bounty_amount,
+        leader_fee
+    );
+
+    Ok(())
+}
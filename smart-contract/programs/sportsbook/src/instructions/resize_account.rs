@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::{BettingPool, RoundAccounting, Bet};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Grow a program account to its type's current `LEN`
+///
+/// Accounts created before a layout grew (e.g. before `reserved` existed)
+/// are too short to deserialize. This reallocates them in place, zero-filling
+/// the new tail, with the authority paying the extra rent. Works on raw
+/// account data since the old layout can't be loaded as the new type.
+#[derive(Accounts)]
+pub struct ResizeAccount<'info> {
+    /// Betting pool PDA (read raw so a not-yet-resized pool still works)
+    /// CHECK: PDA checked by seeds, authority read from raw data
+    #[account(seeds = [BETTING_POOL_SEED], bump)]
+    pub betting_pool: UncheckedAccount<'info>,
+
+    /// Account to resize (BettingPool, RoundAccounting or Bet)
+    /// CHECK: Owner and discriminator checked in handler
+    #[account(mut, owner = crate::ID @ SportsbookError::AccountNotResizable)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ResizeAccount>) -> Result<()> {
+    // BettingPool layout starts with discriminator + authority
+    {
+        let pool_data = ctx.accounts.betting_pool.try_borrow_data()?;
+        require!(
+            pool_data.len() >= 8 + 32 && pool_data[..8] == *BettingPool::DISCRIMINATOR,
+            SportsbookError::InvalidAuthority
+        );
+        let authority = Pubkey::try_from(&pool_data[8..40])
+            .map_err(|_| SportsbookError::InvalidAuthority)?;
+        require!(
+            authority == ctx.accounts.authority.key(),
+            SportsbookError::InvalidAuthority
+        );
+    }
+
+    let target = ctx.accounts.target.to_account_info();
+    let target_len = {
+        let data = target.try_borrow_data()?;
+        require!(data.len() >= 8, SportsbookError::AccountNotResizable);
+        let discriminator = &data[..8];
+
+        if discriminator == BettingPool::DISCRIMINATOR {
+            BettingPool::LEN
+        } else if discriminator == RoundAccounting::DISCRIMINATOR {
+            RoundAccounting::LEN
+        } else if discriminator == Bet::DISCRIMINATOR {
+            Bet::LEN
+        } else {
+            return err!(SportsbookError::AccountNotResizable);
+        }
+    };
+
+    let old_len = target.data_len();
+    realloc_with_rent(
+        &target,
+        target_len,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!("Resized {} from {} to {} bytes", target.key(), old_len, target_len);
+
+    Ok(())
+}
+
+/// Grow `account` to `new_len` bytes, topping up rent from `payer`
+///
+/// New bytes are zeroed, so fields carved out of reserved space read as
+/// their zero value on old accounts. Never shrinks.
+pub(crate) fn realloc_with_rent<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let top_up = required_lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    account.resize(new_len)?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, RoundMetadata, pack_metadata_uri};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::RoundMetadataSet;
+
+/// Point a round at its off-chain metadata document
+///
+/// Changeable until the first user bet, so what bettors saw when they bet
+/// is what the round keeps.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SetRoundMetadata<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.total_user_deposits == 0 @ SportsbookError::MetadataUpdateAfterBets,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoundMetadata::LEN,
+        seeds = [ROUND_METADATA_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_metadata: Account<'info, RoundMetadata>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetRoundMetadata>,
+    round_id: u64,
+    uri: String,
+    content_hash: [u8; 32],
+) -> Result<()> {
+    let packed_uri = pack_metadata_uri(&uri).ok_or(SportsbookError::InvalidMetadataUri)?;
+    require!(content_hash != [0; 32], SportsbookError::InvalidMetadataUri);
+
+    let round_metadata = &mut ctx.accounts.round_metadata;
+    round_metadata.betting_pool = ctx.accounts.betting_pool.key();
+    round_metadata.round_id = round_id;
+    round_metadata.uri = packed_uri;
+    round_metadata.content_hash = content_hash;
+    round_metadata.updated_at = Clock::get()?.unix_timestamp;
+    round_metadata.bump = ctx.bumps.round_metadata;
+
+    emit!(RoundMetadataSet {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        round_id,
+        uri: uri.clone(),
+        content_hash,
+    });
+
+    msg!("Round {} metadata: {}", round_id, uri);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, RoundSummary};
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+
+/// Create or refresh a round's RoundSummary from its RoundAccounting
+///
+/// Permissionless: the summary only mirrors the round, so anyone (a
+/// keeper, or a client that wants fresh payout totals after claims) may
+/// pay to write it.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SyncRoundSummary<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RoundSummary::LEN,
+        seeds = [ROUND_SUMMARY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_summary: Account<'info, RoundSummary>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn sync_round_summary_handler(ctx: Context<SyncRoundSummary>, round_id: u64) -> Result<()> {
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    write_round_summary(
+        Some(&mut ctx.accounts.round_summary),
+        &ctx.accounts.round_accounting,
+        Some(ctx.bumps.round_summary),
+        current_time,
+    );
+
+    msg!("Round {} summary synced", round_id);
+
+    Ok(())
+}
+
+/// Write the round into its summary, when the instruction was given one
+pub(crate) fn write_round_summary(
+    round_summary: Option<&mut RoundSummary>,
+    round_accounting: &RoundAccounting,
+    bump: Option<u8>,
+    current_time: i64,
+) {
+    if let (Some(round_summary), Some(bump)) = (round_summary, bump) {
+        round_summary.record(round_accounting, current_time);
+        round_summary.bump = bump;
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{BettingPool, RoundAccounting};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Turn per-round token isolation on or off for rounds created from now on
+///
+/// Each isolated round holds its seed and stakes in its own vault, owned by
+/// the round PDA, so settling one round can never move another round's
+/// liquidity. Rounds keep the mode they were created with.
+#[derive(Accounts)]
+pub struct SetRoundVaults<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_round_vaults_handler(ctx: Context<SetRoundVaults>, enabled: bool) -> Result<()> {
+    ctx.accounts.betting_pool.round_vaults = enabled;
+
+    msg!("Round vaults {}", if enabled { "enabled" } else { "disabled" });
+
+    Ok(())
+}
+
+/// Create an isolated round's vault; must happen before seed_round funds it
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CreateRoundVault<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.isolated @ SportsbookError::RoundNotIsolated,
+        constraint = !round_accounting.seeded @ SportsbookError::RoundAlreadySeeded,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = round_accounting,
+    )]
+    pub round_vault: Account<'info, TokenAccount>,
+
+    #[account(address = betting_pool.token_mint @ SportsbookError::InvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_round_vault_handler(ctx: Context<CreateRoundVault>, round_id: u64) -> Result<()> {
+    ctx.accounts.round_accounting.vault_bump = ctx.bumps.round_vault;
+
+    msg!("Round {} vault created at {}", round_id, ctx.accounts.round_vault.key());
+
+    Ok(())
+}
+
+/// Token account holding a round's funds: its vault when the round is
+/// isolated, otherwise the shared pool account
+pub(crate) fn round_funds<T>(isolated: bool, pool_account: T, round_vault: Option<T>) -> Result<T> {
+    if isolated {
+        round_vault.ok_or_else(|| error!(SportsbookError::RoundVaultRequired))
+    } else {
+        Ok(pool_account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_funds_picks_vault_for_isolated_rounds() {
+        assert_eq!(round_funds(false, "pool", Some("vault")).unwrap(), "pool");
+        assert_eq!(round_funds(false, "pool", None).unwrap(), "pool");
+        assert_eq!(round_funds(true, "pool", Some("vault")).unwrap(), "vault");
+        assert!(round_funds(true, "pool", None).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo};
 use crate::state::{BettingPool, SeasonPrediction};
 use crate::errors::SportsbookError;
+use crate::constants::*;
 
 #[derive(Accounts)]
 #[instruction(predicted_team: u8)]
@@ -14,7 +15,7 @@ pub struct MakeSeasonPrediction<'info> {
         payer = user,
         space = SeasonPrediction::LEN,
         seeds = [
-            b"season_prediction",
+            SEASON_PREDICTION_SEED,
             betting_pool.key().as_ref(),
             betting_pool.current_season_id.to_le_bytes().as_ref(),
             user.key().as_ref()
@@ -30,7 +31,7 @@ pub struct MakeSeasonPrediction<'info> {
         mint::decimals = 0,
         mint::authority = betting_pool,
         seeds = [
-            b"prediction_nft",
+            PREDICTION_NFT_SEED,
             betting_pool.key().as_ref(),
             betting_pool.current_season_id.to_le_bytes().as_ref(),
             user.key().as_ref()
@@ -86,7 +87,7 @@ pub fn handler(
 
     // Mint NFT to user (1 token, non-fungible)
     let seeds = &[
-        b"betting_pool".as_ref(),
+        BETTING_POOL_SEED,
         &[ctx.accounts.betting_pool.bump],
     ];
     let signer = &[&seeds[..]];
@@ -117,7 +118,7 @@ pub struct ClaimSeasonReward<'info> {
     #[account(
         mut,
         seeds = [
-            b"season_prediction",
+            SEASON_PREDICTION_SEED,
             betting_pool.key().as_ref(),
             season_prediction.season_id.to_le_bytes().as_ref(),
             user.key().as_ref()
@@ -129,7 +130,11 @@ pub struct ClaimSeasonReward<'info> {
     pub season_prediction: Box<Account<'info, SeasonPrediction>>,
 
     /// Betting pool's token account
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
     pub betting_pool_token_account: Box<Account<'info, TokenAccount>>,
 
     /// User's token account (receives season rewards)
@@ -173,7 +178,7 @@ pub fn claim_season_reward_handler(
 
     // Transfer tokens
     let seeds = &[
-        b"betting_pool".as_ref(),
+        BETTING_POOL_SEED,
         &[ctx.accounts.betting_pool.bump],
     ];
     let signer = &[&seeds[..]];
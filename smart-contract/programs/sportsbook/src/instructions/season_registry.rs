@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::InstructionData;
+use crate::state::{BettingPool, Bet, RoundAccounting, SeasonRegistry, SeasonPoints, MAX_SEASON_POOLS};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::events::SeasonPointsRecorded;
+use crate::pyth::stablecoin_amount_to_usd;
+
+/// Open a points registry for a season that several pools can report to
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct CreateSeasonRegistry<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = SeasonRegistry::LEN,
+        seeds = [SEASON_REGISTRY_SEED, authority.key().as_ref(), season_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub season_registry: Box<Account<'info, SeasonRegistry>>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_season_registry_handler(ctx: Context<CreateSeasonRegistry>, season_id: u64) -> Result<()> {
+    let season_registry = &mut ctx.accounts.season_registry;
+    season_registry.authority = ctx.accounts.authority.key();
+    season_registry.season_id = season_id;
+    season_registry.pool_programs = [Pubkey::default(); MAX_SEASON_POOLS];
+    season_registry.num_pools = 0;
+    // Round start times are cluster time, so this is too
+    season_registry.started_at = Clock::get()?.unix_timestamp;
+    season_registry.ended = false;
+    season_registry.total_points = 0;
+    season_registry.participants = 0;
+    season_registry.bump = ctx.bumps.season_registry;
+
+    msg!("Season registry opened for season {}", season_id);
+
+    Ok(())
+}
+
+/// Registry authority: add a pool or close the registry
+#[derive(Accounts)]
+pub struct ManageSeasonRegistry<'info> {
+    #[account(
+        mut,
+        constraint = !season_registry.ended @ SportsbookError::SeasonRegistryEnded,
+    )]
+    pub season_registry: Box<Account<'info, SeasonRegistry>>,
+
+    #[account(constraint = authority.key() == season_registry.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+/// Let the pool deployed as `pool_program` report points
+pub fn add_season_pool_handler(ctx: Context<ManageSeasonRegistry>, pool_program: Pubkey) -> Result<()> {
+    let season_registry = &mut ctx.accounts.season_registry;
+    require!(
+        !season_registry.has_pool(&pool_program)
+            && (season_registry.num_pools as usize) < MAX_SEASON_POOLS,
+        SportsbookError::InvalidSeasonRegistry
+    );
+
+    let slot = season_registry.num_pools as usize;
+    season_registry.pool_programs[slot] = pool_program;
+    season_registry.num_pools += 1;
+
+    msg!(
+        "Pool program {} added to season {} ({} pools)",
+        pool_program,
+        season_registry.season_id,
+        season_registry.num_pools
+    );
+
+    Ok(())
+}
+
+/// Stop accepting points; the standings are final
+pub fn end_season_registry_handler(ctx: Context<ManageSeasonRegistry>) -> Result<()> {
+    let season_registry = &mut ctx.accounts.season_registry;
+    season_registry.ended = true;
+
+    msg!(
+        "Season {} registry closed: {} points from {} participants",
+        season_registry.season_id,
+        season_registry.total_points,
+        season_registry.participants
+    );
+
+    Ok(())
+}
+
+/// Point this pool at the SeasonRegistry it reports to
+/// (Pubkey::default() stops reporting)
+#[derive(Accounts)]
+pub struct SetSeasonRegistry<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn set_season_registry_handler(ctx: Context<SetSeasonRegistry>, season_registry: Pubkey) -> Result<()> {
+    ctx.accounts.betting_pool.season_registry = season_registry;
+
+    msg!("Season registry set to {}", season_registry);
+
+    Ok(())
+}
+
+/// Add one bet's stake to a wallet's season points
+///
+/// Called by CPI from a registered pool's accrue_season_points, signed by
+/// that pool's season reporter PDA. `points` is the stake in USD (6
+/// decimals); bets from rounds that started before the registry opened
+/// are rejected.
+#[derive(Accounts)]
+#[instruction(pool_program: Pubkey, user: Pubkey)]
+pub struct RecordSeasonPoints<'info> {
+    #[account(
+        mut,
+        constraint = !season_registry.ended @ SportsbookError::SeasonRegistryEnded,
+    )]
+    pub season_registry: Box<Account<'info, SeasonRegistry>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SeasonPoints::LEN,
+        seeds = [SEASON_POINTS_SEED, season_registry.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub season_points: Box<Account<'info, SeasonPoints>>,
+
+    pub reporter: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn record_season_points_handler(
+    ctx: Context<RecordSeasonPoints>,
+    pool_program: Pubkey,
+    user: Pubkey,
+    points: u64,
+    round_start_time: i64,
+) -> Result<()> {
+    let season_registry = &mut ctx.accounts.season_registry;
+    require!(
+        season_registry.has_pool(&pool_program),
+        SportsbookError::InvalidSeasonReporter
+    );
+    let (reporter, _) = Pubkey::find_program_address(&[SEASON_REPORTER_SEED], &pool_program);
+    require!(
+        ctx.accounts.reporter.key() == reporter,
+        SportsbookError::InvalidSeasonReporter
+    );
+    require!(
+        round_start_time >= season_registry.started_at,
+        SportsbookError::BetBeforeSeason
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let season_points = &mut ctx.accounts.season_points;
+    if season_points.user == Pubkey::default() {
+        season_points.registry = season_registry.key();
+        season_points.user = user;
+        season_points.bump = ctx.bumps.season_points;
+        season_registry.participants = season_registry.participants.saturating_add(1);
+    }
+    season_points.points = season_points.points.saturating_add(points);
+    season_points.bets = season_points.bets.saturating_add(1);
+    season_points.last_updated = current_time;
+    season_registry.total_points = season_registry.total_points.saturating_add(points);
+
+    emit!(SeasonPointsRecorded {
+        season_registry: season_registry.key(),
+        pool_program,
+        user,
+        points,
+        total_points: season_points.points,
+    });
+
+    msg!("{} season points for {} (total {})", points, user, season_points.points);
+
+    Ok(())
+}
+
+/// Report a bet's stake to the pool's SeasonRegistry
+///
+/// Permissionless crank, once per bet; the payer covers rent for the
+/// bettor's SeasonPoints on first use. Stakes are valued in USD: priced
+/// pools use the Pyth value recorded at placement, stablecoin pools count
+/// one whole token as $1. The registry may live in another deployment, so
+/// this CPIs into whichever program owns it, signed by this program's
+/// season reporter PDA (which holds no funds and signs nothing else).
+#[derive(Accounts)]
+pub struct AccrueSeasonPoints<'info> {
+    pub betting_pool: Box<Account<'info, BettingPool>>,
+
+    #[account(
+        mut,
+        constraint = !bet.season_points_accrued @ SportsbookError::SeasonPointsAlreadyAccrued,
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), bet.round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Box<Account<'info, RoundAccounting>>,
+
+    /// CHECK: PDA that signs the report; holds nothing
+    #[account(seeds = [SEASON_REPORTER_SEED], bump)]
+    pub season_reporter: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the pool's configured registry; deserialized and
+    /// checked by the registry program
+    #[account(mut, address = betting_pool.season_registry @ SportsbookError::InvalidSeasonRegistry)]
+    pub season_registry: UncheckedAccount<'info>,
+
+    /// CHECK: Seeds are checked by the registry program
+    #[account(mut)]
+    pub season_points: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the program that owns season_registry
+    #[account(
+        executable,
+        constraint = registry_program.key() == *season_registry.owner @ SportsbookError::InvalidSeasonRegistry,
+    )]
+    pub registry_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn accrue_season_points_handler(ctx: Context<AccrueSeasonPoints>) -> Result<()> {
+    let betting_pool = &ctx.accounts.betting_pool;
+    require!(
+        betting_pool.season_registry != Pubkey::default(),
+        SportsbookError::InvalidSeasonRegistry
+    );
+
+    let bet = &ctx.accounts.bet;
+    require!(
+        bet.is_at_address(&bet.key(), &betting_pool.key()),
+        SportsbookError::InvalidBetAccount
+    );
+
+    let points = if betting_pool.price_feed != Pubkey::default() {
+        bet.stake_usd
+    } else {
+        stablecoin_amount_to_usd(bet.amount, betting_pool.token_decimals)
+            .ok_or(SportsbookError::CalculationOverflow)?
+    };
+    let user = bet.bettor;
+
+    let accounts = crate::accounts::RecordSeasonPoints {
+        season_registry: ctx.accounts.season_registry.key(),
+        season_points: ctx.accounts.season_points.key(),
+        reporter: ctx.accounts.season_reporter.key(),
+        payer: ctx.accounts.payer.key(),
+        system_program: ctx.accounts.system_program.key(),
+    };
+    let data = crate::instruction::RecordSeasonPoints {
+        pool_program: crate::ID,
+        user,
+        points,
+        round_start_time: ctx.accounts.round_accounting.round_start_time,
+    };
+    let ix = Instruction {
+        program_id: ctx.accounts.registry_program.key(),
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    };
+
+    let account_infos = [
+        ctx.accounts.season_registry.to_account_info(),
+        ctx.accounts.season_points.to_account_info(),
+        ctx.accounts.season_reporter.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.registry_program.to_account_info(),
+    ];
+    let seeds = &[SEASON_REPORTER_SEED, &[ctx.bumps.season_reporter]];
+    invoke_signed(&ix, &account_infos, &[&seeds[..]])?;
+
+    ctx.accounts.bet.season_points_accrued = true;
+
+    msg!("Bet {} reported: {} season points for {}", ctx.accounts.bet.bet_id, points, user);
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{BettingPool, RoundAccounting, LiquidityPool};
+use crate::state::{BettingPool, RoundAccounting, LiquidityPool, Notifications, NotificationKind};
 use crate::errors::SportsbookError;
 use crate::constants::*;
 use crate::utils::{calculate_pseudo_random_seeds, calculate_locked_odds_from_seeds};
+use crate::invariants::FundsCheck;
+use super::round_vault::round_funds;
+use super::odds_history::record_odds_updates;
+use super::notifications::notify;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
@@ -13,7 +17,7 @@ pub struct SeedRoundPools<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = !round_accounting.seeded @ SportsbookError::RoundAlreadySeeded,
         constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
@@ -22,7 +26,7 @@ pub struct SeedRoundPools<'info> {
 
     #[account(
         mut,
-        seeds = [b"liquidity_pool", betting_pool.key().as_ref()],
+        seeds = [LIQUIDITY_POOL_SEED, betting_pool.key().as_ref()],
         bump = liquidity_pool.bump,
     )]
     pub liquidity_pool: Account<'info, LiquidityPool>,
@@ -32,16 +36,38 @@ pub struct SeedRoundPools<'info> {
     pub lp_token_account: Account<'info, TokenAccount>,
 
     /// Betting pool's token account (receives seed funds)
-    #[account(mut)]
+    #[account(
+        mut,
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
     pub betting_pool_token_account: Account<'info, TokenAccount>,
 
+    /// Required for isolated rounds: receives the seed funds instead
+    #[account(
+        mut,
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Optional: the pool's operator notification queue
+    #[account(
+        mut,
+        seeds = [NOTIFICATIONS_SEED, betting_pool.key().as_ref()],
+        bump = notifications.bump,
+    )]
+    pub notifications: Option<Box<Account<'info, Notifications>>>,
+
     #[account(mut, constraint = authority.key() == betting_pool.authority)]
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<SeedRoundPools>, round_id: u64) -> Result<()> {
+/// OddsHistory accounts of any of the round's matches may be passed in
+/// `remaining_accounts` to record the opening odds.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SeedRoundPools<'info>>, round_id: u64) -> Result<()> {
     let mut total_seed_amount = 0u64;
 
     // Seed each match with DIFFERENTIATED amounts based on team matchup
@@ -104,14 +130,20 @@ pub fn handler(ctx: Context<SeedRoundPools>, round_id: u64) -> Result<()> {
     let lp_bump = ctx.accounts.liquidity_pool.bump;
     let lp_account_info = ctx.accounts.liquidity_pool.to_account_info();
 
-    // Transfer seed funds from LP pool to betting pool
+    // Transfer seed funds from LP pool to betting pool (or the round's vault)
     // This uses a Cross-Program Invocation (CPI) with PDA signer
-    let seeds = &[b"liquidity_pool", betting_pool_key.as_ref(), &[lp_bump]];
+    let destination = round_funds(
+        ctx.accounts.round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?;
+    let funds_check = FundsCheck::start(&ctx.accounts.round_accounting, Some(destination.to_account_info()))?;
+    let seeds = &[LIQUIDITY_POOL_SEED, betting_pool_key.as_ref(), &[lp_bump]];
     let signer = &[&seeds[..]];
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.lp_token_account.to_account_info(),
-        to: ctx.accounts.betting_pool_token_account.to_account_info(),
+        to: destination.to_account_info(),
         authority: lp_account_info,
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -128,6 +160,31 @@ pub fn handler(ctx: Context<SeedRoundPools>, round_id: u64) -> Result<()> {
     // Update round accounting
     ctx.accounts.round_accounting.protocol_seed_amount = total_seed_amount;
     ctx.accounts.round_accounting.seeded = true;
+    ctx.accounts.round_accounting.commit_locked_odds()?;
+    funds_check.finish(&ctx.accounts.round_accounting, 0)?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let all_matches: Vec<u8> = (0..MATCHES_PER_ROUND as u8).collect();
+    record_odds_updates(
+        ctx.remaining_accounts,
+        &ctx.accounts.round_accounting,
+        &all_matches,
+        current_time,
+    )?;
+
+    let available_liquidity = ctx.accounts.liquidity_pool.available_liquidity;
+    if let Some(notifications) = ctx.accounts.notifications.as_deref_mut() {
+        if available_liquidity < notifications.liquidity_low_threshold {
+            notify(
+                Some(notifications),
+                NotificationKind::LiquidityLow,
+                round_id,
+                ctx.accounts.liquidity_pool.key(),
+                available_liquidity,
+                current_time,
+            );
+        }
+    }
 
     msg!("Round {} seeded with {} tokens total", round_id, total_seed_amount);
     msg!("Odds locked for all matches");
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, RoundAccounting, Bet, PendingClaims};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use super::claim_winnings::{calculate_bet_payout, insured_bet_refund};
+
+/// Batch-settle losing bets of a round
+///
+/// Permissionless. Bets are passed in `remaining_accounts`; with
+/// `close_accounts` each bet is followed by its bettor's wallet, which gets
+/// the bet's rent back. Winning bets, insured bets owed a refund and
+/// already resolved bets are skipped so a keeper can throw a whole page of
+/// bets at it.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SettleLostBets<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    /// Optional: the pool's PendingClaims read model, updated when passed
+    #[account(
+        mut,
+        seeds = [PENDING_CLAIMS_SEED, betting_pool.key().as_ref()],
+        bump = pending_claims.bump,
+    )]
+    pub pending_claims: Option<Account<'info, PendingClaims>>,
+
+    pub cranker: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleLostBets<'info>>,
+    round_id: u64,
+    close_accounts: bool,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.round_accounting.claims_blocked(
+            pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?
+        ),
+        SportsbookError::ClaimsPaused
+    );
+
+    let betting_pool_key = ctx.accounts.betting_pool.key();
+    let stride = if close_accounts { 2 } else { 1 };
+    let batches = ctx.remaining_accounts.chunks_exact(stride);
+    require!(
+        batches.remainder().is_empty(),
+        SportsbookError::ArrayLengthMismatch
+    );
+
+    let mut settled = 0u32;
+    let mut skipped = 0u32;
+
+    for accounts in batches {
+        let bet_info = &accounts[0];
+        require!(bet_info.owner == &crate::ID, SportsbookError::InvalidBetAccount);
+
+        let mut bet = Account::<Bet>::try_from(bet_info)?;
+        require!(
+            bet.is_at_address(bet_info.key, &betting_pool_key) && bet.round_id == round_id,
+            SportsbookError::InvalidBetAccount
+        );
+
+        // Closing an already claimed bet is fine too; only unresolved
+        // winners and insured refunds are left alone
+        let (won, _, _) = calculate_bet_payout(&bet, &ctx.accounts.round_accounting)?;
+        let insured = insured_bet_refund(&bet, &ctx.accounts.round_accounting).is_some();
+        if (won || insured) && !bet.claimed {
+            skipped += 1;
+            continue;
+        }
+
+        if !bet.claimed {
+            bet.claimed = true;
+            bet.settled = true;
+            ctx.accounts.round_accounting.record_bet_resolved();
+            if let Some(pending_claims) = ctx.accounts.pending_claims.as_mut() {
+                pending_claims.record_resolution(bet.potential_payout, 0, 0);
+            }
+            settled += 1;
+        } else if !close_accounts {
+            skipped += 1;
+            continue;
+        }
+
+        if close_accounts {
+            let bettor_info = &accounts[1];
+            require!(
+                bettor_info.key() == bet.bettor,
+                SportsbookError::InvalidBetAccount
+            );
+            bet.close(bettor_info.clone())?;
+        } else {
+            bet.exit(&crate::ID)?;
+        }
+    }
+
+    msg!("Round {}: {} losing bets settled, {} skipped", round_id, settled, skipped);
+    msg!(
+        "Resolved {}/{} bets",
+        ctx.accounts.round_accounting.bets_resolved,
+        ctx.accounts.round_accounting.bets_placed
+    );
+
+    Ok(())
+}
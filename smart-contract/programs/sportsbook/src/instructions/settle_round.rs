@@ -1,7 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::state::{BettingPool, RoundAccounting, MatchOutcome};
+use crate::state::{
+    BettingPool, RoundAccounting, MatchOutcome, AccessControl, Role, has_role, RoundSummary, Notifications,
+    NotificationKind,
+};
 use crate::errors::SportsbookError;
 use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use crate::pyth::{report_pool_price, PriceSnapshot};
+use super::round_summary::write_round_summary;
+use super::notifications::notify;
+use sportsbook_core::results_bitmap;
 
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
@@ -11,44 +19,130 @@ pub struct SettleRound<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
         constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
     )]
     pub round_accounting: Account<'info, RoundAccounting>,
 
-    #[account(mut, constraint = authority.key() == betting_pool.authority)]
+    /// Optional: Pyth price feed, snapshotted when the pool is USD-valued;
+    /// settlement goes ahead without a snapshot if it's missing or unusable
+    /// CHECK: Checked against betting_pool.price_feed in load_pool_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Optional: the pool's roles, so a results submitter other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    /// Optional: the round's compact summary, created or refreshed here
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RoundSummary::LEN,
+        seeds = [ROUND_SUMMARY_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round_summary: Option<Account<'info, RoundSummary>>,
+
+    /// Optional: the pool's operator notification queue
+    #[account(
+        mut,
+        seeds = [NOTIFICATIONS_SEED, betting_pool.key().as_ref()],
+        bump = notifications.bump,
+    )]
+    pub notifications: Option<Box<Account<'info, Notifications>>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::ResultsSubmitter)
+            @ SportsbookError::InvalidAuthority,
+    )]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<SettleRound>,
     round_id: u64,
     match_results: Vec<u8>,
+) -> Result<()> {
+    // Winnings become claimable at settlement, so lent-out liquidity
+    // has to be back in the pool first
+    require!(
+        ctx.accounts.betting_pool.lending_deployed == 0,
+        SportsbookError::LiquidityStillDeployed
+    );
+
+    // Price staleness is always judged against cluster time, even when
+    // the pool runs on a mock clock
+    let current_time = Clock::get()?.unix_timestamp;
+    let settlement_price = report_pool_price(
+        &ctx.accounts.betting_pool.price_feed,
+        ctx.accounts.price_feed.as_ref().map(|feed| feed.as_ref()),
+        current_time,
+    );
+
+    let settlement_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    apply_match_results(
+        &mut ctx.accounts.round_accounting,
+        &match_results,
+        settlement_time,
+        settlement_price,
+        ctx.accounts.notifications.as_deref_mut().map(|notifications| &mut **notifications),
+    )?;
+
+    let round_accounting = &ctx.accounts.round_accounting;
+    write_round_summary(
+        ctx.accounts.round_summary.as_deref_mut(),
+        round_accounting,
+        ctx.bumps.round_summary,
+        settlement_time,
+    );
+
+    msg!("Round {} settled", round_id);
+    msg!("Total winning pool: {}", round_accounting.total_winning_pool);
+    msg!("Total losing pool: {}", round_accounting.total_losing_pool);
+    msg!("Total reserved for winners: {}", round_accounting.total_reserved_for_winners);
+
+    Ok(())
+}
+
+/// Record match results and compute the round's winning/losing pools and
+/// the amount reserved for winners. Shared by every settlement path; when
+/// given the pool's notification queue, queues ClaimNeeded if the round
+/// has winners.
+pub fn apply_match_results(
+    round_accounting: &mut RoundAccounting,
+    match_results: &[u8],
+    current_time: i64,
+    settlement_price: Option<PriceSnapshot>,
+    notifications: Option<&mut Notifications>,
 ) -> Result<()> {
     require!(
         match_results.len() == MATCHES_PER_ROUND,
         SportsbookError::InvalidBetCount
     );
 
-    let round_accounting = &mut ctx.accounts.round_accounting;
-
     // Validate and store match results
-    for i in 0..MATCHES_PER_ROUND {
-        let result = match_results[i];
+    for (slot, &result) in round_accounting.match_results.iter_mut().zip(match_results) {
         require!(
-            result >= 1 && result <= 3,
+            (1..=RESULT_VOID).contains(&result),
             SportsbookError::InvalidOutcome
         );
 
-        round_accounting.match_results[i] = match result {
+        *slot = match result {
             1 => MatchOutcome::HomeWin,
             2 => MatchOutcome::AwayWin,
             3 => MatchOutcome::Draw,
+            RESULT_VOID => MatchOutcome::Void,
             _ => MatchOutcome::Pending,
         };
     }
+    round_accounting.result_bits = results_bitmap(match_results);
 
     // Calculate winning and losing pools
     for match_index in 0..MATCHES_PER_ROUND {
@@ -68,7 +162,8 @@ pub fn handler(
                 pool.draw_pool,
                 pool.home_win_pool + pool.away_win_pool,
             ),
-            MatchOutcome::Pending => (0, 0),
+            // Void matches neither win nor lose; their bets are refunded
+            MatchOutcome::Pending | MatchOutcome::Void => (0, 0),
         };
 
         round_accounting.total_winning_pool += winning_pool;
@@ -82,7 +177,7 @@ pub fn handler(
         let pool = &round_accounting.match_pools[match_index];
         let locked_odds = &round_accounting.locked_odds[match_index];
 
-        if *match_result == MatchOutcome::Pending {
+        if matches!(match_result, MatchOutcome::Pending | MatchOutcome::Void) {
             continue;
         }
 
@@ -112,12 +207,19 @@ pub fn handler(
 
     round_accounting.total_reserved_for_winners = total_owed;
     round_accounting.settled = true;
-    round_accounting.round_end_time = Clock::get()?.unix_timestamp;
-
-    msg!("Round {} settled", round_id);
-    msg!("Total winning pool: {}", round_accounting.total_winning_pool);
-    msg!("Total losing pool: {}", round_accounting.total_losing_pool);
-    msg!("Total reserved for winners: {}", total_owed);
+    round_accounting.round_end_time = current_time;
+    round_accounting.settlement_price = settlement_price.unwrap_or_default();
+
+    if total_owed > 0 {
+        notify(
+            notifications,
+            NotificationKind::ClaimNeeded,
+            round_accounting.round_id,
+            Pubkey::default(),
+            total_owed,
+            current_time,
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+use crate::errors::SportsbookError;
+
+/// Switch between plain and Merkle settlement
+///
+/// In Merkle mode every claim must prove its legs' odds against the round's
+/// locked_odds_root. Rounds locked before the root existed have a zero root
+/// and can only be claimed in plain mode.
+#[derive(Accounts)]
+pub struct SetSettlementMode<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetSettlementMode>, merkle_settlement: bool) -> Result<()> {
+    ctx.accounts.betting_pool.merkle_settlement = merkle_settlement;
+
+    msg!("Merkle settlement {}", if merkle_settlement { "enabled" } else { "disabled" });
+
+    Ok(())
+}
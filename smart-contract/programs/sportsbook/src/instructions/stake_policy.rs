@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{BettingPool, StakePolicy};
+use crate::errors::SportsbookError;
+use crate::constants::*;
+
+/// Set the pool's minimum stake and rounding unit (both 0 = off)
+///
+/// Once on, every bet must pass the StakePolicy account. The minimum must
+/// cover at least one rounding unit, or small bets would round to nothing.
+#[derive(Accounts)]
+pub struct SetStakePolicy<'info> {
+    #[account(mut)]
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StakePolicy::LEN,
+        seeds = [STAKE_POLICY_SEED, betting_pool.key().as_ref()],
+        bump
+    )]
+    pub stake_policy: Account<'info, StakePolicy>,
+
+    #[account(mut, constraint = authority.key() == betting_pool.authority @ SportsbookError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetStakePolicy>, min_stake: u64, rounding_unit: u64) -> Result<()> {
+    require!(
+        min_stake <= MAX_BET_AMOUNT && (rounding_unit <= 1 || min_stake >= rounding_unit),
+        SportsbookError::InvalidStakePolicy
+    );
+
+    let stake_policy = &mut ctx.accounts.stake_policy;
+    stake_policy.betting_pool = ctx.accounts.betting_pool.key();
+    stake_policy.min_stake = min_stake;
+    stake_policy.rounding_unit = rounding_unit;
+    stake_policy.bump = ctx.bumps.stake_policy;
+
+    ctx.accounts.betting_pool.stake_policy = min_stake > 0 || rounding_unit > 1;
+
+    msg!("Min stake: {} (0 = none)", min_stake);
+    msg!("Rounding unit: {} (0/1 = exact)", rounding_unit);
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::{BettingPool, RoundAccounting};
+use crate::constants::*;
+use super::round_vault::round_funds;
+use super::settle_round::apply_match_results;
+
+/// Round is already settled
+pub const SETTLEMENT_ALREADY_SETTLED: u16 = 1 << 0;
+/// Round was never seeded
+pub const SETTLEMENT_NOT_SEEDED: u16 = 1 << 1;
+/// Not one result per match, or a result outside 1..=RESULT_VOID
+pub const SETTLEMENT_BAD_RESULTS: u16 = 1 << 2;
+/// A match has no locked odds
+pub const SETTLEMENT_ODDS_NOT_LOCKED: u16 = 1 << 3;
+/// Reserved winnings exceed MAX_ROUND_PAYOUTS
+pub const SETTLEMENT_PAYOUT_CAP_EXCEEDED: u16 = 1 << 4;
+/// The pool (or round vault) can't cover the reserved winnings
+pub const SETTLEMENT_INSUFFICIENT_LIQUIDITY: u16 = 1 << 5;
+/// Lent-out liquidity hasn't been recalled
+pub const SETTLEMENT_LIQUIDITY_DEPLOYED: u16 = 1 << 6;
+/// Working out the winning pools overflowed
+pub const SETTLEMENT_OVERFLOW: u16 = 1 << 7;
+
+/// Dry-run of settle_round for `simulateTransaction`
+///
+/// Mutates nothing and needs no signer. Runs the settlement math on a copy
+/// of the round with the proposed results and returns every invariant it
+/// breaks, so the oracle can catch a bad settlement before `settled` is
+/// set for good.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ValidateSettlement<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        associated_token::mint = betting_pool.token_mint,
+        associated_token::authority = betting_pool,
+    )]
+    pub betting_pool_token_account: Account<'info, TokenAccount>,
+
+    /// Required for isolated rounds: holds the round's liquidity
+    #[account(
+        seeds = [ROUND_VAULT_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.vault_bump,
+    )]
+    pub round_vault: Option<Account<'info, TokenAccount>>,
+}
+
+/// What settle_round would do with the proposed results
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SettlementCheck {
+    /// No violations
+    pub valid: bool,
+
+    /// SETTLEMENT_* flags of every invariant broken
+    pub violations: u16,
+
+    /// Pools the results would make winning / losing
+    pub total_winning_pool: u64,
+    pub total_losing_pool: u64,
+
+    /// Winnings settlement would reserve
+    pub total_reserved_for_winners: u64,
+
+    /// Balance of the account the winnings would be paid from
+    pub available_liquidity: u64,
+}
+
+pub fn handler(ctx: Context<ValidateSettlement>, _round_id: u64, match_results: Vec<u8>) -> Result<SettlementCheck> {
+    let round_accounting = &ctx.accounts.round_accounting;
+    let mut violations = 0u16;
+
+    if round_accounting.settled {
+        violations |= SETTLEMENT_ALREADY_SETTLED;
+    }
+    if !round_accounting.seeded {
+        violations |= SETTLEMENT_NOT_SEEDED;
+    }
+    if ctx.accounts.betting_pool.lending_deployed > 0 {
+        violations |= SETTLEMENT_LIQUIDITY_DEPLOYED;
+    }
+    if match_results.len() != MATCHES_PER_ROUND
+        || match_results.iter().any(|result| !(1..=RESULT_VOID).contains(result))
+    {
+        violations |= SETTLEMENT_BAD_RESULTS;
+    }
+    if round_accounting.locked_odds.iter().any(|odds| !odds.locked) {
+        violations |= SETTLEMENT_ODDS_NOT_LOCKED;
+    }
+
+    let available_liquidity = round_funds(
+        round_accounting.isolated,
+        &ctx.accounts.betting_pool_token_account,
+        ctx.accounts.round_vault.as_ref(),
+    )?
+    .amount;
+
+    // Settle a copy; the real account is never written
+    let mut settled = (**round_accounting).clone();
+    settled.total_winning_pool = 0;
+    settled.total_losing_pool = 0;
+    if violations & SETTLEMENT_BAD_RESULTS == 0
+        && apply_match_results(&mut settled, &match_results, round_accounting.round_end_time, None, None).is_err()
+    {
+        violations |= SETTLEMENT_OVERFLOW;
+    }
+
+    if settled.total_reserved_for_winners > MAX_ROUND_PAYOUTS {
+        violations |= SETTLEMENT_PAYOUT_CAP_EXCEEDED;
+    }
+    if settled.total_reserved_for_winners > available_liquidity {
+        violations |= SETTLEMENT_INSUFFICIENT_LIQUIDITY;
+    }
+
+    if violations != 0 {
+        msg!("Settlement check failed: violations {:#010b}", violations);
+    }
+
+    Ok(SettlementCheck {
+        valid: violations == 0,
+        violations,
+        total_winning_pool: settled.total_winning_pool,
+        total_losing_pool: settled.total_losing_pool,
+        total_reserved_for_winners: settled.total_reserved_for_winners,
+        available_liquidity,
+    })
+}
@@ -1,12 +1,23 @@
 use anchor_lang::prelude::*;
-use crate::state::{BettingPool, RoundAccounting};
-use crate::vrf::{VrfRequest, extract_match_results};
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{BettingPool, RoundAccounting, RoundFixtures, AccessControl, Role, has_role};
+use crate::vrf::{
+    VrfRequest, VrfProof, VRF_PROOF_LEN, randomness_from_proof, round_match_results, vrf_seed,
+};
+use crate::attestation::verify_ed25519_signature;
 use crate::errors::SportsbookError;
+use crate::constants::*;
 
 /// Fulfill VRF request and settle round with random results
 ///
 /// This is called after the Switchboard oracle network has fulfilled
 /// the VRF request with provably random data.
+///
+/// Until the Switchboard CPI is wired in, the oracle is
+/// `betting_pool.results_attestor`: the transaction must carry an Ed25519
+/// program instruction right before this one in which it signs
+/// `vrf_seed(..)`, and that signature is the proof. The proof is stored in
+/// a VrfProof account so verify_round_randomness can re-check it later.
 #[derive(Accounts)]
 #[instruction(round_id: u64)]
 pub struct FulfillVrfRequest<'info> {
@@ -15,7 +26,7 @@ pub struct FulfillVrfRequest<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
         constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
@@ -24,21 +35,54 @@ pub struct FulfillVrfRequest<'info> {
 
     #[account(
         mut,
-        seeds = [b"vrf_request", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [VRF_REQUEST_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = vrf_request.bump,
         constraint = !vrf_request.fulfilled @ SportsbookError::RoundAlreadySettled,
     )]
     pub vrf_request: Account<'info, VrfRequest>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = VrfProof::LEN,
+        seeds = [VRF_PROOF_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vrf_proof: Account<'info, VrfProof>,
+
+    /// Required for league rounds: fixtures with the locked ratings and
+    /// goal model the results are simulated with
+    pub round_fixtures: Option<Box<Account<'info, RoundFixtures>>>,
+
     /// Switchboard VRF account (to read randomness from)
     /// CHECK: This is validated by reading the VRF result
     pub switchboard_vrf: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = authority.key() == betting_pool.authority)]
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Optional: the pool's roles, so a results submitter other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::ResultsSubmitter)
+            @ SportsbookError::InvalidAuthority,
+    )]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<FulfillVrfRequest>, round_id: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<FulfillVrfRequest>,
+    round_id: u64,
+    proof: [u8; VRF_PROOF_LEN],
+) -> Result<()> {
     // NOTE: In production, this would read the VRF result from the Switchboard
     // VRF account and verify the proof. For now, we'll use a placeholder.
     //
@@ -54,16 +98,34 @@ pub fn handler(ctx: Context<FulfillVrfRequest>, round_id: u64) -> Result<()> {
     // ctx.accounts.vrf_request.randomness.copy_from_slice(&result_buffer[0..320]);
     // ```
 
-    // For testing purposes, generate deterministic "randomness" from round_id
-    // In production, this would come from Switchboard VRF
-    let mut test_randomness = [0u8; 320];
-    for i in 0..320 {
-        test_randomness[i] = ((round_id as usize + i) % 256) as u8;
-    }
-    ctx.accounts.vrf_request.randomness = test_randomness;
+    // Until then the oracle's signature over the seed is the proof, and
+    // the randomness is derived from it
+    let oracle = ctx.accounts.betting_pool.results_attestor;
+    require!(oracle != Pubkey::default(), SportsbookError::AttestorNotSet);
+
+    let seed = vrf_seed(
+        &ctx.accounts.betting_pool.key(),
+        round_id,
+        ctx.accounts.vrf_request.request_time,
+    );
+    verify_ed25519_signature(&ctx.accounts.instructions, &oracle, &seed, &proof)?;
+
+    let vrf_proof = &mut ctx.accounts.vrf_proof;
+    vrf_proof.round_id = round_id;
+    vrf_proof.seed = seed;
+    vrf_proof.oracle = oracle;
+    vrf_proof.proof = proof;
+    vrf_proof.last_verified_time = 0;
+    vrf_proof.bump = ctx.bumps.vrf_proof;
+
+    ctx.accounts.vrf_request.randomness = randomness_from_proof(&proof);
 
     // Extract match results from randomness
-    let match_results = extract_match_results(&ctx.accounts.vrf_request.randomness);
+    let match_results = round_match_results(
+        &ctx.accounts.round_accounting,
+        ctx.accounts.round_fixtures.as_deref().map(|fixtures| &**fixtures),
+        &ctx.accounts.vrf_request.randomness,
+    )?;
     ctx.accounts.vrf_request.match_results = match_results;
 
     // Mark VRF request as fulfilled
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{BettingPool, RoundAccounting};
+use crate::state::{BettingPool, RoundAccounting, AccessControl, Role, has_role};
 use crate::vrf::VrfRequest;
 use crate::errors::SportsbookError;
+use crate::constants::*;
 
 /// Request VRF randomness for a round
 ///
@@ -15,7 +16,7 @@ pub struct RequestVrfRandomness<'info> {
 
     #[account(
         mut,
-        seeds = [b"round", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump = round_accounting.bump,
         constraint = round_accounting.seeded @ SportsbookError::RoundNotSeeded,
         constraint = !round_accounting.settled @ SportsbookError::RoundAlreadySettled,
@@ -26,7 +27,7 @@ pub struct RequestVrfRandomness<'info> {
         init,
         payer = authority,
         space = VrfRequest::LEN,
-        seeds = [b"vrf_request", betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        seeds = [VRF_REQUEST_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
         bump
     )]
     pub vrf_request: Box<Account<'info, VrfRequest>>,
@@ -76,7 +77,17 @@ pub struct RequestVrfRandomness<'info> {
     /// CHECK: This is the Switchboard V2 program
     pub switchboard_program: UncheckedAccount<'info>,
 
-    #[account(mut, constraint = authority.key() == betting_pool.authority)]
+    /// Optional: the pool's roles, so a results submitter other than the authority can sign
+    #[account(
+        seeds = [ACCESS_CONTROL_SEED, betting_pool.key().as_ref()],
+        bump = access_control.bump,
+    )]
+    pub access_control: Option<Account<'info, AccessControl>>,
+
+    #[account(
+        mut, constraint = has_role(&betting_pool, access_control.as_deref(), &authority.key(), Role::ResultsSubmitter)
+            @ SportsbookError::InvalidAuthority,
+    )]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::{BettingPool, RoundAccounting, RoundFixtures, MatchOutcome, Notifications, NotificationKind};
+use crate::vrf::{VrfRequest, VrfProof, randomness_from_proof, round_match_results, vrf_seed};
+use crate::attestation::verify_ed25519_signature;
+use crate::errors::SportsbookError;
+use crate::events::RoundRandomnessDisputed;
+use crate::constants::*;
+use crate::clock::{pool_clock, TimeSource};
+use super::notifications::notify;
+
+/// Re-verify a settled round's randomness from its stored VRF proof
+///
+/// Permissionless. The transaction must carry an Ed25519 program
+/// instruction right before this one checking the stored proof against the
+/// stored seed and oracle key. The seed is then re-derived from the round,
+/// and the randomness, the VRF match results (simulated with the locked
+/// ratings for league rounds) and the round's settled results are each
+/// recomputed from the proof. Any mismatch marks the round
+/// disputed, which holds claims and finalization until the guardian
+/// resolves it.
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct VerifyRoundRandomness<'info> {
+    pub betting_pool: Account<'info, BettingPool>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = round_accounting.bump,
+        constraint = round_accounting.settled @ SportsbookError::RoundNotSettled,
+        constraint = !round_accounting.revenue_distributed @ SportsbookError::RevenueAlreadyDistributed,
+    )]
+    pub round_accounting: Account<'info, RoundAccounting>,
+
+    #[account(
+        seeds = [VRF_REQUEST_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = vrf_request.bump,
+    )]
+    pub vrf_request: Account<'info, VrfRequest>,
+
+    #[account(
+        mut,
+        seeds = [VRF_PROOF_SEED, betting_pool.key().as_ref(), round_id.to_le_bytes().as_ref()],
+        bump = vrf_proof.bump,
+    )]
+    pub vrf_proof: Account<'info, VrfProof>,
+
+    /// Required for league rounds (see fulfill_vrf_request)
+    pub round_fixtures: Option<Box<Account<'info, RoundFixtures>>>,
+
+    /// Optional: the pool's operator notification queue
+    #[account(
+        mut,
+        seeds = [NOTIFICATIONS_SEED, betting_pool.key().as_ref()],
+        bump = notifications.bump,
+    )]
+    pub notifications: Option<Box<Account<'info, Notifications>>>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub verifier: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<VerifyRoundRandomness>, round_id: u64) -> Result<()> {
+    let vrf_proof = &ctx.accounts.vrf_proof;
+    verify_ed25519_signature(
+        &ctx.accounts.instructions,
+        &vrf_proof.oracle,
+        &vrf_proof.seed,
+        &vrf_proof.proof,
+    )?;
+
+    let vrf_request = &ctx.accounts.vrf_request;
+    let seed = vrf_seed(&ctx.accounts.betting_pool.key(), round_id, vrf_request.request_time);
+    let randomness = randomness_from_proof(&vrf_proof.proof);
+    let match_results = round_match_results(
+        &ctx.accounts.round_accounting,
+        ctx.accounts.round_fixtures.as_deref().map(|fixtures| &**fixtures),
+        &randomness,
+    )?;
+
+    let settled_results = ctx.accounts.round_accounting.match_results.map(|outcome| match outcome {
+        MatchOutcome::HomeWin => 1,
+        MatchOutcome::AwayWin => 2,
+        MatchOutcome::Draw => 3,
+        _ => 0,
+    });
+
+    let valid = seed == vrf_proof.seed
+        && randomness == vrf_request.randomness
+        && match_results == vrf_request.match_results
+        && match_results == settled_results;
+
+    let current_time = pool_clock(&ctx.accounts.betting_pool).unix_timestamp()?;
+    ctx.accounts.vrf_proof.last_verified_time = current_time;
+
+    if valid {
+        msg!("Round {} randomness verified", round_id);
+        return Ok(());
+    }
+
+    ctx.accounts.round_accounting.disputed = true;
+    notify(
+        ctx.accounts.notifications.as_deref_mut().map(|notifications| &mut **notifications),
+        NotificationKind::DisputeOpened,
+        round_id,
+        ctx.accounts.verifier.key(),
+        0,
+        current_time,
+    );
+
+    emit!(RoundRandomnessDisputed {
+        betting_pool: ctx.accounts.betting_pool.key(),
+        round_id,
+        verifier: ctx.accounts.verifier.key(),
+        expected_results: match_results,
+        settled_results,
+    });
+
+    msg!("Round {} randomness failed verification; round disputed", round_id);
+    msg!("Expected results: {:?}", match_results);
+    msg!("Settled results: {:?}", settled_results);
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Conservation-of-Funds Checks
+//!
+//! A round's accounting says how much of its funds account it should
+//! hold: seed + deposits - payouts - refunds - protocol-funded bounties.
+//! Builds with the `invariant-checks` feature (tests only) snapshot that
+//! figure and the account's balance when a fund-moving handler starts, and
+//! fail the instruction if the two moved by different amounts by the time
+//! it ends, so accounting drift surfaces in the test that caused it rather
+//! than at finalization. Without the feature the checks compile to nothing.
+
+use anchor_lang::prelude::*;
+use crate::errors::SportsbookError;
+use crate::state::RoundAccounting;
+
+/// Whether this build runs the conservation checks
+pub const INVARIANT_CHECKS_ENABLED: bool = cfg!(feature = "invariant-checks");
+
+/// Token account layout shared by SPL Token and Token-2022: amount at 64..72
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// What the round's accounting says has come into its funds account, less
+/// what it says has gone out
+pub fn round_ledger(round_accounting: &RoundAccounting) -> i128 {
+    round_accounting.protocol_seed_amount as i128 + round_accounting.total_user_deposits as i128
+        - round_accounting.total_paid_out as i128
+        - round_accounting.total_refunded as i128
+        - round_accounting.protocol_bounties_paid as i128
+}
+
+/// A round's funds account, with its balance and ledger when a handler
+/// started
+pub struct FundsCheck<'info> {
+    funds_account: Option<AccountInfo<'info>>,
+    balance: u64,
+    ledger: i128,
+}
+
+impl<'info> FundsCheck<'info> {
+    /// `funds_account` is None when the instruction wasn't given one, in
+    /// which case it can't have moved any funds either
+    pub fn start(
+        round_accounting: &RoundAccounting,
+        funds_account: Option<AccountInfo<'info>>,
+    ) -> Result<Self> {
+        let funds_account = funds_account.filter(|_| INVARIANT_CHECKS_ENABLED);
+        let balance = match &funds_account {
+            Some(funds_account) => token_balance(funds_account)?,
+            None => 0,
+        };
+
+        Ok(FundsCheck {
+            funds_account,
+            balance,
+            ledger: round_ledger(round_accounting),
+        })
+    }
+
+    /// Fail unless the funds account moved by exactly what the accounting
+    /// recorded. `counted_elsewhere` is what the handler paid out that the
+    /// round's accounting counts in another instruction (reserve_payouts
+    /// before a reserved claim, merge_claim_shard after a sharded one).
+    pub fn finish(self, round_accounting: &RoundAccounting, counted_elsewhere: u64) -> Result<()> {
+        let Some(funds_account) = &self.funds_account else {
+            return Ok(());
+        };
+
+        let balance_change = token_balance(funds_account)? as i128 - self.balance as i128;
+        let ledger_change = round_ledger(round_accounting) - self.ledger;
+        if !conserved(balance_change, ledger_change, counted_elsewhere) {
+            msg!(
+                "Round {}: funds moved {}, accounting moved {} ({} counted elsewhere)",
+                round_accounting.round_id,
+                balance_change,
+                ledger_change,
+                counted_elsewhere
+            );
+            return err!(SportsbookError::FundsNotConserved);
+        }
+
+        Ok(())
+    }
+}
+
+fn conserved(balance_change: i128, ledger_change: i128, counted_elsewhere: u64) -> bool {
+    balance_change == ledger_change - counted_elsewhere as i128
+}
+
+/// Current amount of a token account, read from its data so it reflects
+/// transfers made earlier in the instruction
+fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    let bytes = data
+        .get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+        .ok_or(SportsbookError::InvalidTokenAccount)?;
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(bytes);
+    Ok(u64::from_le_bytes(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conservation_balances_ledger_against_balance() {
+        // Bet placed: stake after fee in, deposits up by the same
+        assert!(conserved(95, 95, 0));
+        // Claim paid 190 and recorded 190
+        assert!(conserved(-190, -190, 0));
+        // Reserved claim: counted by reserve_payouts, paid now
+        assert!(conserved(-190, 0, 190));
+        // Paid without recording it, or recorded twice
+        assert!(!conserved(-190, 0, 0));
+        assert!(!conserved(-190, -380, 0));
+        assert!(!conserved(-190, -190, 190));
+    }
+}
@@ -1,5 +1,8 @@
+// Anchor 0.31's generated IDL resize handler still calls the deprecated
+// AccountInfo::realloc; `no-idl` builds leave it out and keep the lint
+#![cfg_attr(not(feature = "no-idl"), allow(deprecated))]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 pub mod state;
 pub mod instructions;
@@ -7,6 +10,13 @@ pub mod errors;
 pub mod constants;
 pub mod utils;
 pub mod vrf;
+pub mod events;
+pub mod pyth;
+pub mod attestation;
+pub mod clock;
+pub mod merkle;
+pub mod token_hook;
+pub mod invariants;
 
 use instructions::*;
 use state::*;
@@ -36,8 +46,8 @@ pub mod sportsbook {
     }
 
     /// Seed match pools with differentiated amounts based on team matchup
-    pub fn seed_round_pools(
-        ctx: Context<SeedRoundPools>,
+    pub fn seed_round_pools<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SeedRoundPools<'info>>,
         round_id: u64,
     ) -> Result<()> {
         instructions::seed_round::handler(ctx, round_id)
@@ -50,7 +60,7 @@ pub mod sportsbook {
         match_indices: Vec<u8>,
         outcomes: Vec<u8>,
         amount: u64,
-    ) -> Result<()> {
+    ) -> Result<BetReceipt> {
         instructions::place_bet::handler(ctx, round_id, match_indices, outcomes, amount)
     }
 
@@ -64,11 +74,11 @@ pub mod sportsbook {
     }
 
     /// Claim winnings for a bet (pull pattern)
-    pub fn claim_winnings(
-        ctx: Context<ClaimWinnings>,
+    pub fn claim_winnings<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimWinnings<'info>>,
         bet_id: u64,
         min_payout: u64,
-    ) -> Result<()> {
+    ) -> Result<ClaimReceipt> {
         instructions::claim_winnings::handler(ctx, bet_id, min_payout)
     }
 
@@ -92,8 +102,17 @@ pub mod sportsbook {
     pub fn fulfill_vrf_request(
         ctx: Context<FulfillVrfRequest>,
         round_id: u64,
+        proof: [u8; 64],
     ) -> Result<()> {
-        instructions::vrf_fulfill::handler(ctx, round_id)
+        instructions::vrf_fulfill::handler(ctx, round_id, proof)
+    }
+
+    /// Re-check a settled round's randomness against its stored VRF proof
+    pub fn verify_round_randomness(
+        ctx: Context<VerifyRoundRandomness>,
+        round_id: u64,
+    ) -> Result<()> {
+        instructions::vrf_verify::handler(ctx, round_id)
     }
 
     /// Make a season prediction and receive commemorative NFT
@@ -126,4 +145,686 @@ pub mod sportsbook {
     ) -> Result<()> {
         instructions::end_season::start_new_season_handler(ctx)
     }
+
+    /// Check the pool token balance covers the liabilities of every unclosed round
+    pub fn reconcile_pool(
+        ctx: Context<ReconcilePool>,
+        strict: bool,
+    ) -> Result<()> {
+        instructions::reconcile_pool::handler(ctx, strict)
+    }
+
+    /// Set the lending program for idle pool liquidity; loosening changes are timelocked
+    pub fn set_lending_program(
+        ctx: Context<SetLendingProgram>,
+        lending_program: Pubkey,
+        max_lending_bps: u16,
+    ) -> Result<()> {
+        instructions::idle_liquidity::set_lending_program_handler(ctx, lending_program, max_lending_bps)
+    }
+
+    /// Apply a queued lending program change after its timelock
+    pub fn apply_lending_program(
+        ctx: Context<ApplyLendingProgram>,
+    ) -> Result<()> {
+        instructions::idle_liquidity::apply_lending_program_handler(ctx)
+    }
+
+    /// Deposit idle pool liquidity into the whitelisted lending program
+    pub fn deposit_idle_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, MoveIdleLiquidity<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::idle_liquidity::deposit_handler(ctx, amount)
+    }
+
+    /// Recall liquidity (principal + interest) from the lending program
+    pub fn recall_idle_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, MoveIdleLiquidity<'info>>,
+        collateral_amount: u64,
+    ) -> Result<()> {
+        instructions::idle_liquidity::recall_handler(ctx, collateral_amount)
+    }
+
+    /// Write off lent principal the lending program will not return
+    pub fn recognize_lending_loss(ctx: Context<RecognizeLendingLoss>, loss: u64) -> Result<()> {
+        instructions::idle_liquidity::recognize_loss_handler(ctx, loss)
+    }
+
+    /// Set the Pyth price feed used to value a non-stablecoin pool in USD
+    pub fn set_price_feed(
+        ctx: Context<SetPriceFeed>,
+        max_bet_usd: u64,
+    ) -> Result<()> {
+        instructions::price_feed::handler(ctx, max_bet_usd)
+    }
+
+    /// Set odds for a whole match day from a packed (match_index, home, away, draw) payload
+    pub fn bulk_set_odds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BulkSetOdds<'info>>,
+        round_id: u64,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        instructions::bulk_set_odds::handler(ctx, round_id, payload)
+    }
+
+    /// Set the key that signs packed result attestations
+    pub fn set_results_attestor(
+        ctx: Context<SetResultsAttestor>,
+        results_attestor: Pubkey,
+    ) -> Result<()> {
+        instructions::bulk_submit_results::set_results_attestor_handler(ctx, results_attestor)
+    }
+
+    /// Settle a whole round from one packed (3 bits per match), attested result payload
+    pub fn bulk_submit_results(
+        ctx: Context<BulkSubmitResults>,
+        round_id: u64,
+        packed_results: Vec<u8>,
+    ) -> Result<()> {
+        instructions::bulk_submit_results::handler(ctx, round_id, packed_results)
+    }
+
+    /// Place a bet seeded in the bettor's own nonce namespace
+    pub fn place_user_bet(
+        ctx: Context<PlaceUserBet>,
+        round_id: u64,
+        match_indices: Vec<u8>,
+        outcomes: Vec<u8>,
+        amount: u64,
+    ) -> Result<BetReceipt> {
+        instructions::place_bet::place_user_bet_handler(ctx, round_id, match_indices, outcomes, amount)
+    }
+
+    /// Place a bet paid by the signer but owned (and claimable) by another wallet
+    pub fn place_gift_bet(
+        ctx: Context<PlaceGiftBet>,
+        round_id: u64,
+        match_indices: Vec<u8>,
+        outcomes: Vec<u8>,
+        amount: u64,
+    ) -> Result<BetReceipt> {
+        instructions::place_bet::place_gift_bet_handler(ctx, round_id, match_indices, outcomes, amount)
+    }
+
+    /// Archive a finalized round: write a RoundArchive summary and close its live accounts
+    pub fn archive_round(ctx: Context<ArchiveRound>, round_id: u64) -> Result<()> {
+        instructions::archive_round::handler(ctx, round_id)
+    }
+
+    /// Grow an account created under an older, shorter layout to the current size
+    pub fn resize_account(ctx: Context<ResizeAccount>) -> Result<()> {
+        instructions::resize_account::handler(ctx)
+    }
+
+    /// Settle a losing bet without transfers (cheap keeper crank)
+    pub fn settle_losing_bet(ctx: Context<SettleLosingBet>) -> Result<()> {
+        instructions::claim_winnings::settle_losing_bet_handler(ctx)
+    }
+
+    /// Batch-settle (and optionally close) losing bets of a round
+    pub fn settle_lost_bets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleLostBets<'info>>,
+        round_id: u64,
+        close_accounts: bool,
+    ) -> Result<()> {
+        instructions::settle_lost_bets::handler(ctx, round_id, close_accounts)
+    }
+
+    /// Set max parlay legs, min odds per leg and max combined odds
+    pub fn set_bet_limits(
+        ctx: Context<SetBetLimits>,
+        max_parlay_legs: u8,
+        min_leg_odds: u64,
+        max_combined_odds: u64,
+    ) -> Result<()> {
+        instructions::bet_limits::handler(ctx, max_parlay_legs, min_leg_odds, max_combined_odds)
+    }
+
+    /// Appoint the guardian that can pause claims on a round
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::guardian::set_guardian_handler(ctx, guardian)
+    }
+
+    /// Pause claims on one round for `duration` seconds (guardian or pauser)
+    pub fn pause_round_claims(
+        ctx: Context<SetRoundClaimsPause>,
+        round_id: u64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::guardian::pause_handler(ctx, round_id, duration)
+    }
+
+    /// Resume claims on a paused round before the pause lapses
+    pub fn unpause_round_claims(ctx: Context<SetRoundClaimsPause>, round_id: u64) -> Result<()> {
+        instructions::guardian::unpause_handler(ctx, round_id)
+    }
+
+    /// Clear a round's randomness dispute (guardian only)
+    pub fn resolve_round_dispute(ctx: Context<SetRoundClaimsPause>, round_id: u64) -> Result<()> {
+        instructions::guardian::resolve_dispute_handler(ctx, round_id)
+    }
+
+    /// Simulate a claim and return (won, payout, bounty split) as return data
+    pub fn preview_claim(ctx: Context<PreviewClaim>, bet_id: u64) -> Result<ClaimPreview> {
+        instructions::claim_winnings::preview_claim_handler(ctx, bet_id)
+    }
+
+    /// Pin the pool's clock for testing (mock-clock builds only; 0 = use real time)
+    pub fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64) -> Result<()> {
+        instructions::mock_clock::handler(ctx, unix_timestamp)
+    }
+
+    /// Claim winnings with a Merkle proof of the bet's locked odds (Merkle settlement pools)
+    pub fn claim_winnings_with_proof<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimWinnings<'info>>,
+        bet_id: u64,
+        min_payout: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<ClaimReceipt> {
+        instructions::claim_winnings::claim_with_proof_handler(ctx, bet_id, min_payout, proof)
+    }
+
+    /// Switch the pool between plain and Merkle-proof settlement
+    pub fn set_settlement_mode(ctx: Context<SetSettlementMode>, merkle_settlement: bool) -> Result<()> {
+        instructions::settlement_mode::handler(ctx, merkle_settlement)
+    }
+
+    /// Pay late-claim bounties in new rounds from protocol revenue (or from winnings)
+    pub fn set_bounty_mode(ctx: Context<SetBountyMode>, protocol_funded: bool) -> Result<()> {
+        instructions::bounty_mode::handler(ctx, protocol_funded)
+    }
+
+    /// Set the protocol fee per parlay length (all zero = off)
+    pub fn set_leg_fees(ctx: Context<SetLegFees>, fee_bps: [u16; constants::MATCHES_PER_ROUND]) -> Result<()> {
+        instructions::leg_fees::handler(ctx, fee_bps)
+    }
+
+    /// Set the pool's minimum stake and the unit fees and payouts round to
+    pub fn set_stake_policy(ctx: Context<SetStakePolicy>, min_stake: u64, rounding_unit: u64) -> Result<()> {
+        instructions::stake_policy::handler(ctx, min_stake, rounding_unit)
+    }
+
+    /// Give rounds created from now on their own token vault (or stop)
+    pub fn set_round_vaults(ctx: Context<SetRoundVaults>, enabled: bool) -> Result<()> {
+        instructions::round_vault::set_round_vaults_handler(ctx, enabled)
+    }
+
+    /// Create an isolated round's token vault before it is seeded
+    pub fn create_round_vault(ctx: Context<CreateRoundVault>, round_id: u64) -> Result<()> {
+        instructions::round_vault::create_round_vault_handler(ctx, round_id)
+    }
+
+    /// Start a ring buffer of a match's odds updates
+    pub fn init_odds_history(ctx: Context<InitOddsHistory>, round_id: u64, match_index: u8) -> Result<()> {
+        instructions::odds_history::init_odds_history_handler(ctx, round_id, match_index)
+    }
+
+    /// Write a permanent result attestation (results hash, volume, PnL) for a finalized round
+    pub fn attest_round(ctx: Context<AttestRound>, round_id: u64) -> Result<()> {
+        instructions::attest_round::handler(ctx, round_id)
+    }
+
+    /// Create the DailyVolume, ActiveBettors and PendingClaims read models
+    pub fn initialize_metrics(ctx: Context<InitializeMetrics>) -> Result<()> {
+        instructions::metrics::handler(ctx)
+    }
+
+    /// Set volume-based protocol fee tiers (30-day rolling stake per bettor)
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: [FeeTier; FEE_TIER_COUNT]) -> Result<()> {
+        instructions::fee_tiers::handler(ctx, fee_tiers)
+    }
+
+    /// Create a budget-capped promo (code hash, bonus on stake, expiry, per-wallet limit)
+    pub fn create_promo(
+        ctx: Context<CreatePromo>,
+        code_hash: [u8; 32],
+        budget: u64,
+        bonus_bps: u16,
+        expiry: i64,
+        max_redemptions_per_wallet: u8,
+    ) -> Result<()> {
+        instructions::promo::create_promo_handler(
+            ctx,
+            code_hash,
+            budget,
+            bonus_bps,
+            expiry,
+            max_redemptions_per_wallet,
+        )
+    }
+
+    /// Redeem a promo code against one of the caller's open bets
+    pub fn redeem_promo(ctx: Context<RedeemPromo>, code: String) -> Result<()> {
+        instructions::promo::redeem_promo_handler(ctx, code)
+    }
+
+    /// Authorize or update mirroring of a leader's bets (per-round budget, success fee)
+    pub fn set_copy_config(
+        ctx: Context<SetCopyConfig>,
+        leader: Pubkey,
+        stake_bps: u16,
+        budget_per_round: u64,
+        success_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::copy_betting::set_copy_config_handler(ctx, leader, stake_bps, budget_per_round, success_fee_bps)
+    }
+
+    /// Stop copying a leader and close the config
+    pub fn close_copy_config(ctx: Context<CloseCopyConfig>) -> Result<()> {
+        instructions::copy_betting::close_copy_config_handler(ctx)
+    }
+
+    /// Crank: mirror a leader's bet for a follower at the leader's locked multiplier
+    pub fn copy_bet(ctx: Context<CopyBet>) -> Result<BetReceipt> {
+        instructions::copy_betting::copy_bet_handler(ctx)
+    }
+
+    /// Whitelist, update or deactivate a creator of branded rounds
+    pub fn set_creator(
+        ctx: Context<SetCreator>,
+        wallet: Pubkey,
+        revenue_share_bps: u16,
+        active: bool,
+    ) -> Result<()> {
+        instructions::creator_rounds::set_creator_handler(ctx, wallet, revenue_share_bps, active)
+    }
+
+    /// Start the next round under a whitelisted creator's brand
+    pub fn initialize_branded_round(
+        ctx: Context<InitializeBrandedRound>,
+        round_id: u64,
+        brand: [u8; 32],
+    ) -> Result<()> {
+        instructions::creator_rounds::initialize_branded_round_handler(ctx, round_id, brand)
+    }
+
+    /// Open a phantom league (double round-robin) for a season
+    pub fn create_league(ctx: Context<CreateLeague>, season_id: u64) -> Result<()> {
+        instructions::league_schedule::create_league_handler(ctx, season_id)
+    }
+
+    /// Register a team in a league that hasn't started
+    pub fn register_league_team(ctx: Context<RegisterLeagueTeam>, name: [u8; 16]) -> Result<()> {
+        instructions::league_schedule::register_league_team_handler(ctx, name)
+    }
+
+    /// Change one team's strength rating
+    pub fn set_team_rating(ctx: Context<SetLeagueModel>, team: u8, rating: u16) -> Result<()> {
+        instructions::league_schedule::set_team_rating_handler(ctx, team, rating)
+    }
+
+    /// Change the goal model future league rounds are simulated with
+    pub fn set_goal_model(ctx: Context<SetLeagueModel>, goal_model: GoalModelParams) -> Result<()> {
+        instructions::league_schedule::set_goal_model_handler(ctx, goal_model)
+    }
+
+    /// Crank: create the round and fixtures for the league's next matchday
+    pub fn materialize_league_round(ctx: Context<MaterializeLeagueRound>, round_id: u64) -> Result<()> {
+        instructions::league_schedule::materialize_league_round_handler(ctx, round_id)
+    }
+
+    /// Record goals for a settled league round (feeds goal difference)
+    pub fn record_fixture_scores(ctx: Context<RecordFixtureScores>, round_id: u64, scores: Vec<u8>) -> Result<()> {
+        instructions::league_schedule::record_fixture_scores_handler(ctx, round_id, scores)
+    }
+
+    /// Crank: apply the league's next settled matchday to its season table
+    pub fn update_season_table(ctx: Context<UpdateSeasonTable>) -> Result<()> {
+        instructions::league_schedule::update_season_table_handler(ctx)
+    }
+
+    /// Open a season points registry shared across pools
+    pub fn create_season_registry(ctx: Context<CreateSeasonRegistry>, season_id: u64) -> Result<()> {
+        instructions::season_registry::create_season_registry_handler(ctx, season_id)
+    }
+
+    /// Let another pool deployment report into a season registry
+    pub fn add_season_pool(ctx: Context<ManageSeasonRegistry>, pool_program: Pubkey) -> Result<()> {
+        instructions::season_registry::add_season_pool_handler(ctx, pool_program)
+    }
+
+    /// Close a season registry; its standings are final
+    pub fn end_season_registry(ctx: Context<ManageSeasonRegistry>) -> Result<()> {
+        instructions::season_registry::end_season_registry_handler(ctx)
+    }
+
+    /// Choose the season registry this pool reports to
+    pub fn set_season_registry(ctx: Context<SetSeasonRegistry>, season_registry: Pubkey) -> Result<()> {
+        instructions::season_registry::set_season_registry_handler(ctx, season_registry)
+    }
+
+    /// CPI target: add a reported stake to a wallet's season points
+    pub fn record_season_points(
+        ctx: Context<RecordSeasonPoints>,
+        pool_program: Pubkey,
+        user: Pubkey,
+        points: u64,
+        round_start_time: i64,
+    ) -> Result<()> {
+        instructions::season_registry::record_season_points_handler(
+            ctx,
+            pool_program,
+            user,
+            points,
+            round_start_time,
+        )
+    }
+
+    /// Crank: report a bet's USD stake to the pool's season registry
+    pub fn accrue_season_points(ctx: Context<AccrueSeasonPoints>) -> Result<()> {
+        instructions::season_registry::accrue_season_points_handler(ctx)
+    }
+
+    /// Claim a small single-leg payout with minimal compute (no bounty or copy fee)
+    pub fn quick_claim(ctx: Context<QuickClaim>) -> Result<()> {
+        instructions::claim_winnings::quick_claim_handler(ctx)
+    }
+
+    /// Set kickoff times for a round's matches (authority)
+    pub fn set_match_kickoffs(
+        ctx: Context<SetMatchKickoffs>,
+        round_id: u64,
+        kickoff_times: [i64; 10],
+    ) -> Result<()> {
+        instructions::lock_odds::set_match_kickoffs_handler(ctx, round_id, kickoff_times)
+    }
+
+    /// Close a match's market after kickoff (permissionless)
+    pub fn lock_odds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LockOdds<'info>>,
+        round_id: u64,
+        match_index: u8,
+    ) -> Result<()> {
+        instructions::lock_odds::lock_odds_handler(ctx, round_id, match_index)
+    }
+
+    /// Set a round's metadata URI and content hash (authority, before bets)
+    pub fn set_round_metadata(
+        ctx: Context<SetRoundMetadata>,
+        round_id: u64,
+        uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::round_metadata::handler(ctx, round_id, uri, content_hash)
+    }
+
+    /// Set the lottery's share of protocol revenue and winners per draw
+    pub fn configure_lottery(ctx: Context<ConfigureLottery>, share_bps: u16, winners_per_draw: u8) -> Result<()> {
+        instructions::lottery::configure_lottery_handler(ctx, share_bps, winners_per_draw)
+    }
+
+    /// Open the consolation lottery for a week (permissionless)
+    pub fn open_lottery(ctx: Context<OpenLottery>, lottery_id: u64) -> Result<()> {
+        instructions::lottery::open_lottery_handler(ctx, lottery_id)
+    }
+
+    /// Enter a losing bet as a lottery ticket
+    pub fn enter_lottery(ctx: Context<EnterLottery>, lottery_id: u64) -> Result<()> {
+        instructions::lottery::enter_lottery_handler(ctx, lottery_id)
+    }
+
+    /// Draw a closed lottery's winners from the oracle's signature
+    pub fn draw_lottery(ctx: Context<DrawLottery>, lottery_id: u64, proof: [u8; 64]) -> Result<()> {
+        instructions::lottery::draw_lottery_handler(ctx, lottery_id, proof)
+    }
+
+    /// Pay out a winning lottery ticket
+    pub fn claim_lottery_prize(ctx: Context<ClaimLotteryPrize>, lottery_id: u64) -> Result<()> {
+        instructions::lottery::claim_lottery_prize_handler(ctx, lottery_id)
+    }
+
+    /// Package a settled round's results as a cross-chain (Wormhole) message
+    pub fn export_round_results(ctx: Context<ExportRoundResults>, round_id: u64) -> Result<()> {
+        instructions::export_results::handler(ctx, round_id)
+    }
+
+    /// Register a market maker with its assigned matches and quoting bounds
+    pub fn set_market_maker(
+        ctx: Context<SetMarketMaker>,
+        wallet: Pubkey,
+        match_mask: u16,
+        max_deviation_bps: u16,
+        min_update_interval: i64,
+        active: bool,
+    ) -> Result<()> {
+        instructions::market_maker::set_market_maker_handler(
+            ctx,
+            wallet,
+            match_mask,
+            max_deviation_bps,
+            min_update_interval,
+            active,
+        )
+    }
+
+    /// Market maker: quote new odds for an assigned match within its bounds
+    pub fn quote_odds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QuoteOdds<'info>>,
+        round_id: u64,
+        match_index: u8,
+        home_odds: u64,
+        away_odds: u64,
+        draw_odds: u64,
+    ) -> Result<()> {
+        instructions::market_maker::quote_odds_handler(ctx, round_id, match_index, home_odds, away_odds, draw_odds)
+    }
+
+    /// Bounty hunter: claim many expired winning bets of a round at once
+    pub fn bounty_sweep<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BountySweep<'info>>,
+        round_id: u64,
+    ) -> Result<()> {
+        instructions::bounty_sweep::handler(ctx, round_id)
+    }
+
+    /// Bettor: ask to be reminded `lead_time` seconds before a bet's claim deadline
+    pub fn register_reminder(ctx: Context<RegisterReminder>, lead_time: i64) -> Result<()> {
+        instructions::claim_reminder::register_reminder_handler(ctx, lead_time)
+    }
+
+    /// Write a settled round's claim deadline onto its bets and reminders
+    pub fn stamp_claim_deadlines<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StampClaimDeadlines<'info>>,
+        round_id: u64,
+    ) -> Result<()> {
+        instructions::claim_reminder::stamp_claim_deadlines_handler(ctx, round_id)
+    }
+
+    /// Bettor: close a claim reminder and reclaim its rent
+    pub fn close_reminder(ctx: Context<CloseReminder>) -> Result<()> {
+        instructions::claim_reminder::close_reminder_handler(ctx)
+    }
+
+    /// Dry-run settle_round with proposed results and report broken invariants
+    pub fn validate_settlement(
+        ctx: Context<ValidateSettlement>,
+        round_id: u64,
+        match_results: Vec<u8>,
+    ) -> Result<SettlementCheck> {
+        instructions::validate_settlement::handler(ctx, round_id, match_results)
+    }
+
+    /// Count a settled round's winning and voided bets into its totals ahead of their claims
+    pub fn reserve_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReservePayouts<'info>>,
+        round_id: u64,
+    ) -> Result<()> {
+        instructions::reserve_payouts::reserve_payouts_handler(ctx, round_id)
+    }
+
+    /// Pay out a bet whose payout reserve_payouts already counted
+    pub fn claim_reserved(ctx: Context<ClaimReserved>, bet_id: u64) -> Result<()> {
+        instructions::reserve_payouts::claim_reserved_handler(ctx, bet_id)
+    }
+
+    /// Open one of a settled round's claim shards
+    pub fn init_claim_shard(ctx: Context<InitClaimShard>, round_id: u64, shard_index: u8) -> Result<()> {
+        instructions::claim_shard::init_claim_shard_handler(ctx, round_id, shard_index)
+    }
+
+    /// Claim a bet, writing its claim shard instead of the round
+    pub fn claim_sharded(ctx: Context<ClaimSharded>, bet_id: u64) -> Result<()> {
+        instructions::claim_shard::claim_sharded_handler(ctx, bet_id)
+    }
+
+    /// Fold a claim shard back into its round and close it
+    pub fn merge_claim_shard(ctx: Context<MergeClaimShard>) -> Result<()> {
+        instructions::claim_shard::merge_claim_shard_handler(ctx)
+    }
+
+    /// Authority: move seed capital into the LP pool
+    pub fn fund_liquidity_pool(ctx: Context<FundLiquidityPool>, amount: u64) -> Result<()> {
+        instructions::fund_liquidity::handler(ctx, amount)
+    }
+
+    /// Grant an operational role (odds manager, results submitter, treasurer, pauser)
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        instructions::access_control::grant_role_handler(ctx, role, holder)
+    }
+
+    /// Revoke an operational role
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        instructions::access_control::revoke_role_handler(ctx, role)
+    }
+
+    /// Authority: bound how far one odds update can move a line and how often a match's odds can change
+    pub fn set_odds_update_limits(
+        ctx: Context<SetOddsUpdateLimits>,
+        max_move_bps: u16,
+        min_update_interval: i64,
+    ) -> Result<()> {
+        instructions::odds_update_limits::set_odds_update_limits_handler(ctx, max_move_bps, min_update_interval)
+    }
+
+    /// Place a parlay across matches of different rounds
+    pub fn place_accumulator<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceAccumulator<'info>>,
+        anchor_round_id: u64,
+        round_ids: Vec<u64>,
+        match_indices: Vec<u8>,
+        outcomes: Vec<u8>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::accumulator::place_accumulator_handler(
+            ctx,
+            anchor_round_id,
+            round_ids,
+            match_indices,
+            outcomes,
+            amount,
+        )
+    }
+
+    /// Settle a cross-round accumulator after its last round settles
+    pub fn settle_accumulator<'info>(ctx: Context<'_, '_, 'info, 'info, SettleAccumulator<'info>>) -> Result<()> {
+        instructions::accumulator::settle_accumulator_handler(ctx)
+    }
+
+    /// Set the premium for insuring parlays (creates the insurance fund)
+    pub fn configure_insurance(ctx: Context<ConfigureInsurance>, premium_bps: u16) -> Result<()> {
+        instructions::bet_insurance::configure_insurance_handler(ctx, premium_bps)
+    }
+
+    /// Top up the insurance vault (authority or treasurer)
+    pub fn fund_insurance(ctx: Context<FundInsurance>, amount: u64) -> Result<()> {
+        instructions::bet_insurance::fund_insurance_handler(ctx, amount)
+    }
+
+    /// Place a parlay insured against losing by exactly one leg
+    pub fn place_insured_bet(
+        ctx: Context<PlaceBet>,
+        round_id: u64,
+        match_indices: Vec<u8>,
+        outcomes: Vec<u8>,
+        amount: u64,
+    ) -> Result<BetReceipt> {
+        instructions::bet_insurance::place_insured_bet_handler(ctx, round_id, match_indices, outcomes, amount)
+    }
+
+    /// Create or refresh a round's compact RoundSummary (permissionless)
+    pub fn sync_round_summary(ctx: Context<SyncRoundSummary>, round_id: u64) -> Result<()> {
+        instructions::round_summary::sync_round_summary_handler(ctx, round_id)
+    }
+
+    /// Choose what close_season does with unclaimed season rewards
+    pub fn set_season_policy(
+        ctx: Context<SetSeasonPolicy>,
+        rollover: SeasonRollover,
+        claim_period_seconds: i64,
+    ) -> Result<()> {
+        instructions::close_season::set_season_policy_handler(ctx, rollover, claim_period_seconds)
+    }
+
+    /// Settle the ended season's unclaimed rewards and start the next season
+    pub fn close_season(ctx: Context<CloseSeason>) -> Result<()> {
+        instructions::close_season::close_season_handler(ctx)
+    }
+
+    /// Create the operator notification queue or set its liquidity threshold
+    pub fn configure_notifications(
+        ctx: Context<ConfigureNotifications>,
+        liquidity_low_threshold: u64,
+    ) -> Result<()> {
+        instructions::notifications::configure_notifications_handler(ctx, liquidity_low_threshold)
+    }
+
+    /// Create the pool's protocol PnL ledger, appended to by finalize_round_revenue
+    pub fn create_pn_ledger(ctx: Context<CreatePnLedger>) -> Result<()> {
+        instructions::pn_ledger::create_pn_ledger_handler(ctx)
+    }
+
+    /// Extend a round's claim deadline after an outage (authority only)
+    pub fn extend_claim_deadline(
+        ctx: Context<ExtendClaimDeadline>,
+        round_id: u64,
+        seconds: i64,
+    ) -> Result<()> {
+        instructions::guardian::extend_claim_deadline_handler(ctx, round_id, seconds)
+    }
+
+    /// Set whether claimed winnings should be held as betting credit when a
+    /// claim passes the bettor's UserStats (advisory)
+    pub fn set_compound_preference(ctx: Context<SetCompoundPreference>, prefer_compound: bool) -> Result<()> {
+        instructions::auto_compound::set_compound_preference_handler(ctx, prefer_compound)
+    }
+
+    /// Withdraw betting credit to the bettor's wallet
+    pub fn withdraw_credit(ctx: Context<WithdrawCredit>, amount: u64) -> Result<()> {
+        instructions::auto_compound::withdraw_credit_handler(ctx, amount)
+    }
+
+    /// Place a bet staked from the bettor's betting credit
+    pub fn place_credit_bet(
+        ctx: Context<PlaceBet>,
+        round_id: u64,
+        match_indices: Vec<u8>,
+        outcomes: Vec<u8>,
+        amount: u64,
+    ) -> Result<BetReceipt> {
+        instructions::auto_compound::place_credit_bet_handler(ctx, round_id, match_indices, outcomes, amount)
+    }
+
+    /// Create the pool's reconcile cursor so reconcile_pool can skip closed rounds
+    pub fn initialize_reconcile_cursor(
+        ctx: Context<InitializeReconcileCursor>,
+    ) -> Result<()> {
+        instructions::reconcile_pool::initialize_reconcile_cursor_handler(ctx)
+    }
+
+    /// Propose a destination pool liquidity may be sent to, usable after a timelock
+    pub fn propose_outflow_destination(
+        ctx: Context<ProposeOutflowDestination>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        instructions::outflow_allowlist::propose_outflow_destination_handler(ctx, destination)
+    }
+
+    /// Take a destination off the pool's outflow allowlist
+    pub fn remove_outflow_destination(
+        ctx: Context<RemoveOutflowDestination>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        instructions::outflow_allowlist::remove_outflow_destination_handler(ctx, destination)
+    }
 }
@@ -0,0 +1,47 @@
+//! Locked Odds Merkle Proof Module
+//!
+//! On-chain side of Merkle settlement mode. Tree layout, hashing and proof
+//! checking all come from `sportsbook_core::merkle`, so a client building a
+//! proof and the program verifying it run the same code.
+
+use anchor_lang::prelude::*;
+use sportsbook_core::{odds_leaf, verify_legs};
+use crate::state::{Bet, LockedOdds, RoundAccounting};
+use crate::errors::SportsbookError;
+
+/// Locked odds as the (home, away, draw) table the tree is built over
+pub fn locked_odds_table(locked_odds: &[LockedOdds; 10]) -> [(u64, u64, u64); 10] {
+    locked_odds.map(|odds| (odds.home_odds, odds.away_odds, odds.draw_odds))
+}
+
+/// Check a multi-proof that every leg of `bet` was priced from the odds
+/// table committed in `round_accounting.locked_odds_root`
+pub fn verify_bet_odds_proof(
+    bet: &Bet,
+    round_accounting: &RoundAccounting,
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    require!(
+        round_accounting.locked_odds_root != [0; 32],
+        SportsbookError::InvalidOddsProof
+    );
+
+    let legs: Vec<(u8, [u8; 32])> = bet
+        .get_predictions()
+        .iter()
+        .map(|prediction| {
+            let odds = &round_accounting.locked_odds[prediction.match_index as usize];
+            (
+                prediction.match_index,
+                odds_leaf(prediction.match_index, odds.home_odds, odds.away_odds, odds.draw_odds),
+            )
+        })
+        .collect();
+
+    require!(
+        verify_legs(&round_accounting.locked_odds_root, &legs, proof),
+        SportsbookError::InvalidOddsProof
+    );
+
+    Ok(())
+}
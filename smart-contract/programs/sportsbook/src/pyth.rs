@@ -0,0 +1,270 @@
+//! Pyth Price Feed Integration Module
+//!
+//! Reads the aggregate price straight out of a Pyth v2 price account so that
+//! non-stablecoin pools can value stakes and payouts in USD.
+//! Only the handful of fields we need are decoded.
+
+use anchor_lang::prelude::*;
+use crate::errors::SportsbookError;
+
+/// Pyth v2 account header constants
+pub const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+pub const PYTH_VERSION_2: u32 = 2;
+pub const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const PYTH_STATUS_TRADING: u32 = 1;
+
+/// Pyth v2 oracle program (mainnet-beta), the owner of its price accounts
+pub const PYTH_ORACLE_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Pyth v2 oracle program on devnet
+pub const PYTH_ORACLE_DEVNET_PROGRAM_ID: Pubkey = pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+
+/// Whether `owner` is a Pyth v2 oracle program
+pub fn is_pyth_oracle_program(owner: &Pubkey) -> bool {
+    *owner == PYTH_ORACLE_PROGRAM_ID || *owner == PYTH_ORACLE_DEVNET_PROGRAM_ID
+}
+
+/// Byte offsets inside a Pyth v2 price account
+const OFFSET_MAGIC: usize = 0;
+const OFFSET_VERSION: usize = 4;
+const OFFSET_ACCOUNT_TYPE: usize = 8;
+const OFFSET_EXPO: usize = 20;
+const OFFSET_TIMESTAMP: usize = 96;
+const OFFSET_AGG_PRICE: usize = 208;
+const OFFSET_AGG_CONF: usize = 216;
+const OFFSET_AGG_STATUS: usize = 224;
+const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// USD values are stored with 6 decimals (1_000_000 = $1)
+pub const USD_DECIMALS: u32 = 6;
+
+/// Prices older than this are rejected (seconds)
+pub const MAX_PRICE_AGE_SECONDS: i64 = 60;
+
+/// Max confidence interval relative to price (2%)
+pub const MAX_PRICE_CONF_BPS: u64 = 200;
+
+/// Price snapshot recorded on bets and rounds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct PriceSnapshot {
+    /// Aggregate price (scaled by 10^expo)
+    pub price: i64,
+
+    /// Confidence interval (same scale as price)
+    pub conf: u64,
+
+    /// Price exponent (e.g. -8)
+    pub expo: i32,
+
+    /// Publish time of the aggregate price
+    pub publish_time: i64,
+}
+
+impl PriceSnapshot {
+    pub const LEN: usize = 8 + // price
+        8 + // conf
+        4 + // expo
+        8;  // publish_time
+}
+
+/// Read and validate the current aggregate price from a Pyth price account
+pub fn load_price_snapshot(price_account: &AccountInfo, current_time: i64) -> Result<PriceSnapshot> {
+    let data = price_account.try_borrow_data()?;
+    let snapshot = parse_price_account(&data)?;
+
+    require!(
+        current_time.saturating_sub(snapshot.publish_time) <= MAX_PRICE_AGE_SECONDS,
+        SportsbookError::StalePrice
+    );
+
+    // Reject prices the publishers themselves are unsure about
+    let max_conf = (snapshot.price as u128)
+        .checked_mul(MAX_PRICE_CONF_BPS as u128)
+        .ok_or(SportsbookError::CalculationOverflow)?
+        / 10_000;
+    require!(
+        (snapshot.conf as u128) <= max_conf,
+        SportsbookError::InvalidPriceFeed
+    );
+
+    Ok(snapshot)
+}
+
+/// Load the pool's price snapshot, if the pool is USD-valued
+///
+/// Pools without a configured feed (stablecoin pools) return None.
+/// Pools with a feed must be given exactly that account.
+pub fn load_pool_price(
+    configured_feed: &Pubkey,
+    price_feed: Option<&AccountInfo>,
+    current_time: i64,
+) -> Result<Option<PriceSnapshot>> {
+    if *configured_feed == Pubkey::default() {
+        return Ok(None);
+    }
+
+    let price_feed = price_feed.ok_or(SportsbookError::InvalidPriceFeed)?;
+    require!(
+        price_feed.key() == *configured_feed,
+        SportsbookError::InvalidPriceFeed
+    );
+
+    load_price_snapshot(price_feed, current_time).map(Some)
+}
+
+/// `load_pool_price` for a snapshot that is only reported, never priced
+///
+/// Settlement records the price for reporting; an oracle outage (stale,
+/// too uncertain, not trading) logs and records no snapshot rather than
+/// holding up results.
+pub fn report_pool_price(
+    configured_feed: &Pubkey,
+    price_feed: Option<&AccountInfo>,
+    current_time: i64,
+) -> Option<PriceSnapshot> {
+    load_pool_price(configured_feed, price_feed, current_time).unwrap_or_else(|err| {
+        msg!("No price snapshot recorded: {}", err);
+        None
+    })
+}
+
+/// Decode the fields we use from raw Pyth v2 price account data
+pub fn parse_price_account(data: &[u8]) -> Result<PriceSnapshot> {
+    require!(
+        data.len() >= PRICE_ACCOUNT_MIN_LEN,
+        SportsbookError::InvalidPriceFeed
+    );
+    require!(
+        read_u32(data, OFFSET_MAGIC) == PYTH_MAGIC
+            && read_u32(data, OFFSET_VERSION) == PYTH_VERSION_2
+            && read_u32(data, OFFSET_ACCOUNT_TYPE) == PYTH_ACCOUNT_TYPE_PRICE,
+        SportsbookError::InvalidPriceFeed
+    );
+    require!(
+        read_u32(data, OFFSET_AGG_STATUS) == PYTH_STATUS_TRADING,
+        SportsbookError::InvalidPriceFeed
+    );
+
+    let price = read_i64(data, OFFSET_AGG_PRICE);
+    require!(price > 0, SportsbookError::InvalidPriceFeed);
+
+    Ok(PriceSnapshot {
+        price,
+        conf: read_u64(data, OFFSET_AGG_CONF),
+        expo: read_u32(data, OFFSET_EXPO) as i32,
+        publish_time: read_i64(data, OFFSET_TIMESTAMP),
+    })
+}
+
+/// Convert a raw token amount to USD (6 decimals) using a price snapshot
+///
+/// usd = amount × price × 10^(expo + USD_DECIMALS - token_decimals)
+pub fn token_amount_to_usd(amount: u64, token_decimals: u8, snapshot: &PriceSnapshot) -> Option<u64> {
+    if snapshot.price <= 0 {
+        return None;
+    }
+
+    let value = (amount as u128).checked_mul(snapshot.price as u128)?;
+    let exponent = snapshot.expo as i64 + USD_DECIMALS as i64 - token_decimals as i64;
+
+    let usd = if exponent >= 0 {
+        value.checked_mul(10u128.checked_pow(exponent as u32)?)?
+    } else {
+        value / 10u128.checked_pow((-exponent) as u32)?
+    };
+
+    u64::try_from(usd).ok()
+}
+
+/// Value a stablecoin amount at $1 per whole token, in USD (6 decimals)
+pub fn stablecoin_amount_to_usd(amount: u64, token_decimals: u8) -> Option<u64> {
+    let exponent = USD_DECIMALS as i64 - token_decimals as i64;
+    let usd = if exponent >= 0 {
+        (amount as u128).checked_mul(10u128.checked_pow(exponent as u32)?)?
+    } else {
+        amount as u128 / 10u128.checked_pow((-exponent) as u32)?
+    };
+
+    u64::try_from(usd).ok()
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_price_account(price: i64, conf: u64, expo: i32, timestamp: i64) -> Vec<u8> {
+        let mut data = vec![0u8; 3312];
+        data[OFFSET_MAGIC..OFFSET_MAGIC + 4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[OFFSET_VERSION..OFFSET_VERSION + 4].copy_from_slice(&PYTH_VERSION_2.to_le_bytes());
+        data[OFFSET_ACCOUNT_TYPE..OFFSET_ACCOUNT_TYPE + 4].copy_from_slice(&PYTH_ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[OFFSET_EXPO..OFFSET_EXPO + 4].copy_from_slice(&expo.to_le_bytes());
+        data[OFFSET_TIMESTAMP..OFFSET_TIMESTAMP + 8].copy_from_slice(&timestamp.to_le_bytes());
+        data[OFFSET_AGG_PRICE..OFFSET_AGG_PRICE + 8].copy_from_slice(&price.to_le_bytes());
+        data[OFFSET_AGG_CONF..OFFSET_AGG_CONF + 8].copy_from_slice(&conf.to_le_bytes());
+        data[OFFSET_AGG_STATUS..OFFSET_AGG_STATUS + 4].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_parse_price_account() {
+        let data = make_price_account(15_000_000_000, 1_000_000, -8, 1_700_000_000);
+        let snapshot = parse_price_account(&data).unwrap();
+        assert_eq!(snapshot.price, 15_000_000_000); // $150
+        assert_eq!(snapshot.conf, 1_000_000);
+        assert_eq!(snapshot.expo, -8);
+        assert_eq!(snapshot.publish_time, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_magic() {
+        let mut data = make_price_account(15_000_000_000, 0, -8, 0);
+        data[0] = 0;
+        assert!(parse_price_account(&data).is_err());
+    }
+
+    #[test]
+    fn test_token_amount_to_usd() {
+        // 2 tokens (9 decimals) at $150.00 (expo -8) = $300.000000
+        let snapshot = PriceSnapshot { price: 15_000_000_000, conf: 0, expo: -8, publish_time: 0 };
+        assert_eq!(token_amount_to_usd(2_000_000_000, 9, &snapshot), Some(300_000_000));
+
+        // 1.5 tokens (6 decimals) at $0.50 (expo -2) = $0.750000
+        let snapshot = PriceSnapshot { price: 50, conf: 0, expo: -2, publish_time: 0 };
+        assert_eq!(token_amount_to_usd(1_500_000, 6, &snapshot), Some(750_000));
+    }
+
+    #[test]
+    fn test_stablecoin_amount_to_usd() {
+        assert_eq!(stablecoin_amount_to_usd(2_500_000, 6), Some(2_500_000));
+        assert_eq!(stablecoin_amount_to_usd(2_500_000_000, 9), Some(2_500_000));
+        assert_eq!(stablecoin_amount_to_usd(25, 1), Some(2_500_000));
+    }
+
+    #[test]
+    fn test_stale_price_skips_the_reported_snapshot() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = make_price_account(15_000_000_000, 0, -8, 1_700_000_000);
+        let feed = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &PYTH_ORACLE_PROGRAM_ID, false, 0);
+
+        let fresh = 1_700_000_000 + MAX_PRICE_AGE_SECONDS;
+        assert!(report_pool_price(&key, Some(&feed), fresh).is_some());
+
+        let stale = fresh + 1;
+        assert!(load_pool_price(&key, Some(&feed), stale).is_err());
+        assert_eq!(report_pool_price(&key, Some(&feed), stale), None);
+        assert_eq!(report_pool_price(&key, None, fresh), None);
+    }
+}
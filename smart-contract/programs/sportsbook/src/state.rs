@@ -4,6 +4,41 @@ pub mod bet;
 pub mod match_pool;
 pub mod liquidity_pool;
 pub mod season_prediction;
+pub mod user_bet_counter;
+pub mod open_liability;
+pub mod round_archive;
+pub mod round_attestation;
+pub mod metrics;
+pub mod user_volume;
+pub mod promo;
+pub mod copy_config;
+pub mod creator;
+pub mod league;
+pub mod season_table;
+pub mod season_registry;
+pub mod round_kickoffs;
+pub mod round_metadata;
+pub mod odds_history;
+pub mod lottery;
+pub mod results_export;
+pub mod market_maker;
+pub mod leg_fees;
+pub mod stake_policy;
+pub mod odds_update_limits;
+pub mod accumulator;
+pub mod claim_reminder;
+pub mod claim_shard;
+pub mod access_control;
+pub mod insurance;
+pub mod season_policy;
+pub mod notifications;
+pub mod pn_ledger;
+pub mod round_summary;
+pub mod user_stats;
+pub mod reconcile_cursor;
+pub mod lending_proposal;
+pub mod outflow_allowlist;
+pub mod bet_record;
 
 pub use betting_pool::*;
 pub use round_accounting::*;
@@ -11,3 +46,264 @@ pub use bet::*;
 pub use match_pool::*;
 pub use liquidity_pool::*;
 pub use season_prediction::*;
+pub use user_bet_counter::*;
+pub use open_liability::*;
+pub use round_archive::*;
+pub use round_attestation::*;
+pub use metrics::*;
+pub use user_volume::*;
+pub use promo::*;
+pub use copy_config::*;
+pub use creator::*;
+pub use league::*;
+pub use season_table::*;
+pub use season_registry::*;
+pub use round_kickoffs::*;
+pub use round_metadata::*;
+pub use odds_history::*;
+pub use lottery::*;
+pub use results_export::*;
+pub use market_maker::*;
+pub use leg_fees::*;
+pub use stake_policy::*;
+pub use odds_update_limits::*;
+pub use accumulator::*;
+pub use claim_reminder::*;
+pub use claim_shard::*;
+pub use access_control::*;
+pub use insurance::*;
+pub use season_policy::*;
+pub use notifications::*;
+pub use pn_ledger::*;
+pub use round_summary::*;
+pub use user_stats::*;
+pub use reconcile_cursor::*;
+pub use lending_proposal::*;
+pub use outflow_allowlist::*;
+pub use bet_record::*;
+
+/// Layouts of accounts already on chain, pinned from the Anchor 0.29 build:
+/// a toolchain upgrade must keep their discriminators, sizes and field
+/// offsets, or existing pools, rounds and bets stop deserializing
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::*;
+    use anchor_lang::Discriminator;
+
+    /// Sizes the 0.29 build allocated
+    const DEPLOYED_BETTING_POOL_LEN: usize = 529;
+    const DEPLOYED_ROUND_ACCOUNTING_LEN: usize = 744;
+    const DEPLOYED_BET_LEN: usize = 311;
+
+    /// Zeroed account of `len` bytes with `discriminator` and `fields`
+    /// written at their offsets
+    fn snapshot(discriminator: &[u8], len: usize, fields: &[(usize, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        data[..8].copy_from_slice(discriminator);
+        for (offset, bytes) in fields {
+            data[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+        }
+        data
+    }
+
+    /// What resize_account does to the account's data: zero-extend to `len`
+    fn resized(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+        data.resize(len, 0);
+        data
+    }
+
+    /// Re-serializing a loaded account must write its deployed fields back
+    /// to the same bytes
+    fn assert_prefix_round_trips<T: AccountSerialize>(account: &T, deployed: &[u8], prefix_len: usize) {
+        let mut written = Vec::new();
+        account.try_serialize(&mut written).unwrap();
+        assert_eq!(written[..prefix_len], deployed[..prefix_len]);
+    }
+
+    #[test]
+    fn test_account_layouts_unchanged_from_anchor_0_29() {
+        assert_eq!(BettingPool::DISCRIMINATOR, &[59, 136, 47, 53, 37, 99, 87, 104]);
+        assert_eq!(RoundAccounting::DISCRIMINATOR, &[6, 202, 139, 242, 106, 134, 74, 34]);
+        assert_eq!(Bet::DISCRIMINATOR, &[147, 23, 35, 59, 15, 75, 155, 32]);
+
+        // Current sizes; resize_account brings deployed accounts up to them
+        assert_eq!(BettingPool::LEN, 772);
+        // Grown from 908 by total_reserved_unpaid and shard_payout_cap
+        assert_eq!(RoundAccounting::LEN, 924);
+        assert_eq!(Bet::LEN, 420);
+    }
+
+    #[test]
+    fn test_deployed_betting_pool_loads_after_resize() {
+        let authority = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let protocol_treasury = Pubkey::new_unique();
+        let liquidity_pool = Pubkey::new_unique();
+        let team_mint = Pubkey::new_unique();
+        let season_nft_collection = Pubkey::new_unique();
+        let deployed = snapshot(
+            BettingPool::DISCRIMINATOR,
+            DEPLOYED_BETTING_POOL_LEN,
+            &[
+                (8, authority.as_ref()),
+                (40, token_mint.as_ref()),
+                (72, protocol_treasury.as_ref()),
+                (104, liquidity_pool.as_ref()),
+                (136, &100u16.to_le_bytes()),
+                (138, &5_000u16.to_le_bytes()),
+                (140, &200u16.to_le_bytes()),
+                (142, &9_000u64.to_le_bytes()),
+                (150, &42u64.to_le_bytes()),
+                (158, &7u64.to_le_bytes()),
+                (166 + 32 * 9, team_mint.as_ref()),
+                (486, season_nft_collection.as_ref()),
+                (518, &3u64.to_le_bytes()),
+                (526, &[1]),
+                (527, &[4]),
+                (528, &[254]),
+            ],
+        );
+
+        // The deployed size is too short for the current layout
+        assert!(BettingPool::try_deserialize(&mut &deployed[..]).is_err());
+
+        let data = resized(deployed.clone(), BettingPool::LEN);
+        let pool = BettingPool::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(
+            (pool.authority, pool.token_mint, pool.protocol_treasury, pool.liquidity_pool),
+            (authority, token_mint, protocol_treasury, liquidity_pool)
+        );
+        assert_eq!((pool.protocol_fee_bps, pool.winner_share_bps, pool.season_pool_share_bps), (100, 5_000, 200));
+        assert_eq!((pool.season_reward_pool, pool.next_bet_id, pool.next_round_id), (9_000, 42, 7));
+        assert_eq!((pool.team_token_mints[9], pool.season_nft_collection), (team_mint, season_nft_collection));
+        assert_eq!((pool.current_season_id, pool.season_ended, pool.season_winning_team), (3, true, 4));
+        assert_eq!(pool.bump, 254);
+        // Fields added since read as zero until configured
+        assert_eq!((pool.lending_program, pool.lending_deployed), (Pubkey::default(), 0));
+        assert_eq!(pool.reserved, [0; 3]);
+        assert_prefix_round_trips(&pool, &deployed, DEPLOYED_BETTING_POOL_LEN);
+    }
+
+    #[test]
+    fn test_deployed_round_accounting_loads_after_resize() {
+        let betting_pool = Pubkey::new_unique();
+        let deployed = snapshot(
+            RoundAccounting::DISCRIMINATOR,
+            DEPLOYED_ROUND_ACCOUNTING_LEN,
+            &[
+                (8, &7u64.to_le_bytes()),
+                (16, betting_pool.as_ref()),
+                // match_pools[9].total_pool
+                (48 + 32 * 9 + 24, &500u64.to_le_bytes()),
+                // locked_odds[9]: draw odds, locked
+                (368 + 25 * 9 + 16, &3_000_000_000u64.to_le_bytes()),
+                (368 + 25 * 9 + 24, &[1]),
+                // match_results[9] = Draw
+                (618 + 9, &[3]),
+                (628, &1_000u64.to_le_bytes()),
+                (636, &600u64.to_le_bytes()),
+                (644, &400u64.to_le_bytes()),
+                (652, &700u64.to_le_bytes()),
+                (660, &300u64.to_le_bytes()),
+                (668, &300u64.to_le_bytes()),
+                (676, &50u64.to_le_bytes()),
+                (684, &40u64.to_le_bytes()),
+                (692, &10u64.to_le_bytes()),
+                (700, &[1]),
+                (701, &2_000u64.to_le_bytes()),
+                (709, &[1]),
+                (710, &800u64.to_le_bytes()),
+                (718, &12u64.to_le_bytes()),
+                (726, &1_700_000_000i64.to_le_bytes()),
+                (734, &1_700_007_200i64.to_le_bytes()),
+                (742, &[1]),
+                (743, &[253]),
+            ],
+        );
+
+        assert!(RoundAccounting::try_deserialize(&mut &deployed[..]).is_err());
+
+        let data = resized(deployed.clone(), RoundAccounting::LEN);
+        let round = RoundAccounting::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!((round.round_id, round.betting_pool), (7, betting_pool));
+        assert_eq!(round.match_pools[9].total_pool, 500);
+        assert_eq!((round.locked_odds[9].draw_odds, round.locked_odds[9].locked), (3_000_000_000, true));
+        assert!(matches!(round.match_results[9], MatchOutcome::Draw));
+        assert_eq!(
+            (round.total_bet_volume, round.total_winning_pool, round.total_losing_pool),
+            (1_000, 600, 400)
+        );
+        assert_eq!(
+            (round.total_reserved_for_winners, round.total_claimed, round.total_paid_out),
+            (700, 300, 300)
+        );
+        assert_eq!(
+            (round.protocol_fee_collected, round.protocol_revenue_share, round.season_revenue_share),
+            (50, 40, 10)
+        );
+        assert!(round.revenue_distributed && round.seeded && round.settled);
+        assert_eq!((round.protocol_seed_amount, round.total_user_deposits, round.parlay_count), (2_000, 800, 12));
+        assert_eq!((round.round_start_time, round.round_end_time), (1_700_000_000, 1_700_007_200));
+        assert_eq!(round.bump, 253);
+        assert_eq!((round.bets_placed, round.total_reserved_unpaid), (0, 0));
+        assert_prefix_round_trips(&round, &deployed, DEPLOYED_ROUND_ACCOUNTING_LEN);
+    }
+
+    #[test]
+    fn test_deployed_bet_loads_after_resize() {
+        // The 0.29 LEN budgets 17 bytes per Prediction, but Borsh writes
+        // 10 (u8, u8, u64) and Option<Pubkey> is 1 byte when None. The
+        // 311-byte account therefore holds the fields packed from offset
+        // 8, with bump right after bounty_claimer: at 240 when a bounty
+        // claimer is set, 208 when not, and slack up to 311 after it.
+        let bettor = Pubkey::new_unique();
+        let bounty_claimer = Pubkey::new_unique();
+        let common: Vec<(usize, Vec<u8>)> = vec![
+            (8, bettor.as_ref().to_vec()),
+            (40, 7u64.to_le_bytes().to_vec()),
+            (48, 42u64.to_le_bytes().to_vec()),
+            (56, 1_000u64.to_le_bytes().to_vec()),
+            (64, 980u64.to_le_bytes().to_vec()),
+            (72, 980u64.to_le_bytes().to_vec()),
+            (80, 0u64.to_le_bytes().to_vec()),
+            (88, 3_500_000_000u64.to_le_bytes().to_vec()),
+            (96, vec![2]),
+            // predictions[1]: match 4, away win, 490 in pool
+            (97 + 10, vec![4, 2]),
+            (97 + 10 + 2, 490u64.to_le_bytes().to_vec()),
+            (197, vec![1]),
+            (198, vec![1]),
+            (199, 1_700_093_600i64.to_le_bytes().to_vec()),
+        ];
+        let with_claimer = [(207, vec![1]), (208, bounty_claimer.as_ref().to_vec()), (240, vec![252])];
+        let without_claimer = [(207, vec![0]), (208, vec![252])];
+
+        for (tail, claimer, bump_offset) in [
+            (&with_claimer[..], Some(bounty_claimer), 240),
+            (&without_claimer[..], None, 208),
+        ] {
+            let mut fields = common.clone();
+            fields.extend_from_slice(tail);
+            let borrowed: Vec<(usize, &[u8])> = fields.iter().map(|(offset, bytes)| (*offset, &bytes[..])).collect();
+            let deployed = snapshot(Bet::DISCRIMINATOR, DEPLOYED_BET_LEN, &borrowed);
+            assert_eq!(deployed[bump_offset], 252);
+
+            let data = resized(deployed.clone(), Bet::LEN);
+            let bet = Bet::try_deserialize(&mut &data[..]).unwrap();
+            assert_eq!((bet.bettor, bet.round_id, bet.bet_id), (bettor, 7, 42));
+            assert_eq!((bet.amount, bet.amount_after_fee, bet.allocated_amount, bet.bonus), (1_000, 980, 980, 0));
+            assert_eq!((bet.locked_multiplier, bet.num_predictions), (3_500_000_000, 2));
+            let leg = &bet.predictions[1];
+            assert_eq!((leg.match_index, leg.predicted_outcome, leg.amount_in_pool), (4, 2, 490));
+            assert!(bet.settled && bet.claimed);
+            assert_eq!((bet.claim_deadline, bet.bounty_claimer), (1_700_093_600, claimer));
+            assert_eq!(bet.bump, 252);
+            // Fields added since start out empty: the slack the 0.29 build
+            // left after bump is zero on chain
+            assert_eq!((bet.user_nonce, bet.copy_leader, bet.potential_payout), (None, Pubkey::default(), 0));
+            assert!(!bet.payout_reserved && !bet.insured);
+            assert_prefix_round_trips(&bet, &deployed, bump_offset + 1);
+        }
+    }
+}
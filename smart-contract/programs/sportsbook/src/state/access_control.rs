@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::BettingPool;
+
+/// Operational roles the pool authority can hand to separate keys
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Sets and locks odds and kickoff times
+    OddsManager,
+    /// Requests randomness and settles rounds
+    ResultsSubmitter,
+    /// Funds the LP, moves idle liquidity and finalizes round revenue
+    Treasurer,
+    /// Pauses claims on a round, alongside the guardian
+    Pauser,
+}
+
+/// Holders of a pool's operational roles
+///
+/// Each role's instructions accept its holder as well as the pool
+/// authority, so day-to-day keys can run the pool while the authority key
+/// stays offline, and a leaked operational key only reaches its own role.
+/// Only the authority grants and revokes.
+#[account]
+pub struct AccessControl {
+    /// Betting pool the roles apply to
+    pub betting_pool: Pubkey,
+
+    /// Pubkey::default() = role not granted
+    pub odds_manager: Pubkey,
+    pub results_submitter: Pubkey,
+    pub treasurer: Pubkey,
+    pub pauser: Pubkey,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl AccessControl {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // odds_manager
+        32 + // results_submitter
+        32 + // treasurer
+        32 + // pauser
+        1;   // bump
+
+    pub fn holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::OddsManager => self.odds_manager,
+            Role::ResultsSubmitter => self.results_submitter,
+            Role::Treasurer => self.treasurer,
+            Role::Pauser => self.pauser,
+        }
+    }
+
+    pub fn set_holder(&mut self, role: Role, holder: Pubkey) {
+        match role {
+            Role::OddsManager => self.odds_manager = holder,
+            Role::ResultsSubmitter => self.results_submitter = holder,
+            Role::Treasurer => self.treasurer = holder,
+            Role::Pauser => self.pauser = holder,
+        }
+    }
+
+    /// Whether `key` has been granted `role`
+    pub fn holds(&self, role: Role, key: &Pubkey) -> bool {
+        let holder = self.holder(role);
+        holder != Pubkey::default() && holder == *key
+    }
+}
+
+/// Whether `signer` may act as `role`: the pool authority always may, a
+/// role holder only if the pool's AccessControl account was passed
+pub fn has_role(
+    betting_pool: &BettingPool,
+    access_control: Option<&AccessControl>,
+    signer: &Pubkey,
+    role: Role,
+) -> bool {
+    *signer == betting_pool.authority
+        || access_control.is_some_and(|access_control| access_control.holds(role, signer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_are_held_separately() {
+        let odds_key = Pubkey::new_unique();
+        let mut access_control = AccessControl {
+            betting_pool: Pubkey::new_unique(),
+            odds_manager: Pubkey::default(),
+            results_submitter: Pubkey::default(),
+            treasurer: Pubkey::default(),
+            pauser: Pubkey::default(),
+            bump: 255,
+        };
+        assert!(!access_control.holds(Role::OddsManager, &Pubkey::default()));
+
+        access_control.set_holder(Role::OddsManager, odds_key);
+        assert!(access_control.holds(Role::OddsManager, &odds_key));
+        assert!(!access_control.holds(Role::Treasurer, &odds_key));
+        assert!(!access_control.holds(Role::ResultsSubmitter, &odds_key));
+
+        access_control.set_holder(Role::OddsManager, Pubkey::default());
+        assert!(!access_control.holds(Role::OddsManager, &odds_key));
+    }
+}
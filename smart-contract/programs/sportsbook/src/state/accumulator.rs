@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_ACCUMULATOR_LEGS;
+
+/// One leg of a cross-round accumulator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AccumulatorLeg {
+    /// Round the match belongs to
+    pub round_id: u64,
+
+    /// Match index within that round (0-9)
+    pub match_index: u8,
+
+    /// Predicted outcome (1=HOME_WIN, 2=AWAY_WIN, 3=DRAW)
+    pub predicted_outcome: u8,
+
+    /// Locked odds of the predicted outcome at placement (scaled by 1e9)
+    pub odds: u64,
+}
+
+impl AccumulatorLeg {
+    pub const LEN: usize = 8 + 1 + 1 + 8;
+}
+
+/// A parlay whose legs are matches of different rounds
+///
+/// The whole after-fee stake rides every leg. Stake and payout are booked
+/// in the anchor round (the latest round among the legs), and every round
+/// with a leg counts the accumulator in `open_accumulators` until it's
+/// settled, which holds back their revenue finalization. Settled once the
+/// last of its rounds settles.
+#[account]
+pub struct Accumulator {
+    /// Bettor's public key
+    pub bettor: Pubkey,
+
+    /// Bet ID, from the pool's bet counter (shared with regular bets)
+    pub bet_id: u64,
+
+    /// Round whose accounting holds the stake and pays out
+    pub anchor_round_id: u64,
+
+    /// Original bet amount
+    pub amount: u64,
+
+    /// Amount after protocol fee
+    pub amount_after_fee: u64,
+
+    /// Protocol fee charged at placement
+    pub fee_bps: u16,
+
+    /// Parlay multiplier locked at placement (scaled by 1e9)
+    pub locked_multiplier: u64,
+
+    /// Payout if every leg wins, fixed at placement
+    pub potential_payout: u64,
+
+    /// Number of legs
+    pub num_legs: u8,
+
+    /// Legs (max MAX_ACCUMULATOR_LEGS)
+    pub legs: [AccumulatorLeg; MAX_ACCUMULATOR_LEGS],
+
+    /// Every round has settled and the result was paid out or recorded
+    pub settled: bool,
+
+    /// Paid on settlement: winnings, or the stake if every leg was voided
+    /// (0 = lost)
+    pub paid_out: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Accumulator {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        8 +  // bet_id
+        8 +  // anchor_round_id
+        8 +  // amount
+        8 +  // amount_after_fee
+        2 +  // fee_bps
+        8 +  // locked_multiplier
+        8 +  // potential_payout
+        1 +  // num_legs
+        AccumulatorLeg::LEN * MAX_ACCUMULATOR_LEGS + // legs
+        1 +  // settled
+        8 +  // paid_out
+        1;   // bump
+
+    pub fn get_legs(&self) -> &[AccumulatorLeg] {
+        &self.legs[0..self.num_legs as usize]
+    }
+
+    /// Distinct rounds the legs are in, ascending
+    pub fn round_ids(&self) -> Vec<u64> {
+        leg_round_ids(self.get_legs().iter().map(|leg| leg.round_id))
+    }
+}
+
+/// Distinct round IDs, ascending
+pub fn leg_round_ids(round_ids: impl Iterator<Item = u64>) -> Vec<u64> {
+    let mut round_ids: Vec<u64> = round_ids.collect();
+    round_ids.sort_unstable();
+    round_ids.dedup();
+    round_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leg_round_ids_are_distinct_and_ascending() {
+        assert_eq!(leg_round_ids([7, 5, 7, 6].into_iter()), vec![5, 6, 7]);
+        assert!(leg_round_ids(std::iter::empty()).is_empty());
+    }
+}
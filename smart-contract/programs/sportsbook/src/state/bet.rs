@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::pyth::PriceSnapshot;
+use crate::constants::*;
 
 /// Individual prediction for a single match within a bet
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -52,7 +54,9 @@ pub struct Bet {
     /// Has user claimed winnings?
     pub claimed: bool,
 
-    /// Deadline for claiming (settlement_time + 24 hours)
+    /// Deadline for claiming (settlement_time + 24 hours + any pause
+    /// extension), stamped by stamp_claim_deadlines once the round settles
+    /// or at the latest on the first claim attempt.
     /// After this, anyone can claim with 10% bounty
     pub claim_deadline: i64,
 
@@ -61,6 +65,54 @@ pub struct Bet {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Token price at placement (zeroed for stablecoin pools)
+    pub price_at_placement: PriceSnapshot,
+
+    /// Stake value in USD (6 decimals) at placement
+    pub stake_usd: u64,
+
+    /// Bettor's nonce if placed in the per-user namespace
+    /// (None = seeded with the pool-wide bet_id)
+    pub user_nonce: Option<u64>,
+
+    /// Payout if every leg wins (post-multiplier, within MAX_PAYOUT_PER_BET),
+    /// fixed at placement. Claims pay exactly this; 0 on bets placed before
+    /// it was recorded, which fall back to recomputing from locked odds.
+    pub potential_payout: u64,
+
+    /// Protocol fee charged at placement (after volume tier and team token
+    /// discounts); 0 on bets placed before it was recorded
+    pub fee_bps: u16,
+
+    /// Promo bonus paid against this bet (0 = no promo redeemed)
+    pub promo_bonus: u64,
+
+    /// Leader this bet was copied from (default = not a copy)
+    pub copy_leader: Pubkey,
+
+    /// Leader's share of the profit on a winning copy, in basis points
+    pub copy_fee_bps: u16,
+
+    /// Stake already reported to the pool's SeasonRegistry
+    pub season_points_accrued: bool,
+
+    /// What flooring potential_payout to the pool's rounding unit took
+    /// off; becomes round dust if the bet wins
+    pub payout_dust: u64,
+
+    /// Payout (or void refund) already counted into the round by
+    /// reserve_payouts; paid by claim_reserved, which never writes the
+    /// round's accounting
+    pub payout_reserved: bool,
+
+    /// Bought insurance at placement: refunded the stake from the pool's
+    /// InsuranceFund if exactly one leg loses
+    pub insured: bool,
+
+    /// Zeroed headroom for future fields; new fields are carved out of
+    /// this so existing accounts keep their size and layout
+    pub reserved: [u8; 1],
 }
 
 impl Bet {
@@ -79,9 +131,51 @@ impl Bet {
         1 +  // claimed
         8 +  // claim_deadline
         (1 + 32) + // bounty_claimer (Option<Pubkey>)
-        1;   // bump
+        1 +  // bump
+        PriceSnapshot::LEN + // price_at_placement
+        8 +  // stake_usd
+        (1 + 8) + // user_nonce (Option<u64>)
+        8 +  // potential_payout
+        2 +  // fee_bps
+        8 +  // promo_bonus
+        32 + // copy_leader
+        2 +  // copy_fee_bps
+        1 +  // season_points_accrued
+        8 +  // payout_dust
+        1 +  // payout_reserved
+        1 +  // insured
+        1;   // reserved
 
     pub fn get_predictions(&self) -> &[Prediction] {
         &self.predictions[0..self.num_predictions as usize]
     }
+
+    /// Check `address` is this bet's PDA under whichever seed scheme it was
+    /// created with (pool-wide bet_id or per-user nonce)
+    pub fn is_at_address(&self, address: &Pubkey, betting_pool: &Pubkey) -> bool {
+        let bump = [self.bump];
+        let derived = match self.user_nonce {
+            Some(nonce) => Pubkey::create_program_address(
+                &[
+                    BET_SEED,
+                    betting_pool.as_ref(),
+                    self.bettor.as_ref(),
+                    nonce.to_le_bytes().as_ref(),
+                    &bump,
+                ],
+                &crate::ID,
+            ),
+            None => Pubkey::create_program_address(
+                &[
+                    BET_SEED,
+                    betting_pool.as_ref(),
+                    self.bet_id.to_le_bytes().as_ref(),
+                    &bump,
+                ],
+                &crate::ID,
+            ),
+        };
+
+        derived.map(|key| key == *address).unwrap_or(false)
+    }
 }
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Terms a bet was placed on, kept beside the Bet so its layout doesn't grow
+///
+/// Created with every bet placed through execute_bet (the placer pays its
+/// rent). `terms_hash` is the sportsbook_core::BetTerms hash of the
+/// bettor, legs, stake, locked odds and multiplier, so the bettor can prove
+/// the wager's terms to a third party, and `leg_bits` lets claims passing
+/// the record settle most losing bets without pricing any leg. Bets placed
+/// before it existed have none.
+#[account]
+pub struct BetRecord {
+    /// Bet these terms belong to
+    pub bet: Pubkey,
+
+    /// sportsbook_core::BetTerms hash, written at placement
+    pub terms_hash: [u8; 32],
+
+    /// sportsbook_core::leg_bitmap of the bet's legs, checked against the
+    /// round's result_bits
+    pub leg_bits: u32,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BetRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bet
+        32 + // terms_hash
+        4 +  // leg_bits
+        1;   // bump
+}
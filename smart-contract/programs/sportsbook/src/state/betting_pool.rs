@@ -1,5 +1,24 @@
 use anchor_lang::prelude::*;
 
+/// Number of volume-based fee tiers a pool can configure
+pub const FEE_TIER_COUNT: usize = 3;
+
+/// Protocol fee for bettors with at least `min_volume` staked over the
+/// last `ROLLING_VOLUME_DAYS` days
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    /// Rolling volume (raw token amount, before fee) that unlocks the tier
+    /// (0 = slot unused)
+    pub min_volume: u64,
+
+    /// Fee in basis points charged from this tier up
+    pub fee_bps: u16,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8 + 2;
+}
+
 /// Global betting pool configuration and state
 #[account]
 pub struct BettingPool {
@@ -27,7 +46,7 @@ pub struct BettingPool {
     /// Total season reward pool accumulated
     pub season_reward_pool: u64,
 
-    /// Next bet ID counter
+    /// Next bet ID counter (assigned on-chain, seeds the bet PDA)
     pub next_bet_id: u64,
 
     /// Next round ID counter
@@ -52,6 +71,80 @@ pub struct BettingPool {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Whitelisted lending program idle liquidity may be deposited into
+    /// (Pubkey::default() = lending disabled)
+    pub lending_program: Pubkey,
+
+    /// Max share of pool liquidity that may sit in the lending program (bps)
+    pub max_lending_bps: u16,
+
+    /// Principal currently deposited in the lending program
+    pub lending_deployed: u64,
+
+    /// Pyth price feed for the betting token (Pubkey::default() = stablecoin pool)
+    pub price_feed: Pubkey,
+
+    /// Decimals of the betting token mint (for USD conversion)
+    pub token_decimals: u8,
+
+    /// Max bet in USD (6 decimals) for priced pools (0 = use MAX_BET_AMOUNT)
+    pub max_bet_usd: u64,
+
+    /// Key whose Ed25519 signature authorizes packed result submissions
+    /// (Pubkey::default() = bulk submission disabled)
+    pub results_attestor: Pubkey,
+
+    /// Max legs per bet (0 = MATCHES_PER_ROUND)
+    pub max_parlay_legs: u8,
+
+    /// Min locked odds for any single leg, scaled by 1e9 (0 = no minimum)
+    pub min_leg_odds: u64,
+
+    /// Max combined odds (Π leg odds × multiplier), scaled by 1e9 (0 = no maximum)
+    pub max_combined_odds: u64,
+
+    /// Guardian that can pause claims on a single round
+    /// (Pubkey::default() = no guardian)
+    pub guardian: Pubkey,
+
+    /// Pinned unix time for `mock-clock` builds (0 = use the Clock sysvar;
+    /// ignored entirely without the feature)
+    pub mock_unix_timestamp: i64,
+
+    /// Claims must carry a Merkle proof of each leg's odds against the
+    /// round's locked_odds_root (claim_winnings_with_proof)
+    pub merkle_settlement: bool,
+
+    /// Volume-based fee tiers; below the first tier `protocol_fee_bps`
+    /// applies (all unused = flat fee)
+    pub fee_tiers: [FeeTier; FEE_TIER_COUNT],
+
+    /// SeasonRegistry this pool reports season points to
+    /// (Pubkey::default() = not reporting)
+    pub season_registry: Pubkey,
+
+    /// New rounds keep their funds in a round vault of their own instead of
+    /// the shared pool token account
+    pub round_vaults: bool,
+
+    /// New rounds pay the late-claim bounty out of protocol revenue instead
+    /// of the winner's payout
+    pub protocol_funded_bounty: bool,
+
+    /// Base fee depends on parlay length; bets must pass the pool's
+    /// LegFeeSchedule
+    pub leg_fees: bool,
+
+    /// Bets must pass the pool's StakePolicy (minimum stake, rounding)
+    pub stake_policy: bool,
+
+    /// Odds updates must pass the pool's OddsUpdateLimits
+    pub odds_update_limits: bool,
+
+    /// Zeroed headroom for future fields; new fields are carved out of
+    /// this so existing accounts keep their size and layout
+    pub reserved: [u8; 3],
 }
 
 impl BettingPool {
@@ -71,5 +164,39 @@ impl BettingPool {
         8 +  // current_season_id
         1 +  // season_ended
         1 +  // season_winning_team
-        1;   // bump
+        1 +  // bump
+        32 + // lending_program
+        2 +  // max_lending_bps
+        8 +  // lending_deployed
+        32 + // price_feed
+        1 +  // token_decimals
+        8 +  // max_bet_usd
+        32 + // results_attestor
+        1 +  // max_parlay_legs
+        8 +  // min_leg_odds
+        8 +  // max_combined_odds
+        32 + // guardian
+        8 +  // mock_unix_timestamp
+        1 +  // merkle_settlement
+        FeeTier::LEN * FEE_TIER_COUNT + // fee_tiers
+        32 + // season_registry
+        1 +  // round_vaults
+        1 +  // protocol_funded_bounty
+        1 +  // leg_fees
+        1 +  // stake_policy
+        1 +  // odds_update_limits
+        3;   // reserved
+
+    /// Protocol fee for a bettor with the given rolling volume
+    pub fn fee_bps_for_volume(&self, rolling_volume: u64) -> u16 {
+        let tiers = self.fee_tiers.map(|tier| (tier.min_volume, tier.fee_bps));
+        sportsbook_core::tiered_fee_bps(rolling_volume, self.protocol_fee_bps, &tiers)
+    }
+
+    /// Protocol fee for a bet: the parlay length's base fee, lowered to
+    /// the bettor's volume tier if that is cheaper
+    pub fn fee_bps_for_bet(&self, rolling_volume: u64, base_fee_bps: u16) -> u16 {
+        let tiers = self.fee_tiers.map(|tier| (tier.min_volume, tier.fee_bps));
+        sportsbook_core::tiered_fee_bps(rolling_volume, base_fee_bps, &tiers).min(base_fee_bps)
+    }
 }
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+/// A bettor's request to be warned before their claim window closes
+///
+/// Notification services watch these accounts: once the round settles and
+/// stamp_claim_deadlines has run, `notify_at` says when to send the
+/// reminder, `lead_time` ahead of the bounty window opening.
+#[account]
+pub struct ClaimReminder {
+    /// Betting pool the bet belongs to
+    pub betting_pool: Pubkey,
+
+    /// Bet to be reminded about
+    pub bet: Pubkey,
+
+    /// Bettor to remind
+    pub bettor: Pubkey,
+
+    /// Round of the bet
+    pub round_id: u64,
+
+    /// How long before the deadline to remind, in seconds
+    pub lead_time: i64,
+
+    /// End of the bettor-only claim window (0 until the round settles)
+    pub claim_deadline: i64,
+
+    /// claim_deadline - lead_time (0 until the round settles)
+    pub notify_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ClaimReminder {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // bet
+        32 + // bettor
+        8 +  // round_id
+        8 +  // lead_time
+        8 +  // claim_deadline
+        8 +  // notify_at
+        1;   // bump
+
+    pub fn set_deadline(&mut self, claim_deadline: i64) {
+        self.claim_deadline = claim_deadline;
+        self.notify_at = claim_deadline.saturating_sub(self.lead_time);
+    }
+}
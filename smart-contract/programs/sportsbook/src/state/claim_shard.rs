@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use crate::constants::{CLAIM_SHARDS, MAX_ROUND_PAYOUTS};
+
+/// One slice of a round's claim counters
+///
+/// claim_sharded writes the shard picked by `bet_id % CLAIM_SHARDS` instead
+/// of the RoundAccounting, so claims landing in the same slot spread over
+/// several accounts. merge_claim_shard folds the totals back into the round
+/// (and the pool's PendingClaims) before finalize_revenue.
+#[account]
+pub struct ClaimShard {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// 0..CLAIM_SHARDS
+    pub shard_index: u8,
+
+    /// Paid the rent; gets it back at merge
+    pub payer: Pubkey,
+
+    /// Bets claimed or settled as lost through this shard
+    pub bets_resolved: u32,
+
+    /// Of those, bets that lost
+    pub lost_bets: u32,
+
+    /// Sum of potential_payout over the resolved bets
+    pub resolved_potential_payout: u64,
+
+    /// Winnings paid (bounties cut from them included)
+    pub total_paid_out: u64,
+
+    /// Stakes refunded to fully voided bets
+    pub total_refunded: u64,
+
+    /// Part of total_paid_out that went to bounty hunters, plus any
+    /// protocol-funded bounties
+    pub bounty_paid: u64,
+
+    /// Bounties paid on top of payouts, charged to protocol revenue
+    pub protocol_bounties_paid: u64,
+
+    /// Payout rounding remainders of the winning bets
+    pub payout_dust: u64,
+
+    /// Most this shard may pay out, fixed when it opens and counted in the
+    /// round's shard_payout_cap until it merges
+    pub payout_cap: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ClaimShard {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        1 +  // shard_index
+        32 + // payer
+        4 +  // bets_resolved
+        4 +  // lost_bets
+        8 +  // resolved_potential_payout
+        8 +  // total_paid_out
+        8 +  // total_refunded
+        8 +  // bounty_paid
+        8 +  // protocol_bounties_paid
+        8 +  // payout_dust
+        8 +  // payout_cap
+        1;   // bump
+
+    /// Payout cap for a shard opening now: an equal part of what the round
+    /// can still pay out, after what it has paid and what the
+    /// `open_shards` already open have been promised
+    ///
+    /// Nothing else pays out of the round while shards are open, and a
+    /// shard never pays past its cap, so the caps can't add up past
+    /// MAX_ROUND_PAYOUTS even when shards merge and reopen.
+    pub fn payout_cap_for(round_paid_out: u64, round_shard_cap: u64, open_shards: u8) -> u64 {
+        let unpromised = MAX_ROUND_PAYOUTS
+            .saturating_sub(round_paid_out)
+            .saturating_sub(round_shard_cap);
+        unpromised / CLAIM_SHARDS.saturating_sub(open_shards).max(1) as u64
+    }
+
+    /// Whether paying `payout` keeps this shard within its payout cap
+    pub fn within_payout_cap(&self, payout: u64) -> bool {
+        self.total_paid_out
+            .checked_add(payout)
+            .is_some_and(|paid| paid <= self.payout_cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shards_split_remaining_payout_cap() {
+        let slice = MAX_ROUND_PAYOUTS / CLAIM_SHARDS as u64;
+        let mut shard = ClaimShard {
+            betting_pool: Pubkey::default(),
+            round_id: 1,
+            shard_index: 0,
+            payer: Pubkey::default(),
+            bets_resolved: 0,
+            lost_bets: 0,
+            resolved_potential_payout: 0,
+            total_paid_out: 0,
+            total_refunded: 0,
+            bounty_paid: 0,
+            protocol_bounties_paid: 0,
+            payout_dust: 0,
+            payout_cap: ClaimShard::payout_cap_for(0, 0, 0),
+            bump: 0,
+        };
+        assert_eq!(shard.payout_cap, slice);
+        assert!(shard.within_payout_cap(slice));
+        assert!(!shard.within_payout_cap(slice + 1));
+
+        shard.total_paid_out = slice - 10;
+        assert!(shard.within_payout_cap(10));
+        assert!(!shard.within_payout_cap(11));
+    }
+
+    #[test]
+    fn test_reopened_shards_stay_within_round_cap() {
+        // All four open together get equal slices
+        let mut promised = 0;
+        let mut caps = Vec::new();
+        for open in 0..CLAIM_SHARDS {
+            let cap = ClaimShard::payout_cap_for(0, promised, open);
+            promised += cap;
+            caps.push(cap);
+        }
+        assert!(caps.iter().all(|cap| *cap == MAX_ROUND_PAYOUTS / CLAIM_SHARDS as u64));
+
+        // Shard 0 pays its whole cap and merges; the others have paid
+        // theirs too. Reopening it leaves nothing to promise
+        let paid = caps[0];
+        promised -= caps[0];
+        let reopened = ClaimShard::payout_cap_for(paid, promised, CLAIM_SHARDS - 1);
+        assert!(paid + promised + reopened <= MAX_ROUND_PAYOUTS);
+        assert_eq!(reopened, MAX_ROUND_PAYOUTS % CLAIM_SHARDS as u64);
+
+        assert_eq!(ClaimShard::payout_cap_for(MAX_ROUND_PAYOUTS, 0, 0), 0);
+    }
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+/// A follower's standing authorization to mirror a leader's bets
+///
+/// The follower approves this PDA as token delegate on their token account;
+/// a crank then copies each of the leader's bets at `stake_bps` of the
+/// leader's stake, spending at most `budget_per_round` per round.
+#[account]
+pub struct CopyConfig {
+    /// Betting pool the bets are placed in
+    pub betting_pool: Pubkey,
+
+    /// Wallet whose bets are mirrored
+    pub leader: Pubkey,
+
+    /// Wallet whose tokens fund the copies and who owns them
+    pub follower: Pubkey,
+
+    /// Copy stake as basis points of the leader's stake
+    pub stake_bps: u16,
+
+    /// Most the follower stakes on copies in one round (0 = paused)
+    pub budget_per_round: u64,
+
+    /// Leader's share of the profit on winning copies, in basis points
+    pub success_fee_bps: u16,
+
+    /// Round the spend below belongs to
+    pub budget_round_id: u64,
+
+    /// Staked on copies in budget_round_id
+    pub spent_this_round: u64,
+
+    /// Highest leader bet_id copied so far; leader bets are copied in
+    /// bet_id order, each at most once
+    pub last_copied_bet_id: u64,
+
+    /// Whether any leader bet has been copied yet
+    pub has_copied: bool,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl CopyConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // leader
+        32 + // follower
+        2 +  // stake_bps
+        8 +  // budget_per_round
+        2 +  // success_fee_bps
+        8 +  // budget_round_id
+        8 +  // spent_this_round
+        8 +  // last_copied_bet_id
+        1 +  // has_copied
+        1;   // bump
+
+    /// Budget still available in `round_id`
+    pub fn remaining_budget(&self, round_id: u64) -> u64 {
+        if round_id == self.budget_round_id {
+            self.budget_per_round.saturating_sub(self.spent_this_round)
+        } else {
+            self.budget_per_round
+        }
+    }
+
+    /// Record a copy of leader bet `bet_id` staking `amount` in `round_id`
+    pub fn record_copy(&mut self, round_id: u64, bet_id: u64, amount: u64) {
+        if round_id != self.budget_round_id {
+            self.budget_round_id = round_id;
+            self.spent_this_round = 0;
+        }
+        self.spent_this_round = self.spent_this_round.saturating_add(amount);
+        self.last_copied_bet_id = bet_id;
+        self.has_copied = true;
+    }
+
+    /// Whether leader bet `bet_id` is still eligible to be copied
+    pub fn can_copy(&self, bet_id: u64) -> bool {
+        !self.has_copied || bet_id > self.last_copied_bet_id
+    }
+}
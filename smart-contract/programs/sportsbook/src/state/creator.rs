@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+/// A creator whitelisted to run branded rounds on the pool's bankroll
+#[account]
+pub struct Creator {
+    /// Betting pool this creator may brand rounds in
+    pub betting_pool: Pubkey,
+
+    /// Creator's wallet (signs round creation, receives the revenue share)
+    pub wallet: Pubkey,
+
+    /// Share of a branded round's protocol revenue paid to the creator
+    pub revenue_share_bps: u16,
+
+    /// Whether the creator may start new branded rounds
+    pub active: bool,
+
+    /// Branded rounds started so far
+    pub rounds_created: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Creator {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // wallet
+        2 +  // revenue_share_bps
+        1 +  // active
+        8 +  // rounds_created
+        1;   // bump
+}
+
+/// Branding and revenue terms of one creator round
+#[account]
+pub struct BrandedRound {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Creator's wallet
+    pub creator: Pubkey,
+
+    /// Display name (UTF-8, zero-padded)
+    pub brand: [u8; 32],
+
+    /// Creator's revenue share, fixed when the round was created
+    pub revenue_share_bps: u16,
+
+    /// Paid to the creator at finalize_revenue
+    pub creator_revenue: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl BrandedRound {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        32 + // creator
+        32 + // brand
+        2 +  // revenue_share_bps
+        8 +  // creator_revenue
+        1;   // bump
+}
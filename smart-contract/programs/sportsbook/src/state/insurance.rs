@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+
+/// A pool's bet insurance: the premium rate and the fund paying the refunds
+///
+/// Insured parlays pay `premium_bps` of their stake on top of it at
+/// placement, into the insurance vault; a parlay that loses by exactly one
+/// leg gets its whole stake back from the vault when claimed. The vault is
+/// separate from the pool and round funds, so insurance never shows up in
+/// a round's accounting.
+#[account]
+pub struct InsuranceFund {
+    /// Betting pool the insurance is offered on
+    pub betting_pool: Pubkey,
+
+    /// Premium as a share of the stake (0 = no new insured bets)
+    pub premium_bps: u16,
+
+    /// Premiums paid into the vault over the fund's lifetime
+    pub total_premiums: u64,
+
+    /// Stakes refunded from the vault over the fund's lifetime
+    pub total_refunded: u64,
+
+    /// Bump seed of the insurance vault
+    pub vault_bump: u8,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        2 +  // premium_bps
+        8 +  // total_premiums
+        8 +  // total_refunded
+        1 +  // vault_bump
+        1;   // bump
+
+    /// Premium for insuring `stake`, rounded down
+    pub fn premium(&self, stake: u64) -> Option<u64> {
+        let premium = (stake as u128)
+            .checked_mul(self.premium_bps as u128)?
+            .checked_div(BPS_DENOMINATOR as u128)?;
+        u64::try_from(premium).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_premium_is_share_of_stake() {
+        let fund = InsuranceFund {
+            betting_pool: Pubkey::default(),
+            premium_bps: 1_000,
+            total_premiums: 0,
+            total_refunded: 0,
+            vault_bump: 0,
+            bump: 0,
+        };
+        assert_eq!(fund.premium(100_000_000), Some(10_000_000));
+        assert_eq!(fund.premium(9), Some(0));
+        assert_eq!(fund.premium(u64::MAX), Some(u64::MAX / 10));
+    }
+}
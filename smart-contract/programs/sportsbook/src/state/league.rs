@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use sportsbook_core::{simulate_round, GoalModel, MAX_LEAGUE_TEAMS};
+use crate::constants::MATCHES_PER_ROUND;
+
+/// Goal model parameters as stored on-chain (see sportsbook_core::GoalModel)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GoalModelParams {
+    /// Expected goals for either side of an even match, in milli-goals
+    pub base_goals_milli: u16,
+
+    /// Rating points added to the home side
+    pub home_advantage: u16,
+
+    /// Rating points of advantage that multiply expected goals by e
+    pub rating_scale: u16,
+
+    /// Most goals one side can score
+    pub max_goals: u8,
+}
+
+impl GoalModelParams {
+    pub const LEN: usize = 2 + 2 + 2 + 1;
+
+    pub fn to_core(&self) -> GoalModel {
+        GoalModel {
+            base_goals_milli: self.base_goals_milli,
+            home_advantage: self.home_advantage,
+            rating_scale: self.rating_scale,
+            max_goals: self.max_goals,
+        }
+    }
+}
+
+impl From<GoalModel> for GoalModelParams {
+    fn from(model: GoalModel) -> Self {
+        Self {
+            base_goals_milli: model.base_goals_milli,
+            home_advantage: model.home_advantage,
+            rating_scale: model.rating_scale,
+            max_goals: model.max_goals,
+        }
+    }
+}
+
+/// A phantom (VRF-simulated) league season and its double round-robin
+///
+/// Teams are registered while the league is open; the first materialized
+/// round freezes the team list. Each matchday then becomes one pool round
+/// whose fixtures come from `sportsbook_core::round_robin_fixtures`.
+/// Team ratings and the goal model can change between matchdays; each
+/// round locks the values in force when it was materialized.
+#[account]
+pub struct LeagueSchedule {
+    /// Betting pool the league's rounds run in
+    pub betting_pool: Pubkey,
+
+    /// Season this league belongs to
+    pub season_id: u64,
+
+    /// Team names (UTF-8, zero-padded), indexed by team number
+    pub teams: [[u8; 16]; MAX_LEAGUE_TEAMS as usize],
+
+    /// Teams registered so far
+    pub num_teams: u8,
+
+    /// Strength rating per team (Elo scale), weighting simulated outcomes
+    pub ratings: [u16; MAX_LEAGUE_TEAMS as usize],
+
+    /// Goal model simulated rounds are played with
+    pub goal_model: GoalModelParams,
+
+    /// Matchdays in the full double round-robin (0 until started)
+    pub total_matchdays: u16,
+
+    /// Next matchday to materialize
+    pub next_matchday: u16,
+
+    /// Round ID of the last materialized matchday
+    pub last_round_id: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl LeagueSchedule {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // season_id
+        16 * MAX_LEAGUE_TEAMS as usize + // teams
+        1 +  // num_teams
+        2 * MAX_LEAGUE_TEAMS as usize + // ratings
+        GoalModelParams::LEN + // goal_model
+        2 +  // total_matchdays
+        2 +  // next_matchday
+        8 +  // last_round_id
+        1;   // bump
+
+    /// Team list is frozen once the first matchday is materialized
+    pub fn started(&self) -> bool {
+        self.total_matchdays > 0
+    }
+
+    pub fn finished(&self) -> bool {
+        self.started() && self.next_matchday >= self.total_matchdays
+    }
+}
+
+/// One league fixture: team numbers in the LeagueSchedule and their ratings
+/// at materialization, plus the score once recorded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Fixture {
+    pub home_team: u8,
+    pub away_team: u8,
+    pub home_goals: u8,
+    pub away_goals: u8,
+    pub home_rating: u16,
+    pub away_rating: u16,
+}
+
+impl Fixture {
+    pub const LEN: usize = 8;
+}
+
+/// Which teams play in each match of a league round
+///
+/// Match `i` of the round is `fixtures[i]`; matches from `num_fixtures` on
+/// are unused when the league has fewer than 20 teams. The goal model and
+/// ratings locked here are what the round's VRF randomness is simulated
+/// with, so anyone can recompute its results.
+#[account]
+pub struct RoundFixtures {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// League this round was generated from
+    pub league: Pubkey,
+
+    /// Matchday within the league (0-based)
+    pub matchday: u16,
+
+    /// Fixtures, indexed by match
+    pub fixtures: [Fixture; MATCHES_PER_ROUND],
+
+    /// Fixtures in use
+    pub num_fixtures: u8,
+
+    /// Goal model locked for this round
+    pub goal_model: GoalModelParams,
+
+    /// Scores have been recorded (goals count towards goal difference)
+    pub scores_recorded: bool,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundFixtures {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        32 + // league
+        2 +  // matchday
+        Fixture::LEN * MATCHES_PER_ROUND + // fixtures
+        1 +  // num_fixtures
+        GoalModelParams::LEN + // goal_model
+        1 +  // scores_recorded
+        1;   // bump
+
+    /// Match results simulated from VRF randomness (1 = home, 2 = away,
+    /// 3 = draw), using 32 bytes per match; unused matches fall back to
+    /// an unweighted draw
+    pub fn simulate_results(&self, randomness: &[u8; 320]) -> [u8; MATCHES_PER_ROUND] {
+        let ratings: Vec<(u16, u16)> = self
+            .fixtures
+            .iter()
+            .take(self.num_fixtures as usize)
+            .map(|fixture| (fixture.home_rating, fixture.away_rating))
+            .collect();
+        simulate_round(&self.goal_model.to_core(), &ratings, randomness).map(|simulated| simulated.outcome)
+    }
+}
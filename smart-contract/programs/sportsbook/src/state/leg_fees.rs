@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::constants::MATCHES_PER_ROUND;
+
+/// Protocol fee by parlay length
+///
+/// Entry `n - 1` is the base fee for a bet with `n` legs, 0 meaning the
+/// pool's `protocol_fee_bps`. Volume tiers and the team token rate still
+/// apply on top, but only ever lower the fee.
+#[account]
+pub struct LegFeeSchedule {
+    /// Betting pool the schedule applies to
+    pub betting_pool: Pubkey,
+
+    /// Base fee per number of legs, in basis points
+    pub fee_bps: [u16; MATCHES_PER_ROUND],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl LegFeeSchedule {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        2 * MATCHES_PER_ROUND + // fee_bps
+        1;   // bump
+
+    /// Base fee for a bet with `legs` legs
+    pub fn base_fee_bps(&self, legs: usize, protocol_fee_bps: u16) -> u16 {
+        match self.fee_bps.get(legs.wrapping_sub(1)) {
+            Some(&fee_bps) if fee_bps > 0 => fee_bps,
+            _ => protocol_fee_bps,
+        }
+    }
+
+    /// Every configured entry charges no more than the shorter parlays
+    /// before it
+    pub fn is_sliding(fee_bps: &[u16; MATCHES_PER_ROUND]) -> bool {
+        let configured: Vec<u16> = fee_bps.iter().copied().filter(|fee| *fee > 0).collect();
+        configured.windows(2).all(|pair| pair[1] <= pair[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_fee_by_parlay_length() {
+        let mut fee_bps = [0u16; MATCHES_PER_ROUND];
+        fee_bps[0] = 700;
+        fee_bps[2] = 300;
+        let schedule = LegFeeSchedule { betting_pool: Pubkey::default(), fee_bps, bump: 0 };
+
+        assert_eq!(schedule.base_fee_bps(1, 500), 700);
+        // Unset lengths fall back to the pool fee
+        assert_eq!(schedule.base_fee_bps(2, 500), 500);
+        assert_eq!(schedule.base_fee_bps(3, 500), 300);
+        assert_eq!(schedule.base_fee_bps(0, 500), 500);
+
+        assert!(LegFeeSchedule::is_sliding(&fee_bps));
+        fee_bps[5] = 400;
+        assert!(!LegFeeSchedule::is_sliding(&fee_bps));
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// A queued change to the pool's lending program or lending share
+///
+/// set_lending_program records anything that would send more liquidity out
+/// (a new program, or a higher share) here instead of applying it;
+/// apply_lending_program makes it live once `activates_at` has passed.
+/// Disabling lending or lowering the share applies at once and drops any
+/// queued change.
+#[account]
+pub struct LendingProposal {
+    /// Betting pool the change is for
+    pub betting_pool: Pubkey,
+
+    /// Proposed lending program
+    pub lending_program: Pubkey,
+
+    /// Proposed max lending share (bps)
+    pub max_lending_bps: u16,
+
+    /// Earliest time the change can be applied (0 = nothing queued)
+    pub activates_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl LendingProposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // lending_program
+        2 +  // max_lending_bps
+        8 +  // activates_at
+        1;   // bump
+
+    /// Whether a change is queued
+    pub fn is_pending(&self) -> bool {
+        self.activates_at != 0
+    }
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use crate::vrf::VRF_PROOF_LEN;
+
+/// Most winning tickets one lottery draws
+pub const MAX_LOTTERY_WINNERS: usize = 5;
+
+/// Pool-wide lottery settings and the prize money not yet drawn
+#[account]
+pub struct LotteryConfig {
+    /// Betting pool the lottery runs on
+    pub betting_pool: Pubkey,
+
+    /// Share of each round's protocol revenue set aside for prizes
+    /// (0 = lottery off)
+    pub share_bps: u16,
+
+    /// Set aside and waiting for the next draw; held in the pool token account
+    pub reserve: u64,
+
+    /// Drawn prizes not yet claimed; also held in the pool token account
+    pub prizes_unclaimed: u64,
+
+    /// Winning tickets per draw (1..=MAX_LOTTERY_WINNERS)
+    pub winners_per_draw: u8,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl LotteryConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        2 +  // share_bps
+        8 +  // reserve
+        8 +  // prizes_unclaimed
+        1 +  // winners_per_draw
+        1;   // bump
+
+    /// Lottery money the pool token account has to keep: the undrawn
+    /// reserve plus prizes drawn but not yet claimed
+    pub fn funds_held(&self) -> Option<u64> {
+        self.reserve.checked_add(self.prizes_unclaimed)
+    }
+}
+
+/// One week's consolation lottery
+///
+/// Losing bets from rounds that ended during the week can be entered as
+/// tickets. After the week closes the oracle signs `lottery_seed(..)`; the
+/// signature picks the winners, who split the prize pool evenly.
+#[account]
+pub struct Lottery {
+    /// Betting pool the lottery runs on
+    pub betting_pool: Pubkey,
+
+    /// Week number (unix time / LOTTERY_PERIOD_SECONDS)
+    pub lottery_id: u64,
+
+    /// Rounds ending in [opens_at, closes_at) qualify
+    pub opens_at: i64,
+    pub closes_at: i64,
+
+    /// Tickets entered; ticket numbers run 0..tickets
+    pub tickets: u32,
+
+    /// Moved in from the config reserve at the draw
+    pub prize_pool: u64,
+
+    /// prize_pool split evenly between the winners
+    pub prize_per_winner: u64,
+
+    /// Winning ticket numbers (first num_winners are valid)
+    pub winning_tickets: [u32; MAX_LOTTERY_WINNERS],
+
+    /// Winners drawn
+    pub num_winners: u8,
+
+    /// Whether the draw happened
+    pub drawn: bool,
+
+    /// Oracle signature over the lottery seed, kept for re-verification
+    pub proof: [u8; VRF_PROOF_LEN],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Lottery {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // lottery_id
+        8 +  // opens_at
+        8 +  // closes_at
+        4 +  // tickets
+        8 +  // prize_pool
+        8 +  // prize_per_winner
+        4 * MAX_LOTTERY_WINNERS + // winning_tickets
+        1 +  // num_winners
+        1 +  // drawn
+        VRF_PROOF_LEN + // proof
+        1;   // bump
+
+    pub fn is_winner(&self, ticket_number: u32) -> bool {
+        self.drawn
+            && self.winning_tickets[..self.num_winners as usize].contains(&ticket_number)
+    }
+}
+
+/// A losing bet entered into a lottery
+#[account]
+pub struct LotteryTicket {
+    /// Lottery entered
+    pub lottery: Pubkey,
+
+    /// Losing bet this ticket stands for
+    pub bet: Pubkey,
+
+    /// Bettor (receives the prize)
+    pub bettor: Pubkey,
+
+    /// Position in the draw
+    pub ticket_number: u32,
+
+    /// Prize paid out
+    pub claimed: bool,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl LotteryTicket {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // lottery
+        32 + // bet
+        32 + // bettor
+        4 +  // ticket_number
+        1 +  // claimed
+        1;   // bump
+}
+
+/// Distinct winning ticket numbers out of `tickets`, from 320 bytes of
+/// randomness (8 bytes per pick; a repeat moves on to the next free ticket)
+pub fn draw_winning_tickets(randomness: &[u8; 320], tickets: u32, winners: usize) -> Vec<u32> {
+    let winners = winners.min(tickets as usize).min(MAX_LOTTERY_WINNERS);
+    let mut picked: Vec<u32> = Vec::with_capacity(winners);
+
+    for chunk in randomness.chunks_exact(8).take(winners) {
+        let value = u64::from_le_bytes(chunk.try_into().unwrap());
+        let mut ticket = (value % tickets as u64) as u32;
+        while picked.contains(&ticket) {
+            ticket = (ticket + 1) % tickets;
+        }
+        picked.push(ticket);
+    }
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lottery_funds_held_include_unclaimed_prizes() {
+        let mut config = LotteryConfig {
+            betting_pool: Pubkey::new_unique(),
+            share_bps: 500,
+            reserve: 1_003,
+            prizes_unclaimed: 0,
+            winners_per_draw: 3,
+            bump: 255,
+        };
+        assert_eq!(config.funds_held(), Some(1_003));
+
+        // A draw moves prizes out of the reserve but not out of the pool
+        config.reserve -= 1_002;
+        config.prizes_unclaimed += 1_002;
+        assert_eq!(config.funds_held(), Some(1_003));
+
+        config.prizes_unclaimed = u64::MAX;
+        assert_eq!(config.funds_held(), None);
+    }
+
+    #[test]
+    fn test_draw_winning_tickets_distinct_and_in_range() {
+        let randomness = [7u8; 320];
+        // Identical chunks would all pick the same ticket without probing
+        let winners = draw_winning_tickets(&randomness, 10, 3);
+        assert_eq!(winners.len(), 3);
+        assert!(winners.iter().all(|ticket| *ticket < 10));
+        assert!(winners[0] != winners[1] && winners[1] != winners[2] && winners[0] != winners[2]);
+
+        assert_eq!(draw_winning_tickets(&randomness, 2, 5).len(), 2);
+        assert!(draw_winning_tickets(&randomness, 0, 5).is_empty());
+    }
+}
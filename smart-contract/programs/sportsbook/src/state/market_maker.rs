@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+
+/// An external odds provider allowed to quote some of each round's matches
+///
+/// Set up by the pool authority. The maker can only move odds on the
+/// matches in `match_mask`, by at most `max_deviation_bps` per outcome per
+/// quote, and no more often than every `min_update_interval` seconds.
+#[account]
+pub struct MarketMaker {
+    /// Betting pool the maker quotes for
+    pub betting_pool: Pubkey,
+
+    /// Maker's signing wallet
+    pub wallet: Pubkey,
+
+    /// Bit i set = may quote match i of any round
+    pub match_mask: u16,
+
+    /// Largest move of any outcome's odds in one quote
+    pub max_deviation_bps: u16,
+
+    /// Seconds required between two quotes
+    pub min_update_interval: i64,
+
+    /// Time of the last accepted quote (0 = none yet)
+    pub last_update: i64,
+
+    /// Quotes accepted so far
+    pub quotes: u64,
+
+    /// Whether the maker may quote
+    pub active: bool,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MarketMaker {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // wallet
+        2 +  // match_mask
+        2 +  // max_deviation_bps
+        8 +  // min_update_interval
+        8 +  // last_update
+        8 +  // quotes
+        1 +  // active
+        1;   // bump
+
+    pub fn may_quote(&self, match_index: usize) -> bool {
+        self.active && match_index < 16 && self.match_mask & (1 << match_index) != 0
+    }
+
+    pub fn interval_elapsed(&self, current_time: i64) -> bool {
+        self.last_update == 0 || current_time >= self.last_update.saturating_add(self.min_update_interval)
+    }
+
+    /// `new_odds` is within max_deviation_bps of `current_odds`
+    pub fn within_deviation(&self, current_odds: u64, new_odds: u64) -> bool {
+        let max_move = current_odds as u128 * self.max_deviation_bps as u128 / BPS_DENOMINATOR as u128;
+        (current_odds.abs_diff(new_odds) as u128) <= max_move
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_market_maker_bounds() {
+        let maker = MarketMaker {
+            betting_pool: Pubkey::default(),
+            wallet: Pubkey::default(),
+            match_mask: 0b101,
+            max_deviation_bps: 500,
+            min_update_interval: 60,
+            last_update: 1_000,
+            quotes: 1,
+            active: true,
+            bump: 0,
+        };
+
+        assert!(maker.may_quote(0) && maker.may_quote(2));
+        assert!(!maker.may_quote(1));
+
+        assert!(!maker.interval_elapsed(1_059));
+        assert!(maker.interval_elapsed(1_060));
+
+        // 5% of 2.0x is 0.1x either way
+        assert!(maker.within_deviation(2_000_000_000, 2_100_000_000));
+        assert!(maker.within_deviation(2_000_000_000, 1_900_000_000));
+        assert!(!maker.within_deviation(2_000_000_000, 2_100_000_001));
+    }
+}
@@ -83,16 +83,14 @@ impl LockedOdds {
 }
 
 /// Match outcome enumeration
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MatchOutcome {
+    #[default]
     Pending = 0,
     HomeWin = 1,
     AwayWin = 2,
     Draw = 3,
+    /// Match cancelled; bets on it are voided
+    Void = 4,
 }
 
-impl Default for MatchOutcome {
-    fn default() -> Self {
-        MatchOutcome::Pending
-    }
-}
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::state::ClaimShard;
+
+/// Days of history kept in DailyVolume
+pub const DAILY_VOLUME_DAYS: usize = 32;
+
+/// Seconds per UTC day
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Betting activity for one UTC day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DayVolume {
+    /// Days since the unix epoch (0 = slot unused)
+    pub day: u32,
+
+    /// Bets placed that day
+    pub bet_count: u32,
+
+    /// Stakes placed (before fee)
+    pub volume: u64,
+
+    /// Stakes placed in USD (0 for stablecoin pools)
+    pub volume_usd: u64,
+
+    /// Protocol fees collected
+    pub fees_collected: u64,
+}
+
+impl DayVolume {
+    pub const LEN: usize = 4 + 4 + 8 + 8 + 8;
+}
+
+/// Rolling per-day volume for a pool (read model for operator dashboards)
+///
+/// A ring of `DAILY_VOLUME_DAYS` slots indexed by day number; a slot is
+/// reset when a new day lands on it.
+#[account]
+pub struct DailyVolume {
+    /// Betting pool these metrics belong to
+    pub betting_pool: Pubkey,
+
+    /// Per-day slots, indexed by day % DAILY_VOLUME_DAYS
+    pub days: [DayVolume; DAILY_VOLUME_DAYS],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl DailyVolume {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        DayVolume::LEN * DAILY_VOLUME_DAYS + // days
+        1;   // bump
+
+    /// Add one bet to the day containing `timestamp`
+    pub fn record_bet(&mut self, timestamp: i64, amount: u64, fee: u64, amount_usd: u64) {
+        let day = (timestamp.max(0) / SECONDS_PER_DAY) as u32;
+        let slot = &mut self.days[day as usize % DAILY_VOLUME_DAYS];
+        if slot.day != day {
+            *slot = DayVolume { day, ..Default::default() };
+        }
+
+        slot.bet_count = slot.bet_count.saturating_add(1);
+        slot.volume = slot.volume.saturating_add(amount);
+        slot.volume_usd = slot.volume_usd.saturating_add(amount_usd);
+        slot.fees_collected = slot.fees_collected.saturating_add(fee);
+    }
+}
+
+/// Who is betting on a pool (read model for operator dashboards)
+#[account]
+pub struct ActiveBettors {
+    /// Betting pool these metrics belong to
+    pub betting_pool: Pubkey,
+
+    /// Distinct bettors, counted on the first bet staked from their wallet
+    /// (every placement path creates their UserVolume exactly once)
+    pub unique_bettors: u64,
+
+    /// Bets placed through any path
+    pub total_bets: u64,
+
+    /// Most recent bettor
+    pub last_bettor: Pubkey,
+
+    /// Time of the most recent bet
+    pub last_bet_time: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ActiveBettors {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // unique_bettors
+        8 +  // total_bets
+        32 + // last_bettor
+        8 +  // last_bet_time
+        1;   // bump
+
+    pub fn record_bet(&mut self, bettor: Pubkey, new_bettor: bool, timestamp: i64) {
+        if new_bettor {
+            self.unique_bettors = self.unique_bettors.saturating_add(1);
+        }
+        self.total_bets = self.total_bets.saturating_add(1);
+        self.last_bettor = bettor;
+        self.last_bet_time = timestamp;
+    }
+}
+
+/// Bets still waiting to be claimed or settled (read model for operator dashboards)
+#[account]
+pub struct PendingClaims {
+    /// Betting pool these metrics belong to
+    pub betting_pool: Pubkey,
+
+    /// Bets placed and not yet claimed or settled as lost
+    pub unresolved_bets: u64,
+
+    /// Sum of potential_payout over unresolved bets (worst-case owed)
+    pub unresolved_potential_payout: u64,
+
+    /// Bets claimed as winners
+    pub claimed_bets: u64,
+
+    /// Bets settled as lost
+    pub lost_bets: u64,
+
+    /// Total paid out on claims (bettor + bounty)
+    pub total_paid_out: u64,
+
+    /// Part of total_paid_out that went to bounty hunters
+    pub total_bounty_paid: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PendingClaims {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // unresolved_bets
+        8 +  // unresolved_potential_payout
+        8 +  // claimed_bets
+        8 +  // lost_bets
+        8 +  // total_paid_out
+        8 +  // total_bounty_paid
+        1;   // bump
+
+    pub fn record_bet(&mut self, potential_payout: u64) {
+        self.unresolved_bets = self.unresolved_bets.saturating_add(1);
+        self.unresolved_potential_payout = self
+            .unresolved_potential_payout
+            .saturating_add(potential_payout);
+    }
+
+    /// A bet left the unresolved set; `payout` is 0 for a loss
+    pub fn record_resolution(&mut self, potential_payout: u64, payout: u64, bounty: u64) {
+        self.unresolved_bets = self.unresolved_bets.saturating_sub(1);
+        self.unresolved_potential_payout = self
+            .unresolved_potential_payout
+            .saturating_sub(potential_payout);
+
+        if payout > 0 {
+            self.claimed_bets = self.claimed_bets.saturating_add(1);
+            self.total_paid_out = self.total_paid_out.saturating_add(payout);
+            self.total_bounty_paid = self.total_bounty_paid.saturating_add(bounty);
+        } else {
+            self.lost_bets = self.lost_bets.saturating_add(1);
+        }
+    }
+
+    /// A merged ClaimShard's bets left the unresolved set; `paid` is
+    /// winnings plus refunds
+    pub fn record_shard(&mut self, shard: &ClaimShard) {
+        let paid = shard.total_paid_out.saturating_add(shard.total_refunded);
+        self.unresolved_bets = self.unresolved_bets.saturating_sub(shard.bets_resolved as u64);
+        self.unresolved_potential_payout = self
+            .unresolved_potential_payout
+            .saturating_sub(shard.resolved_potential_payout);
+        self.claimed_bets = self
+            .claimed_bets
+            .saturating_add((shard.bets_resolved - shard.lost_bets) as u64);
+        self.lost_bets = self.lost_bets.saturating_add(shard.lost_bets as u64);
+        self.total_paid_out = self.total_paid_out.saturating_add(paid);
+        self.total_bounty_paid = self.total_bounty_paid.saturating_add(shard.bounty_paid);
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+/// Notifications kept; older ones are overwritten
+pub const NOTIFICATION_QUEUE_LEN: usize = 32;
+
+/// What an operator is being told about
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// Never written
+    #[default]
+    Empty,
+    /// A round settled and its winners have claims to make
+    /// (`amount` = reserved for winners)
+    ClaimNeeded,
+    /// LP liquidity left after seeding a round fell below the threshold
+    /// (`amount` = available liquidity)
+    LiquidityLow,
+    /// A round's randomness failed verification and the round is disputed
+    /// (`subject` = verifier)
+    DisputeOpened,
+}
+
+/// One queued notification
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Notification {
+    /// Position in the pool's notification stream, from 0
+    pub sequence: u64,
+
+    pub kind: NotificationKind,
+
+    /// Round concerned (0 when not about a round)
+    pub round_id: u64,
+
+    /// Account concerned, per kind (Pubkey::default() = none)
+    pub subject: Pubkey,
+
+    /// Token amount, per kind
+    pub amount: u64,
+
+    /// When it was queued
+    pub timestamp: i64,
+}
+
+impl Notification {
+    pub const LEN: usize = 8 + 1 + 8 + 32 + 8 + 8;
+}
+
+/// A pool's operator notifications, as a ring buffer
+///
+/// Handlers given the account append compact typed entries, so an operator
+/// bot can poll one account and pick up everything since the last
+/// `sequence` it saw instead of scraping logs. A bot that falls more than
+/// NOTIFICATION_QUEUE_LEN entries behind sees a gap in the sequence.
+#[account]
+pub struct Notifications {
+    /// Betting pool the notifications are about
+    pub betting_pool: Pubkey,
+
+    /// Queue LiquidityLow when available LP liquidity after seeding a round
+    /// drops below this (0 = never)
+    pub liquidity_low_threshold: u64,
+
+    /// Ring buffer; slot `total % NOTIFICATION_QUEUE_LEN` is written next
+    pub entries: [Notification; NOTIFICATION_QUEUE_LEN],
+
+    /// Notifications queued since creation
+    pub total: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Notifications {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // liquidity_low_threshold
+        Notification::LEN * NOTIFICATION_QUEUE_LEN + // entries
+        8 +  // total
+        1;   // bump
+
+    pub fn push(&mut self, kind: NotificationKind, round_id: u64, subject: Pubkey, amount: u64, timestamp: i64) {
+        let slot = (self.total % NOTIFICATION_QUEUE_LEN as u64) as usize;
+        self.entries[slot] = Notification {
+            sequence: self.total,
+            kind,
+            round_id,
+            subject,
+            amount,
+            timestamp,
+        };
+        self.total = self.total.saturating_add(1);
+    }
+
+    /// Queued notifications from `sequence` on that are still in the
+    /// buffer, oldest first
+    pub fn since(&self, sequence: u64) -> Vec<Notification> {
+        let oldest = self.total.saturating_sub(NOTIFICATION_QUEUE_LEN as u64);
+        (sequence.max(oldest)..self.total)
+            .map(|sequence| self.entries[(sequence % NOTIFICATION_QUEUE_LEN as u64) as usize])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_latest_entries() {
+        let mut notifications = Notifications {
+            betting_pool: Pubkey::default(),
+            liquidity_low_threshold: 0,
+            entries: [Notification::default(); NOTIFICATION_QUEUE_LEN],
+            total: 0,
+            bump: 0,
+        };
+        assert!(notifications.since(0).is_empty());
+
+        for round_id in 0..40 {
+            notifications.push(NotificationKind::ClaimNeeded, round_id, Pubkey::default(), 0, 100);
+        }
+        assert_eq!(notifications.total, 40);
+
+        // The first 8 were overwritten
+        let all = notifications.since(0);
+        assert_eq!(all.len(), NOTIFICATION_QUEUE_LEN);
+        assert_eq!((all[0].sequence, all[0].round_id), (8, 8));
+        assert_eq!(all[NOTIFICATION_QUEUE_LEN - 1].sequence, 39);
+
+        let recent = notifications.since(37);
+        assert_eq!(recent.iter().map(|entry| entry.sequence).collect::<Vec<_>>(), vec![37, 38, 39]);
+        assert!(notifications.since(40).is_empty());
+    }
+}
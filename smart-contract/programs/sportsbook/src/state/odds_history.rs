@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::state::LockedOdds;
+
+/// Odds updates kept per match; older ones are overwritten
+pub const ODDS_HISTORY_LEN: usize = 16;
+
+/// One recorded odds update
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct OddsPoint {
+    /// Home win odds (scaled by 1e9)
+    pub home_odds: u64,
+
+    /// Away win odds (scaled by 1e9)
+    pub away_odds: u64,
+
+    /// Draw odds (scaled by 1e9)
+    pub draw_odds: u64,
+
+    /// When the odds were set
+    pub timestamp: i64,
+}
+
+impl OddsPoint {
+    pub const LEN: usize = 8 + 8 + 8 + 8;
+}
+
+/// Last ODDS_HISTORY_LEN odds updates of one match, as a ring buffer
+///
+/// Written by whichever instruction changes the match's locked odds
+/// (seed_round_pools, bulk_set_odds, lock_odds) when the history account is
+/// passed, so line movement can be read straight from chain.
+#[account]
+pub struct OddsHistory {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Match within the round
+    pub match_index: u8,
+
+    /// Ring buffer; slot `total_updates % ODDS_HISTORY_LEN` is written next
+    pub points: [OddsPoint; ODDS_HISTORY_LEN],
+
+    /// Updates recorded since creation
+    pub total_updates: u32,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OddsHistory {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        1 +  // match_index
+        OddsPoint::LEN * ODDS_HISTORY_LEN + // points
+        4 +  // total_updates
+        1;   // bump
+
+    pub fn record(&mut self, odds: &LockedOdds, timestamp: i64) {
+        let slot = self.total_updates as usize % ODDS_HISTORY_LEN;
+        self.points[slot] = OddsPoint {
+            home_odds: odds.home_odds,
+            away_odds: odds.away_odds,
+            draw_odds: odds.draw_odds,
+            timestamp,
+        };
+        self.total_updates = self.total_updates.saturating_add(1);
+    }
+
+    /// Recorded points still in the buffer, oldest first
+    pub fn recent(&self) -> Vec<OddsPoint> {
+        let stored = (self.total_updates as usize).min(ODDS_HISTORY_LEN);
+        let start = self.total_updates as usize - stored;
+        (start..self.total_updates as usize)
+            .map(|update| self.points[update % ODDS_HISTORY_LEN])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odds(home_odds: u64) -> LockedOdds {
+        LockedOdds { home_odds, away_odds: 2, draw_odds: 3, locked: true }
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_latest_updates_in_order() {
+        let mut history = OddsHistory {
+            betting_pool: Pubkey::default(),
+            round_id: 1,
+            match_index: 0,
+            points: [OddsPoint::default(); ODDS_HISTORY_LEN],
+            total_updates: 0,
+            bump: 0,
+        };
+        assert!(history.recent().is_empty());
+
+        history.record(&odds(100), 10);
+        history.record(&odds(101), 11);
+        let homes: Vec<u64> = history.recent().iter().map(|point| point.home_odds).collect();
+        assert_eq!(homes, vec![100, 101]);
+
+        for (i, home_odds) in (102..120).enumerate() {
+            history.record(&odds(home_odds), 12 + i as i64);
+        }
+        let recent = history.recent();
+        assert_eq!(recent.len(), ODDS_HISTORY_LEN);
+        assert_eq!(recent[0].home_odds, 104);
+        assert_eq!(recent[ODDS_HISTORY_LEN - 1].home_odds, 119);
+        assert!(recent.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+    }
+}
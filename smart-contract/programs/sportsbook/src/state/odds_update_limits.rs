@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, MATCHES_PER_ROUND};
+
+/// Pool-wide bounds on how fast any signer can move a match's odds
+///
+/// Set by the pool authority. Applies to bulk_set_odds and market maker
+/// quotes alike, on top of a maker's own bounds, so no single update can
+/// swing a line just before it locks.
+#[account]
+pub struct OddsUpdateLimits {
+    /// Betting pool the limits apply to
+    pub betting_pool: Pubkey,
+
+    /// Largest move of any outcome's odds in one update (0 = unbounded)
+    pub max_move_bps: u16,
+
+    /// Seconds required between two updates of the same match (0 = none)
+    pub min_update_interval: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OddsUpdateLimits {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        2 +  // max_move_bps
+        8 +  // min_update_interval
+        1;   // bump
+
+    /// `new_odds` is within max_move_bps of `current_odds`
+    pub fn allows_move(&self, current_odds: u64, new_odds: u64) -> bool {
+        let max_move = current_odds as u128 * self.max_move_bps as u128 / BPS_DENOMINATOR as u128;
+        self.max_move_bps == 0 || (current_odds.abs_diff(new_odds) as u128) <= max_move
+    }
+}
+
+/// When each match of a round last had its odds updated
+#[account]
+pub struct RoundOddsUpdates {
+    /// Betting pool this round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Unix timestamp of the last update per match (0 = none since seeding)
+    pub last_update: [i64; MATCHES_PER_ROUND],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundOddsUpdates {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        8 * MATCHES_PER_ROUND + // last_update
+        1;   // bump
+
+    pub fn interval_elapsed(&self, limits: &OddsUpdateLimits, match_index: usize, current_time: i64) -> bool {
+        let last_update = self.last_update[match_index];
+        last_update == 0 || current_time >= last_update.saturating_add(limits.min_update_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odds_update_limits() {
+        let mut limits = OddsUpdateLimits {
+            betting_pool: Pubkey::default(),
+            max_move_bps: 1_000,
+            min_update_interval: 300,
+            bump: 0,
+        };
+
+        // 10% of 1.5x is 0.15x either way
+        assert!(limits.allows_move(1_500_000_000, 1_650_000_000));
+        assert!(limits.allows_move(1_500_000_000, 1_350_000_000));
+        assert!(!limits.allows_move(1_500_000_000, 1_650_000_001));
+
+        let mut updates = RoundOddsUpdates {
+            betting_pool: Pubkey::default(),
+            round_id: 1,
+            last_update: [0; MATCHES_PER_ROUND],
+            bump: 0,
+        };
+        assert!(updates.interval_elapsed(&limits, 4, 1_000));
+        updates.last_update[4] = 1_000;
+        assert!(!updates.interval_elapsed(&limits, 4, 1_299));
+        assert!(updates.interval_elapsed(&limits, 4, 1_300));
+        assert!(updates.interval_elapsed(&limits, 5, 1_001));
+
+        limits.max_move_bps = 0;
+        assert!(limits.allows_move(1_500_000_000, 1_950_000_000));
+    }
+}
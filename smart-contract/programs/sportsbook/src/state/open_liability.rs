@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::errors::SportsbookError;
+
+/// User money and potential payouts riding on one outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OutcomeLiability {
+    /// User stake allocated to this outcome (excludes protocol seed)
+    pub stake: u64,
+
+    /// Payout owed on this stake if the outcome wins (incl. parlay multiplier)
+    pub potential_payout: u64,
+}
+
+/// Placed stake and potential payouts for a single match, split by outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct MatchLiability {
+    pub home: OutcomeLiability,
+    pub away: OutcomeLiability,
+    pub draw: OutcomeLiability,
+}
+
+impl MatchLiability {
+    pub const LEN: usize = 3 * (8 + 8);
+}
+
+/// Compact per-round totals of placed bets for frontends
+///
+/// Updated at placement when the placing instruction passes it, so market
+/// sentiment ("72% of money on Home") and worst-case exposure can be read
+/// from one account instead of scanning every Bet. Nothing is taken off
+/// when bets are claimed, refunded or settled as lost: these are
+/// placement-time totals, not the liability still open. Rounds created
+/// before the account existed have none, and bets placed without it are
+/// missing from it.
+#[account]
+pub struct OpenLiability {
+    /// Betting pool this round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Per-match, per-outcome totals
+    pub matches: [MatchLiability; 10],
+
+    /// Total user stake across all matches
+    pub total_stake: u64,
+
+    /// Sum of Bet::potential_payout over the round's bets
+    pub total_potential_payout: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OpenLiability {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        (MatchLiability::LEN * 10) + // matches
+        8 +  // total_stake
+        8 +  // total_potential_payout
+        1;   // bump
+
+    /// Add one prediction leg to the per-outcome totals
+    pub fn record_leg(
+        &mut self,
+        match_index: u8,
+        outcome: u8,
+        stake: u64,
+        potential_payout: u64,
+    ) -> Result<()> {
+        let match_liability = self
+            .matches
+            .get_mut(match_index as usize)
+            .ok_or(SportsbookError::InvalidMatchIndex)?;
+
+        let entry = match outcome {
+            1 => &mut match_liability.home,
+            2 => &mut match_liability.away,
+            3 => &mut match_liability.draw,
+            _ => return err!(SportsbookError::InvalidOutcome),
+        };
+
+        entry.stake = entry.stake
+            .checked_add(stake)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+        entry.potential_payout = entry.potential_payout
+            .checked_add(potential_payout)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+
+        self.total_stake = self.total_stake
+            .checked_add(stake)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+
+        Ok(())
+    }
+
+    /// Add a bet's stored potential payout to the round total
+    pub fn record_bet(&mut self, potential_payout: u64) -> Result<()> {
+        self.total_potential_payout = self.total_potential_payout
+            .checked_add(potential_payout)
+            .ok_or(SportsbookError::CalculationOverflow)?;
+
+        Ok(())
+    }
+}
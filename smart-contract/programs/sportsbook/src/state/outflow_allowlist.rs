@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+/// Destinations an allowlist can hold
+pub const MAX_OUTFLOW_DESTINATIONS: usize = 8;
+
+/// One allowlisted destination
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutflowDestination {
+    /// Destination address (Pubkey::default() = free slot)
+    pub destination: Pubkey,
+
+    /// When funds may start going to it
+    pub allowed_from: i64,
+}
+
+impl OutflowDestination {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Where pool liquidity may be sent outside bettor payouts
+///
+/// The authority proposes a destination with propose_outflow_destination
+/// and it becomes usable OUTFLOW_DESTINATION_TIMELOCK_SECONDS later;
+/// remove_outflow_destination drops it at once. Lending deposits may only
+/// go to an allowlisted reserve, and apply_lending_program only switches
+/// to an allowlisted lending program, so a leaked treasurer key can only
+/// move funds to addresses that were public for the whole timelock.
+#[account]
+pub struct OutflowAllowlist {
+    /// Betting pool the allowlist belongs to
+    pub betting_pool: Pubkey,
+
+    /// Allowlisted destinations, in no particular order
+    pub destinations: [OutflowDestination; MAX_OUTFLOW_DESTINATIONS],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl OutflowAllowlist {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        OutflowDestination::LEN * MAX_OUTFLOW_DESTINATIONS + // destinations
+        1;   // bump
+
+    /// Whether funds may go to `destination` at `now`
+    pub fn allows(&self, destination: &Pubkey, now: i64) -> bool {
+        *destination != Pubkey::default()
+            && self
+                .destinations
+                .iter()
+                .any(|entry| entry.destination == *destination && now >= entry.allowed_from)
+    }
+
+    /// List `destination` from `allowed_from`; None if it's already listed
+    /// or the allowlist is full
+    pub fn propose(&mut self, destination: Pubkey, allowed_from: i64) -> Option<()> {
+        if self.destinations.iter().any(|entry| entry.destination == destination) {
+            return None;
+        }
+        let slot = self
+            .destinations
+            .iter_mut()
+            .find(|entry| entry.destination == Pubkey::default())?;
+        *slot = OutflowDestination { destination, allowed_from };
+        Some(())
+    }
+
+    /// Drop `destination`; false if it wasn't listed
+    pub fn remove(&mut self, destination: &Pubkey) -> bool {
+        match self.destinations.iter_mut().find(|entry| entry.destination == *destination) {
+            Some(entry) => {
+                *entry = OutflowDestination::default();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destinations_wait_out_the_timelock() {
+        let mut allowlist = OutflowAllowlist {
+            betting_pool: Pubkey::default(),
+            destinations: [OutflowDestination::default(); MAX_OUTFLOW_DESTINATIONS],
+            bump: 0,
+        };
+        let reserve = Pubkey::new_unique();
+
+        allowlist.propose(reserve, 1_000).unwrap();
+        assert!(!allowlist.allows(&reserve, 999));
+        assert!(allowlist.allows(&reserve, 1_000));
+        assert!(!allowlist.allows(&Pubkey::new_unique(), 1_000));
+        assert!(!allowlist.allows(&Pubkey::default(), 1_000));
+
+        // Re-proposing can't restart or shorten the timelock
+        assert_eq!(allowlist.propose(reserve, 0), None);
+
+        assert!(allowlist.remove(&reserve));
+        assert!(!allowlist.allows(&reserve, 1_000));
+        assert!(!allowlist.remove(&reserve));
+
+        for _ in 0..MAX_OUTFLOW_DESTINATIONS {
+            allowlist.propose(Pubkey::new_unique(), 0).unwrap();
+        }
+        assert_eq!(allowlist.propose(reserve, 0), None);
+    }
+}
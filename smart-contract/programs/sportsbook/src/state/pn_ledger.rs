@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+/// Rounds kept in the ledger's entry list; older ones are overwritten, but
+/// still counted in the running totals
+pub const PN_LEDGER_LEN: usize = 64;
+
+/// One finalized round's result for the protocol
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct PnlEntry {
+    pub round_id: u64,
+
+    /// Stakes placed, fees included
+    pub volume: u64,
+
+    /// Winnings, void refunds and protocol-funded bounties paid
+    pub payouts: u64,
+
+    /// Protocol fees taken at placement (sent to the treasury then)
+    pub fees: u64,
+
+    /// Stakes after fees less payouts; negative when the seed covered a loss
+    pub profit: i64,
+
+    /// Credited to the season reward pool, rounding dust included
+    pub season_share: u64,
+
+    /// Left to the protocol after the creator and lottery shares
+    pub protocol_revenue: u64,
+
+    /// When finalize_revenue recorded it
+    pub finalized_at: i64,
+}
+
+impl PnlEntry {
+    pub const LEN: usize = 8 * 8;
+}
+
+/// A pool's protocol profit and loss, round by round
+///
+/// finalize_revenue appends each round it allocates when given the ledger,
+/// so cumulative performance can be read from chain and the fee total
+/// reconciled against what reached the treasury.
+#[account]
+pub struct PnLedger {
+    /// Betting pool the ledger covers
+    pub betting_pool: Pubkey,
+
+    /// Rounds recorded since creation
+    pub rounds_recorded: u64,
+
+    /// Running totals over every recorded round
+    pub total_volume: u64,
+    pub total_payouts: u64,
+    pub total_fees: u64,
+    pub total_profit: i64,
+    pub total_season_share: u64,
+    pub total_protocol_revenue: u64,
+
+    /// Ring buffer; slot `rounds_recorded % PN_LEDGER_LEN` is written next
+    pub entries: [PnlEntry; PN_LEDGER_LEN],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PnLedger {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // rounds_recorded
+        8 +  // total_volume
+        8 +  // total_payouts
+        8 +  // total_fees
+        8 +  // total_profit
+        8 +  // total_season_share
+        8 +  // total_protocol_revenue
+        PnlEntry::LEN * PN_LEDGER_LEN + // entries
+        1;   // bump
+
+    pub fn record(&mut self, entry: PnlEntry) {
+        let slot = (self.rounds_recorded % PN_LEDGER_LEN as u64) as usize;
+        self.entries[slot] = entry;
+        self.rounds_recorded = self.rounds_recorded.saturating_add(1);
+
+        self.total_volume = self.total_volume.saturating_add(entry.volume);
+        self.total_payouts = self.total_payouts.saturating_add(entry.payouts);
+        self.total_fees = self.total_fees.saturating_add(entry.fees);
+        self.total_profit = self.total_profit.saturating_add(entry.profit);
+        self.total_season_share = self.total_season_share.saturating_add(entry.season_share);
+        self.total_protocol_revenue = self.total_protocol_revenue.saturating_add(entry.protocol_revenue);
+    }
+
+    /// Recorded rounds still in the buffer, oldest first
+    pub fn recent(&self) -> Vec<PnlEntry> {
+        let oldest = self.rounds_recorded.saturating_sub(PN_LEDGER_LEN as u64);
+        (oldest..self.rounds_recorded)
+            .map(|recorded| self.entries[(recorded % PN_LEDGER_LEN as u64) as usize])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_cover_overwritten_rounds() {
+        let mut ledger = PnLedger {
+            betting_pool: Pubkey::default(),
+            rounds_recorded: 0,
+            total_volume: 0,
+            total_payouts: 0,
+            total_fees: 0,
+            total_profit: 0,
+            total_season_share: 0,
+            total_protocol_revenue: 0,
+            entries: [PnlEntry::default(); PN_LEDGER_LEN],
+            bump: 0,
+        };
+
+        for round_id in 0..70u64 {
+            // Every third round loses 50 to winners
+            let profit = if round_id % 3 == 0 { -50 } else { 100 };
+            ledger.record(PnlEntry {
+                round_id,
+                volume: 1_000,
+                payouts: (950 - profit) as u64,
+                fees: 50,
+                profit,
+                season_share: 20,
+                protocol_revenue: profit.max(0) as u64,
+                finalized_at: 0,
+            });
+        }
+
+        assert_eq!(ledger.rounds_recorded, 70);
+        assert_eq!(ledger.total_volume, 70_000);
+        assert_eq!(ledger.total_fees, 3_500);
+        assert_eq!(ledger.total_profit, 24 * -50 + 46 * 100);
+
+        let recent = ledger.recent();
+        assert_eq!(recent.len(), PN_LEDGER_LEN);
+        assert_eq!(recent[0].round_id, 6);
+        assert_eq!(recent[PN_LEDGER_LEN - 1].round_id, 69);
+    }
+}
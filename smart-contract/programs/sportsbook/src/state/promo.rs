@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// A marketing campaign funded from the pool, e.g. a deposit match
+///
+/// Bettors redeem the plaintext code against one of their open bets and
+/// receive `bonus_bps` of its stake, until `budget` is used up or the promo
+/// expires. Only the hash of the code is stored.
+#[account]
+pub struct Promo {
+    /// Betting pool funding this promo
+    pub betting_pool: Pubkey,
+
+    /// sha256 of the promo code
+    pub code_hash: [u8; 32],
+
+    /// Total bonus this promo may pay out
+    pub budget: u64,
+
+    /// Bonus paid so far (never exceeds budget)
+    pub spent: u64,
+
+    /// Bonus as basis points of the bet's stake (before fee)
+    pub bonus_bps: u16,
+
+    /// Unix time after which the code can no longer be redeemed
+    pub expiry: i64,
+
+    /// Redemptions allowed per wallet
+    pub max_redemptions_per_wallet: u8,
+
+    /// Redemptions across all wallets
+    pub redemptions: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Promo {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        32 + // code_hash
+        8 +  // budget
+        8 +  // spent
+        2 +  // bonus_bps
+        8 +  // expiry
+        1 +  // max_redemptions_per_wallet
+        8 +  // redemptions
+        1;   // bump
+
+    pub fn remaining_budget(&self) -> u64 {
+        self.budget.saturating_sub(self.spent)
+    }
+}
+
+/// How many times one wallet has redeemed one promo
+#[account]
+pub struct PromoRedemption {
+    /// Promo redeemed
+    pub promo: Pubkey,
+
+    /// Redeeming wallet
+    pub wallet: Pubkey,
+
+    /// Redemptions so far
+    pub count: u8,
+
+    /// Bonus paid to this wallet so far
+    pub total_bonus: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PromoRedemption {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // promo
+        32 + // wallet
+        1 +  // count
+        8 +  // total_bonus
+        1;   // bump
+}
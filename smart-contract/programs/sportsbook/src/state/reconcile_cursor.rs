@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// How far back reconcile_pool has to look
+///
+/// Every round below `closed_below` has been finalized (or archived) and
+/// owes nothing, so a reconciliation only has to pass the rounds from
+/// `closed_below` up to `betting_pool.next_round_id`. reconcile_pool moves
+/// it forward past the leading closed rounds it was shown.
+#[account]
+pub struct ReconcileCursor {
+    /// Betting pool this cursor belongs to
+    pub betting_pool: Pubkey,
+
+    /// Lowest round ID that may still owe winnings
+    pub closed_below: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ReconcileCursor {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // closed_below
+        1;   // bump
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use sportsbook_core::ROUND_RESULTS_PAYLOAD_LEN;
+
+/// A settled round's results packaged for a cross-chain message
+///
+/// `payload` is sportsbook_core::RoundResultsPayload in its big-endian
+/// layout and is what the bridge emitter posts as the Wormhole message
+/// body (with `nonce` and `consistency_level`). `payload_digest` is
+/// keccak256(payload), for relayers and guardians to sign.
+#[account]
+pub struct ResultsExport {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// PDA that posts the message ([BRIDGE_EMITTER_SEED]); mirrors trust
+    /// messages from this emitter only
+    pub emitter: Pubkey,
+
+    /// Wormhole message nonce (low 32 bits of the round ID)
+    pub nonce: u32,
+
+    /// Wormhole consistency level the message is posted with
+    pub consistency_level: u8,
+
+    /// Encoded RoundResultsPayload
+    pub payload: [u8; ROUND_RESULTS_PAYLOAD_LEN],
+
+    /// keccak256(payload)
+    pub payload_digest: [u8; 32],
+
+    /// When the export was written
+    pub exported_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ResultsExport {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        32 + // emitter
+        4 +  // nonce
+        1 +  // consistency_level
+        ROUND_RESULTS_PAYLOAD_LEN + // payload
+        32 + // payload_digest
+        8 +  // exported_at
+        1;   // bump
+}
@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 use super::{MatchPool, LockedOdds, MatchOutcome};
+use crate::pyth::PriceSnapshot;
+use crate::events::OddsLocked;
+use crate::utils::hash_locked_odds;
+use crate::merkle::locked_odds_table;
+use sportsbook_core::odds_root;
+use crate::constants::CLAIM_WINDOW_SECONDS;
 
 /// Accounting data for a single betting round (10 matches)
 #[account]
@@ -72,6 +78,98 @@ pub struct RoundAccounting {
 
     /// Bump seed for PDA
     pub bump: u8,
+
+    /// Total user stakes in USD (6 decimals), valued at placement
+    pub total_volume_usd: u64,
+
+    /// Token price at settlement (zeroed for stablecoin pools)
+    pub settlement_price: PriceSnapshot,
+
+    /// Number of bets placed in this round
+    pub bets_placed: u32,
+
+    /// Number of bets claimed or settled as lost
+    pub bets_resolved: u32,
+
+    /// hash_locked_odds() of the table at lock time (zero until locked)
+    pub locked_odds_hash: [u8; 32],
+
+    /// Claims (and loss settlement) are paused until this timestamp
+    pub claims_paused_until: i64,
+
+    /// Time added to the claim window to make up for pauses
+    pub claim_window_extension: i64,
+
+    /// Merkle root of the locked odds table, for claim proofs
+    /// (sportsbook_core::odds_root; zero until locked)
+    pub locked_odds_root: [u8; 32],
+
+    /// Created by a whitelisted creator; its BrandedRound takes a share of
+    /// protocol revenue at finalize_revenue
+    pub branded: bool,
+
+    /// verify_round_randomness found the round's results don't follow from
+    /// its VRF proof; claims and finalization stop until the guardian
+    /// resolves it
+    pub disputed: bool,
+
+    /// Materialized from a phantom league; its VRF results are simulated
+    /// from the ratings locked in its RoundFixtures
+    pub league_round: bool,
+
+    /// Bit i set once match i kicked off and lock_odds closed its market;
+    /// no further bets on it
+    pub kickoff_locked: u16,
+
+    /// Created while the pool had round vaults on: seed, stakes and payouts
+    /// go through this round's own vault, never the shared pool account
+    pub isolated: bool,
+
+    /// Bump of the round vault PDA (set by create_round_vault)
+    pub vault_bump: u8,
+
+    /// Stakes refunded to fully voided bets; kept apart from
+    /// total_claimed/total_paid_out, which are winnings only
+    pub total_refunded: u64,
+
+    /// Created while the pool funded bounties itself: late-claim bounties
+    /// are paid on top of the payout rather than cut from it
+    pub protocol_funded_bounty: bool,
+
+    /// Bounties paid on top of payouts, charged to protocol revenue
+    pub protocol_bounties_paid: u64,
+
+    /// finalize_revenue has recorded the season, creator and lottery shares;
+    /// a retry only runs the transfers, so nothing is allocated twice
+    pub revenue_allocated: bool,
+
+    /// Payout remainders kept back by rounding on winning claims; goes to
+    /// the season pool at finalize_revenue
+    pub payout_dust: u64,
+
+    /// ClaimShards opened and not yet merged back; while any are open,
+    /// winnings are paid only through them
+    pub claim_shards: u8,
+
+    /// Cross-round accumulators with a leg in this round that haven't been
+    /// settled yet; revenue can't be finalized while any are open
+    pub open_accumulators: u16,
+
+    /// sportsbook_core::results_bitmap of match_results, set at settlement
+    /// (0 before, and on rounds settled before it existed). Took the last
+    /// of the reserved bytes; further fields need resize_account.
+    pub result_bits: u32,
+
+    /// Payouts and refunds counted by reserve_payouts that claim_reserved
+    /// hasn't paid yet; still owed, though already in total_claimed and
+    /// total_paid_out. Grew the account from 908 bytes: older rounds go
+    /// through resize_account.
+    pub total_reserved_unpaid: u64,
+
+    /// Sum of the payout caps of the open ClaimShards, so total_paid_out
+    /// plus this stays within MAX_ROUND_PAYOUTS. Grew the account from
+    /// 916 bytes: older rounds go through resize_account.
+    pub shard_payout_cap: u64,
 }
 
 impl RoundAccounting {
@@ -80,7 +178,7 @@ impl RoundAccounting {
         32 + // betting_pool
         (10 * 32) + // match_pools (10 matches * 32 bytes each)
         (10 * 25) + // locked_odds (10 matches * 25 bytes each)
-        (10 * 1) +  // match_results (10 outcomes)
+        10 + // match_results (10 outcomes)
         8 +  // total_bet_volume
         8 +  // total_winning_pool
         8 +  // total_losing_pool
@@ -98,5 +196,93 @@ impl RoundAccounting {
         8 +  // round_start_time
         8 +  // round_end_time
         1 +  // settled
-        1;   // bump
+        1 +  // bump
+        8 +  // total_volume_usd
+        PriceSnapshot::LEN + // settlement_price
+        4 +  // bets_placed
+        4 +  // bets_resolved
+        32 + // locked_odds_hash
+        8 +  // claims_paused_until
+        8 +  // claim_window_extension
+        32 + // locked_odds_root
+        1 +  // branded
+        1 +  // disputed
+        1 +  // league_round
+        2 +  // kickoff_locked
+        1 +  // isolated
+        1 +  // vault_bump
+        8 +  // total_refunded
+        1 +  // protocol_funded_bounty
+        8 +  // protocol_bounties_paid
+        1 +  // revenue_allocated
+        8 +  // payout_dust
+        1 +  // claim_shards
+        2 +  // open_accumulators
+        4 +  // result_bits
+        8 +  // total_reserved_unpaid
+        8;   // shard_payout_cap
+
+    /// Winnings still owed to bettors for this round
+    ///
+    /// Unclaimed winnings are owed by settled rounds whose revenue has not
+    /// been finalized; once finalized, they become protocol profit. Reserved
+    /// payouts are owed until claim_reserved pays them, finalized or not,
+    /// since finalization already counted them as paid out.
+    pub fn outstanding_liability(&self) -> u64 {
+        let unclaimed = if !self.settled || self.revenue_distributed {
+            0
+        } else {
+            self.total_reserved_for_winners.saturating_sub(self.total_claimed)
+        };
+        unclaimed.saturating_add(self.total_reserved_unpaid)
+    }
+
+    /// Hash the current locked-odds table, store it and emit OddsLocked
+    pub fn commit_locked_odds(&mut self) -> Result<()> {
+        let odds_hash = hash_locked_odds(&self.betting_pool, self.round_id, &self.locked_odds);
+        self.locked_odds_hash = odds_hash;
+        self.locked_odds_root = odds_root(&locked_odds_table(&self.locked_odds));
+
+        emit!(OddsLocked {
+            betting_pool: self.betting_pool,
+            round_id: self.round_id,
+            locked_odds: self.locked_odds,
+            odds_hash,
+            odds_root: self.locked_odds_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Match `match_index` was closed at kickoff
+    pub fn is_kickoff_locked(&self, match_index: usize) -> bool {
+        self.kickoff_locked & (1 << match_index) != 0
+    }
+
+    /// End of the bettor-only claim window (extended by any claims pause)
+    pub fn claim_deadline(&self) -> i64 {
+        self.round_end_time + CLAIM_WINDOW_SECONDS + self.claim_window_extension
+    }
+
+    /// Whether a guardian pause is in effect (pauses lapse on their own)
+    pub fn claims_paused(&self, current_time: i64) -> bool {
+        current_time < self.claims_paused_until
+    }
+
+    /// Whether claims, loss settlement and finalization are held back,
+    /// by a guardian pause or an open randomness dispute
+    pub fn claims_blocked(&self, current_time: i64) -> bool {
+        self.disputed || self.claims_paused(current_time)
+    }
+
+    /// Count one more bet as claimed or settled as lost
+    pub fn record_bet_resolved(&mut self) {
+        self.bets_resolved = self.bets_resolved.saturating_add(1);
+    }
+
+    /// Every bet placed in the round has been claimed or settled as lost
+    pub fn all_bets_resolved(&self) -> bool {
+        self.bets_resolved >= self.bets_placed
+    }
 }
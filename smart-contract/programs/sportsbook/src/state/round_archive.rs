@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use super::{MatchOutcome, RoundAccounting};
+
+/// Immutable summary left behind when a round is archived
+///
+/// Written once by `archive_round`, after which the round's
+/// RoundAccounting account is closed and its rent returned.
+#[account]
+pub struct RoundArchive {
+    /// Betting pool this round belonged to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Final match results
+    pub match_results: [MatchOutcome; 10],
+
+    /// Total bet volume (after fees)
+    pub total_bet_volume: u64,
+
+    /// User deposits (after fees)
+    pub total_user_deposits: u64,
+
+    /// Total paid out to winners and bounty hunters
+    pub total_paid_out: u64,
+
+    /// Protocol fee collected
+    pub protocol_fee_collected: u64,
+
+    /// Protocol revenue at finalization
+    pub protocol_revenue_share: u64,
+
+    /// Season pool share at finalization
+    pub season_revenue_share: u64,
+
+    /// Protocol seed amount
+    pub protocol_seed_amount: u64,
+
+    /// Number of parlays placed
+    pub parlay_count: u64,
+
+    /// Round start timestamp
+    pub round_start_time: i64,
+
+    /// Round settlement timestamp
+    pub round_end_time: i64,
+
+    /// When the round was archived
+    pub archived_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundArchive {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        10 + // match_results (10 outcomes)
+        8 +  // total_bet_volume
+        8 +  // total_user_deposits
+        8 +  // total_paid_out
+        8 +  // protocol_fee_collected
+        8 +  // protocol_revenue_share
+        8 +  // season_revenue_share
+        8 +  // protocol_seed_amount
+        8 +  // parlay_count
+        8 +  // round_start_time
+        8 +  // round_end_time
+        8 +  // archived_at
+        1;   // bump
+
+    /// Copy the final figures out of a round about to be closed
+    pub fn record(&mut self, round: &RoundAccounting, archived_at: i64, bump: u8) {
+        self.betting_pool = round.betting_pool;
+        self.round_id = round.round_id;
+        self.match_results = round.match_results;
+        self.total_bet_volume = round.total_bet_volume;
+        self.total_user_deposits = round.total_user_deposits;
+        self.total_paid_out = round.total_paid_out;
+        self.protocol_fee_collected = round.protocol_fee_collected;
+        self.protocol_revenue_share = round.protocol_revenue_share;
+        self.season_revenue_share = round.season_revenue_share;
+        self.protocol_seed_amount = round.protocol_seed_amount;
+        self.parlay_count = round.parlay_count;
+        self.round_start_time = round.round_start_time;
+        self.round_end_time = round.round_end_time;
+        self.archived_at = archived_at;
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use super::RoundAccounting;
+
+/// Domain separator for round result hashes
+pub const ROUND_RESULT_HASH_DOMAIN: &[u8] = b"sportsbook:round_result:v1";
+
+/// Permanent record of a finalized round's outcome
+///
+/// Written once after `finalize_round_revenue` and never closed, so it
+/// outlives the round's RoundAccounting (and RoundArchive) accounts.
+#[account]
+pub struct RoundAttestation {
+    /// Betting pool this round belonged to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// hash_round_result() over results and locked odds
+    pub results_hash: [u8; 32],
+
+    /// Total bet volume (after fees)
+    pub total_bet_volume: u64,
+
+    /// Total bet volume in USD (0 for stablecoin pools)
+    pub total_volume_usd: u64,
+
+    /// Total paid out to winners and bounty hunters
+    pub total_paid_out: u64,
+
+    /// Operating PnL: user deposits − payouts (negative = paid from seed)
+    pub pnl: i64,
+
+    /// Protocol fee collected
+    pub protocol_fee_collected: u64,
+
+    /// Season pool share at finalization
+    pub season_revenue_share: u64,
+
+    /// Round settlement timestamp
+    pub round_end_time: i64,
+
+    /// When the attestation was written
+    pub attested_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        32 + // results_hash
+        8 +  // total_bet_volume
+        8 +  // total_volume_usd
+        8 +  // total_paid_out
+        8 +  // pnl
+        8 +  // protocol_fee_collected
+        8 +  // season_revenue_share
+        8 +  // round_end_time
+        8 +  // attested_at
+        1;   // bump
+
+    /// Copy the final figures out of a finalized round
+    pub fn record(&mut self, round: &RoundAccounting, attested_at: i64, bump: u8) {
+        self.betting_pool = round.betting_pool;
+        self.round_id = round.round_id;
+        self.results_hash = hash_round_result(round);
+        self.total_bet_volume = round.total_bet_volume;
+        self.total_volume_usd = round.total_volume_usd;
+        self.total_paid_out = round.total_paid_out;
+        self.pnl = (round.total_user_deposits as i128 - round.total_paid_out as i128)
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        self.protocol_fee_collected = round.protocol_fee_collected;
+        self.season_revenue_share = round.season_revenue_share;
+        self.round_end_time = round.round_end_time;
+        self.attested_at = attested_at;
+        self.bump = bump;
+    }
+}
+
+/// SHA-256 commitment to a round's outcome
+///
+/// sha256(domain || betting_pool || round_id LE || match results (1 byte each) || locked_odds_hash)
+pub fn hash_round_result(round: &RoundAccounting) -> [u8; 32] {
+    let results = round.match_results.map(|result| result as u8);
+
+    hashv(&[
+        ROUND_RESULT_HASH_DOMAIN,
+        round.betting_pool.as_ref(),
+        &round.round_id.to_le_bytes(),
+        &results,
+        &round.locked_odds_hash,
+    ])
+    .to_bytes()
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+/// Kickoff time of each match in a round
+///
+/// Once a match's kickoff passes, anyone can call lock_odds for it, which
+/// closes its market in RoundAccounting::kickoff_locked.
+#[account]
+pub struct RoundKickoffs {
+    /// Betting pool this round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Kickoff unix timestamp per match (0 = not scheduled)
+    pub kickoff_times: [i64; 10],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundKickoffs {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        8 * 10 + // kickoff_times
+        1;   // bump
+
+    /// Match has a kickoff time and it has passed
+    pub fn has_kicked_off(&self, match_index: usize, current_time: i64) -> bool {
+        let kickoff = self.kickoff_times[match_index];
+        kickoff > 0 && current_time >= kickoff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_kicked_off() {
+        let mut kickoffs = RoundKickoffs {
+            betting_pool: Pubkey::default(),
+            round_id: 1,
+            kickoff_times: [0; 10],
+            bump: 0,
+        };
+        kickoffs.kickoff_times[3] = 1_000;
+
+        assert!(!kickoffs.has_kicked_off(3, 999));
+        assert!(kickoffs.has_kicked_off(3, 1_000));
+        // Unscheduled matches never kick off
+        assert!(!kickoffs.has_kicked_off(0, i64::MAX));
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// Longest metadata URI a round can carry
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Off-chain display metadata of a round
+///
+/// `uri` points at a JSON document with team names, logos and league
+/// info; `content_hash` is the SHA-256 of that document's exact bytes, so
+/// clients can reject anything served at the URI that doesn't match.
+#[account]
+pub struct RoundMetadata {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Metadata URI (UTF-8, zero-padded)
+    pub uri: [u8; MAX_METADATA_URI_LEN],
+
+    /// SHA-256 of the metadata document
+    pub content_hash: [u8; 32],
+
+    /// Last time the metadata was set
+    pub updated_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundMetadata {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        MAX_METADATA_URI_LEN + // uri
+        32 + // content_hash
+        8 +  // updated_at
+        1;   // bump
+
+    /// URI without its zero padding
+    pub fn uri_bytes(&self) -> &[u8] {
+        let len = self.uri.iter().position(|b| *b == 0).unwrap_or(MAX_METADATA_URI_LEN);
+        &self.uri[..len]
+    }
+}
+
+/// Zero-padded copy of a URI, or None if it's empty, too long or has NULs
+pub fn pack_metadata_uri(uri: &str) -> Option<[u8; MAX_METADATA_URI_LEN]> {
+    let bytes = uri.as_bytes();
+    if bytes.is_empty() || bytes.len() > MAX_METADATA_URI_LEN || bytes.contains(&0) {
+        return None;
+    }
+    let mut packed = [0u8; MAX_METADATA_URI_LEN];
+    packed[..bytes.len()].copy_from_slice(bytes);
+    Some(packed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_metadata_uri() {
+        let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/round-42.json";
+        let metadata = RoundMetadata {
+            betting_pool: Pubkey::default(),
+            round_id: 42,
+            uri: pack_metadata_uri(uri).unwrap(),
+            content_hash: [0; 32],
+            updated_at: 0,
+            bump: 0,
+        };
+        assert_eq!(metadata.uri_bytes(), uri.as_bytes());
+
+        assert!(pack_metadata_uri("").is_none());
+        assert!(pack_metadata_uri(&"a".repeat(MAX_METADATA_URI_LEN + 1)).is_none());
+        assert!(pack_metadata_uri("https://x\0y").is_none());
+        assert!(pack_metadata_uri(&"a".repeat(MAX_METADATA_URI_LEN)).is_some());
+    }
+}
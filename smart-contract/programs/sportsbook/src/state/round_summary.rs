@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use super::{MatchOutcome, RoundAccounting};
+
+/// Bits per match in `RoundSummary::results` (0 = pending .. 4 = void)
+pub const SUMMARY_RESULT_BITS: u32 = 3;
+
+/// Compact, read-only view of a round for clients that poll
+///
+/// Written at settlement and refreshed by the permissionless
+/// sync_round_summary, so a client can watch one small account per round
+/// instead of deserializing RoundAccounting with its pools and odds. The
+/// program never reads it back; RoundAccounting stays the source of truth.
+#[account]
+pub struct RoundSummary {
+    /// Betting pool the round belongs to
+    pub betting_pool: Pubkey,
+
+    /// Round ID
+    pub round_id: u64,
+
+    /// Results are in and bets can be claimed
+    pub settled: bool,
+
+    /// Revenue has been finalized
+    pub revenue_distributed: bool,
+
+    /// Match results, SUMMARY_RESULT_BITS per match, match 0 in the low bits
+    pub results: u32,
+
+    /// Settlement timestamp
+    pub round_end_time: i64,
+
+    /// End of the bettor-only claim window
+    pub claim_deadline: i64,
+
+    /// Stakes after fees
+    pub total_bet_volume: u64,
+
+    /// Owed to winners as computed at settlement
+    pub total_reserved_for_winners: u64,
+
+    /// Paid to winners and bounty hunters so far
+    pub total_paid_out: u64,
+
+    /// Void refunds paid so far
+    pub total_refunded: u64,
+
+    /// Bets placed in the round
+    pub bets_placed: u32,
+
+    /// Bets claimed or settled as lost
+    pub bets_resolved: u32,
+
+    /// When the summary was last written
+    pub updated_at: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RoundSummary {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // round_id
+        1 +  // settled
+        1 +  // revenue_distributed
+        4 +  // results
+        8 +  // round_end_time
+        8 +  // claim_deadline
+        8 +  // total_bet_volume
+        8 +  // total_reserved_for_winners
+        8 +  // total_paid_out
+        8 +  // total_refunded
+        4 +  // bets_placed
+        4 +  // bets_resolved
+        8 +  // updated_at
+        1;   // bump
+
+    /// Copy the current figures out of the round
+    pub fn record(&mut self, round: &RoundAccounting, updated_at: i64) {
+        self.betting_pool = round.betting_pool;
+        self.round_id = round.round_id;
+        self.settled = round.settled;
+        self.revenue_distributed = round.revenue_distributed;
+        self.results = pack_summary_results(&round.match_results);
+        self.round_end_time = round.round_end_time;
+        self.claim_deadline = if round.settled { round.claim_deadline() } else { 0 };
+        self.total_bet_volume = round.total_bet_volume;
+        self.total_reserved_for_winners = round.total_reserved_for_winners;
+        self.total_paid_out = round.total_paid_out;
+        self.total_refunded = round.total_refunded;
+        self.bets_placed = round.bets_placed;
+        self.bets_resolved = round.bets_resolved;
+        self.updated_at = updated_at;
+    }
+
+    /// Result of match `match_index` (0 = pending, 4 = void)
+    pub fn result(&self, match_index: usize) -> u8 {
+        let shift = match_index as u32 * SUMMARY_RESULT_BITS;
+        ((self.results >> shift) & ((1 << SUMMARY_RESULT_BITS) - 1)) as u8
+    }
+}
+
+/// Pack a round's results into RoundSummary::results
+pub fn pack_summary_results(match_results: &[MatchOutcome; 10]) -> u32 {
+    match_results
+        .iter()
+        .enumerate()
+        .fold(0, |packed, (i, result)| {
+            packed | ((*result as u32) << (i as u32 * SUMMARY_RESULT_BITS))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_results_round_trip() {
+        let mut match_results = [MatchOutcome::Pending; 10];
+        match_results[0] = MatchOutcome::HomeWin;
+        match_results[3] = MatchOutcome::Draw;
+        match_results[9] = MatchOutcome::Void;
+
+        let packed = pack_summary_results(&match_results);
+        assert!(packed < 1 << 30);
+
+        let summary = RoundSummary {
+            betting_pool: Pubkey::default(),
+            round_id: 0,
+            settled: true,
+            revenue_distributed: false,
+            results: packed,
+            round_end_time: 0,
+            claim_deadline: 0,
+            total_bet_volume: 0,
+            total_reserved_for_winners: 0,
+            total_paid_out: 0,
+            total_refunded: 0,
+            bets_placed: 0,
+            bets_resolved: 0,
+            updated_at: 0,
+            bump: 0,
+        };
+        for (i, result) in match_results.iter().enumerate() {
+            assert_eq!(summary.result(i), *result as u8);
+        }
+    }
+}
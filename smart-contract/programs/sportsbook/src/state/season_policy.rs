@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+/// What close_season does with the season reward pool nobody claimed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeasonRollover {
+    /// Carry it into the next season's reward pool
+    RollOver,
+    /// Burn it
+    Burn,
+    /// Send it to the protocol treasury
+    Treasury,
+}
+
+/// A pool's rules for closing out a season
+///
+/// end_season records when the season ended; winners then have
+/// `claim_period_seconds` to claim their share before close_season may
+/// settle whatever is left of `season_reward_pool` by `rollover` and start
+/// the next season.
+#[account]
+pub struct SeasonPolicy {
+    /// Betting pool the policy applies to
+    pub betting_pool: Pubkey,
+
+    /// Fate of the unclaimed reward pool at close_season
+    pub rollover: SeasonRollover,
+
+    /// How long after a season ends its rewards stay claimable
+    pub claim_period_seconds: i64,
+
+    /// Season whose end time is recorded in `season_ended_at`
+    pub ended_season_id: u64,
+
+    /// When that season ended (0 = no end recorded yet)
+    pub season_ended_at: i64,
+
+    /// Unclaimed rewards carried into a following season, over all seasons
+    pub total_rolled_over: u64,
+
+    /// Unclaimed rewards burned, over all seasons
+    pub total_burned: u64,
+
+    /// Unclaimed rewards returned to the treasury, over all seasons
+    pub total_returned: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SeasonPolicy {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        1 +  // rollover
+        8 +  // claim_period_seconds
+        8 +  // ended_season_id
+        8 +  // season_ended_at
+        8 +  // total_rolled_over
+        8 +  // total_burned
+        8 +  // total_returned
+        1;   // bump
+
+    pub fn record_season_end(&mut self, season_id: u64, now: i64) {
+        self.ended_season_id = season_id;
+        self.season_ended_at = now;
+    }
+
+    /// When `season_id`'s rewards stop being claimable; None if its end
+    /// was never recorded
+    pub fn claims_close_at(&self, season_id: u64) -> Option<i64> {
+        if self.season_ended_at == 0 || self.ended_season_id != season_id {
+            return None;
+        }
+        self.season_ended_at.checked_add(self.claim_period_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claims_close_after_recorded_end() {
+        let mut policy = SeasonPolicy {
+            betting_pool: Pubkey::default(),
+            rollover: SeasonRollover::RollOver,
+            claim_period_seconds: 604_800,
+            ended_season_id: 0,
+            season_ended_at: 0,
+            total_rolled_over: 0,
+            total_burned: 0,
+            total_returned: 0,
+            bump: 0,
+        };
+        assert_eq!(policy.claims_close_at(0), None);
+
+        policy.record_season_end(3, 1_700_000_000);
+        assert_eq!(policy.claims_close_at(3), Some(1_700_604_800));
+        assert_eq!(policy.claims_close_at(4), None);
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+/// Most pools (program deployments) one registry aggregates
+pub const MAX_SEASON_POOLS: usize = 8;
+
+/// One season's points table shared by several pools
+///
+/// A pool is a deployment of this program with its own mint. Each pool
+/// reports bets to the registry by CPI, signed by its season reporter PDA
+/// ([SEASON_REPORTER_SEED] under the pool's program), so only registered
+/// deployments can add points. Points are stake value in USD (6 decimals),
+/// so volume in different currencies ranks on one leaderboard.
+#[account]
+pub struct SeasonRegistry {
+    /// Wallet that manages the registry
+    pub authority: Pubkey,
+
+    /// Season this registry tallies
+    pub season_id: u64,
+
+    /// Program IDs of the pools reporting into this registry
+    pub pool_programs: [Pubkey; MAX_SEASON_POOLS],
+
+    /// Pools registered so far
+    pub num_pools: u8,
+
+    /// Bets from rounds starting before this time don't count
+    pub started_at: i64,
+
+    /// No more points once set
+    pub ended: bool,
+
+    /// Points across all participants
+    pub total_points: u64,
+
+    /// Wallets with at least one scored bet
+    pub participants: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SeasonRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 +  // season_id
+        32 * MAX_SEASON_POOLS + // pool_programs
+        1 +  // num_pools
+        8 +  // started_at
+        1 +  // ended
+        8 +  // total_points
+        8 +  // participants
+        1;   // bump
+
+    pub fn has_pool(&self, program_id: &Pubkey) -> bool {
+        self.pool_programs
+            .iter()
+            .take(self.num_pools as usize)
+            .any(|registered| registered == program_id)
+    }
+}
+
+/// A wallet's points in one SeasonRegistry, summed over every pool
+#[account]
+pub struct SeasonPoints {
+    /// Registry these points belong to
+    pub registry: Pubkey,
+
+    /// Wallet that placed the bets
+    pub user: Pubkey,
+
+    /// Stake value in USD (6 decimals) across all pools
+    pub points: u64,
+
+    /// Bets scored
+    pub bets: u32,
+
+    /// Time of the last scored bet's report
+    pub last_updated: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SeasonPoints {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // registry
+        32 + // user
+        8 +  // points
+        4 +  // bets
+        8 +  // last_updated
+        1;   // bump
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use core::cmp::Ordering;
+use sportsbook_core::MAX_LEAGUE_TEAMS;
+use super::{Fixture, MatchOutcome};
+
+/// Points for a win / draw
+pub const POINTS_FOR_WIN: u16 = 3;
+pub const POINTS_FOR_DRAW: u16 = 1;
+
+/// One team's row in the standings
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TeamStanding {
+    pub played: u8,
+    pub wins: u8,
+    pub draws: u8,
+    pub losses: u8,
+    pub points: u16,
+    pub goals_for: u16,
+    pub goals_against: u16,
+}
+
+impl TeamStanding {
+    pub const LEN: usize = 4 + 2 + 2 + 2;
+
+    pub fn goal_difference(&self) -> i32 {
+        self.goals_for as i32 - self.goals_against as i32
+    }
+
+    fn record(&mut self, scored: u8, conceded: u8, points: u16) {
+        self.played = self.played.saturating_add(1);
+        match points {
+            POINTS_FOR_WIN => self.wins = self.wins.saturating_add(1),
+            POINTS_FOR_DRAW => self.draws = self.draws.saturating_add(1),
+            _ => self.losses = self.losses.saturating_add(1),
+        }
+        self.points = self.points.saturating_add(points);
+        self.goals_for = self.goals_for.saturating_add(scored as u16);
+        self.goals_against = self.goals_against.saturating_add(conceded as u16);
+    }
+
+    /// League order: points, then goal difference, then goals scored
+    fn ranks_above(&self, other: &TeamStanding) -> Ordering {
+        self.points
+            .cmp(&other.points)
+            .then(self.goal_difference().cmp(&other.goal_difference()))
+            .then(self.goals_for.cmp(&other.goals_for))
+    }
+}
+
+/// Standings of a phantom league, built from settled rounds
+///
+/// Matchdays are tabulated strictly in order, so outright "league winner"
+/// markets can settle from `winner()` without trusting any off-chain table.
+#[account]
+pub struct SeasonTable {
+    /// League these standings belong to
+    pub league: Pubkey,
+
+    /// Rows indexed by team number
+    pub standings: [TeamStanding; MAX_LEAGUE_TEAMS as usize],
+
+    /// Teams in the league
+    pub num_teams: u8,
+
+    /// Matchdays applied so far
+    pub matchdays_tabulated: u16,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl SeasonTable {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // league
+        TeamStanding::LEN * MAX_LEAGUE_TEAMS as usize + // standings
+        1 +  // num_teams
+        2 +  // matchdays_tabulated
+        1;   // bump
+
+    /// Apply one fixture's result; goals are only counted when `with_scores`
+    pub fn apply_result(&mut self, fixture: &Fixture, outcome: MatchOutcome, with_scores: bool) {
+        let (home_points, away_points) = match outcome {
+            MatchOutcome::HomeWin => (POINTS_FOR_WIN, 0),
+            MatchOutcome::AwayWin => (0, POINTS_FOR_WIN),
+            MatchOutcome::Draw => (POINTS_FOR_DRAW, POINTS_FOR_DRAW),
+            MatchOutcome::Pending | MatchOutcome::Void => return,
+        };
+        let (home_goals, away_goals) = if with_scores {
+            (fixture.home_goals, fixture.away_goals)
+        } else {
+            (0, 0)
+        };
+
+        self.standings[fixture.home_team as usize].record(home_goals, away_goals, home_points);
+        self.standings[fixture.away_team as usize].record(away_goals, home_goals, away_points);
+    }
+
+    /// Team numbers from top of the table down (ties keep team order)
+    pub fn ranking(&self) -> Vec<u8> {
+        let mut teams: Vec<u8> = (0..self.num_teams).collect();
+        teams.sort_by(|a, b| {
+            self.standings[*b as usize].ranks_above(&self.standings[*a as usize])
+        });
+        teams
+    }
+
+    /// League winner once every matchday is in; None while the season runs
+    /// or if the top two are level on every tie-breaker
+    pub fn winner(&self, total_matchdays: u16) -> Option<u8> {
+        if total_matchdays == 0 || self.matchdays_tabulated < total_matchdays {
+            return None;
+        }
+
+        let ranking = self.ranking();
+        let first = ranking.first()?;
+        match ranking.get(1) {
+            Some(second)
+                if self.standings[*first as usize]
+                    .ranks_above(&self.standings[*second as usize])
+                    == Ordering::Equal =>
+            {
+                None
+            }
+            _ => Some(*first),
+        }
+    }
+}
+
+/// Check a recorded score agrees with the settled outcome
+pub fn score_matches_outcome(home_goals: u8, away_goals: u8, outcome: MatchOutcome) -> bool {
+    match outcome {
+        MatchOutcome::HomeWin => home_goals > away_goals,
+        MatchOutcome::AwayWin => home_goals < away_goals,
+        MatchOutcome::Draw => home_goals == away_goals,
+        MatchOutcome::Pending | MatchOutcome::Void => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_table(num_teams: u8) -> SeasonTable {
+        SeasonTable {
+            league: Pubkey::default(),
+            standings: [TeamStanding::default(); MAX_LEAGUE_TEAMS as usize],
+            num_teams,
+            matchdays_tabulated: 0,
+            bump: 0,
+        }
+    }
+
+    fn fixture(home_team: u8, away_team: u8, home_goals: u8, away_goals: u8) -> Fixture {
+        Fixture { home_team, away_team, home_goals, away_goals, ..Default::default() }
+    }
+
+    #[test]
+    fn test_points_and_goal_difference() {
+        let mut table = empty_table(4);
+        table.apply_result(&fixture(0, 1, 3, 0), MatchOutcome::HomeWin, true);
+        table.apply_result(&fixture(2, 3, 1, 1), MatchOutcome::Draw, true);
+
+        assert_eq!(table.standings[0].points, 3);
+        assert_eq!(table.standings[0].goal_difference(), 3);
+        assert_eq!(table.standings[1].losses, 1);
+        assert_eq!(table.standings[2].points, 1);
+        assert_eq!(table.standings[3].draws, 1);
+        assert_eq!(table.ranking(), vec![0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_goal_difference_breaks_ties() {
+        let mut table = empty_table(4);
+        table.apply_result(&fixture(0, 1, 1, 0), MatchOutcome::HomeWin, true);
+        table.apply_result(&fixture(3, 2, 4, 0), MatchOutcome::HomeWin, true);
+        assert_eq!(table.ranking()[0], 3);
+
+        // Without scores only points count, and team order decides
+        let mut table = empty_table(4);
+        table.apply_result(&fixture(0, 1, 1, 0), MatchOutcome::HomeWin, false);
+        table.apply_result(&fixture(3, 2, 4, 0), MatchOutcome::HomeWin, false);
+        assert_eq!(table.ranking()[0], 0);
+        table.matchdays_tabulated = 1;
+        assert_eq!(table.winner(1), None);
+    }
+
+    #[test]
+    fn test_winner_only_when_complete() {
+        let mut table = empty_table(4);
+        table.apply_result(&fixture(0, 1, 2, 0), MatchOutcome::HomeWin, true);
+        table.matchdays_tabulated = 1;
+        assert_eq!(table.winner(6), None);
+
+        table.matchdays_tabulated = 6;
+        assert_eq!(table.winner(6), Some(0));
+    }
+
+    #[test]
+    fn test_score_must_match_outcome() {
+        assert!(score_matches_outcome(2, 1, MatchOutcome::HomeWin));
+        assert!(!score_matches_outcome(1, 1, MatchOutcome::HomeWin));
+        assert!(score_matches_outcome(0, 0, MatchOutcome::Draw));
+        assert!(!score_matches_outcome(0, 0, MatchOutcome::Pending));
+    }
+}
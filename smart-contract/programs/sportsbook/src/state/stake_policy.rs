@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Minimum stake and rounding unit for a pool's bets
+///
+/// Fees and fixed payouts are floored to whole `rounding_unit`s. The fee
+/// remainder stays with the stake; the payout remainder is the round's
+/// dust, credited to the season pool when revenue is finalized.
+#[account]
+pub struct StakePolicy {
+    /// Betting pool the policy applies to
+    pub betting_pool: Pubkey,
+
+    /// Smallest stake accepted, in base units (0 = none)
+    pub min_stake: u64,
+
+    /// Fees and payouts are multiples of this many base units (0/1 = exact)
+    pub rounding_unit: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl StakePolicy {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // betting_pool
+        8 +  // min_stake
+        8 +  // rounding_unit
+        1;   // bump
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// Per-user bet nonce for the `[b"bet", pool, bettor, user_nonce]` namespace
+#[account]
+pub struct UserBetCounter {
+    /// Bettor this counter belongs to
+    pub bettor: Pubkey,
+
+    /// Betting pool this counter belongs to
+    pub betting_pool: Pubkey,
+
+    /// Nonce the bettor's next namespaced bet will use
+    /// (bets 0..next_nonce exist)
+    pub next_nonce: u64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl UserBetCounter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        32 + // betting_pool
+        8 +  // next_nonce
+        1;   // bump
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+/// A bettor's compounding preference and betting credit
+///
+/// `prefer_compound` is advisory: a claim_winnings that passes this
+/// account and the credit vault pays the bettor's share of a winning bet
+/// into the vault instead of their wallet and adds it to `credit`. Claims
+/// that leave it out (a bounty claimer's may) and quick_claim,
+/// claim_reserved, claim_sharded and bounty_sweep pay the wallet.
+/// place_credit_bet stakes from the credit and withdraw_credit takes it
+/// back out. The vault holds exactly `credit`.
+#[account]
+pub struct UserStats {
+    /// Bettor these stats belong to
+    pub bettor: Pubkey,
+
+    /// Betting pool these stats belong to
+    pub betting_pool: Pubkey,
+
+    /// Hold winnings as credit when a claim passes this account
+    pub prefer_compound: bool,
+
+    /// Betting credit held in the credit vault
+    pub credit: u64,
+
+    /// Winnings compounded into credit over the account's lifetime
+    pub total_compounded: u64,
+
+    /// Credit staked on bets over the account's lifetime
+    pub total_credit_staked: u64,
+
+    /// Bump seed of the credit vault
+    pub vault_bump: u8,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl UserStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        32 + // betting_pool
+        1 +  // prefer_compound
+        8 +  // credit
+        8 +  // total_compounded
+        8 +  // total_credit_staked
+        1 +  // vault_bump
+        1;   // bump
+
+    /// Record winnings paid into the credit vault
+    pub fn compound(&mut self, amount: u64) -> Option<()> {
+        self.credit = self.credit.checked_add(amount)?;
+        self.total_compounded = self.total_compounded.checked_add(amount)?;
+        Some(())
+    }
+
+    /// Take `amount` out of credit for a stake; None if there isn't enough
+    pub fn stake_credit(&mut self, amount: u64) -> Option<()> {
+        self.credit = self.credit.checked_sub(amount)?;
+        self.total_credit_staked = self.total_credit_staked.checked_add(amount)?;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credit_compounds_and_stakes() {
+        let mut stats = UserStats {
+            bettor: Pubkey::default(),
+            betting_pool: Pubkey::default(),
+            prefer_compound: true,
+            credit: 0,
+            total_compounded: 0,
+            total_credit_staked: 0,
+            vault_bump: 0,
+            bump: 0,
+        };
+
+        stats.compound(190).unwrap();
+        stats.compound(50).unwrap();
+        stats.stake_credit(200).unwrap();
+        assert_eq!(stats.credit, 40);
+        assert_eq!((stats.total_compounded, stats.total_credit_staked), (240, 200));
+
+        // Can't stake more than is held, and a failed stake changes nothing
+        assert_eq!(stats.stake_credit(41), None);
+        assert_eq!(stats.credit, 40);
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use super::metrics::SECONDS_PER_DAY;
+
+/// Days of stake counted towards a bettor's fee tier
+pub const ROLLING_VOLUME_DAYS: usize = 30;
+
+/// Stake placed by one bettor on one UTC day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct UserDayVolume {
+    /// Days since the unix epoch (0 = slot unused)
+    pub day: u32,
+
+    /// Stakes placed that day (before fee)
+    pub volume: u64,
+}
+
+impl UserDayVolume {
+    pub const LEN: usize = 4 + 8;
+}
+
+/// A bettor's stake over the last `ROLLING_VOLUME_DAYS` days, used to pick
+/// their protocol fee tier
+///
+/// A ring of day slots indexed by day number, like `DailyVolume`; slots
+/// older than the window are ignored when summing and reset when reused.
+#[account]
+pub struct UserVolume {
+    /// Bettor this volume belongs to
+    pub bettor: Pubkey,
+
+    /// Betting pool this volume belongs to
+    pub betting_pool: Pubkey,
+
+    /// Per-day slots, indexed by day % ROLLING_VOLUME_DAYS
+    pub days: [UserDayVolume; ROLLING_VOLUME_DAYS],
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl UserVolume {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        32 + // betting_pool
+        UserDayVolume::LEN * ROLLING_VOLUME_DAYS + // days
+        1;   // bump
+
+    fn day_of(timestamp: i64) -> u32 {
+        (timestamp.max(0) / SECONDS_PER_DAY) as u32
+    }
+
+    /// Stake placed in the window ending on the day containing `timestamp`
+    pub fn rolling_volume(&self, timestamp: i64) -> u64 {
+        let today = Self::day_of(timestamp);
+        let window_start = today.saturating_sub(ROLLING_VOLUME_DAYS as u32 - 1);
+        self.days
+            .iter()
+            .filter(|slot| slot.day != 0 && (window_start..=today).contains(&slot.day))
+            .fold(0u64, |total, slot| total.saturating_add(slot.volume))
+    }
+
+    /// Add a stake to the day containing `timestamp`
+    pub fn record_bet(&mut self, timestamp: i64, amount: u64) {
+        let day = Self::day_of(timestamp);
+        let slot = &mut self.days[day as usize % ROLLING_VOLUME_DAYS];
+        if slot.day != day {
+            *slot = UserDayVolume { day, volume: 0 };
+        }
+        slot.volume = slot.volume.saturating_add(amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = SECONDS_PER_DAY;
+
+    fn empty() -> UserVolume {
+        UserVolume {
+            bettor: Pubkey::default(),
+            betting_pool: Pubkey::default(),
+            days: [UserDayVolume::default(); ROLLING_VOLUME_DAYS],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_rolling_volume_window() {
+        let start = 20_000 * DAY;
+        let mut volume = empty();
+
+        volume.record_bet(start, 100);
+        volume.record_bet(start + 3_600, 50);
+        volume.record_bet(start + 10 * DAY, 200);
+        assert_eq!(volume.rolling_volume(start + 10 * DAY), 350);
+
+        // Day 0 of the window is still counted on day 29, gone on day 30
+        assert_eq!(volume.rolling_volume(start + 29 * DAY), 350);
+        assert_eq!(volume.rolling_volume(start + 30 * DAY), 200);
+        assert_eq!(volume.rolling_volume(start + 40 * DAY), 0);
+    }
+
+    #[test]
+    fn test_reused_slot_is_reset() {
+        let start = 20_000 * DAY;
+        let mut volume = empty();
+
+        volume.record_bet(start, 100);
+        volume.record_bet(start + 30 * DAY, 10);
+        assert_eq!(volume.rolling_volume(start + 30 * DAY), 10);
+    }
+}
@@ -0,0 +1,147 @@
+//! Token-2022 Transfer Hook Support for Pool Payouts
+//!
+//! Claims pay out with a plain SPL Token transfer. When the pool mint is a
+//! Token-2022 mint with the TransferHook extension, the token program calls
+//! the hook program on every transfer and needs the hook's extra accounts on
+//! the instruction. Clients pass those in `remaining_accounts`; the
+//! spl-token-2022 on-chain helper picks out the ones the hook's validation
+//! account (["extra-account-metas", mint] under the hook program) asks for.
+//!
+//! Missing hook accounts are caught before the CPI and reported as
+//! TransferHookAccountsMissing. A hook that rejects the transfer aborts the
+//! transaction from inside the CPI, so the hook program is logged right
+//! before the call to make the failure traceable.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_hook, StateWithExtensions},
+    onchain::invoke_transfer_checked,
+    state::Mint,
+};
+use crate::errors::SportsbookError;
+
+/// Seed of a hook program's validation account for a mint
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Decimals and transfer hook program of a Token-2022 mint
+pub fn read_hooked_mint(mint_data: &[u8]) -> Result<(u8, Option<Pubkey>)> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)
+        .map_err(|_| SportsbookError::InvalidTokenMint)?;
+    Ok((mint.base.decimals, transfer_hook::get_program_id(&mint)))
+}
+
+/// Accounts every hooked transfer needs besides the ones the hook declares:
+/// the hook program and its validation account for the mint
+pub fn hook_account_keys(mint: &Pubkey, hook_program: &Pubkey) -> [Pubkey; 2] {
+    let (validation, _) =
+        Pubkey::find_program_address(&[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()], hook_program);
+    [*hook_program, validation]
+}
+
+/// Transfers out of the pool's token account (or an isolated round's vault),
+/// signed by the PDA that owns it
+pub struct PoolTransfer<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: Option<AccountInfo<'info>>,
+    pub from: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    /// Hook program, validation account and the hook's extra accounts
+    pub extra_accounts: &'a [AccountInfo<'info>],
+    pub signer: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> PoolTransfer<'a, 'info> {
+    pub fn transfer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if self.token_program.key() == token::ID {
+            let cpi_accounts = Transfer {
+                from: self.from.clone(),
+                to,
+                authority: self.authority.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, self.signer);
+            return token::transfer(cpi_ctx, amount);
+        }
+
+        // Token-2022: transfer_checked, with the hook's accounts if it has one
+        let mint = self.mint.as_ref().ok_or(SportsbookError::InvalidTokenMint)?;
+        let (decimals, hook_program) = read_hooked_mint(&mint.try_borrow_data()?)?;
+
+        if let Some(hook_program) = hook_program {
+            let required = hook_account_keys(mint.key, &hook_program);
+            require!(
+                required
+                    .iter()
+                    .all(|key| self.extra_accounts.iter().any(|account| account.key == key)),
+                SportsbookError::TransferHookAccountsMissing
+            );
+            msg!("Transfer of {} routed through transfer hook {}", amount, hook_program);
+        }
+
+        invoke_transfer_checked(
+            &spl_token_2022::ID,
+            self.from.clone(),
+            mint.clone(),
+            to,
+            self.authority.clone(),
+            self.extra_accounts,
+            amount,
+            decimals,
+            self.signer,
+        )
+        .map_err(|e| {
+            msg!("Transfer hook account resolution failed: {:?}", e);
+            error!(SportsbookError::TransferHookRejected)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_option::COption;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    };
+    use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+
+    fn mint_data(hook_program: Option<Pubkey>) -> Vec<u8> {
+        let extensions: Vec<ExtensionType> = hook_program
+            .map(|_| vec![ExtensionType::TransferHook])
+            .unwrap_or_default();
+        let len = ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap();
+        let mut data = vec![0u8; len];
+
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        if let Some(hook_program) = hook_program {
+            let extension = state.init_extension::<TransferHook>(true).unwrap();
+            extension.program_id = Some(hook_program).try_into().unwrap();
+        }
+        state.base = Mint {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_read_hooked_mint() {
+        let hook_program = Pubkey::new_unique();
+        assert_eq!(read_hooked_mint(&mint_data(Some(hook_program))).unwrap(), (6, Some(hook_program)));
+        assert_eq!(read_hooked_mint(&mint_data(None)).unwrap(), (6, None));
+        assert_eq!(mint_data(None).len(), Mint::LEN);
+
+        assert!(read_hooked_mint(&[0u8; 10]).is_err());
+
+        let [program, validation] = hook_account_keys(&Pubkey::new_unique(), &hook_program);
+        assert_eq!(program, hook_program);
+        assert_ne!(validation, hook_program);
+    }
+}
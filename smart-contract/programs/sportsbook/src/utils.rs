@@ -1,7 +1,9 @@
 pub mod odds;
 pub mod seeding;
 pub mod parlay;
+pub mod packing;
 
 pub use odds::*;
 pub use seeding::*;
 pub use parlay::*;
+pub use packing::*;
@@ -0,0 +1,208 @@
+use crate::constants::*;
+
+/// Size of one packed odds entry: match_index (u8) + home/away/draw odds (3 × u64 LE)
+pub const PACKED_ODDS_ENTRY_LEN: usize = 1 + 8 + 8 + 8;
+
+/// One entry of a packed odds payload
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OddsEntry {
+    pub match_index: u8,
+    pub home_odds: u64,
+    pub away_odds: u64,
+    pub draw_odds: u64,
+}
+
+/// Decode a packed array of (match_index, home, away, draw) odds entries
+///
+/// Every entry is validated: match index in range, no duplicates, and all
+/// three odds inside the protocol's compressed odds range.
+pub fn unpack_odds_entries(payload: &[u8]) -> Result<Vec<OddsEntry>, &'static str> {
+    let chunks = payload.chunks_exact(PACKED_ODDS_ENTRY_LEN);
+    if payload.is_empty() || !chunks.remainder().is_empty() {
+        return Err("Payload length must be a multiple of 25 bytes");
+    }
+
+    let num_entries = chunks.len();
+    if num_entries > MATCHES_PER_ROUND {
+        return Err("Too many odds entries");
+    }
+
+    let mut seen = [false; MATCHES_PER_ROUND];
+    let mut entries = Vec::with_capacity(num_entries);
+
+    for chunk in chunks {
+        let entry = OddsEntry {
+            match_index: chunk[0],
+            home_odds: read_u64(&chunk[1..9]),
+            away_odds: read_u64(&chunk[9..17]),
+            draw_odds: read_u64(&chunk[17..25]),
+        };
+
+        let index = entry.match_index as usize;
+        if index >= MATCHES_PER_ROUND {
+            return Err("Invalid match index (must be 0-9)");
+        }
+        if seen[index] {
+            return Err("Duplicate match index");
+        }
+        seen[index] = true;
+
+        for odds in [entry.home_odds, entry.away_odds, entry.draw_odds] {
+            if !(MIN_COMPRESSED_ODDS..=MAX_COMPRESSED_ODDS).contains(&odds) {
+                return Err("Odds outside allowed range");
+            }
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Encode odds entries into the packed format (used by clients and tests)
+pub fn pack_odds_entries(entries: &[OddsEntry]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(entries.len() * PACKED_ODDS_ENTRY_LEN);
+    for entry in entries {
+        payload.push(entry.match_index);
+        payload.extend_from_slice(&entry.home_odds.to_le_bytes());
+        payload.extend_from_slice(&entry.away_odds.to_le_bytes());
+        payload.extend_from_slice(&entry.draw_odds.to_le_bytes());
+    }
+    payload
+}
+
+/// Bits per packed match result: enough for 1=Home .. 4=Void
+pub const PACKED_RESULT_BITS: usize = 3;
+
+/// Size of a packed result set: 10 matches × 3 bits, rounded up to bytes
+pub const PACKED_RESULTS_LEN: usize = (MATCHES_PER_ROUND * PACKED_RESULT_BITS).div_ceil(8);
+
+const PACKED_RESULT_MASK: u32 = (1 << PACKED_RESULT_BITS) - 1;
+
+/// Decode packed match results (3 bits per match, match 0 in the low bits
+/// of a little-endian u32) into 1=Home, 2=Away, 3=Draw, 4=Void
+pub fn unpack_match_results(packed: &[u8]) -> Result<[u8; MATCHES_PER_ROUND], &'static str> {
+    if packed.len() != PACKED_RESULTS_LEN {
+        return Err("Packed results must be 4 bytes");
+    }
+    let word = u32::from_le_bytes(packed.try_into().unwrap());
+
+    let mut results = [0u8; MATCHES_PER_ROUND];
+    for (i, result) in results.iter_mut().enumerate() {
+        *result = ((word >> (i * PACKED_RESULT_BITS)) & PACKED_RESULT_MASK) as u8;
+        if !(1..=RESULT_VOID).contains(result) {
+            return Err("Invalid result (must be 1-4)");
+        }
+    }
+
+    // Trailing bits must be clear so every result set has one encoding
+    if word >> (MATCHES_PER_ROUND * PACKED_RESULT_BITS) != 0 {
+        return Err("Unused result bits must be zero");
+    }
+
+    Ok(results)
+}
+
+/// Encode match results (1-4 each) into the packed 3-bit format
+///
+/// Values outside 1..=RESULT_VOID are encoded as-is within their 3 bits,
+/// so `unpack_match_results` rejects them instead of seeing another outcome.
+pub fn pack_match_results(results: &[u8; MATCHES_PER_ROUND]) -> [u8; PACKED_RESULTS_LEN] {
+    let mut word = 0u32;
+    for (i, result) in results.iter().enumerate() {
+        word |= (*result as u32 & PACKED_RESULT_MASK) << (i * PACKED_RESULT_BITS);
+    }
+    word.to_le_bytes()
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(match_index: u8) -> OddsEntry {
+        OddsEntry {
+            match_index,
+            home_odds: 1_400_000_000,
+            away_odds: 2_100_000_000,
+            draw_odds: 1_800_000_000,
+        }
+    }
+
+    #[test]
+    fn test_pack_unpack_roundtrip() {
+        let entries: Vec<_> = (0..10).map(entry).collect();
+        let payload = pack_odds_entries(&entries);
+        assert_eq!(payload.len(), 10 * PACKED_ODDS_ENTRY_LEN);
+        assert_eq!(unpack_odds_entries(&payload).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_unpack_rejects_bad_length() {
+        let mut payload = pack_odds_entries(&[entry(0)]);
+        payload.pop();
+        assert!(unpack_odds_entries(&payload).is_err());
+        assert!(unpack_odds_entries(&[]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_rejects_duplicates_and_bad_index() {
+        let payload = pack_odds_entries(&[entry(3), entry(3)]);
+        assert!(unpack_odds_entries(&payload).is_err());
+
+        let payload = pack_odds_entries(&[entry(10)]);
+        assert!(unpack_odds_entries(&payload).is_err());
+    }
+
+    #[test]
+    fn test_unpack_rejects_out_of_range_odds() {
+        let mut bad = entry(0);
+        bad.away_odds = MAX_COMPRESSED_ODDS + 1;
+        assert!(unpack_odds_entries(&pack_odds_entries(&[bad])).is_err());
+
+        let mut bad = entry(0);
+        bad.home_odds = ODDS_SCALE; // 1.0x pays nothing
+        assert!(unpack_odds_entries(&pack_odds_entries(&[bad])).is_err());
+    }
+
+    #[test]
+    fn test_pack_unpack_match_results() {
+        let results = [1, 2, 3, 1, 1, 2, 3, 3, 2, 1];
+        let packed = pack_match_results(&results);
+        assert_eq!(packed.len(), 4);
+        assert_eq!(unpack_match_results(&packed).unwrap(), results);
+    }
+
+    #[test]
+    fn test_pack_unpack_match_results_with_void() {
+        let results = [RESULT_VOID, 2, 3, 1, RESULT_VOID, 2, 3, 3, 2, RESULT_VOID];
+        let packed = pack_match_results(&results);
+        assert_eq!(unpack_match_results(&packed).unwrap(), results);
+
+        let all_void = [RESULT_VOID; MATCHES_PER_ROUND];
+        assert_eq!(unpack_match_results(&pack_match_results(&all_void)).unwrap(), all_void);
+    }
+
+    #[test]
+    fn test_unpack_match_results_rejects_invalid() {
+        // Match 0 left as 0 (pending)
+        let mut packed = pack_match_results(&[1; MATCHES_PER_ROUND]);
+        packed[0] &= !0b111;
+        assert!(unpack_match_results(&packed).is_err());
+
+        // Out-of-range outcome is not silently folded into a valid one
+        let mut results = [1; MATCHES_PER_ROUND];
+        results[5] = 5;
+        assert!(unpack_match_results(&pack_match_results(&results)).is_err());
+
+        // Stray bits past match 9
+        let mut packed = pack_match_results(&[1; MATCHES_PER_ROUND]);
+        packed[3] |= 0b0100_0000;
+        assert!(unpack_match_results(&packed).is_err());
+
+        assert!(unpack_match_results(&[0xff, 0xff, 0xff]).is_err());
+    }
+}
@@ -2,6 +2,8 @@ use crate::constants::*;
 use crate::state::RoundAccounting;
 use crate::utils::odds::calculate_pool_imbalance;
 
+pub use sportsbook_core::{calculate_combined_odds, calculate_potential_payout};
+
 /// Get base parlay multiplier based on number of matches
 ///
 /// Linear progression: 1.0x (1 match) to 1.25x (10 matches)
@@ -134,7 +136,7 @@ pub fn calculate_odds_weighted_allocations(
     }
 
     for &outcome in outcomes.iter() {
-        if outcome < 1 || outcome > 3 {
+        if !(1..=3).contains(&outcome) {
             return Err("Invalid outcome (must be 1, 2, or 3)");
         }
     }
@@ -189,11 +191,7 @@ pub fn calculate_odds_weighted_allocations(
     }
 
     // Step 4: Calculate LP borrowing needed
-    let lp_borrowed = if total_allocated > amount_after_fee {
-        total_allocated - amount_after_fee
-    } else {
-        0
-    };
+    let lp_borrowed = total_allocated.saturating_sub(amount_after_fee);
 
     Ok((allocations, total_allocated, lp_borrowed))
 }
@@ -1,5 +1,4 @@
 use crate::constants::*;
-use anchor_lang::prelude::*;
 
 /// Calculate differentiated seed amounts for a match using pseudo-random allocation
 ///
@@ -22,11 +21,7 @@ pub fn calculate_pseudo_random_seeds(
     let total_seed = SEED_PER_MATCH;
 
     // Calculate strength difference
-    let diff = if home_strength > away_strength {
-        home_strength - away_strength
-    } else {
-        away_strength - home_strength
-    };
+    let diff = home_strength.abs_diff(away_strength);
 
     // Determine allocation percentages based on strength difference
     // Compressed odds range: 1.2x (strong favourite) – 2.2x (heavy underdog)
@@ -110,7 +105,7 @@ pub fn calculate_stats_based_seeds(
 /// Round 4+: Stats-based using actual team performance
 pub fn calculate_match_seeds(
     round_id: u64,
-    match_index: u8,
+    _match_index: u8,
     home_team_id: u64,
     away_team_id: u64,
     season_round: u64,
@@ -138,9 +133,9 @@ mod tests {
         let h = odds_x(home_odds);
         let a = odds_x(away_odds);
         let d = odds_x(draw_odds);
-        assert!(h >= 1.2 && h <= 2.2, "[{}] home odds {:.3}x out of 1.2-2.2", label, h);
-        assert!(a >= 1.2 && a <= 2.2, "[{}] away odds {:.3}x out of 1.2-2.2", label, a);
-        assert!(d >= 1.2 && d <= 2.2, "[{}] draw odds {:.3}x out of 1.2-2.2", label, d);
+        assert!((1.2..=2.2).contains(&h), "[{}] home odds {:.3}x out of 1.2-2.2", label, h);
+        assert!((1.2..=2.2).contains(&a), "[{}] away odds {:.3}x out of 1.2-2.2", label, a);
+        assert!((1.2..=2.2).contains(&d), "[{}] draw odds {:.3}x out of 1.2-2.2", label, d);
     }
 
     #[test]
@@ -149,7 +144,7 @@ mod tests {
         // Draw-heavy boost can add up to 16% extra, so total may exceed SEED_PER_MATCH slightly
         let total = home + away + draw;
         // Should be close to SEED_PER_MATCH (within 20% for draw-heavy boosts)
-        assert!(total >= SEED_PER_MATCH / 2 && total <= SEED_PER_MATCH * 2,
+        assert!((SEED_PER_MATCH / 2..=SEED_PER_MATCH * 2).contains(&total),
             "total seeds {} far from SEED_PER_MATCH {}", total, SEED_PER_MATCH);
     }
 
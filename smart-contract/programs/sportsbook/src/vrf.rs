@@ -1,9 +1,21 @@
-/// Switchboard VRF Integration Module
-///
-/// This module provides integration with Switchboard V2 VRF for provably fair randomness
-/// in match outcome generation.
+//! Switchboard VRF Integration Module
+//!
+//! This module provides integration with Switchboard V2 VRF for provably fair randomness
+//! in match outcome generation.
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use crate::state::{RoundAccounting, RoundFixtures};
+use crate::errors::SportsbookError;
+
+/// Domain separator for the seed the VRF oracle signs
+pub const VRF_SEED_DOMAIN: &[u8] = b"sportsbook:vrf-seed:v1";
+
+/// Domain separator for the seed the oracle signs for a lottery draw
+pub const LOTTERY_SEED_DOMAIN: &[u8] = b"sportsbook:lottery-seed:v1";
+
+/// Length of an oracle proof (an Ed25519 signature over the seed)
+pub const VRF_PROOF_LEN: usize = 64;
 
 /// VRF Request account to track randomness requests
 #[account]
@@ -45,31 +57,117 @@ impl VrfRequest {
         1;   // bump
 }
 
+/// Proof behind a round's randomness, kept so anyone can re-verify it
+///
+/// The oracle signs `vrf_seed(..)` with its Ed25519 key; the signature is
+/// the proof and the randomness is derived from it with
+/// `randomness_from_proof`. Ed25519 signatures are deterministic, so an
+/// honest oracle has exactly one proof per seed.
+#[account]
+pub struct VrfProof {
+    /// Round ID this proof is for
+    pub round_id: u64,
+
+    /// Seed the oracle signed, bound to the pool, round and request time
+    pub seed: [u8; 32],
+
+    /// Oracle key the proof was verified against at fulfillment
+    pub oracle: Pubkey,
+
+    /// Ed25519 signature by `oracle` over `seed`
+    pub proof: [u8; VRF_PROOF_LEN],
+
+    /// Last time verify_round_randomness ran (0 = never)
+    pub last_verified_time: i64,
+
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl VrfProof {
+    pub const LEN: usize = 8 + // discriminator
+        8 +  // round_id
+        32 + // seed
+        32 + // oracle
+        VRF_PROOF_LEN + // proof
+        8 +  // last_verified_time
+        1;   // bump
+}
+
+/// Seed the oracle signs for a round's randomness
+pub fn vrf_seed(betting_pool: &Pubkey, round_id: u64, request_time: i64) -> [u8; 32] {
+    hashv(&[
+        VRF_SEED_DOMAIN,
+        betting_pool.as_ref(),
+        &round_id.to_le_bytes(),
+        &request_time.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Seed the oracle signs for a weekly lottery draw
+pub fn lottery_seed(betting_pool: &Pubkey, lottery_id: u64, closes_at: i64) -> [u8; 32] {
+    hashv(&[
+        LOTTERY_SEED_DOMAIN,
+        betting_pool.as_ref(),
+        &lottery_id.to_le_bytes(),
+        &closes_at.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Expand a proof into 320 bytes of randomness (32 per match)
+pub fn randomness_from_proof(proof: &[u8; VRF_PROOF_LEN]) -> [u8; 320] {
+    let mut randomness = [0u8; 320];
+    for (i, chunk) in randomness.chunks_exact_mut(32).enumerate() {
+        chunk.copy_from_slice(&hashv(&[proof.as_ref(), &[i as u8]]).to_bytes());
+    }
+    randomness
+}
+
 /// Generate match outcome from randomness bytes
 ///
 /// Maps random bytes to match outcomes (1, 2, or 3)
 /// Each outcome has equal 33.33% probability
 pub fn outcome_from_randomness(randomness: &[u8]) -> u8 {
-    // Take first 8 bytes and convert to u64
-    let value = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
-
-    // Map to outcome (1, 2, or 3) with equal probability
-    ((value % 3) + 1) as u8
+    // Same draw the simulation falls back to for matches without a fixture
+    sportsbook_core::unweighted_outcome(randomness)
 }
 
 /// Extract all 10 match results from VRF randomness
 pub fn extract_match_results(randomness: &[u8; 320]) -> [u8; 10] {
     let mut results = [0u8; 10];
 
-    for i in 0..10 {
-        let offset = i * 32;
-        let match_randomness = &randomness[offset..offset + 32];
-        results[i] = outcome_from_randomness(match_randomness);
+    for (result, match_randomness) in results.iter_mut().zip(randomness.chunks_exact(32)) {
+        *result = outcome_from_randomness(match_randomness);
     }
 
     results
 }
 
+/// Match results a round's randomness produces
+///
+/// League rounds are simulated with the ratings and goal model locked in
+/// their RoundFixtures, which must then be passed; other rounds use the
+/// unweighted draw.
+pub fn round_match_results(
+    round_accounting: &RoundAccounting,
+    round_fixtures: Option<&RoundFixtures>,
+    randomness: &[u8; 320],
+) -> Result<[u8; 10]> {
+    if !round_accounting.league_round {
+        return Ok(extract_match_results(randomness));
+    }
+
+    let round_fixtures = round_fixtures.ok_or(SportsbookError::RoundFixturesMissing)?;
+    require!(
+        round_fixtures.betting_pool == round_accounting.betting_pool
+            && round_fixtures.round_id == round_accounting.round_id,
+        SportsbookError::RoundFixturesMissing
+    );
+    Ok(round_fixtures.simulate_results(randomness))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,17 +177,17 @@ mod tests {
         // Test with known values
         let randomness1 = [0u8; 32];
         let outcome1 = outcome_from_randomness(&randomness1);
-        assert!(outcome1 >= 1 && outcome1 <= 3);
+        assert!((1..=3).contains(&outcome1));
 
         let randomness2 = [255u8; 32];
         let outcome2 = outcome_from_randomness(&randomness2);
-        assert!(outcome2 >= 1 && outcome2 <= 3);
+        assert!((1..=3).contains(&outcome2));
 
         // Test that different random values can produce different outcomes
         let mut randomness3 = [0u8; 32];
         randomness3[0] = 1;
         let outcome3 = outcome_from_randomness(&randomness3);
-        assert!(outcome3 >= 1 && outcome3 <= 3);
+        assert!((1..=3).contains(&outcome3));
     }
 
     #[test]
@@ -104,4 +202,22 @@ mod tests {
 
         assert_eq!(results.len(), 10);
     }
+
+    #[test]
+    fn test_randomness_from_proof() {
+        let proof = [3u8; VRF_PROOF_LEN];
+        let randomness = randomness_from_proof(&proof);
+        assert_eq!(randomness, randomness_from_proof(&proof));
+
+        // Each match gets its own slice of randomness
+        assert_ne!(randomness[0..32], randomness[32..64]);
+
+        let mut other = proof;
+        other[63] ^= 1;
+        assert_ne!(randomness, randomness_from_proof(&other));
+
+        let pool = Pubkey::new_unique();
+        assert_ne!(vrf_seed(&pool, 1, 100), vrf_seed(&pool, 2, 100));
+        assert_ne!(vrf_seed(&pool, 1, 100), vrf_seed(&pool, 1, 101));
+    }
 }
@@ -0,0 +1,356 @@
+// Localnet fixture generator
+//
+// Builds a working end-to-end state against a local validator: the betting
+// pool (created if missing), a funded LP pool, a seeded 10-match round,
+// sample bets across market types, settlement and the resulting claims.
+// Addresses are written to fixtures/localnet.json for tests and frontends.
+//
+//   anchor localnet          # terminal 1: validator with the program deployed
+//   anchor run fixtures      # terminal 2
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  createAccount,
+  getOrCreateAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+} from "@solana/spl-token";
+import * as fs from "fs";
+import * as path from "path";
+
+const TOKEN = new BN(1_000_000_000); // 9 decimals
+const LP_FUNDING = TOKEN.muln(200_000);
+const BETTOR_BALANCE = TOKEN.muln(10_000);
+const STAKE = TOKEN.muln(100);
+
+// 1 = home, 2 = away, 3 = draw
+const MATCH_RESULTS = [1, 3, 1, 2, 3, 1, 2, 2, 1, 3];
+
+interface SampleBet {
+  label: string;
+  bettor: number;
+  perUser: boolean;
+  matchIndices: number[];
+  outcomes: number[];
+}
+
+// One of each market type; all but the last parlay win on MATCH_RESULTS
+const SAMPLE_BETS: SampleBet[] = [
+  { label: "single home win", bettor: 0, perUser: false, matchIndices: [0], outcomes: [1] },
+  { label: "single draw", bettor: 1, perUser: false, matchIndices: [1], outcomes: [3] },
+  { label: "3-leg parlay", bettor: 0, perUser: false, matchIndices: [2, 3, 4], outcomes: [1, 2, 3] },
+  { label: "2-leg parlay (loses)", bettor: 1, perUser: false, matchIndices: [5, 6], outcomes: [1, 1] },
+  { label: "per-user single away win", bettor: 1, perUser: true, matchIndices: [7], outcomes: [2] },
+];
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+const program = anchor.workspace.Sportsbook as Program;
+const connection = provider.connection;
+const payer = (provider.wallet as anchor.Wallet).payer;
+
+function pda(...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+}
+
+const u64 = (value: BN | number) => new BN(value).toArrayLike(Buffer, "le", 8);
+
+const bettingPool = pda(Buffer.from("betting_pool"));
+const liquidityPool = pda(Buffer.from("liquidity_pool"), bettingPool.toBuffer());
+const dailyVolume = pda(Buffer.from("daily_volume"), bettingPool.toBuffer());
+const activeBettors = pda(Buffer.from("active_bettors"), bettingPool.toBuffer());
+const pendingClaims = pda(Buffer.from("pending_claims"), bettingPool.toBuffer());
+
+async function airdrop(to: PublicKey, sol: number) {
+  const signature = await connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(signature);
+}
+
+/** Create the pool and its metrics accounts, or reuse the existing pool */
+async function ensurePool(): Promise<{ tokenMint: PublicKey; protocolTreasury: PublicKey }> {
+  const existing = await program.account.bettingPool.fetchNullable(bettingPool);
+  if (existing) {
+    if (!existing.authority.equals(payer.publicKey)) {
+      throw new Error(`Betting pool ${bettingPool} belongs to ${existing.authority}, not the provider wallet`);
+    }
+    console.log(`Reusing betting pool ${bettingPool}`);
+    return { tokenMint: existing.tokenMint, protocolTreasury: existing.protocolTreasury };
+  }
+
+  const tokenMint = await createMint(connection, payer, payer.publicKey, null, 9);
+  const protocolTreasury = await createAccount(connection, payer, tokenMint, payer.publicKey, Keypair.generate());
+
+  await program.methods
+    .initialize(500, 0, 200) // 5% protocol fee, 2% season pool
+    .accountsPartial({
+      bettingPool,
+      liquidityPool,
+      authority: payer.publicKey,
+      tokenMint,
+      bettingPoolTokenAccount: getAssociatedTokenAddressSync(tokenMint, bettingPool, true),
+      protocolTreasury,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  await program.methods
+    .initializeMetrics()
+    .accountsPartial({
+      bettingPool,
+      dailyVolume,
+      activeBettors,
+      pendingClaims,
+      authority: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  console.log(`Created betting pool ${bettingPool} (mint ${tokenMint})`);
+  return { tokenMint, protocolTreasury };
+}
+
+async function fundLiquidityPool(tokenMint: PublicKey): Promise<PublicKey> {
+  const lpTokenAccount = await getOrCreateAssociatedTokenAccount(connection, payer, tokenMint, liquidityPool, true);
+  const authorityTokenAccount = await getOrCreateAssociatedTokenAccount(connection, payer, tokenMint, payer.publicKey);
+  await mintTo(connection, payer, tokenMint, authorityTokenAccount.address, payer, BigInt(LP_FUNDING.toString()));
+
+  await program.methods
+    .fundLiquidityPool(LP_FUNDING)
+    .accountsPartial({
+      bettingPool,
+      liquidityPool,
+      lpTokenAccount: lpTokenAccount.address,
+      authorityTokenAccount: authorityTokenAccount.address,
+      accessControl: null,
+      authority: payer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+  console.log(`Funded LP pool with ${LP_FUNDING.div(TOKEN)} tokens`);
+  return lpTokenAccount.address;
+}
+
+async function createRound(tokenMint: PublicKey, lpTokenAccount: PublicKey): Promise<{ roundId: BN; round: PublicKey }> {
+  const { nextRoundId } = await program.account.bettingPool.fetch(bettingPool);
+  const roundId: BN = nextRoundId;
+  const round = pda(Buffer.from("round"), bettingPool.toBuffer(), u64(roundId));
+
+  await program.methods
+    .initializeRound(roundId)
+    .accountsPartial({
+      bettingPool,
+      roundAccounting: round,
+      openLiability: pda(Buffer.from("open_liability"), bettingPool.toBuffer(), u64(roundId)),
+      authority: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  await program.methods
+    .seedRoundPools(roundId)
+    .accountsPartial({
+      bettingPool,
+      roundAccounting: round,
+      liquidityPool,
+      lpTokenAccount,
+      bettingPoolTokenAccount: getAssociatedTokenAddressSync(tokenMint, bettingPool, true),
+      roundVault: null,
+      notifications: null,
+      authority: payer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .rpc();
+
+  console.log(`Round ${roundId} created and seeded: ${round}`);
+  return { roundId, round };
+}
+
+async function placeBet(
+  sample: SampleBet,
+  bettor: Keypair,
+  bettorTokenAccount: PublicKey,
+  tokenMint: PublicKey,
+  protocolTreasury: PublicKey,
+  roundId: BN,
+  round: PublicKey
+): Promise<PublicKey> {
+  let bet: PublicKey;
+  let userBetCounter: PublicKey | undefined;
+  if (sample.perUser) {
+    userBetCounter = pda(Buffer.from("user_bet_counter"), bettingPool.toBuffer(), bettor.publicKey.toBuffer());
+    const counter = await program.account.userBetCounter.fetchNullable(userBetCounter);
+    const nonce: BN = counter ? counter.nextNonce : new BN(0);
+    bet = pda(Buffer.from("bet"), bettingPool.toBuffer(), bettor.publicKey.toBuffer(), u64(nonce));
+  } else {
+    const { nextBetId } = await program.account.bettingPool.fetch(bettingPool);
+    bet = pda(Buffer.from("bet"), bettingPool.toBuffer(), u64(nextBetId));
+  }
+
+  const accounts = {
+    bettingPool,
+    roundAccounting: round,
+    openLiability: pda(Buffer.from("open_liability"), bettingPool.toBuffer(), u64(roundId)),
+    dailyVolume,
+    activeBettors,
+    pendingClaims,
+    userVolume: pda(Buffer.from("user_volume"), bettingPool.toBuffer(), bettor.publicKey.toBuffer()),
+    bet,
+    betRecord: pda(Buffer.from("bet_record"), bet.toBuffer()),
+    bettorTokenAccount,
+    bettingPoolTokenAccount: getAssociatedTokenAddressSync(tokenMint, bettingPool, true),
+    roundVault: null,
+    legFeeSchedule: null,
+    stakePolicy: null,
+    protocolTreasuryTokenAccount: protocolTreasury,
+    teamTokenAccount: null,
+    priceFeed: null,
+    insuranceFund: null,
+    insuranceVault: null,
+    userStats: null,
+    creditVault: null,
+    bettor: bettor.publicKey,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+  };
+
+  const args = [roundId, Buffer.from(sample.matchIndices), Buffer.from(sample.outcomes), STAKE] as const;
+  const builder = sample.perUser
+    ? program.methods.placeUserBet(...args).accountsPartial({ ...accounts, userBetCounter })
+    : program.methods.placeBet(...args).accountsPartial(accounts);
+  await builder.signers([bettor]).rpc();
+
+  console.log(`  ${sample.label}: ${bet}`);
+  return bet;
+}
+
+async function settleAndClaim(
+  tokenMint: PublicKey,
+  roundId: BN,
+  round: PublicKey,
+  bets: { bet: PublicKey; bettor: Keypair; bettorTokenAccount: PublicKey }[]
+) {
+  await program.methods
+    .settleRound(roundId, Buffer.from(MATCH_RESULTS))
+    .accountsPartial({
+      bettingPool,
+      roundAccounting: round,
+      priceFeed: null,
+      accessControl: null,
+      roundSummary: null,
+      notifications: null,
+      authority: payer.publicKey,
+    })
+    .rpc();
+  console.log(`Round ${roundId} settled with results ${MATCH_RESULTS.join(",")}`);
+
+  for (const { bet, bettor, bettorTokenAccount } of bets) {
+    const { betId } = await program.account.bet.fetch(bet);
+    const betRecord = pda(Buffer.from("bet_record"), bet.toBuffer());
+    const preview = await program.methods
+      .previewClaim(betId)
+      .accountsPartial({ bettingPool, roundAccounting: round, bet, claimer: bettor.publicKey })
+      .view();
+
+    if (!preview.won && preview.refund.isZero()) {
+      await program.methods
+        .settleLosingBet()
+        .accountsPartial({ bettingPool, roundAccounting: round, bet, betRecord, pendingClaims, cranker: payer.publicKey })
+        .rpc();
+      console.log(`  bet ${betId} lost, settled`);
+      continue;
+    }
+
+    await program.methods
+      .claimWinnings(betId, new BN(0))
+      .accountsPartial({
+        bettingPool,
+        roundAccounting: round,
+        bet,
+        betRecord,
+        pendingClaims,
+        bettingPoolTokenAccount: getAssociatedTokenAddressSync(tokenMint, bettingPool, true),
+        roundVault: null,
+        bettorTokenAccount,
+        claimer: bettor.publicKey,
+        claimerTokenAccount: bettorTokenAccount,
+        leaderTokenAccount: null,
+        tokenMint: null,
+        insuranceFund: null,
+        insuranceVault: null,
+        userStats: null,
+        creditVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bettor])
+      .rpc();
+    console.log(`  bet ${betId} claimed ${preview.payout.div(TOKEN)} tokens`);
+  }
+}
+
+async function main() {
+  const { tokenMint, protocolTreasury } = await ensurePool();
+  const lpTokenAccount = await fundLiquidityPool(tokenMint);
+  const { roundId, round } = await createRound(tokenMint, lpTokenAccount);
+
+  const bettors = [Keypair.generate(), Keypair.generate()];
+  const bettorTokenAccounts: PublicKey[] = [];
+  for (const bettor of bettors) {
+    await airdrop(bettor.publicKey, 2);
+    const account = await getOrCreateAssociatedTokenAccount(connection, payer, tokenMint, bettor.publicKey);
+    await mintTo(connection, payer, tokenMint, account.address, payer, BigInt(BETTOR_BALANCE.toString()));
+    bettorTokenAccounts.push(account.address);
+  }
+
+  console.log("Placing sample bets:");
+  const bets = [];
+  for (const sample of SAMPLE_BETS) {
+    const bettor = bettors[sample.bettor];
+    const bettorTokenAccount = bettorTokenAccounts[sample.bettor];
+    const bet = await placeBet(sample, bettor, bettorTokenAccount, tokenMint, protocolTreasury, roundId, round);
+    bets.push({ bet, bettor, bettorTokenAccount, label: sample.label });
+  }
+
+  await settleAndClaim(tokenMint, roundId, round, bets);
+
+  const out = path.join(__dirname, "..", "fixtures", "localnet.json");
+  fs.mkdirSync(path.dirname(out), { recursive: true });
+  fs.writeFileSync(
+    out,
+    JSON.stringify(
+      {
+        programId: program.programId.toBase58(),
+        bettingPool: bettingPool.toBase58(),
+        liquidityPool: liquidityPool.toBase58(),
+        tokenMint: tokenMint.toBase58(),
+        roundId: roundId.toNumber(),
+        round: round.toBase58(),
+        matchResults: MATCH_RESULTS,
+        bettors: bettors.map((bettor) => ({
+          publicKey: bettor.publicKey.toBase58(),
+          secretKey: Array.from(bettor.secretKey),
+        })),
+        bets: bets.map(({ bet, bettor, label }) => ({
+          label,
+          bet: bet.toBase58(),
+          bettor: bettor.publicKey.toBase58(),
+        })),
+      },
+      null,
+      2
+    )
+  );
+  console.log(`Fixtures written to ${out}`);
+}
+
+main().catch((err) => {
+  console.error(err);
+  process.exit(1);
+});
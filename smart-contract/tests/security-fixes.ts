@@ -1,7 +1,14 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  createAccount,
+  mintTo,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import { assert } from "chai";
 
 describe("Security Fixes Integration Tests", () => {
@@ -88,7 +95,10 @@ describe("Security Fixes Integration Tests", () => {
             liquidityPool,
             authority: authority.publicKey,
             tokenMint,
+            bettingPoolTokenAccount: getAssociatedTokenAddressSync(tokenMint, bettingPool, true),
             protocolTreasury,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([authority])
@@ -197,7 +207,10 @@ describe("Security Fixes Integration Tests", () => {
             liquidityPool: fakeLiquidityPool,
             authority: authority.publicKey,
             tokenMint: fakeMint, // Invalid mint
+            bettingPoolTokenAccount: getAssociatedTokenAddressSync(fakeMint, fakeBettingPool, true),
             protocolTreasury,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([authority])